@@ -1,27 +1,143 @@
+use crate::config::AppConfig;
 use crate::export_queue::{JobStatus, SharedQueue, create_shared_queue};
-use crate::ffmpeg::{FFmpegWrapper, SilenceInterval, TaskProgress, compute_cut_points, BitrateMap, extract_bitrate_map, compute_cut_points_accurate, ffmpeg_command};
+use crate::ffmpeg::{FFmpegWrapper, SilenceInterval, compute_cut_points, BitrateMap, extract_bitrate_map, compute_cut_points_accurate, auto_split_segment, ConcatMethod, over_limit_parts, ffmpeg_command, weighted_progress, LoudnessInfo, extract_waveform_peaks};
 use crate::player::{MediaPlayer, PlaybackState};
 use crate::project::{MediaFile, Project};
-use crate::ui::{EditingMode, SplitSegment, SplitSettings, TrimMode};
+use crate::ui::{AnimationSettings, AudioExtractFormat, BatchTrimAnchor, ConflictPolicy, EditingMode, EncodeProfile, ExportSettings, OpenOutputAction, ProofSheetSettings, SplitSegment, SplitSettings, SubtitleMode, TrimMode};
 use eframe::egui;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::path::PathBuf;
 use std::sync::{Arc, Mutex};
 use tokio::runtime::Runtime;
 
+/// Silence intervals detected per file (by `MediaFile::id`) during batch analysis
+type BatchResults = Arc<Mutex<Vec<(u64, Vec<SilenceInterval>)>>>;
+/// Analysed fraction (0..1) of each file in the running batch, by batch row
+type BatchProgress = Arc<Mutex<Vec<f32>>>;
+/// Waveform peaks handed back by the background extraction thread
+type WaveformSlot = Arc<Mutex<Option<(PathBuf, Vec<f32>)>>>;
+/// Keyframe timestamps handed back by the background ffprobe thread
+type KeyframeSlot = Arc<Mutex<Option<(PathBuf, Vec<f64>)>>>;
+/// Scene detection result for a file (timestamps or error, freshly built bitrate map)
+type SceneSlot = Arc<Mutex<Option<(u64, Result<Vec<f64>, String>, Option<Result<BitrateMap, String>>)>>>;
+/// Silences found for "Trim silence ends" (file id, intervals or error)
+type SilenceEndsSlot = Arc<Mutex<Option<(u64, Result<Vec<SilenceInterval>, String>)>>>;
+/// Loudness measurement of a file (or the error), from the background analysis
+type LoudnessSlot = Arc<Mutex<Option<(PathBuf, Result<LoudnessInfo, String>)>>>;
+/// Version, encoders and filters of the ffmpeg binary, probed once at startup
+type CapabilitiesSlot = Arc<Mutex<Option<Result<crate::ffmpeg::FfmpegCapabilities, String>>>>;
+/// What a filtered preview frame shows: file, time (ms) and `-vf` chain
+type FilterPreviewKey = (PathBuf, i64, String);
+/// Still frame as RGBA data, width, height
+type RgbaFrame = (Vec<u8>, u32, u32);
+/// Filtered still frame or the extraction error, plus the unfiltered frame
+/// when an A/B comparison asked for it
+type FilterPreviewSlot = Arc<Mutex<Option<(FilterPreviewKey, Result<(RgbaFrame, Option<RgbaFrame>), String>)>>>;
+/// Raw RGBA thumbnails (path, data, width, height) waiting to be uploaded
+type ThumbnailQueue = Arc<Mutex<Vec<(PathBuf, Vec<u8>, u32, u32)>>>;
+/// Durations read from the whole file (file id, seconds or `None`) for
+/// files ffprobe found none in
+type DurationQueue = Arc<Mutex<Vec<(u64, Option<f64>)>>>;
+
+/// Background work still running for the file just loaded (first preview
+/// frame, waveform). The status line only follows it while nothing else
+/// has replaced it.
+#[derive(Debug, Default)]
+struct LoadStatus {
+    filename: String,
+    preview: bool,
+    waveform: bool,
+    /// Last message written to `status_message`
+    shown: String,
+}
+
+impl LoadStatus {
+    fn message(&self) -> String {
+        match (self.preview, self.waveform) {
+            (true, true) => format!("Loading preview, analyzing audio: {}...", self.filename),
+            (true, false) => format!("Loading preview: {}...", self.filename),
+            (false, true) => format!("Analyzing audio: {}...", self.filename),
+            (false, false) => format!("Loaded: {}", self.filename),
+        }
+    }
+
+    fn is_pending(&self) -> bool {
+        self.preview || self.waveform
+    }
+}
+
+/// Per-file state of a batch Auto-Cut run
+#[derive(Debug, Clone)]
+pub struct BatchRow {
+    pub file_id: u64,
+    pub name: String,
+    pub path: PathBuf,
+    pub duration: f64,
+    pub progress: f32,
+    /// Analysis finished and its segments are stored
+    pub done: bool,
+}
+
+/// Free space on the output volume against the estimated size of an export
+#[derive(Debug, Clone)]
+pub struct DiskSpaceCheck {
+    pub folder: PathBuf,
+    /// Sum of the estimated sizes of the queued outputs
+    pub needed: u64,
+    pub free: u64,
+}
+
+impl DiskSpaceCheck {
+    /// Bytes missing on the volume, `None` when the export fits
+    pub fn shortfall(&self) -> Option<u64> {
+        crate::utils::space_shortfall(self.needed, self.free)
+    }
+}
+
+/// Which trim marker keyboard nudges apply to
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TrimHandle {
+    In,
+    Out,
+}
+
 pub struct FFmpegApp {
     pub project: Project,
     pub ffmpeg: FFmpegWrapper,
     pub runtime: Runtime,
     pub selected_file_index: Option<usize>,
     pub trim_settings: crate::ui::TrimSettings,
-    pub current_task: Arc<Mutex<Option<TaskProgress>>>,
+    pub dark_mode: bool,
+    /// Settings as last written to disk, and when they started to differ
+    saved_config: AppConfig,
+    config_changed_at: Option<std::time::Instant>,
+    /// Keyboard seek steps (arrows, J/L, Shift+J/L)
+    pub seek_settings: crate::ui::SeekSettings,
+    /// Keyframe-only scrub previews for sources above 1440p
+    pub fast_scrub_high_res: bool,
+    /// Hardware decode for the video preview, with software fallback
+    pub hw_decode: bool,
+    /// Smaller, slower preview and lazier scrubbing for weak machines
+    pub low_power: bool,
+    /// Low-power mode was already suggested this session
+    low_power_suggested: bool,
+    /// Preview frames decoded around a settled seek (off in low-power mode)
+    pub prefetch: crate::player::PrefetchSettings,
+    /// Selecting a file only shows its first frame; audio extraction and
+    /// the waveform wait for the first Play (quick browsing)
+    pub defer_playback_init: bool,
+    /// How "Trim all files" maps the IN/OUT points onto other files
+    pub batch_trim_anchor: BatchTrimAnchor,
+    /// Open or import the result of a single-output export when it finishes
+    pub open_output: OpenOutputAction,
     pub status_message: String,
 
     // Player state
     pub player: Option<MediaPlayer>,
     pub current_time: f64,
     pub volume: f32,
+    /// Muted: players get 0 while `volume` keeps the level to restore
+    pub muted: bool,
     pub preview_texture: Option<egui::TextureHandle>,
     last_frame_pts: f64,
 
@@ -30,25 +146,105 @@ pub struct FFmpegApp {
     pub timeline_scroll: f32,
     pub in_point: Option<f64>,
     pub out_point: Option<f64>,
+    /// Marker targeted by Shift+Arrow nudges (the one set last)
+    active_handle: Option<TrimHandle>,
 
     // Segments
     pub segments: Vec<SplitSegment>,
     pub split_settings: SplitSettings,
+    pub export_settings: ExportSettings,
+    /// Named encode profiles loaded from a JSON file
+    pub encode_profiles: Vec<EncodeProfile>,
+    pub profiles_path: Option<PathBuf>,
+    /// Last profile applied to `export_settings` (`None` = mode defaults)
+    pub selected_profile: Option<String>,
     pub selected_segment: Option<usize>,
+    /// Multi-selection (Ctrl+click) for bulk delete / toggle / export.
+    /// Empty when only `selected_segment` is selected.
+    pub selected_segments: HashSet<usize>,
     pub show_export_progress: bool,
+    /// First job id not yet listed in an export summary
+    manifest_from_id: u32,
+    /// Text buffers (start, end) of the selected segment's timecode fields
+    pub segment_time_edit: (String, String),
 
     // Export queue
     pub export_queue: SharedQueue,
 
     // Auto-cut state
     pub auto_cut_running: bool,
+    /// `MediaFile::id` of the file being auto-cut
+    auto_cut_target: u64,
     pub auto_cut_status: String,
     auto_cut_silences: Arc<Mutex<Option<Vec<SilenceInterval>>>>,
-    auto_cut_bitrate_map: Arc<Mutex<Option<BitrateMap>>>,
+    /// Packet-size map, or why it is unavailable (Auto-Cut then uses the average bitrate)
+    auto_cut_bitrate_map: Arc<Mutex<Option<Result<BitrateMap, String>>>>,
+
+    // Scene split state
+    pub scene_split_running: bool,
+    /// Scene score threshold (0.0–1.0, lower = more cuts)
+    pub scene_threshold: f64,
+    scene_results: SceneSlot,
+
+    // Trim silence ends state
+    pub silence_ends_running: bool,
+    silence_ends_result: SilenceEndsSlot,
+
+    /// Measured loudness per file (cached; errors such as "no audio" too)
+    pub loudness: HashMap<PathBuf, Result<LoudnessInfo, String>>,
+    /// File whose loudness is being measured
+    pub loudness_running: Option<PathBuf>,
+    loudness_result: LoudnessSlot,
+    /// Format, fps and width of IN/OUT animation exports
+    pub animation_settings: AnimationSettings,
+    /// Format of "Extract audio" on all files
+    pub audio_extract_format: AudioExtractFormat,
+    /// Output folders picked lately, most recent first (quick-pick)
+    pub recent_output_folders: Vec<PathBuf>,
+    /// Segment being renamed from the timeline menu (index, edited label)
+    pub segment_rename: Option<(usize, String)>,
+    /// Queue window (job list with editable outputs)
+    pub show_queue_panel: bool,
+    /// Pending job whose output path is being edited, and the typed path
+    pub queue_output_edit: Option<(u32, String)>,
+    /// FFmpeg info window (version, encoders, filters)
+    pub show_ffmpeg_info: bool,
+    /// Show the paused frame through the crop / resize / color filters
+    pub filter_preview: bool,
+    /// Filters applied by the player itself, also while playing
+    pub filter_live: bool,
+    /// Chain the player currently applies, and the next one once it has
+    /// stopped changing (sliders send a new chain every frame)
+    live_filter_sent: Option<String>,
+    live_filter_wanted: Option<(Option<String>, std::time::Instant)>,
+    filter_preview_texture: Option<(FilterPreviewKey, egui::TextureHandle)>,
+    /// Last requested key and when it changed (debounces drags on the values)
+    filter_preview_wanted: Option<(FilterPreviewKey, std::time::Instant)>,
+    /// Key whose extraction failed, not retried until something changes
+    filter_preview_failed: Option<FilterPreviewKey>,
+    filter_preview_running: bool,
+    filter_preview_result: FilterPreviewSlot,
+    /// A/B: show the unfiltered frame next to the filtered one
+    pub filter_compare: bool,
+    /// Split position of the A/B view (0 = all filtered, 1 = all source)
+    pub compare_split: f32,
+    /// Unfiltered frame for the A/B view, by file and time (ms)
+    compare_raw_texture: Option<((PathBuf, i64), egui::TextureHandle)>,
+    /// What the ffmpeg build supports; `None` while probing
+    pub capabilities: Option<Result<crate::ffmpeg::FfmpegCapabilities, String>>,
+    capabilities_result: CapabilitiesSlot,
+
+    // Proof sheet (preview montage) state
+    pub proof_sheet_settings: ProofSheetSettings,
+    pub proof_sheet_running: bool,
+    proof_sheet_result: Arc<Mutex<Option<Result<PathBuf, String>>>>,
 
     // Per-file bitrate maps (cached)
     bitrate_maps: HashMap<PathBuf, BitrateMap>,
 
+    /// Last `MediaFile::id` handed out
+    next_file_id: u64,
+
     // Per-file segments (persisted when switching files)
     pub file_segments: HashMap<PathBuf, Vec<SplitSegment>>,
 
@@ -56,17 +252,43 @@ pub struct FFmpegApp {
     pub batch_running: bool,
     pub batch_status: String,
     batch_total: usize,
-    batch_results: Arc<Mutex<Vec<(usize, Vec<SilenceInterval>)>>>,
+    batch_results: BatchResults,
+    batch_progress: BatchProgress,
+    /// One row per analysed file: name, duration and whether its segments are in
+    pub batch_rows: Vec<BatchRow>,
+    batch_segments_found: usize,
+    /// Cuts of the batch that found no silence in their window
+    batch_uniform_cuts: usize,
     /// When true, automatically export all files once batch detection finishes
     pub batch_auto_export: bool,
+    /// Queued parts still over the size limit after auto-split (last export)
+    pub size_warnings: Vec<String>,
+    /// Estimated vs. actual sizes of the last finished batch
+    pub size_checks: Vec<crate::export_queue::SizeCheck>,
+    /// Free space on the output volume at the last segment export
+    pub disk_space: Option<DiskSpaceCheck>,
 
     // Merge state
     pub merge_file_order: Vec<usize>,
+    /// Output file name for merges (extension optional)
+    pub merge_output_name: String,
+    /// Merge through the concat filter (re-encode) instead of stream copy
+    pub merge_reencode: bool,
+    /// Target height (16:9) of a re-encoded merge. `None` = largest input
+    pub merge_resolution: Option<u32>,
 
     // Waveform state
     pub waveform_peaks: HashMap<PathBuf, Vec<f32>>,
     pub current_waveform: Vec<f32>,
-    waveform_loading: Arc<Mutex<Option<(PathBuf, Vec<f32>)>>>,
+    waveform_loading: WaveformSlot,
+    load_status: LoadStatus,
+    /// Keyframe timestamps per file, probed on demand
+    keyframes: HashMap<PathBuf, Vec<f64>>,
+    keyframe_loading: KeyframeSlot,
+    /// File whose keyframes are being probed (one probe at a time)
+    keyframes_probing: Option<PathBuf>,
+    /// File whose keyframes are being probed for a pending IN/OUT snap
+    snap_lossless_pending: Option<PathBuf>,
 
     // Editing mode
     pub editing_mode: EditingMode,
@@ -74,7 +296,8 @@ pub struct FFmpegApp {
     // Thumbnails: raw RGBA data per file (extracted in background)
     pub thumbnails: HashMap<PathBuf, (Vec<u8>, u32, u32)>,
     pub thumbnail_textures: HashMap<String, egui::TextureHandle>,
-    thumbnail_loading: Arc<Mutex<Vec<(PathBuf, Vec<u8>, u32, u32)>>>,
+    thumbnail_loading: ThumbnailQueue,
+    duration_estimates: DurationQueue,
 
     // Playback speed
     pub playback_speed: f64,
@@ -84,20 +307,33 @@ pub struct FFmpegApp {
 }
 
 impl FFmpegApp {
-    pub fn new(_cc: &eframe::CreationContext<'_>) -> Self {
-        Self {
+    pub fn new(cc: &eframe::CreationContext<'_>) -> Self {
+        let config = AppConfig::load();
+        // Before FFmpegWrapper::new resolves the binaries
+        crate::ffmpeg::set_configured_paths(config.ffmpeg_path.clone(), config.ffprobe_path.clone());
+
+        let mut app = Self {
             project: Project::new(),
             ffmpeg: FFmpegWrapper::new(),
             runtime: Runtime::new().expect("Failed to create Tokio runtime"),
             selected_file_index: None,
             trim_settings: crate::ui::TrimSettings::default(),
-            current_task: Arc::new(Mutex::new(None)),
+            seek_settings: crate::ui::SeekSettings::default(),
+            fast_scrub_high_res: true,
+            hw_decode: cfg!(target_os = "macos"),
+            low_power: false,
+            low_power_suggested: false,
+            prefetch: crate::player::PrefetchSettings::default(),
+            defer_playback_init: false,
+            batch_trim_anchor: BatchTrimAnchor::default(),
+            open_output: OpenOutputAction::default(),
             status_message: String::from("Ready"),
 
             // Player state
             player: None,
             current_time: 0.0,
             volume: 1.0,
+            muted: false,
             preview_texture: None,
             last_frame_pts: -1.0,
 
@@ -106,26 +342,68 @@ impl FFmpegApp {
             timeline_scroll: 0.0,
             in_point: None,
             out_point: None,
+            active_handle: None,
 
             // Segments
             segments: Vec::new(),
             split_settings: SplitSettings::default(),
+            export_settings: ExportSettings::default(),
+            encode_profiles: Vec::new(),
+            profiles_path: None,
+            selected_profile: None,
             selected_segment: None,
+            selected_segments: HashSet::new(),
             show_export_progress: false,
+            manifest_from_id: 0,
+            segment_time_edit: (String::new(), String::new()),
 
             // Export queue
             export_queue: create_shared_queue(),
 
             // Auto-cut state
             auto_cut_running: false,
+            auto_cut_target: 0,
             auto_cut_status: String::new(),
             auto_cut_silences: Arc::new(Mutex::new(None)),
             auto_cut_bitrate_map: Arc::new(Mutex::new(None)),
+            scene_split_running: false,
+            scene_threshold: 0.4,
+            scene_results: Arc::new(Mutex::new(None)),
+            silence_ends_running: false,
+            silence_ends_result: Arc::new(Mutex::new(None)),
+            loudness: HashMap::new(),
+            loudness_running: None,
+            loudness_result: Arc::new(Mutex::new(None)),
+            animation_settings: AnimationSettings::default(),
+            audio_extract_format: AudioExtractFormat::default(),
+            recent_output_folders: Vec::new(),
+            segment_rename: None,
+            show_queue_panel: false,
+            show_ffmpeg_info: false,
+            filter_preview: false,
+            filter_live: false,
+            live_filter_sent: None,
+            live_filter_wanted: None,
+            filter_preview_texture: None,
+            filter_preview_wanted: None,
+            filter_preview_failed: None,
+            filter_preview_running: false,
+            filter_preview_result: Arc::new(Mutex::new(None)),
+            filter_compare: false,
+            compare_split: 0.5,
+            compare_raw_texture: None,
+            capabilities: None,
+            capabilities_result: Arc::new(Mutex::new(None)),
+            queue_output_edit: None,
+            proof_sheet_settings: ProofSheetSettings::default(),
+            proof_sheet_running: false,
+            proof_sheet_result: Arc::new(Mutex::new(None)),
 
             // Bitrate maps
             bitrate_maps: HashMap::new(),
 
             // Per-file segments
+            next_file_id: 0,
             file_segments: HashMap::new(),
 
             // Batch processing
@@ -133,15 +411,30 @@ impl FFmpegApp {
             batch_status: String::new(),
             batch_total: 0,
             batch_results: Arc::new(Mutex::new(Vec::new())),
+            batch_progress: Arc::new(Mutex::new(Vec::new())),
+            batch_rows: Vec::new(),
+            batch_segments_found: 0,
+            batch_uniform_cuts: 0,
             batch_auto_export: false,
+            size_warnings: Vec::new(),
+            disk_space: None,
+            size_checks: Vec::new(),
 
             // Merge
             merge_file_order: Vec::new(),
+            merge_output_name: "merged_output".to_string(),
+            merge_reencode: false,
+            merge_resolution: None,
 
             // Waveform
             waveform_peaks: HashMap::new(),
             current_waveform: Vec::new(),
             waveform_loading: Arc::new(Mutex::new(None)),
+            load_status: LoadStatus::default(),
+            keyframes: HashMap::new(),
+            keyframes_probing: None,
+            keyframe_loading: Arc::new(Mutex::new(None)),
+            snap_lossless_pending: None,
 
             // Editing mode
             editing_mode: EditingMode::Split,
@@ -150,32 +443,228 @@ impl FFmpegApp {
             thumbnails: HashMap::new(),
             thumbnail_textures: HashMap::new(),
             thumbnail_loading: Arc::new(Mutex::new(Vec::new())),
+            duration_estimates: Arc::new(Mutex::new(Vec::new())),
 
             // Playback speed
             playback_speed: 1.0,
 
             // Timeline
             timeline_follow_playhead: true,
+
+            // Settings file
+            dark_mode: true,
+            saved_config: AppConfig::default(),
+            config_changed_at: None,
+        };
+        app.apply_config(config, &cc.egui_ctx);
+        app.start_capabilities_probe();
+        app
+    }
+
+    /// Run `ffmpeg -version / -encoders / -filters` in the background
+    fn start_capabilities_probe(&mut self) {
+        let slot = self.capabilities_result.clone();
+        std::thread::spawn(move || {
+            let caps = crate::ffmpeg::probe_capabilities().map_err(|e| e.to_string());
+            *slot.lock().unwrap() = Some(caps);
+        });
+    }
+
+    fn poll_capabilities(&mut self) {
+        if self.capabilities.is_some() {
+            return;
+        }
+        if let Some(caps) = self.capabilities_result.lock().ok().and_then(|mut g| g.take()) {
+            self.capabilities = Some(caps);
         }
     }
 
-    pub fn add_files(&mut self, paths: Vec<PathBuf>) {
-        for path in &paths {
-            if let Some(media_file) = self.probe_file(path) {
-                self.project.files.push(media_file);
+    /// Probed capabilities, `None` while probing or if the probe failed
+    fn probed_capabilities(&self) -> Option<&crate::ffmpeg::FfmpegCapabilities> {
+        self.capabilities.as_ref().and_then(|c| c.as_ref().ok())
+    }
+
+    /// `None` if `encoder` is usable (or unknown yet), else why it is not
+    pub fn missing_encoder(&self, encoder: &str) -> Option<String> {
+        self.probed_capabilities()
+            .filter(|c| !c.has_encoder(encoder))
+            .map(|_| format!("This FFmpeg build has no {} encoder", encoder))
+    }
+
+    /// `None` if `filter` is usable (or unknown yet), else why it is not
+    pub fn missing_filter(&self, filter: &str) -> Option<String> {
+        self.probed_capabilities()
+            .filter(|c| !c.has_filter(filter))
+            .map(|_| format!("This FFmpeg build has no {} filter", filter))
+    }
+
+    /// `None` if re-encoding to `container` works with this build
+    pub fn missing_container_encoder(&self, container: &str) -> Option<String> {
+        let missing = self.probed_capabilities()?.missing_for_container(container)?;
+        Some(format!("This FFmpeg build has no {} encoder (needed for {})", missing, container))
+    }
+
+    /// Restore persisted settings (startup)
+    fn apply_config(&mut self, config: AppConfig, ctx: &egui::Context) {
+        self.volume = config.volume.clamp(0.0, 2.0);
+        self.set_dark_mode(ctx, config.dark_mode);
+        self.split_settings = config.split_settings.clone();
+        self.export_settings = config.export_settings.clone();
+        self.seek_settings = config.seek_settings;
+        self.fast_scrub_high_res = config.fast_scrub_high_res;
+        self.hw_decode = config.hw_decode;
+        self.low_power = config.low_power;
+        self.prefetch = config.prefetch;
+        self.defer_playback_init = config.defer_playback_init;
+        self.merge_reencode = config.merge_reencode;
+        self.merge_resolution = config.merge_resolution;
+        self.batch_trim_anchor = config.batch_trim_anchor;
+        self.open_output = config.open_output;
+        if let Ok(mut queue) = self.export_queue.lock() {
+            queue.order = config.queue_order;
+        }
+        self.proof_sheet_settings = config.proof_sheet;
+        self.animation_settings = config.animation;
+        self.audio_extract_format = config.audio_extract;
+        // Folders deleted or on an unplugged drive since last time
+        self.recent_output_folders = config.recent_output_folders.iter()
+            .filter(|f| f.is_dir())
+            .cloned()
+            .collect();
+        if let Some(path) = config.profiles_path.clone() {
+            match crate::ffmpeg::load_encode_profiles(&path) {
+                Ok(profiles) => {
+                    self.encode_profiles = profiles;
+                    self.profiles_path = Some(path);
+                    // Re-applied so edits to the shared file are picked up
+                    self.selected_profile = config.selected_profile.clone();
+                    self.select_profile(config.selected_profile.clone());
+                }
+                Err(e) => eprintln!("Encode profiles not loaded: {}", e),
             }
         }
-        // Extract thumbnails in background for new files
+        // Keeps the file-only fields (ffmpeg paths) in later snapshots
+        self.saved_config = config;
+        self.saved_config = self.current_config();
+    }
+
+    /// Snapshot of the settings worth keeping across launches
+    fn current_config(&self) -> AppConfig {
+        AppConfig {
+            volume: self.volume,
+            dark_mode: self.dark_mode,
+            split_settings: self.split_settings.clone(),
+            // A subtitle file belongs to one video
+            export_settings: ExportSettings { subtitles: None, ..self.export_settings.clone() },
+            seek_settings: self.seek_settings,
+            fast_scrub_high_res: self.fast_scrub_high_res,
+            hw_decode: self.hw_decode,
+            low_power: self.low_power,
+            prefetch: self.prefetch,
+            defer_playback_init: self.defer_playback_init,
+            merge_reencode: self.merge_reencode,
+            merge_resolution: self.merge_resolution,
+            batch_trim_anchor: self.batch_trim_anchor,
+            open_output: self.open_output,
+            queue_order: self.export_queue.lock().map(|q| q.order).unwrap_or_default(),
+            proof_sheet: self.proof_sheet_settings,
+            animation: self.animation_settings,
+            audio_extract: self.audio_extract_format,
+            recent_output_folders: self.recent_output_folders.clone(),
+            profiles_path: self.profiles_path.clone(),
+            selected_profile: self.selected_profile.clone(),
+            // Only editable in the file itself
+            ..self.saved_config.clone()
+        }
+    }
+
+    /// Save settings once they have stopped changing for a moment
+    /// (a slider drag is one write, not one per frame)
+    fn autosave_config(&mut self, ctx: &egui::Context) {
+        const SAVE_DELAY: std::time::Duration = std::time::Duration::from_secs(1);
+        let config = self.current_config();
+        if config == self.saved_config {
+            self.config_changed_at = None;
+            return;
+        }
+        let changed_at = *self.config_changed_at.get_or_insert_with(std::time::Instant::now);
+        if changed_at.elapsed() >= SAVE_DELAY {
+            self.save_config(config);
+        } else {
+            ctx.request_repaint_after(SAVE_DELAY);
+        }
+    }
+
+    fn save_config(&mut self, config: AppConfig) {
+        if let Err(e) = config.save() {
+            eprintln!("Failed to save settings: {}", e);
+        }
+        // Not retried every frame on failure
+        self.saved_config = config;
+        self.config_changed_at = None;
+    }
+
+    pub fn set_dark_mode(&mut self, ctx: &egui::Context, dark: bool) {
+        self.dark_mode = dark;
+        ctx.set_visuals(if dark { egui::Visuals::dark() } else { egui::Visuals::light() });
+    }
+
+    /// Open the folder holding `config.json`
+    pub fn open_settings_folder(&mut self) {
+        let Some(dir) = crate::config::config_dir() else {
+            self.status_message = "No config directory on this system".to_string();
+            return;
+        };
+        let _ = std::fs::create_dir_all(&dir);
+        if let Err(e) = open::that(&dir) {
+            self.status_message = format!("Cannot open {}: {}", dir.display(), e);
+        }
+    }
+
+    /// Add files to the project. Paths already imported (compared after
+    /// canonicalization, so `./a.mp4` and `/abs/a.mp4` match) or repeated in
+    /// the same selection are skipped.
+    pub fn add_files(&mut self, paths: Vec<PathBuf>) {
+        let mut known: std::collections::HashSet<PathBuf> = self.project.files
+            .iter()
+            .map(|f| Self::import_key(&f.path))
+            .collect();
+        let (paths, duplicates): (Vec<PathBuf>, Vec<PathBuf>) = paths
+            .into_iter()
+            .partition(|p| known.insert(Self::import_key(p)));
+
+        // Audio-only files have no frame to extract a thumbnail from
+        let mut video_paths = Vec::new();
+        let mut problems = Vec::new();
+        let mut unreadable = Vec::new();
         for path in &paths {
-            if !self.thumbnails.contains_key(path) {
-                let p = path.clone();
-                let slot = self.thumbnail_loading.clone();
-                std::thread::spawn(move || {
-                    if let Some((data, w, h)) = extract_thumbnail_rgba(&p) {
-                        slot.lock().unwrap().push((p, data, w, h));
-                    }
-                });
+            let Some(mut media_file) = self.probe_file(path) else {
+                unreadable.push(path.file_name().map(|n| n.to_string_lossy().into_owned()).unwrap_or_default());
+                continue;
+            };
+            self.next_file_id += 1;
+            media_file.id = self.next_file_id;
+            if media_file.duration_unknown() {
+                // Reported by `poll_duration_estimates` once the read ends
+                self.estimate_duration(&mut media_file);
+            } else if let Some(problem) = media_file.problem() {
+                problems.push(format!("{} ({})", media_file.filename(), problem));
+            } else if !media_file.is_audio_only() {
+                video_paths.push(path.clone());
             }
+            self.project.files.push(media_file);
+        }
+        if !duplicates.is_empty() {
+            self.status_message = format!("{} file(s) skipped (already imported)", duplicates.len());
+        }
+        if !problems.is_empty() {
+            self.status_message = format!("⚠ Unreadable or corrupt: {}", problems.join(", "));
+        }
+        if !unreadable.is_empty() {
+            self.status_message = format!("⚠ Not a media file FFmpeg can read: {}", unreadable.join(", "));
+        }
+        for path in &video_paths {
+            self.request_thumbnail(path);
         }
         if self.selected_file_index.is_none() && !self.project.files.is_empty() {
             self.selected_file_index = Some(0);
@@ -188,11 +677,77 @@ impl FFmpegApp {
         }
     }
 
+    /// Extract the file list thumbnail in background
+    fn request_thumbnail(&self, path: &std::path::Path) {
+        if self.thumbnails.contains_key(path) {
+            return;
+        }
+        let p = path.to_path_buf();
+        let slot = self.thumbnail_loading.clone();
+        std::thread::spawn(move || {
+            if let Some((data, w, h)) = extract_thumbnail_rgba(&p) {
+                slot.lock().unwrap().push((p, data, w, h));
+            }
+        });
+    }
+
+    /// Read the whole file once in background to find the duration ffprobe
+    /// did not report; the result lands in `poll_duration_estimates`
+    fn estimate_duration(&self, file: &mut MediaFile) {
+        file.estimating_duration = true;
+        let (id, path) = (file.id, file.path.clone());
+        let slot = self.duration_estimates.clone();
+        std::thread::spawn(move || {
+            let duration = crate::ffmpeg::estimate_duration(&path);
+            slot.lock().unwrap().push((id, duration));
+        });
+    }
+
+    /// Store the durations read in background on their file (dropped if it
+    /// was removed meanwhile) and reload the preview if it shows that file
+    pub fn poll_duration_estimates(&mut self) {
+        let results: Vec<(u64, Option<f64>)> = {
+            let Ok(mut slot) = self.duration_estimates.lock() else { return };
+            std::mem::take(&mut *slot)
+        };
+        for (id, duration) in results {
+            let Some(index) = self.project.files.iter().position(|f| f.id == id) else { continue };
+            let file = &mut self.project.files[index];
+            file.estimating_duration = false;
+            if let Some(duration) = duration {
+                file.info.duration = duration;
+            }
+            let (path, name) = (file.path.clone(), file.filename());
+            match file.problem() {
+                Some(problem) => {
+                    self.status_message = format!("⚠ Unreadable or corrupt: {} ({})", name, problem);
+                }
+                None => {
+                    self.status_message = format!("{}: duration {} (read from the whole file)", name, file.duration_string());
+                    if !file.is_audio_only() {
+                        self.request_thumbnail(&path);
+                    }
+                }
+            }
+            if self.selected_file_index == Some(index) {
+                self.load_player_for_selected_file();
+            }
+        }
+    }
+
+    /// Identity used for import dedup; falls back to the raw path when it
+    /// cannot be resolved (e.g. file already gone).
+    fn import_key(path: &std::path::Path) -> PathBuf {
+        std::fs::canonicalize(path).unwrap_or_else(|_| path.to_path_buf())
+    }
+
     fn probe_file(&self, path: &PathBuf) -> Option<MediaFile> {
         match self.ffmpeg.probe(path) {
             Ok(info) => Some(MediaFile {
                 path: path.clone(),
                 info,
+                id: 0,
+                estimating_duration: false,
             }),
             Err(e) => {
                 eprintln!("Failed to probe file {:?}: {}", path, e);
@@ -201,89 +756,169 @@ impl FFmpegApp {
         }
     }
 
+    /// Loaded file with this import id (`None` once it has been removed)
+    fn file_by_id(&self, id: u64) -> Option<&MediaFile> {
+        self.project.files.iter().find(|f| f.id == id)
+    }
+
+    /// Still part of the project: background results for other paths are dropped
+    fn is_loaded(&self, path: &std::path::Path) -> bool {
+        self.project.files.iter().any(|f| f.path == path)
+    }
+
     pub fn selected_file(&self) -> Option<&MediaFile> {
         self.selected_file_index
             .and_then(|i| self.project.files.get(i))
     }
 
+    /// Selected file has no video stream (waveform preview, no video tools)
+    pub fn selected_is_audio_only(&self) -> bool {
+        self.selected_file().is_some_and(|f| f.is_audio_only())
+    }
+
+    /// Why the video preview of the selected file could not be decoded
+    pub fn preview_error(&self) -> Option<String> {
+        self.player.as_ref().and_then(|p| p.preview_error())
+    }
+
     pub fn select_file(&mut self, index: usize) {
         if index < self.project.files.len() {
             self.save_current_segments();
             self.selected_file_index = Some(index);
             self.load_player_for_selected_file();
+            self.apply_default_trim_mode();
         }
     }
 
-    pub fn remove_selected_file(&mut self) {
-        if let Some(index) = self.selected_file_index {
-            if index < self.project.files.len() {
-                // Remove from per-file map
-                let path = self.project.files[index].path.clone();
-                self.file_segments.remove(&path);
-
-                self.stop_player();
-                self.player = None;
-
-                self.project.files.remove(index);
-                self.segments.clear();
-                self.selected_segment = None;
-
-                if self.project.files.is_empty() {
-                    self.selected_file_index = None;
-                } else if index >= self.project.files.len() {
-                    self.selected_file_index = Some(self.project.files.len() - 1);
-                }
+    /// Loudness normalization needs re-encoded audio: switch a stream-copy
+    /// mode to Precise when it is turned on
+    pub fn set_normalize_on_export(&mut self, enabled: bool) {
+        self.export_settings.normalize_loudness = enabled;
+        if enabled && !self.split_settings.trim_mode.is_reencode() {
+            self.split_settings.trim_mode = TrimMode::Precise;
+            self.status_message = "Normalize on export re-encodes: switched to Precise mode (slower than lossless)".to_string();
+        }
+    }
 
-                if self.selected_file_index.is_some() {
-                    self.load_player_for_selected_file();
-                }
+    /// Switch to the trim mode configured for the selected file's type, if any
+    fn apply_default_trim_mode(&mut self) {
+        let Some(file) = self.selected_file() else { return };
+        let ext = file.path.extension().unwrap_or_default().to_string_lossy().to_string();
+        let Some(mode) = self.split_settings.default_trim_mode(&ext, file.info.video_codec.as_deref()) else { return };
+        if mode != self.split_settings.trim_mode {
+            self.split_settings.trim_mode = mode;
+            self.status_message = format!("{} - trim mode: {}", self.status_message, mode.name());
+        }
+    }
 
-                self.sync_merge_order();
-            }
+    /// Remember the current trim mode for files of the selected file's type
+    /// (its video codec, else its extension)
+    pub fn set_default_trim_mode_for_selected(&mut self) {
+        let Some(file) = self.selected_file() else { return };
+        let source = match &file.info.video_codec {
+            Some(codec) => codec.clone(),
+            None => file.path.extension().unwrap_or_default().to_string_lossy().to_lowercase(),
+        };
+        let mode = self.split_settings.trim_mode;
+        let rules = &mut self.split_settings.trim_mode_rules;
+        match rules.iter_mut().find(|r| r.source.eq_ignore_ascii_case(&source)) {
+            Some(rule) => rule.mode = mode,
+            None => rules.push(crate::ui::TrimModeRule { source: source.clone(), mode }),
         }
+        self.status_message = format!("{} files will start in {}", source, mode.name());
     }
 
     /// Load media player for the currently selected file
     pub fn load_player_for_selected_file(&mut self) {
-        let file_info = self.selected_file().map(|f| (f.path.clone(), f.filename()));
+        let file_info = self.selected_file().map(|f| (f.path.clone(), f.filename(), f.info.clone()));
 
-        if let Some((path, filename)) = file_info {
-            match MediaPlayer::new(&path) {
-                Ok(player) => {
+        if let Some((path, filename, info)) = file_info {
+            // Tear the previous player down first: its decoder thread is joined
+            // and its audio extraction killed before the new ones start, and
+            // its last frame no longer shows while the new file loads
+            self.player = None;
+            self.preview_texture = None;
+            match MediaPlayer::new(&path, &info, self.preview_hwaccel()) {
+                Ok(mut player) => {
+                    player.set_audio_offset(self.audio_offset_secs());
+                    player.set_fast_scrub(self.fast_scrub_high_res);
+                    player.set_low_power(self.low_power);
+                    player.set_prefetch(self.prefetch);
+                    // Audio-only: the waveform is the preview, never deferred
+                    let defer = self.defer_playback_init && !player.is_audio_only();
+                    if !defer {
+                        player.prepare_playback(self.effective_volume());
+                    }
+                    if self.live_filter_sent.is_some() {
+                        player.set_preview_filter(self.live_filter_sent.clone());
+                    }
                     self.player = Some(player);
                     self.current_time = 0.0;
                     self.last_frame_pts = -1.0;
-                    self.in_point = None;
-                    self.out_point = None;
+                    self.clear_in_out_points();
                     // Restore segments from per-file map (or empty)
                     self.segments = self.file_segments.get(&path).cloned().unwrap_or_default();
-                    self.selected_segment = if self.segments.is_empty() { None } else { Some(0) };
-
-                    // Load waveform: from cache or start background extraction
-                    if let Some(peaks) = self.waveform_peaks.get(&path) {
-                        self.current_waveform = peaks.clone();
-                    } else {
-                        self.current_waveform.clear();
-                        let slot = self.waveform_loading.clone();
-                        let path_clone = path.clone();
-                        std::thread::spawn(move || {
-                            let peaks = extract_waveform_peaks(&path_clone);
-                            *slot.lock().unwrap() = Some((path_clone, peaks));
-                        });
+                    self.select_segment(if self.segments.is_empty() { None } else { Some(0) });
+
+                    self.load_status = LoadStatus {
+                        filename,
+                        preview: self.player.as_ref().is_some_and(|p| p.has_video()),
+                        waveform: !defer && !self.waveform_peaks.contains_key(&path),
+                        shown: String::new(),
+                    };
+                    self.current_waveform.clear();
+                    if !defer {
+                        self.load_waveform(&path);
                     }
 
-                    self.status_message = format!("Loaded: {}", filename);
+                    self.status_message = self.load_status.message();
+                    self.load_status.shown = self.status_message.clone();
                 }
                 Err(e) => {
                     self.status_message = format!("Failed to load player: {}", e);
                     self.player = None;
+                    self.load_status = LoadStatus::default();
                 }
             }
         }
     }
 
+    /// Waveform of `path` for the timeline: from the cache, or extracted in
+    /// the background (`poll_waveform` stores it)
+    fn load_waveform(&mut self, path: &std::path::Path) {
+        if let Some(peaks) = self.waveform_peaks.get(path) {
+            self.current_waveform = peaks.clone();
+            return;
+        }
+        let slot = self.waveform_loading.clone();
+        let path = path.to_path_buf();
+        std::thread::spawn(move || {
+            let peaks = extract_waveform_peaks(&path);
+            *slot.lock().unwrap() = Some((path, peaks));
+        });
+    }
+
+    /// Second half of a deferred load (`defer_playback_init`): open the
+    /// audio and start the waveform before the first Play
+    fn prepare_playback(&mut self) {
+        let volume = self.effective_volume();
+        let Some(player) = self.player.as_mut() else { return };
+        if player.is_playback_prepared() {
+            return;
+        }
+        player.prepare_playback(volume);
+        let Some(path) = self.selected_file().map(|f| f.path.clone()) else { return };
+        if !self.waveform_peaks.contains_key(&path) {
+            self.load_status.waveform = true;
+            self.status_message = self.load_status.message();
+            self.load_status.shown = self.status_message.clone();
+        }
+        self.load_waveform(&path);
+    }
+
     // Player controls
     pub fn play(&mut self) {
+        self.prepare_playback();
         if let Some(ref player) = self.player {
             player.play();
         }
@@ -296,6 +931,7 @@ impl FFmpegApp {
     }
 
     pub fn toggle_play_pause(&mut self) {
+        self.prepare_playback();
         if let Some(ref player) = self.player {
             player.toggle_play_pause();
         }
@@ -324,13 +960,112 @@ impl FFmpegApp {
         self.seek(new_time);
     }
 
+    /// Set the volume; moving it while muted unmutes
     pub fn set_volume(&mut self, vol: f32) {
         self.volume = vol.clamp(0.0, 2.0);
+        self.muted = false;
+        self.apply_volume();
+    }
+
+    pub fn toggle_mute(&mut self) {
+        self.muted = !self.muted;
+        self.apply_volume();
+        self.status_message = if self.muted { "Muted".to_string() } else { "Unmuted".to_string() };
+    }
+
+    /// Volume sent to the players: 0 while muted
+    fn effective_volume(&self) -> f32 {
+        if self.muted { 0.0 } else { self.volume }
+    }
+
+    fn apply_volume(&mut self) {
+        if let Some(ref player) = self.player {
+            player.set_volume(self.effective_volume());
+        }
+    }
+
+    pub fn set_fast_scrub_high_res(&mut self, enabled: bool) {
+        self.fast_scrub_high_res = enabled;
+        if let Some(ref player) = self.player {
+            player.set_fast_scrub(enabled);
+        }
+    }
+
+    /// `-hwaccel` method for the preview: the build's preferred one once the
+    /// capabilities are known, FFmpeg's own pick (`auto`) until then
+    fn preview_hwaccel(&self) -> Option<String> {
+        if !self.hw_decode {
+            return None;
+        }
+        match &self.capabilities {
+            Some(Ok(caps)) => caps.preferred_hwaccel().map(str::to_string),
+            _ => Some("auto".to_string()),
+        }
+    }
+
+    /// Export into `folder` (`None` = next to each source) and put it at the
+    /// top of the recent folders
+    pub fn set_output_folder(&mut self, folder: Option<PathBuf>) {
+        if let Some(folder) = &folder {
+            crate::utils::remember_folder(&mut self.recent_output_folders, folder);
+            self.recent_output_folders.retain(|f| f.is_dir());
+        }
+        self.split_settings.output_folder = folder;
+    }
+
+    /// Recent output folders other than the current one that still exist
+    pub fn recent_output_choices(&self) -> Vec<PathBuf> {
+        self.recent_output_folders.iter()
+            .filter(|f| Some(*f) != self.split_settings.output_folder.as_ref() && f.is_dir())
+            .cloned()
+            .collect()
+    }
+
+    pub fn set_hw_decode(&mut self, enabled: bool) {
+        self.hw_decode = enabled;
+        let hwaccel = self.preview_hwaccel();
+        if let Some(ref player) = self.player {
+            player.set_hwaccel(hwaccel);
+        }
+    }
+
+    pub fn set_low_power(&mut self, enabled: bool) {
+        self.low_power = enabled;
+        if let Some(ref player) = self.player {
+            player.set_low_power(enabled);
+        }
+    }
+
+    pub fn set_prefetch(&mut self, settings: crate::player::PrefetchSettings) {
+        self.prefetch = settings;
         if let Some(ref player) = self.player {
-            player.set_volume(self.volume);
+            player.set_prefetch(settings);
+        }
+    }
+
+    /// Retry opening the audio device for the current player
+    pub fn reinit_audio(&mut self) {
+        let volume = self.effective_volume();
+        let Some(player) = self.player.as_mut() else { return };
+        self.status_message = match player.reinit_audio(volume) {
+            Ok(()) => "Audio reinitialized".to_string(),
+            Err(e) => format!("Audio still unavailable: {}", e),
+        };
+    }
+
+    /// Set the export audio offset and apply it to the preview player
+    pub fn set_audio_offset_ms(&mut self, offset_ms: Option<i64>) {
+        self.export_settings.audio_offset_ms = offset_ms.filter(|ms| *ms != 0);
+        let offset = self.audio_offset_secs();
+        if let Some(ref mut player) = self.player {
+            player.set_audio_offset(offset);
         }
     }
 
+    fn audio_offset_secs(&self) -> f64 {
+        self.export_settings.audio_offset_ms.unwrap_or(0) as f64 / 1000.0
+    }
+
     pub fn get_playback_state(&self) -> PlaybackState {
         self.player
             .as_ref()
@@ -344,20 +1079,142 @@ impl FFmpegApp {
 
     // In/Out points for trimming
     pub fn set_in_point(&mut self) {
-        self.in_point = Some(self.current_time);
-        self.trim_settings.start_time = self.current_time;
-        self.trim_settings.start_time_str = crate::utils::format_time(self.current_time);
+        self.set_in_point_at(self.current_time);
     }
 
     pub fn set_out_point(&mut self) {
-        self.out_point = Some(self.current_time);
-        self.trim_settings.end_time = self.current_time;
-        self.trim_settings.end_time_str = crate::utils::format_time(self.current_time);
+        self.set_out_point_at(self.current_time);
+    }
+
+    /// Set the IN marker to an absolute time (keeps trim settings in sync)
+    pub fn set_in_point_at(&mut self, time: f64) {
+        let time = self.clamp_to_duration(time);
+        self.in_point = Some(time);
+        self.active_handle = Some(TrimHandle::In);
+        self.trim_settings.start_time = time;
+        self.trim_settings.start_time_str = crate::utils::format_time(time);
+    }
+
+    /// Set the OUT marker to an absolute time (keeps trim settings in sync)
+    pub fn set_out_point_at(&mut self, time: f64) {
+        let time = self.clamp_to_duration(time);
+        self.out_point = Some(time);
+        self.active_handle = Some(TrimHandle::Out);
+        self.trim_settings.end_time = time;
+        self.trim_settings.end_time_str = crate::utils::format_time(time);
+    }
+
+    /// Set both markers at once (dual-handle trim slider)
+    pub fn set_in_out_range(&mut self, start: f64, end: f64) {
+        let (start, end) = if start <= end { (start, end) } else { (end, start) };
+        self.set_out_point_at(end);
+        self.set_in_point_at(start);
+    }
+
+    /// Move the active handle (last set, or nearest to the playhead) by `frames`.
+    /// The playhead follows so the new boundary frame is shown.
+    pub fn nudge_active_handle(&mut self, frames: i32) {
+        let handle = match (self.in_point, self.out_point) {
+            (None, None) => {
+                self.status_message = "Set an IN or OUT point first".to_string();
+                return;
+            }
+            (Some(_), None) => TrimHandle::In,
+            (None, Some(_)) => TrimHandle::Out,
+            (Some(i), Some(o)) => self.active_handle.unwrap_or(
+                if (self.current_time - i).abs() <= (self.current_time - o).abs() {
+                    TrimHandle::In
+                } else {
+                    TrimHandle::Out
+                },
+            ),
+        };
+
+        let fps = self.player.as_ref().map(|p| p.framerate)
+            .or_else(|| self.selected_file().and_then(|f| f.info.framerate))
+            .filter(|f| *f > 0.0)
+            .unwrap_or(30.0);
+        let delta = frames as f64 / fps;
+
+        if self.get_playback_state() == PlaybackState::Playing {
+            self.pause();
+        }
+        let new_time = match handle {
+            TrimHandle::In => {
+                let t = self.in_point.unwrap_or(self.current_time) + delta;
+                self.set_in_point_at(t);
+                self.in_point.unwrap_or(t)
+            }
+            TrimHandle::Out => {
+                let t = self.out_point.unwrap_or(self.current_time) + delta;
+                self.set_out_point_at(t);
+                self.out_point.unwrap_or(t)
+            }
+        };
+        self.seek(new_time);
+    }
+
+    /// Apply the typed IN timecode (`trim_settings.start_time_str`) to the
+    /// marker. An empty field clears it; a time at or after OUT is refused.
+    pub fn apply_typed_in_point(&mut self) {
+        let text = self.trim_settings.start_time_str.trim();
+        if text.is_empty() {
+            self.in_point = None;
+            self.trim_settings.start_time = 0.0;
+            return;
+        }
+        let restore = self.in_point.map(crate::utils::format_time).unwrap_or_default();
+        match crate::utils::parse_time(text).map(|t| self.clamp_to_duration(t)) {
+            Some(t) if self.out_point.is_some_and(|out| t >= out) => {
+                self.status_message = format!("IN {} must be before OUT", crate::utils::format_time(t));
+                self.trim_settings.start_time_str = restore;
+            }
+            Some(t) => self.set_in_point_at(t),
+            None => {
+                self.status_message = format!("Invalid IN timecode: {}", self.trim_settings.start_time_str);
+                self.trim_settings.start_time_str = restore;
+            }
+        }
+    }
+
+    /// Apply the typed OUT timecode (`trim_settings.end_time_str`) to the
+    /// marker. An empty field clears it; a time at or before IN is refused.
+    pub fn apply_typed_out_point(&mut self) {
+        let text = self.trim_settings.end_time_str.trim();
+        if text.is_empty() {
+            self.out_point = None;
+            self.trim_settings.end_time = self.get_duration();
+            return;
+        }
+        let restore = self.out_point.map(crate::utils::format_time).unwrap_or_default();
+        match crate::utils::parse_time(text).map(|t| self.clamp_to_duration(t)) {
+            Some(t) if self.in_point.is_some_and(|inp| t <= inp) => {
+                self.status_message = format!("OUT {} must be after IN", crate::utils::format_time(t));
+                self.trim_settings.end_time_str = restore;
+            }
+            Some(t) => self.set_out_point_at(t),
+            None => {
+                self.status_message = format!("Invalid OUT timecode: {}", self.trim_settings.end_time_str);
+                self.trim_settings.end_time_str = restore;
+            }
+        }
+    }
+
+    fn clamp_to_duration(&self, time: f64) -> f64 {
+        let duration = self.get_duration();
+        if duration > 0.0 {
+            time.clamp(0.0, duration)
+        } else {
+            time.max(0.0)
+        }
     }
 
     pub fn clear_in_out_points(&mut self) {
         self.in_point = None;
         self.out_point = None;
+        self.active_handle = None;
+        self.trim_settings.start_time_str.clear();
+        self.trim_settings.end_time_str.clear();
     }
 
     // ---- Segment management ----
@@ -376,39 +1233,79 @@ impl FFmpegApp {
         let mut segment = SplitSegment::new(
             in_pt,
             out_pt,
-            format!("Segment {}", index),
+            self.split_settings.segment_label(index, index),
         );
 
         // Estimate size
-        if let Some(file) = self.selected_file() {
-            segment.estimated_size_bytes =
-                crate::utils::estimate_segment_size(&file.info, in_pt, out_pt);
+        if let Some(path) = self.selected_file().map(|f| f.path.clone()) {
+            segment.estimated_size_bytes = self.estimate_segment_size_best(&path, in_pt, out_pt);
         }
 
         self.segments.push(segment);
-        self.selected_segment = Some(self.segments.len() - 1);
+        self.select_segment(Some(self.segments.len() - 1));
 
         // Reset in/out points for next segment
-        self.in_point = None;
-        self.out_point = None;
+        self.clear_in_out_points();
 
         self.status_message = format!("{} segment(s) defined", self.segments.len());
     }
 
+    /// Apply the typed start/end timecodes (`segment_time_edit`) to a segment
+    pub fn apply_segment_time_edit(&mut self, index: usize) {
+        let (start_str, end_str) = self.segment_time_edit.clone();
+        let (Some(start), Some(end)) = (
+            crate::utils::parse_time(start_str.trim()),
+            crate::utils::parse_time(end_str.trim()),
+        ) else {
+            self.status_message = "Invalid segment timecode".to_string();
+            return;
+        };
+        if let Err(e) = self.set_segment_times(index, start, end) {
+            self.status_message = e;
+        }
+    }
+
+    /// Set a segment's boundaries, clamped to the file duration.
+    /// Rejects empty or inverted ranges. Size is re-estimated.
+    pub fn set_segment_times(&mut self, index: usize, start: f64, end: f64) -> Result<(), String> {
+        let start = self.clamp_to_duration(start);
+        let end = self.clamp_to_duration(end);
+        if end <= start {
+            return Err("Segment end must be after its start".to_string());
+        }
+        let path = self.selected_file().map(|f| f.path.clone());
+        let size = path.map(|p| self.estimate_segment_size_best(&p, start, end)).unwrap_or(0);
+        let Some(seg) = self.segments.get_mut(index) else {
+            return Err("No such segment".to_string());
+        };
+        seg.start_time = start;
+        seg.end_time = end;
+        seg.estimated_size_bytes = size;
+        Ok(())
+    }
+
+    /// Renumber every segment with `split_settings.segment_label_format`
+    /// after the list changed shape
+    pub fn relabel_segments(&mut self) {
+        let total = self.segments.len();
+        for (i, seg) in self.segments.iter_mut().enumerate() {
+            seg.label = self.split_settings.segment_label(i + 1, total);
+        }
+    }
+
     /// Remove a segment by index
     pub fn remove_segment(&mut self, index: usize) {
         if index < self.segments.len() {
             self.segments.remove(index);
+            self.selected_segments.clear();
             // Re-label segments
-            for (i, seg) in self.segments.iter_mut().enumerate() {
-                seg.label = format!("Segment {}", i + 1);
-            }
+            self.relabel_segments();
             // Adjust selection
             if self.segments.is_empty() {
-                self.selected_segment = None;
+                self.select_segment(None);
             } else if let Some(sel) = self.selected_segment {
                 if sel >= self.segments.len() {
-                    self.selected_segment = Some(self.segments.len() - 1);
+                    self.select_segment(Some(self.segments.len() - 1));
                 }
             }
         }
@@ -434,130 +1331,128 @@ impl FFmpegApp {
         self.segments.splice(index..=index, [first_half, second_half]);
 
         // Re-label all segments and recalculate sizes using real bitrate data
-        for (i, s) in self.segments.iter_mut().enumerate() {
-            s.label = format!("Segment {}", i + 1);
+        self.relabel_segments();
+        self.recalculate_sizes();
+
+        self.select_segment(Some(index));
+        self.status_message = format!("Segment split into {} segments", self.segments.len());
+    }
+
+    /// Join a segment with the following one (start of the first, end of the second)
+    pub fn merge_segment_with_next(&mut self, index: usize) {
+        if index + 1 >= self.segments.len() {
+            self.status_message = "No next segment to merge with".to_string();
+            return;
         }
-        if let Some(file) = self.selected_file() {
-            let path = file.path.clone();
-            let info = file.info.clone();
-            let bmap = self.bitrate_maps.get(&path);
-            for s in &mut self.segments {
-                if let Some(bm) = bmap {
-                    s.estimated_size_bytes = bm.bytes_between(s.start_time, s.end_time);
-                } else {
-                    s.estimated_size_bytes =
-                        crate::utils::estimate_segment_size(&info, s.start_time, s.end_time);
-                }
-            }
+        // Names set with `rename_segment` are kept; only generated labels
+        // are renumbered (the merged segment keeps the first one's name)
+        let total = self.segments.len();
+        let mut generated: Vec<bool> = self.segments.iter().enumerate()
+            .map(|(i, seg)| seg.label == self.split_settings.segment_label(i + 1, total))
+            .collect();
+        generated.remove(index + 1);
+        let next = self.segments.remove(index + 1);
+        let seg = &mut self.segments[index];
+        seg.start_time = seg.start_time.min(next.start_time);
+        seg.end_time = seg.end_time.max(next.end_time);
+        seg.enabled |= next.enabled;
+
+        let total = self.segments.len();
+        for (i, seg) in self.segments.iter_mut().enumerate().filter(|(i, _)| generated[*i]) {
+            seg.label = self.split_settings.segment_label(i + 1, total);
         }
+        self.recalculate_sizes();
 
-        self.selected_segment = Some(index);
-        self.status_message = format!("Segment split into {} segments", self.segments.len());
+        self.select_segment(Some(index));
+        self.status_message = format!("Segments merged, {} left", self.segments.len());
     }
 
-    /// Recalculate estimated sizes for all segments
-    pub fn recalculate_sizes(&mut self) {
-        if let Some(file) = self.selected_file() {
-            let info = file.info.clone();
-            for seg in &mut self.segments {
-                seg.estimated_size_bytes =
-                    crate::utils::estimate_segment_size(&info, seg.start_time, seg.end_time);
-            }
+    /// Drag a segment edge to `time`. With `rolling`, the neighbour sharing
+    /// that edge moves too, so auto-cut segments keep covering the file.
+    pub fn move_segment_boundary(&mut self, index: usize, is_start: bool, time: f64, rolling: bool) {
+        let duration = self.get_duration();
+        if !crate::utils::move_segment_boundary(&mut self.segments, index, is_start, time, rolling, duration) {
+            return;
         }
+        self.recalculate_sizes();
+        let seg = &self.segments[index];
+        let edge = if is_start { seg.start_time } else { seg.end_time };
+        self.status_message = format!(
+            "{} {} of {} at {}",
+            if rolling { "Rolled" } else { "Moved" },
+            if is_start { "start" } else { "end" },
+            seg.label,
+            crate::utils::format_time(edge),
+        );
     }
 
-    /// Auto-split a segment that exceeds max_bytes into smaller sub-segments.
-    /// Uses the bitrate map (cumulative real byte sums) when available,
-    /// falls back to uniform bitrate estimate otherwise.
-    fn auto_split_segment(
-        segment: &SplitSegment,
-        max_bytes: u64,
-        bitrate_bps: f64,
-        bitrate_map: Option<&BitrateMap>,
-    ) -> Vec<SplitSegment> {
-        if max_bytes == 0 {
-            return vec![segment.clone()];
+    /// Rename a segment (used as the export job label)
+    pub fn rename_segment(&mut self, index: usize, label: &str) {
+        let label = label.trim();
+        if let Some(seg) = self.segments.get_mut(index).filter(|_| !label.is_empty()) {
+            seg.label = label.to_string();
         }
+    }
 
-        // Get the real size from the bitrate map if available
-        let real_size = bitrate_map
-            .map(|bm| bm.bytes_between(segment.start_time, segment.end_time))
-            .unwrap_or(segment.estimated_size_bytes);
+    /// Recalculate estimated sizes for all segments
+    pub fn recalculate_sizes(&mut self) {
+        let Some(path) = self.selected_file().map(|f| f.path.clone()) else { return };
+        let sizes: Vec<u64> = self.segments.iter()
+            .map(|seg| self.estimate_segment_size_best(&path, seg.start_time, seg.end_time))
+            .collect();
+        for (seg, size) in self.segments.iter_mut().zip(sizes) {
+            seg.estimated_size_bytes = size;
+        }
+    }
 
-        if real_size <= max_bytes {
-            return vec![segment.clone()];
+    /// Best available size estimate for `[start, end]` of a file: real byte sums
+    /// from the cached bitrate map when present, uniform bitrate otherwise.
+    pub fn estimate_segment_size_best(&self, path: &std::path::Path, start: f64, end: f64) -> u64 {
+        if let Some(bm) = self.bitrate_maps.get(path).filter(|bm| !bm.is_empty()) {
+            return bm.bytes_between(start, end);
         }
+        self.project.files.iter()
+            .find(|f| f.path == path)
+            .map(|f| crate::utils::estimate_segment_size(&f.info, start, end))
+            .unwrap_or(0)
+    }
 
-        let effective_max = (max_bytes as f64 * 0.98) as u64; // 2% safety margin
-
-        if let Some(bm) = bitrate_map {
-            // --- Bitrate-map path: cut by cumulative byte sum ---
-            let mut result = Vec::new();
-            let mut cursor = segment.start_time;
-            let mut part = 1;
-
-            while cursor < segment.end_time - 0.1 {
-                // Walk forward until we've accumulated effective_max bytes
-                let cut = bm.time_for_bytes(cursor, effective_max).min(segment.end_time);
-
-                // Ensure we always advance at least 1 second
-                let cut = if cut <= cursor + 0.5 {
-                    (cursor + 1.0).min(segment.end_time)
-                } else {
-                    cut
-                };
-
-                let end = if segment.end_time - cut < 1.0 { segment.end_time } else { cut };
-
-                let mut sub = SplitSegment::new(
-                    cursor,
-                    end,
-                    format!("{} ({}/...)", segment.label, part),
-                );
-                sub.enabled = segment.enabled;
-                sub.estimated_size_bytes = bm.bytes_between(cursor, end);
-                result.push(sub);
-
-                cursor = end;
-                part += 1;
-
-                if end >= segment.end_time - 0.1 {
-                    break;
-                }
-            }
+    /// Snap every segment boundary of the current file to the nearest frame time
+    pub fn snap_segments_to_frames(&mut self) {
+        let Some(fps) = self.selected_file().and_then(|f| f.info.framerate) else {
+            self.status_message = "Unknown framerate, cannot snap segments".to_string();
+            return;
+        };
+        let duration = self.get_duration();
+        let changed = Self::snap_segment_list(self.segments.iter_mut(), fps, duration);
+        self.recalculate_sizes();
+        self.status_message = format!("Snapped {} segment(s) to {:.3} fps frame grid", changed, fps);
+    }
 
-            // Fix labels now that we know the total part count
-            let total = result.len();
-            for (i, s) in result.iter_mut().enumerate() {
-                s.label = format!("{} ({}/{})", segment.label, i + 1, total);
+    /// Snap boundaries in place, keeping at least one frame per segment and
+    /// never past `duration` (when known). Returns the number of segments that moved.
+    fn snap_segment_list<'a>(
+        segments: impl IntoIterator<Item = &'a mut SplitSegment>,
+        fps: f64,
+        duration: f64,
+    ) -> usize {
+        let frame = 1.0 / fps;
+        let limit = if duration > 0.0 { duration } else { f64::INFINITY };
+        let mut changed = 0;
+        for seg in segments {
+            let mut start = crate::utils::snap_to_frame(seg.start_time, fps).min(limit);
+            let mut end = crate::utils::snap_to_frame(seg.end_time, fps).min(limit);
+            if end <= start {
+                end = (start + frame).min(limit);
+                start = start.min((end - frame).max(0.0));
             }
-
-            result
-        } else {
-            // --- Fallback: uniform bitrate estimate ---
-            let bytes_per_second = bitrate_bps / 8.0;
-            if bytes_per_second <= 0.0 {
-                return vec![segment.clone()];
-            }
-            let max_duration = effective_max as f64 / bytes_per_second;
-            let total_duration = segment.duration();
-            let num_parts = (total_duration / max_duration).ceil() as usize;
-
-            let mut result = Vec::new();
-            for i in 0..num_parts {
-                let start = segment.start_time + i as f64 * max_duration;
-                let end = (segment.start_time + (i + 1) as f64 * max_duration).min(segment.end_time);
-                let mut sub = SplitSegment::new(
-                    start,
-                    end,
-                    format!("{} ({}/{})", segment.label, i + 1, num_parts),
-                );
-                sub.enabled = segment.enabled;
-                sub.estimated_size_bytes = (bytes_per_second * (end - start)) as u64;
-                result.push(sub);
+            if (start - seg.start_time).abs() > 1e-9 || (end - seg.end_time).abs() > 1e-9 {
+                changed += 1;
             }
-            result
+            seg.start_time = start;
+            seg.end_time = end;
         }
+        changed
     }
 
     /// Start automatic silence-aware cutting.
@@ -570,6 +1465,10 @@ impl FFmpegApp {
                 return;
             }
         };
+        if file.duration_unknown() {
+            self.status_message = format!("{}: duration unknown, remux it first for Auto-Cut", file.filename());
+            return;
+        }
 
         if self.split_settings.max_size_mb <= 0.0 {
             self.status_message = "Set max size > 0 for Auto-Cut".to_string();
@@ -578,6 +1477,7 @@ impl FFmpegApp {
 
         let input_path = file.path.clone();
         let file_duration = file.info.duration;
+        self.auto_cut_target = file.id;
         let ffmpeg = self.ffmpeg.clone();
         let silence_slot = self.auto_cut_silences.clone();
         let bitrate_slot = self.auto_cut_bitrate_map.clone();
@@ -604,6 +1504,266 @@ impl FFmpegApp {
         });
     }
 
+    /// Start scene detection on the selected file; `poll_scene_split` turns
+    /// the detected cuts into one segment per scene.
+    pub fn start_scene_split(&mut self) {
+        let Some(file) = self.selected_file() else {
+            self.status_message = "No file selected".to_string();
+            return;
+        };
+
+        let input_path = file.path.clone();
+        let file_id = file.id;
+        let file_duration = file.info.duration;
+        let need_bitrate_map = !self.bitrate_maps.contains_key(&input_path);
+        let ffmpeg = self.ffmpeg.clone();
+        let threshold = self.scene_threshold;
+        let slot = self.scene_results.clone();
+
+        *slot.lock().unwrap() = None;
+        self.scene_split_running = true;
+        self.status_message = "Scene split: detecting scene changes...".to_string();
+
+        self.runtime.spawn(async move {
+            let scenes = ffmpeg.detect_scenes(&input_path, threshold).await.map_err(|e| e.to_string());
+            let bmap = if need_bitrate_map && scenes.is_ok() {
+                let path = input_path.clone();
+                tokio::task::spawn_blocking(move || extract_bitrate_map(&path, file_duration))
+                    .await
+                    .ok()
+            } else {
+                None
+            };
+            *slot.lock().unwrap() = Some((file_id, scenes, bmap));
+        });
+    }
+
+    /// Detect silences on the selected file; `poll_silence_ends` moves IN/OUT
+    /// past the dead air at its very start and end
+    pub fn start_trim_silence_ends(&mut self) {
+        let Some(file) = self.selected_file() else {
+            self.status_message = "No file selected".to_string();
+            return;
+        };
+        let input_path = file.path.clone();
+        let file_id = file.id;
+        let ffmpeg = self.ffmpeg.clone();
+        let slot = self.silence_ends_result.clone();
+
+        *slot.lock().unwrap() = None;
+        self.silence_ends_running = true;
+        self.status_message = "Trim silence ends: detecting silence...".to_string();
+
+        self.runtime.spawn(async move {
+            // Same thresholds as Auto-Cut
+            let silences = ffmpeg.detect_silence(&input_path, -30.0, 0.3).await.map_err(|e| e.to_string());
+            *slot.lock().unwrap() = Some((file_id, silences));
+        });
+    }
+
+    /// Build a short preview montage of the selected file (`proof_sheet_settings`
+    /// clips spread over its duration) as `<stem>_proof.<ext>`
+    pub fn start_proof_sheet(&mut self) {
+        let Some(file) = self.selected_file() else {
+            self.status_message = "No file selected".to_string();
+            return;
+        };
+        if let Err(e) = file.export_blocker() {
+            self.status_message = e;
+            return;
+        }
+        let settings = self.proof_sheet_settings;
+        let clips = crate::utils::proof_sheet_clips(file.info.duration, settings.clip_count, settings.clip_length);
+        if clips.is_empty() {
+            self.status_message = "Proof sheet: set a clip count and length > 0".to_string();
+            return;
+        }
+
+        let input_path = file.path.clone();
+        let stem = input_path.file_stem().unwrap_or_default().to_string_lossy().to_string();
+        let ext = self.export_settings.output_extension(&input_path);
+        let folder = self.split_settings.output_folder.clone()
+            .unwrap_or_else(|| input_path.parent().unwrap_or(std::path::Path::new(".")).to_path_buf());
+        let planned = folder.join(format!("{}_proof.{}", stem, ext));
+        let policy = self.split_settings.conflict_policy;
+        let output = {
+            let Ok(queue) = self.export_queue.lock() else { return };
+            queue.plan_output(&planned, policy)
+        };
+        // Runs outside the queue: nothing would skip a file already on disk
+        let output = output.filter(|o| policy != ConflictPolicy::Skip || !o.exists());
+        let Some(output) = output else {
+            self.status_message = format!("Proof sheet skipped: {} already exists", planned.display());
+            return;
+        };
+        if let Err(e) = std::fs::create_dir_all(&folder) {
+            self.status_message = format!("Cannot create output folder: {}", e);
+            return;
+        }
+
+        let ffmpeg = self.ffmpeg.clone();
+        let slot = self.proof_sheet_result.clone();
+        *slot.lock().unwrap() = None;
+        self.proof_sheet_running = true;
+        self.status_message = format!("Proof sheet: cutting {} clips...", clips.len());
+
+        self.runtime.spawn(async move {
+            let result = ffmpeg
+                .proof_sheet(&input_path, &output, &clips, &ExportSettings::default())
+                .await
+                .map(|_| output)
+                .map_err(|e| e.to_string());
+            *slot.lock().unwrap() = Some(result);
+        });
+    }
+
+    /// Measure the selected file's loudness in the background (result cached)
+    pub fn start_loudness_analysis(&mut self) {
+        if self.loudness_running.is_some() {
+            return;
+        }
+        let Some(path) = self.selected_file().map(|f| f.path.clone()) else { return };
+
+        let ffmpeg = self.ffmpeg.clone();
+        let slot = self.loudness_result.clone();
+        *slot.lock().unwrap() = None;
+        self.loudness_running = Some(path.clone());
+        self.status_message = "Measuring loudness...".to_string();
+
+        self.runtime.spawn(async move {
+            let result = ffmpeg.analyze_loudness(&path).await.map_err(|e| e.to_string());
+            *slot.lock().unwrap() = Some((path, result));
+        });
+    }
+
+    /// Called every frame: store a finished loudness measurement
+    pub fn poll_loudness(&mut self) {
+        if self.loudness_running.is_none() {
+            return;
+        }
+        let Some((path, result)) = self.loudness_result.lock().ok().and_then(|mut g| g.take()) else {
+            return;
+        };
+        self.loudness_running = None;
+        self.status_message = match &result {
+            Ok(info) => format!(
+                "Loudness: {:.1} LUFS, peak {:.1} dBTP, LRA {:.1} LU",
+                info.integrated_lufs, info.true_peak_db, info.lra
+            ),
+            Err(e) => format!("Loudness analysis failed: {}", e),
+        };
+        if self.is_loaded(&path) {
+            self.loudness.insert(path, result);
+        }
+    }
+
+    /// Called every frame: report the finished proof sheet
+    pub fn poll_proof_sheet(&mut self) {
+        if !self.proof_sheet_running {
+            return;
+        }
+        let Some(result) = self.proof_sheet_result.lock().ok().and_then(|mut g| g.take()) else {
+            return;
+        };
+        self.proof_sheet_running = false;
+        self.status_message = match result {
+            Ok(path) => format!("Proof sheet saved: {}", path.display()),
+            Err(e) => format!("Proof sheet failed: {}", e),
+        };
+    }
+
+    /// Called every frame: once scene detection is done, replace the current
+    /// segments with "Scene N" segments.
+    pub fn poll_silence_ends(&mut self) {
+        if !self.silence_ends_running {
+            return;
+        }
+        let Some((file_id, silences)) = self.silence_ends_result.lock().ok().and_then(|mut g| g.take()) else {
+            return;
+        };
+        self.silence_ends_running = false;
+
+        let duration = match self.selected_file() {
+            Some(f) if f.id == file_id => f.info.duration,
+            _ => {
+                self.status_message = "File changed during silence detection".to_string();
+                return;
+            }
+        };
+        let silences = match silences {
+            Ok(s) => s,
+            Err(e) => {
+                self.status_message = format!("Silence detection failed: {}", e);
+                return;
+            }
+        };
+        // A short margin keeps the first / last word intact
+        match crate::ffmpeg::trim_silence_ends(&silences, duration, 0.25) {
+            Some((start, end)) => {
+                self.set_in_point_at(start);
+                self.set_out_point_at(end);
+                self.seek(start);
+                self.status_message = format!(
+                    "IN/OUT set past dead air: {} - {} (press S to add the segment)",
+                    crate::utils::format_time(start),
+                    crate::utils::format_time(end)
+                );
+            }
+            None => self.status_message = "No silence at the start or end of the file".to_string(),
+        }
+    }
+
+    pub fn poll_scene_split(&mut self) {
+        if !self.scene_split_running {
+            return;
+        }
+        let Some((file_id, scenes, bmap)) = self.scene_results.lock().ok().and_then(|mut g| g.take()) else {
+            return;
+        };
+        self.scene_split_running = false;
+
+        let Some(path) = self.file_by_id(file_id).map(|f| f.path.clone()) else {
+            self.status_message = "File removed during scene detection".to_string();
+            return;
+        };
+
+        let scene_times = match scenes {
+            Ok(t) => t,
+            Err(e) => {
+                self.status_message = format!("Scene detection failed: {}", e);
+                return;
+            }
+        };
+        match bmap {
+            Some(Ok(bm)) => {
+                self.bitrate_maps.insert(path.clone(), bm);
+            }
+            Some(Err(e)) => eprintln!("Accurate bitrate map unavailable for {:?}, using estimate: {}", path, e),
+            None => {}
+        }
+
+        let duration = match self.selected_file() {
+            Some(f) if f.id == file_id => f.info.duration,
+            _ => {
+                self.status_message = "File changed during scene detection".to_string();
+                return;
+            }
+        };
+
+        let ranges = crate::ffmpeg::scenes_to_ranges(duration, &scene_times, 1.0);
+        self.segments = ranges.iter().enumerate()
+            .map(|(i, (start, end))| {
+                let mut seg = SplitSegment::new(*start, *end, format!("Scene {}", i + 1));
+                seg.estimated_size_bytes = self.estimate_segment_size_best(&path, *start, *end);
+                seg
+            })
+            .collect();
+        self.select_segment(if self.segments.is_empty() { None } else { Some(0) });
+        self.file_segments.insert(path, self.segments.clone());
+
+        self.status_message = format!("Scene split: {} scene(s)", self.segments.len());
+    }
+
     /// Called every frame to check if silence detection + bitrate map finished,
     /// then compute segments using accurate bitrate data.
     pub fn poll_auto_cut(&mut self) {
@@ -629,32 +1789,44 @@ impl FFmpegApp {
             Some(s) => s,
             None => { self.auto_cut_running = false; return; }
         };
-        let bitrate_map = match self.auto_cut_bitrate_map.lock().ok().and_then(|mut g| g.take()) {
-            Some(b) => b,
+        let (bitrate_map, bitrate_error) = match self.auto_cut_bitrate_map.lock().ok().and_then(|mut g| g.take()) {
+            Some(Ok(b)) => (b, None),
+            // Cached empty so estimates don't probe the file again
+            Some(Err(e)) => (BitrateMap { cumulative_bytes: Vec::new(), duration: 0.0 }, Some(e)),
             None => { self.auto_cut_running = false; return; }
         };
 
         // Detection is done
         self.auto_cut_running = false;
 
-        // Clone what we need from the selected file before mutating self
-        let (file_path, info) = match self.selected_file() {
-            Some(f) => (f.path.clone(), f.info.clone()),
-            None => {
-                self.auto_cut_status = "File removed during detection".to_string();
-                self.status_message = self.auto_cut_status.clone();
-                return;
-            }
+        // Results belong to the file analyzed, which may have been removed
+        // or deselected meanwhile
+        let Some(target) = self.file_by_id(self.auto_cut_target) else {
+            self.auto_cut_status = "File removed during detection".to_string();
+            self.status_message = self.auto_cut_status.clone();
+            return;
         };
+        if self.selected_file().map(|f| f.id) != Some(self.auto_cut_target) {
+            let path = target.path.clone();
+            if !bitrate_map.is_empty() {
+                self.bitrate_maps.insert(path, bitrate_map);
+            }
+            self.auto_cut_status = "File changed during detection, run Auto-Cut again".to_string();
+            self.status_message = self.auto_cut_status.clone();
+            return;
+        }
+        // Clone what we need from the selected file before mutating self
+        let (file_path, info) = (target.path.clone(), target.info.clone());
 
-        let max_bytes = (self.split_settings.max_size_mb * 1024.0 * 1024.0) as u64;
+        let max_bytes = self.split_settings.max_size_bytes();
+        let tolerance = self.split_settings.cut_tolerance_secs;
 
         // Use accurate bitrate-aware cutting if we got data, fallback to uniform
         let cut_points = if !bitrate_map.is_empty() {
             compute_cut_points_accurate(
                 info.duration,
                 max_bytes,
-                30.0,
+                tolerance,
                 &silences,
                 &bitrate_map,
             )
@@ -664,7 +1836,7 @@ impl FFmpegApp {
                 info.duration,
                 total_bitrate_bps,
                 max_bytes,
-                30.0,
+                tolerance,
                 &silences,
             )
         };
@@ -675,7 +1847,7 @@ impl FFmpegApp {
             let mut seg = SplitSegment::new(
                 *start,
                 *end,
-                format!("Segment {}", i + 1),
+                self.split_settings.segment_label(i + 1, cut_points.len()),
             );
             // Use bitrate map for accurate size if available
             if !bitrate_map.is_empty() {
@@ -687,21 +1859,19 @@ impl FFmpegApp {
             self.segments.push(seg);
         }
 
-        self.selected_segment = if self.segments.is_empty() {
-            None
-        } else {
-            Some(0)
-        };
+        self.select_segment(if self.segments.is_empty() { None } else { Some(0) });
 
-        let method_info = if !bitrate_map.is_empty() {
-            "bitrate-aware"
-        } else {
-            "uniform estimate"
+        let method_info = match &bitrate_error {
+            None => "bitrate-aware".to_string(),
+            Some(e) => {
+                eprintln!("Accurate bitrate map unavailable for {:?}, using estimate: {}", file_path, e);
+                format!("accurate bitrate map unavailable ({}), using estimate", e)
+            }
         };
-        let silence_info = if silences.is_empty() {
-            "no silence detected"
-        } else {
-            "silence-aware"
+        let silence_info = match crate::ffmpeg::cuts_outside_silence(&cut_points, &silences) {
+            _ if silences.is_empty() => "no silence detected".to_string(),
+            0 => "silence-aware".to_string(),
+            n => format!("{} cut(s) with no silence within ±{:.0} s, cut at the size limit", n, tolerance),
         };
 
         // Cache the bitrate map
@@ -729,12 +1899,59 @@ impl FFmpegApp {
         }
     }
 
+    /// Copy the current file's segments to other loaded files (e.g. synced
+    /// multi-cam angles), replacing their segment lists. Segments are clamped
+    /// to each target's duration; those starting past its end are dropped.
+    pub fn copy_segments_to(&mut self, targets: &[usize]) {
+        self.save_current_segments();
+        let source = self.segments.clone();
+        if source.is_empty() {
+            self.status_message = "No segments to copy".to_string();
+            return;
+        }
+
+        let mut copied_to = 0usize;
+        let mut dropped = 0usize;
+        for &idx in targets {
+            if Some(idx) == self.selected_file_index { continue; }
+            let Some(file) = self.project.files.get(idx) else { continue };
+            let path = file.path.clone();
+            let duration = file.info.duration;
+
+            let mut segments = Vec::with_capacity(source.len());
+            for seg in &source {
+                let start = seg.start_time.clamp(0.0, duration);
+                let end = seg.end_time.clamp(0.0, duration);
+                if end - start < 0.001 {
+                    dropped += 1;
+                    continue;
+                }
+                let mut copy = seg.clone();
+                copy.start_time = start;
+                copy.end_time = end;
+                copy.estimated_size_bytes = self.estimate_segment_size_best(&path, start, end);
+                segments.push(copy);
+            }
+            self.file_segments.insert(path, segments);
+            copied_to += 1;
+        }
+
+        self.status_message = match (copied_to, dropped) {
+            (0, _) => "No other file to copy segments to".to_string(),
+            (n, 0) => format!("{} segment(s) copied to {} file(s)", source.len(), n),
+            (n, d) => format!(
+                "{} segment(s) copied to {} file(s), {} dropped (past the end of a shorter file)",
+                source.len(), n, d
+            ),
+        };
+    }
+
     /// Restore segments for the currently selected file from the map
     fn restore_segments_for_current_file(&mut self) {
         if let Some(file) = self.selected_file() {
             if let Some(segs) = self.file_segments.get(&file.path) {
                 self.segments = segs.clone();
-                self.selected_segment = if self.segments.is_empty() { None } else { Some(0) };
+                self.select_segment(if self.segments.is_empty() { None } else { Some(0) });
             }
         }
     }
@@ -755,7 +1972,7 @@ impl FFmpegApp {
             self.stop_player();
             self.player = None;
             self.segments.clear();
-            self.selected_segment = None;
+            self.select_segment(None);
             self.current_waveform.clear();
             self.preview_texture = None;
         }
@@ -788,18 +2005,23 @@ impl FFmpegApp {
         self.player = None;
         self.project.files.clear();
         self.segments.clear();
-        self.selected_segment = None;
+        self.select_segment(None);
         self.selected_file_index = None;
         self.file_segments.clear();
         self.waveform_peaks.clear();
         self.current_waveform.clear();
         self.bitrate_maps.clear();
+        self.keyframes.clear();
+        self.loudness.clear();
+        if !self.batch_running {
+            self.batch_rows.clear();
+        }
         self.preview_texture = None;
         self.merge_file_order.clear();
-        self.in_point = None;
-        self.out_point = None;
+        self.clear_in_out_points();
         self.current_time = 0.0;
         self.last_frame_pts = -1.0;
+        self.load_status = LoadStatus::default();
         self.status_message = "All files removed".to_string();
     }
 
@@ -810,13 +2032,144 @@ impl FFmpegApp {
             slot.take()
         };
 
-        if let Some((path, peaks)) = result {
+        if let Some((path, peaks)) = result.filter(|(path, _)| self.is_loaded(path)) {
             self.waveform_peaks.insert(path.clone(), peaks.clone());
             // If this is the currently selected file, update current_waveform
-            if let Some(file) = self.selected_file() {
-                if file.path == path {
-                    self.current_waveform = peaks;
-                }
+            if self.selected_file().is_some_and(|file| file.path == path) {
+                self.current_waveform = peaks;
+                self.load_step_done(false, true);
+            }
+        }
+    }
+
+    /// The first preview frame or the waveform of the loaded file arrived:
+    /// update the transient load status
+    fn load_step_done(&mut self, preview: bool, waveform: bool) {
+        let status = &mut self.load_status;
+        if !status.is_pending() {
+            return;
+        }
+        status.preview &= !preview;
+        status.waveform &= !waveform;
+        if self.status_message == status.shown {
+            status.shown = status.message();
+            self.status_message = status.shown.clone();
+        }
+    }
+
+    /// Poll keyframe probing results (called each frame)
+    pub fn poll_keyframes(&mut self) {
+        let result = {
+            let Ok(mut slot) = self.keyframe_loading.lock() else { return };
+            slot.take()
+        };
+        let Some((path, keyframes)) = result else { return };
+        if self.keyframes_probing.as_ref() == Some(&path) {
+            self.keyframes_probing = None;
+        }
+        let loaded = self.is_loaded(&path);
+        if loaded {
+            self.keyframes.insert(path.clone(), keyframes);
+        }
+        if self.snap_lossless_pending.as_ref() == Some(&path) {
+            self.snap_lossless_pending = None;
+            // Only if the user is still on that file
+            if loaded && self.selected_file().is_some_and(|f| f.path == path) {
+                self.snap_in_out_lossless();
+            }
+        } else if let Some(pending) = self.snap_lossless_pending.clone() {
+            // The snap waited for another file's probe to finish
+            self.request_keyframes(&pending);
+        }
+    }
+
+    /// Probe the keyframes of `path` in the background, unless they are cached
+    /// or another probe is running (`poll_keyframes` stores them)
+    fn request_keyframes(&mut self, path: &std::path::Path) {
+        if self.keyframes.contains_key(path) || self.keyframes_probing.is_some() {
+            return;
+        }
+        let path = path.to_path_buf();
+        self.keyframes_probing = Some(path.clone());
+        let slot = self.keyframe_loading.clone();
+        std::thread::spawn(move || {
+            let keyframes = crate::ffmpeg::extract_keyframes(&path);
+            *slot.lock().unwrap() = Some((path, keyframes));
+        });
+    }
+
+    /// Keyframes of the selected file in Lossless mode, to show where each
+    /// `-c copy` cut really lands. Empty until `probe_lossless_keyframes` is done.
+    pub fn lossless_keyframes(&self) -> &[f64] {
+        match self.selected_file() {
+            Some(f) if self.split_settings.trim_mode == TrimMode::Lossless => {
+                self.keyframes.get(&f.path).map_or(&[], Vec::as_slice)
+            }
+            _ => &[],
+        }
+    }
+
+    /// Start probing the selected file's keyframes when Lossless mode needs them
+    pub fn probe_lossless_keyframes(&mut self) {
+        let path = match self.selected_file() {
+            Some(f) if self.split_settings.trim_mode == TrimMode::Lossless && !f.is_audio_only() => f.path.clone(),
+            _ => return,
+        };
+        self.request_keyframes(&path);
+    }
+
+    /// Keyframes of the selected file whatever the mode (trim mode comparison).
+    /// Empty until `probe_in_point_keyframes` is done.
+    pub fn selected_keyframes(&self) -> &[f64] {
+        self.selected_file()
+            .and_then(|f| self.keyframes.get(&f.path))
+            .map_or(&[], Vec::as_slice)
+    }
+
+    /// Start probing the selected file's keyframes once an IN point is set,
+    /// so the mode list can show where a Lossless cut would land
+    pub fn probe_in_point_keyframes(&mut self) {
+        let path = match self.selected_file() {
+            Some(f) if self.in_point.is_some() && !f.is_audio_only() => f.path.clone(),
+            _ => return,
+        };
+        self.request_keyframes(&path);
+    }
+
+    /// Move IN forward to the next keyframe and OUT back to the previous one,
+    /// so a `-c copy` cut starts cleanly on a keyframe (no frozen frames).
+    /// Keyframes are probed in the background the first time.
+    pub fn snap_in_out_lossless(&mut self) {
+        let (Some(start), Some(end)) = (self.in_point, self.out_point) else {
+            self.status_message = "Set IN and OUT points first".to_string();
+            return;
+        };
+        let Some(path) = self.selected_file().map(|f| f.path.clone()) else { return };
+        let Some(keyframes) = self.keyframes.get(&path) else {
+            if self.snap_lossless_pending.is_none() {
+                self.snap_lossless_pending = Some(path.clone());
+                self.request_keyframes(&path);
+            }
+            self.status_message = "Reading keyframes...".to_string();
+            return;
+        };
+        let (start, end) = (start.min(end), start.max(end));
+        match crate::ffmpeg::snap_range_inward(keyframes, start, end, 0.001) {
+            Some((new_start, new_end)) => {
+                self.set_out_point_at(new_end);
+                self.set_in_point_at(new_start);
+                self.status_message = format!(
+                    "Snapped to keyframes: IN +{:.3}s, OUT -{:.3}s ({:.3}s trimmed)",
+                    new_start - start,
+                    end - new_end,
+                    (new_start - start) + (end - new_end)
+                );
+            }
+            None if keyframes.is_empty() => {
+                self.status_message = "No keyframes found (ffprobe failed?)".to_string();
+            }
+            None => {
+                self.status_message = "Fewer than two keyframes between IN and OUT, nothing to snap to".to_string();
             }
         }
     }
@@ -875,14 +2228,24 @@ impl FFmpegApp {
             if let Some(seg) = self.segments.get(idx).cloned() {
                 self.segments.insert(idx + 1, seg);
                 // Re-label
-                for (i, s) in self.segments.iter_mut().enumerate() {
-                    s.label = format!("Segment {}", i + 1);
-                }
-                self.selected_segment = Some(idx + 1);
+                self.relabel_segments();
+                self.select_segment(Some(idx + 1));
             }
         }
     }
 
+    /// Flip the selected segment's enabled flag, then select and play the
+    /// next one: keyboard triage of a long auto-cut list (E)
+    pub fn toggle_segment_and_advance(&mut self) {
+        let Some(idx) = self.selected_segment else { return };
+        let Some(seg) = self.segments.get_mut(idx) else { return };
+        seg.enabled = !seg.enabled;
+        if idx + 1 < self.segments.len() {
+            self.select_next_segment();
+            self.play();
+        }
+    }
+
     /// Select next segment
     pub fn select_next_segment(&mut self) {
         if self.segments.is_empty() { return; }
@@ -890,7 +2253,7 @@ impl FFmpegApp {
             Some(idx) => (idx + 1).min(self.segments.len() - 1),
             None => 0,
         };
-        self.selected_segment = Some(next);
+        self.select_segment(Some(next));
         // Seek to segment start
         if let Some(seg) = self.segments.get(next) {
             self.seek(seg.start_time);
@@ -901,21 +2264,120 @@ impl FFmpegApp {
     pub fn select_prev_segment(&mut self) {
         if self.segments.is_empty() { return; }
         let prev = match self.selected_segment {
-            Some(idx) => if idx > 0 { idx - 1 } else { 0 },
+            Some(idx) => idx.saturating_sub(1),
             None => 0,
         };
-        self.selected_segment = Some(prev);
+        self.select_segment(Some(prev));
         if let Some(seg) = self.segments.get(prev) {
             self.seek(seg.start_time);
         }
     }
 
+    /// Select one segment, dropping any multi-selection
+    pub fn select_segment(&mut self, index: Option<usize>) {
+        self.selected_segment = index;
+        self.selected_segments.clear();
+    }
+
+    /// Click on a segment in the list or the timeline. With `extend` (Ctrl)
+    /// the segment is added to or removed from the multi-selection.
+    pub fn click_segment(&mut self, index: usize, extend: bool) {
+        if !extend {
+            self.select_segment(Some(index));
+            return;
+        }
+        // First Ctrl+click: the segment selected so far joins the selection
+        if self.selected_segments.is_empty() {
+            self.selected_segments.extend(self.selected_segment);
+        }
+        if self.selected_segments.remove(&index) {
+            self.selected_segment = self.selected_segments.iter().min().copied();
+        } else {
+            self.selected_segments.insert(index);
+            self.selected_segment = Some(index);
+        }
+    }
+
+    /// Selected segment indices, sorted: the multi-selection, or else the
+    /// single selected segment
+    pub fn segment_selection(&self) -> Vec<usize> {
+        let mut indices: Vec<usize> = if self.selected_segments.is_empty() {
+            self.selected_segment.into_iter().collect()
+        } else {
+            self.selected_segments.iter().copied().collect()
+        };
+        indices.retain(|&i| i < self.segments.len());
+        indices.sort_unstable();
+        indices
+    }
+
+    /// Is segment `index` part of the selection (highlighted)
+    pub fn is_segment_selected(&self, index: usize) -> bool {
+        self.selected_segment == Some(index) || self.selected_segments.contains(&index)
+    }
+
+    /// Delete every selected segment (Del)
+    pub fn remove_selected_segments(&mut self) {
+        let selection = self.segment_selection();
+        let Some(&first) = selection.first() else { return };
+        for &idx in selection.iter().rev() {
+            self.remove_segment(idx);
+        }
+        let next = (!self.segments.is_empty()).then(|| first.min(self.segments.len() - 1));
+        self.select_segment(next);
+        self.status_message = format!("{} segment(s) removed", selection.len());
+    }
+
+    /// Enable or disable the selected segments together: all enabled → all
+    /// disabled, otherwise all enabled
+    pub fn toggle_selected_segments(&mut self) {
+        let selection = self.segment_selection();
+        let enable = !selection.iter().all(|&i| self.segments[i].enabled);
+        for &idx in &selection {
+            self.segments[idx].enabled = enable;
+        }
+    }
+
+    /// Export the selected segments, enabled or not
+    pub fn export_selected_segments(&mut self) {
+        let selection = self.segment_selection();
+        if selection.is_empty() {
+            self.status_message = "No segment selected".to_string();
+            return;
+        }
+        self.export_segments(Some(&selection));
+    }
+
+    /// Replace the segments with the gaps between them over the whole file:
+    /// mark the parts to drop (ad breaks...), keep everything else
+    pub fn invert_segments(&mut self) {
+        let Some((path, duration)) = self.selected_file().map(|f| (f.path.clone(), f.info.duration)) else {
+            self.status_message = "No file selected".to_string();
+            return;
+        };
+        let ranges: Vec<(f64, f64)> = self.segments.iter().map(|s| (s.start_time, s.end_time)).collect();
+        let gaps = crate::utils::complement_ranges(&ranges, duration);
+        if gaps.is_empty() {
+            self.status_message = "Segments cover the whole file, nothing left to keep".to_string();
+            return;
+        }
+        self.segments = gaps.iter().enumerate()
+            .map(|(i, &(start, end))| {
+                let mut seg = SplitSegment::new(start, end, self.split_settings.segment_label(i + 1, gaps.len()));
+                seg.estimated_size_bytes = self.estimate_segment_size_best(&path, start, end);
+                seg
+            })
+            .collect();
+        self.select_segment(Some(0));
+        self.file_segments.insert(path, self.segments.clone());
+        self.status_message = format!("Inverted: {} segment(s) kept", self.segments.len());
+    }
+
     /// Clear all segments
     pub fn clear_all_segments(&mut self) {
         self.segments.clear();
-        self.selected_segment = None;
-        self.in_point = None;
-        self.out_point = None;
+        self.select_segment(None);
+        self.clear_in_out_points();
         self.status_message = "All segments cleared".to_string();
     }
 
@@ -926,7 +2388,9 @@ impl FFmpegApp {
             std::mem::take(&mut *slot)
         };
         for (path, data, w, h) in results {
-            self.thumbnails.insert(path, (data, w, h));
+            if self.is_loaded(&path) {
+                self.thumbnails.insert(path, (data, w, h));
+            }
         }
     }
 
@@ -956,84 +2420,139 @@ impl FFmpegApp {
         // Save current file's segments first
         self.save_current_segments();
 
-        let files: Vec<(usize, PathBuf)> = self.project.files.iter().enumerate()
-            .map(|(i, f)| (i, f.path.clone()))
+        let files: Vec<(u64, PathBuf, f64)> = self.project.files.iter()
+            .map(|f| (f.id, f.path.clone(), f.info.duration))
             .collect();
 
-        let results: Arc<Mutex<Vec<(usize, Vec<SilenceInterval>)>>> =
-            Arc::new(Mutex::new(Vec::new()));
+        let results: BatchResults = Arc::new(Mutex::new(Vec::new()));
+        let progress: BatchProgress = Arc::new(Mutex::new(vec![0.0; files.len()]));
 
         self.batch_total = files.len();
         self.batch_running = true;
         self.batch_results = results.clone();
+        self.batch_progress = progress.clone();
+        self.batch_segments_found = 0;
+        self.batch_uniform_cuts = 0;
+        self.batch_rows = self.project.files.iter()
+            .map(|f| BatchRow {
+                file_id: f.id,
+                name: f.filename(),
+                path: f.path.clone(),
+                duration: f.info.duration,
+                progress: 0.0,
+                done: false,
+            })
+            .collect();
         self.batch_status = format!("Analyzing 0/{}...", files.len());
         self.status_message = self.batch_status.clone();
 
         let ffmpeg = self.ffmpeg.clone();
 
         // Spawn one async task per file — they run in parallel on the tokio runtime
-        for (idx, path) in files {
+        for (row, (file_id, path, duration)) in files.into_iter().enumerate() {
             let ffmpeg = ffmpeg.clone();
             let results = results.clone();
+            let progress = progress.clone();
 
             self.runtime.spawn(async move {
-                let silences = ffmpeg.detect_silence(&path, -30.0, 0.3).await.unwrap_or_default();
-                results.lock().unwrap().push((idx, silences));
+                let report = |p: f32| {
+                    if let Some(slot) = progress.lock().unwrap().get_mut(row) {
+                        *slot = p;
+                    }
+                };
+                let silences = ffmpeg
+                    .detect_silence_with_progress(&path, -30.0, 0.3, duration, report)
+                    .await
+                    .unwrap_or_default();
+                results.lock().unwrap().push((file_id, silences));
             });
         }
     }
 
     /// Poll batch processing progress. Called each frame.
+    /// Files are turned into segments as soon as their analysis finishes, so
+    /// early results can be reviewed while long files are still running.
     pub fn poll_batch(&mut self) {
         if !self.batch_running {
             return;
         }
 
-        let completed = self.batch_results.lock().ok().map(|g| g.len()).unwrap_or(0);
-        self.batch_status = format!("Analyzing {}/{}...", completed, self.batch_total);
-
-        if completed < self.batch_total {
-            return; // still running
+        if let Ok(progress) = self.batch_progress.lock() {
+            for (row, p) in self.batch_rows.iter_mut().zip(progress.iter()) {
+                row.progress = *p;
+            }
         }
 
-        // All done — compute segments for each file
-        self.batch_running = false;
-
-        let results: Vec<(usize, Vec<SilenceInterval>)> = {
+        let finished: Vec<(u64, Vec<SilenceInterval>)> = {
             let Ok(mut guard) = self.batch_results.lock() else { return };
             std::mem::take(&mut *guard)
         };
 
-        let max_bytes = (self.split_settings.max_size_mb * 1024.0 * 1024.0) as u64;
-        let mut total_segments = 0usize;
+        let max_bytes = self.split_settings.max_size_bytes();
+        let current_path = self.selected_file().map(|f| f.path.clone());
+        let mut current_updated = false;
 
-        for (file_idx, silences) in results {
-            let Some(file) = self.project.files.get(file_idx) else { continue };
+        for (file_id, silences) in finished {
+            if let Some(row) = self.batch_rows.iter_mut().find(|r| r.file_id == file_id) {
+                row.progress = 1.0;
+                row.done = true;
+            }
+            // Removed while being analyzed: nothing to attach the result to
+            let Some(file) = self.project.files.iter().find(|f| f.id == file_id) else { continue };
             let info = &file.info;
 
             let bitrate_bps = Self::compute_bitrate(info);
             let cut_points = compute_cut_points(
-                info.duration, bitrate_bps, max_bytes, 30.0, &silences,
+                info.duration, bitrate_bps, max_bytes, self.split_settings.cut_tolerance_secs, &silences,
             );
+            self.batch_uniform_cuts += crate::ffmpeg::cuts_outside_silence(&cut_points, &silences);
 
             let segments: Vec<SplitSegment> = cut_points.iter().enumerate()
                 .map(|(i, (start, end))| {
-                    let mut seg = SplitSegment::new(*start, *end, format!("Segment {}", i + 1));
-                    seg.estimated_size_bytes = crate::utils::estimate_segment_size(info, *start, *end);
+                    let mut seg = SplitSegment::new(*start, *end, self.split_settings.segment_label(i + 1, cut_points.len()));
+                    seg.estimated_size_bytes = self.estimate_segment_size_best(&file.path, *start, *end);
                     seg
                 })
                 .collect();
 
-            total_segments += segments.len();
+            current_updated |= current_path.as_ref() == Some(&file.path);
+            self.batch_segments_found += segments.len();
             self.file_segments.insert(file.path.clone(), segments);
         }
 
-        // Restore current file's segments from the map
-        self.restore_segments_for_current_file();
+        // Show the current file's new segments right away
+        if current_updated {
+            self.restore_segments_for_current_file();
+        }
+
+        let completed = self.batch_rows.iter().filter(|r| r.done).count();
+        if completed < self.batch_total {
+            let overall = self.batch_overall_progress();
+            let current = self.batch_rows.iter()
+                .find(|r| !r.done && r.progress > 0.0)
+                .map(|r| format!(" - {} {:.0}%", r.name, r.progress * 100.0))
+                .unwrap_or_default();
+            self.batch_status = format!(
+                "Analyzing {}/{} ({:.0}%){}",
+                completed, self.batch_total, overall * 100.0, current
+            );
+            return; // still running
+        }
+
+        // All done
+        self.batch_running = false;
 
+        let uniform = if self.batch_uniform_cuts > 0 {
+            format!(
+                " ({} cut(s) with no silence within ±{:.0} s)",
+                self.batch_uniform_cuts, self.split_settings.cut_tolerance_secs
+            )
+        } else {
+            String::new()
+        };
         self.batch_status = format!(
-            "Batch done: {} files, {} total segments",
-            self.batch_total, total_segments
+            "Batch done: {} files, {} total segments{}",
+            self.batch_total, self.batch_segments_found, uniform
         );
         self.status_message = self.batch_status.clone();
 
@@ -1044,41 +2563,298 @@ impl FFmpegApp {
         }
     }
 
+    /// Overall batch progress, weighted by file duration
+    pub fn batch_overall_progress(&self) -> f32 {
+        let tasks: Vec<(f64, f32)> = self.batch_rows.iter()
+            .map(|r| (r.duration, if r.done { 1.0 } else { r.progress }))
+            .collect();
+        weighted_progress(&tasks)
+    }
+
     /// Combined: detect silences on all files, then auto-export when done
     pub fn batch_process_and_export(&mut self) {
         self.batch_auto_export = true;
         self.start_batch_auto_cut();
     }
 
-    /// Export ALL files' segments into per-file subfolders
-    pub fn export_all_files(&mut self) {
-        // Save current file's segments first
-        self.save_current_segments();
+    /// Queue the IN/OUT range of the selected file as a looping animation
+    /// (`<stem>_anim.webp` / `.apng` / `.gif`)
+    pub fn export_animation(&mut self) {
+        let (Some(start), Some(end)) = (self.in_point, self.out_point) else {
+            self.status_message = "Set IN and OUT points first".to_string();
+            return;
+        };
+        let Some(file) = self.selected_file() else { return };
+        if let Err(e) = file.export_blocker() {
+            self.status_message = e;
+            return;
+        }
+        let (start, end) = (start.min(end), start.max(end));
+        let input = file.path.clone();
+        let stem = input.file_stem().unwrap_or_default().to_string_lossy().to_string();
+        let folder = self.split_settings.output_folder.clone()
+            .unwrap_or_else(|| input.parent().unwrap_or(std::path::Path::new(".")).to_path_buf());
+        if let Err(e) = std::fs::create_dir_all(&folder) {
+            self.status_message = format!("Cannot create output folder: {}", e);
+            return;
+        }
+
+        let settings = self.animation_settings;
+        let planned = folder.join(format!("{}_anim.{}", stem, settings.format.extension()));
+        let policy = self.split_settings.conflict_policy;
+        {
+            let Ok(mut queue) = self.export_queue.lock() else { return };
+            let Some(output) = queue.plan_output(&planned, policy) else {
+                self.status_message = format!("Skipped: {} already exists", planned.display());
+                return;
+            };
+            let id = queue.add_animation(input, output, start, end, settings);
+            queue.set_skip_if_valid(id, policy == ConflictPolicy::Skip);
+        }
+        self.show_export_progress = true;
+        self.status_message = format!("Exporting {} animation...", settings.format.name());
+    }
+
+    /// Queue one JPEG per enabled segment, taken at its start, named like
+    /// the segment's output file (`<stem>_001.jpg`...): poster images for
+    /// per-chapter thumbnails
+    pub fn export_segment_thumbnails(&mut self) {
+        let Some(file) = self.selected_file() else { return };
+        if let Err(e) = file.export_blocker() {
+            self.status_message = e;
+            return;
+        }
+        if file.is_audio_only() {
+            self.status_message = "No video stream to take thumbnails from".to_string();
+            return;
+        }
+        let starts: Vec<(f64, String)> = self.segments.iter()
+            .filter(|s| s.enabled)
+            .map(|s| (s.start_time, s.label.clone()))
+            .collect();
+        if starts.is_empty() {
+            self.status_message = "No segments to export".to_string();
+            return;
+        }
+
+        let input = file.path.clone();
+        let stem = input.file_stem().unwrap_or_default().to_string_lossy().to_string();
+        let folder = self.split_settings.output_folder.clone()
+            .unwrap_or_else(|| input.parent().unwrap_or(std::path::Path::new(".")).to_path_buf());
+        if let Err(e) = std::fs::create_dir_all(&folder) {
+            self.status_message = format!("Cannot create output folder: {}", e);
+            return;
+        }
+
+        let policy = self.split_settings.conflict_policy;
+        let mut queued = 0usize;
+        {
+            let Ok(mut queue) = self.export_queue.lock() else { return };
+            for (i, (start, label)) in starts.into_iter().enumerate() {
+                let planned = folder.join(format!("{}_{:03}.jpg", stem, i + 1));
+                let Some(output) = queue.plan_output(&planned, policy) else { continue };
+                let id = queue.add_thumbnail(input.clone(), output, start, label);
+                queue.set_skip_if_valid(id, policy == ConflictPolicy::Skip);
+                queued += 1;
+            }
+        }
+        if queued == 0 {
+            self.status_message = "All thumbnails already exist, nothing exported".to_string();
+            return;
+        }
+        self.show_export_progress = true;
+        self.status_message = format!("Exporting {} segment thumbnail(s)...", queued);
+    }
+
+    /// Queue a copy of the whole selected file into the chosen output format
+    /// (`export_settings.container`), streams copied: the instant answer to
+    /// "just change mkv to mp4". Refused when the codecs don't fit the container.
+    pub fn remux_selected_file(&mut self) {
+        let Some(file) = self.selected_file() else {
+            self.status_message = "No file selected".to_string();
+            return;
+        };
+        if self.export_settings.container.is_none() {
+            self.status_message = "Pick an output format to remux into".to_string();
+            return;
+        }
+        let ext = match self.output_extension_for(file, TrimMode::Lossless) {
+            Ok(ext) => ext,
+            Err(e) => {
+                self.status_message = e;
+                return;
+            }
+        };
+        let input = file.path.clone();
+        if input.extension().is_some_and(|e| e.eq_ignore_ascii_case(&ext)) {
+            self.status_message = format!("{} is already .{}", file.filename(), ext);
+            return;
+        }
+        let size = file.info.file_size;
+        let label = file.filename();
+        let stem = input.file_stem().unwrap_or_default().to_string_lossy().to_string();
+        if self.queue_remux(input, format!("{}.{}", stem, ext), label, size) {
+            self.status_message = format!("Remuxing {} to .{} (streams copied)...", stem, ext);
+        }
+    }
+
+    /// Rewrite the selected file with its streams copied into the same
+    /// container, for files whose duration could not be read (live
+    /// captures, fragmented MP4): the new file gets a proper index
+    pub fn remux_to_fix_duration(&mut self) {
+        let Some(file) = self.selected_file().filter(|f| f.duration_unknown()) else {
+            return;
+        };
+        let input = file.path.clone();
+        let ext = input.extension().map_or("mkv".to_string(), |e| e.to_string_lossy().to_string());
+        let stem = input.file_stem().unwrap_or_default().to_string_lossy().to_string();
+        let (label, size) = (file.filename(), file.info.file_size);
+        let name = format!("{}_remuxed.{}", stem, ext);
+        if self.queue_remux(input, name.clone(), label, size) {
+            self.status_message = format!("Remuxing to {}: import it to edit with a proper duration", name);
+        }
+    }
+
+    /// Queue a stream-copy of `input` as `file_name` in the output folder
+    /// (next to the source by default); false if nothing was queued
+    fn queue_remux(&mut self, input: PathBuf, file_name: String, label: String, size: u64) -> bool {
+        let folder = self.split_settings.output_folder.clone()
+            .unwrap_or_else(|| input.parent().unwrap_or(std::path::Path::new(".")).to_path_buf());
+        if let Err(e) = std::fs::create_dir_all(&folder) {
+            self.status_message = format!("Cannot create output folder: {}", e);
+            return false;
+        }
+
+        let policy = self.split_settings.conflict_policy;
+        {
+            let Ok(mut queue) = self.export_queue.lock() else { return false };
+            let Some(output) = queue.plan_output(&folder.join(&file_name), policy) else {
+                self.status_message = format!("{} already exists, nothing exported", file_name);
+                return false;
+            };
+            let id = queue.add_remux(input, output, label);
+            queue.set_skip_if_valid(id, policy == ConflictPolicy::Skip);
+            queue.set_estimated_size(id, size);
+        }
+        self.show_export_progress = true;
+        true
+    }
+
+    /// The selected file has streams but no readable duration
+    pub fn selected_duration_unknown(&self) -> bool {
+        self.selected_file().is_some_and(|f| f.duration_unknown())
+    }
+
+    /// The selected file's duration is still being read from the whole file
+    pub fn selected_duration_pending(&self) -> bool {
+        self.selected_file().is_some_and(|f| f.estimating_duration)
+    }
+
+    /// Extension for outputs cut from `file`: the chosen container, else the
+    /// source's. Stream copy into another container must accept its codecs.
+    fn output_extension_for(&self, file: &MediaFile, mode: TrimMode) -> Result<String, String> {
+        file.export_blocker()?;
+        let ext = self.export_settings.output_extension(&file.path);
+        let source_ext = file.path.extension().unwrap_or_default().to_string_lossy();
+        if !mode.is_reencode() && !ext.eq_ignore_ascii_case(&source_ext) {
+            crate::ffmpeg::check_copy_container(
+                &ext,
+                file.info.video_codec.as_deref(),
+                file.info.audio_codec.as_deref(),
+            )
+            .map_err(|e| format!("Cannot copy {} into .{}: {} (use a re-encode mode)", file.filename(), ext, e))?;
+        }
+        Ok(ext)
+    }
+
+    /// Load encode profiles from a JSON file; the selected profile is
+    /// re-applied if the new file still defines it
+    pub fn load_encode_profiles(&mut self, path: PathBuf) {
+        match crate::ffmpeg::load_encode_profiles(&path) {
+            Ok(profiles) => {
+                self.status_message = format!("{} encode profile(s) loaded", profiles.len());
+                self.encode_profiles = profiles;
+                self.profiles_path = Some(path);
+                let selected = self.selected_profile.take();
+                self.select_profile(selected);
+            }
+            Err(e) => self.status_message = e.to_string(),
+        }
+    }
 
-        let output_base = self.split_settings.output_folder.clone()
+    /// Apply a profile by name to the export settings, `None` to go back to
+    /// the trim mode's own encoder settings
+    pub fn select_profile(&mut self, name: Option<String>) {
+        let profile = name.as_ref().and_then(|n| self.encode_profiles.iter().find(|p| &p.name == n));
+        match profile {
+            Some(profile) => {
+                profile.apply_to(&mut self.export_settings);
+                self.selected_profile = name;
+            }
+            None => {
+                if self.selected_profile.is_some() {
+                    self.export_settings.clear_profile();
+                }
+                self.selected_profile = None;
+            }
+        }
+    }
+
+    /// Output folder for batch exports
+    fn batch_output_base(&self) -> PathBuf {
+        self.split_settings.output_folder.clone()
             .unwrap_or_else(|| {
                 // Default: parent of first file, or current dir
                 self.project.files.first()
                     .and_then(|f| f.path.parent())
                     .unwrap_or(std::path::Path::new("."))
                     .to_path_buf()
-            });
+            })
+    }
 
-        let max_size_bytes = if self.split_settings.max_size_mb > 0.0 {
-            (self.split_settings.max_size_mb * 1024.0 * 1024.0) as u64
-        } else {
-            0
-        };
+    /// Export ALL files' segments into per-file subfolders
+    pub fn export_all_files(&mut self) {
+        // Save current file's segments first
+        self.save_current_segments();
+
+        let output_base = self.batch_output_base();
+
+        let max_size_bytes = self.split_settings.max_size_bytes();
         let mode = self.split_settings.trim_mode;
+        let policy = self.split_settings.conflict_policy;
         let mut total_queued = 0usize;
+        let mut queued_bytes = 0u64;
+        let mut skipped = 0usize;
+        self.size_warnings.clear();
+        // A subtitle file belongs to one video, never apply it to the whole batch
+        let settings = ExportSettings { subtitles: None, ..self.export_settings.clone() };
+
+        if let Some(e) = self.display_aspect_error() {
+            self.status_message = e;
+            return;
+        }
+
+        // Check every file first so a bad container doesn't leave a half-queued batch
+        let container_error = self.project.files.iter()
+            .filter(|f| self.file_segments.get(&f.path).is_some_and(|segs| segs.iter().any(|s| s.enabled)))
+            .find_map(|f| self.output_extension_for(f, mode).err());
+        if let Some(e) = container_error {
+            self.status_message = e;
+            return;
+        }
 
         for file in &self.project.files {
             let Some(segments) = self.file_segments.get(&file.path) else { continue };
-            let enabled: Vec<_> = segments.iter().filter(|s| s.enabled).cloned().collect();
+            let mut enabled: Vec<_> = segments.iter().filter(|s| s.enabled).cloned().collect();
             if enabled.is_empty() { continue; }
+            if self.split_settings.snap_to_frames && mode.is_reencode() {
+                if let Some(fps) = file.info.framerate {
+                    Self::snap_segment_list(enabled.iter_mut(), fps, file.info.duration);
+                }
+            }
 
             let stem = file.path.file_stem().unwrap_or_default().to_string_lossy().to_string();
-            let ext = file.path.extension().unwrap_or_default().to_string_lossy().to_string();
+            let ext = self.export_settings.output_extension(&file.path);
             let info = &file.info;
             let bitrate_bps = Self::compute_bitrate(info);
             let bmap = self.bitrate_maps.get(&file.path);
@@ -1094,38 +2870,185 @@ impl FFmpegApp {
             let mut final_segments = Vec::new();
             for seg in &enabled {
                 if max_size_bytes > 0 {
-                    final_segments.extend(Self::auto_split_segment(seg, max_size_bytes, bitrate_bps, bmap));
+                    final_segments.extend(auto_split_segment(seg, max_size_bytes, bitrate_bps, bmap, &self.split_settings.part_label_format));
                 } else {
                     final_segments.push(seg.clone());
                 }
             }
+            self.size_warnings.extend(
+                Self::size_limit_warnings(&final_segments, max_size_bytes)
+                    .into_iter()
+                    .map(|w| format!("{}: {}", stem, w)),
+            );
 
             // Queue exports
             {
                 let Ok(mut queue) = self.export_queue.lock() else { return };
                 for (i, seg) in final_segments.iter().enumerate() {
-                    let output_path = subfolder.join(format!("{}_{:03}.{}", stem, i + 1, ext));
-                    queue.add_trim_with_label(
+                    let planned = subfolder.join(format!("{}_{:03}.{}", stem, i + 1, ext));
+                    let Some(output_path) = queue.plan_output(&planned, policy) else {
+                        skipped += 1;
+                        continue;
+                    };
+                    let id = queue.add_trim_with_label(
                         file.path.clone(),
                         output_path,
                         seg.start_time,
                         seg.end_time,
                         mode,
                         format!("{} - {}", stem, seg.label),
+                        settings.clone(),
                     );
+                    queue.set_skip_if_valid(id, policy == ConflictPolicy::Skip);
+                    queue.set_estimated_size(id, seg.estimated_size_bytes);
+                    queued_bytes += seg.estimated_size_bytes;
+                    total_queued += 1;
                 }
-                total_queued += final_segments.len();
             }
         }
 
         if total_queued == 0 {
-            self.status_message = "No segments to export. Run Batch Auto-Cut first.".to_string();
+            self.status_message = if skipped > 0 {
+                format!("All {} output(s) already queued, nothing exported", skipped)
+            } else {
+                "No segments to export. Run Batch Auto-Cut first.".to_string()
+            };
+            return;
+        }
+
+        self.show_export_progress = true;
+        self.check_disk_space(&output_base, queued_bytes);
+        self.status_message = format!("Exporting {} segment(s) from {} file(s)...{}{}{}",
+            total_queued, self.files_with_segments_count(), skipped_suffix(skipped),
+            over_limit_suffix(self.size_warnings.len()), self.disk_space_suffix());
+    }
+
+    /// Queue one trim per loaded file using the current IN/OUT points,
+    /// remapped to each file's duration (`batch_trim_anchor`)
+    pub fn trim_all_files(&mut self) {
+        let (Some(start), Some(end)) = (self.in_point, self.out_point) else {
+            self.status_message = "Set IN and OUT points first".to_string();
+            return;
+        };
+        let reference = self.get_duration();
+        let out_from_end = self.batch_trim_anchor == BatchTrimAnchor::FromEnd;
+        let output_base = self.batch_output_base();
+        if let Err(e) = std::fs::create_dir_all(&output_base) {
+            self.status_message = format!("Cannot create output folder: {}", e);
+            return;
+        }
+
+        let mode = self.split_settings.trim_mode;
+        let policy = self.split_settings.conflict_policy;
+        // A subtitle file belongs to one video, never apply it to the whole batch
+        let settings = ExportSettings { subtitles: None, ..self.export_settings.clone() };
+        let mut queued = 0usize;
+        let mut skipped = 0usize;
+        let mut too_short = 0usize;
+        // Check every file first so a bad container doesn't leave a half-queued batch
+        if let Some(e) = self.project.files.iter().find_map(|f| self.output_extension_for(f, mode).err()) {
+            self.status_message = e;
+            return;
+        }
+        {
+            let Ok(mut queue) = self.export_queue.lock() else { return };
+            for file in &self.project.files {
+                let Some((s, e)) = crate::utils::remap_trim_range(start, end, reference, file.info.duration, out_from_end) else {
+                    too_short += 1;
+                    continue;
+                };
+                let stem = file.path.file_stem().unwrap_or_default().to_string_lossy().to_string();
+                let ext = self.export_settings.output_extension(&file.path);
+                let planned = output_base.join(format!("{}_trim.{}", stem, ext));
+                let Some(output_path) = queue.plan_output(&planned, policy) else {
+                    skipped += 1;
+                    continue;
+                };
+                let id = queue.add_trim_with_label(
+                    file.path.clone(),
+                    output_path,
+                    s,
+                    e,
+                    mode,
+                    format!("{} - trim", stem),
+                    settings.clone(),
+                );
+                queue.set_skip_if_valid(id, policy == ConflictPolicy::Skip);
+                queued += 1;
+            }
+        }
+
+        let short_suffix = if too_short > 0 {
+            format!(" ({} file(s) too short, skipped)", too_short)
+        } else {
+            String::new()
+        };
+        if queued == 0 {
+            self.status_message = format!("Nothing to trim{}{}", skipped_suffix(skipped), short_suffix);
+            return;
+        }
+        self.show_export_progress = true;
+        self.status_message = format!("Trimming {} file(s)...{}{}", queued, skipped_suffix(skipped), short_suffix);
+    }
+
+    /// Queue an extraction of the first audio track of every loaded file
+    /// that has one, in `audio_extract_format`, into the batch output folder
+    pub fn extract_audio_all_files(&mut self) {
+        let format = self.audio_extract_format;
+        if let Some(why) = self.missing_encoder(format.encoder()) {
+            self.status_message = why;
+            return;
+        }
+        let output_base = self.batch_output_base();
+        if let Err(e) = std::fs::create_dir_all(&output_base) {
+            self.status_message = format!("Cannot create output folder: {}", e);
+            return;
+        }
+
+        let policy = self.split_settings.conflict_policy;
+        let mut queued = 0usize;
+        let mut queued_bytes = 0u64;
+        let mut skipped = 0usize;
+        let mut silent = 0usize;
+        {
+            let Ok(mut queue) = self.export_queue.lock() else { return };
+            for file in &self.project.files {
+                if file.info.audio_codec.is_none() {
+                    silent += 1;
+                    continue;
+                }
+                let stem = file.path.file_stem().unwrap_or_default().to_string_lossy().to_string();
+                let mut planned = output_base.join(format!("{}.{}", stem, format.extension()));
+                // An mp3 extracted next to itself would overwrite its source
+                if planned == file.path {
+                    planned = output_base.join(format!("{}_audio.{}", stem, format.extension()));
+                }
+                let Some(output_path) = queue.plan_output(&planned, policy) else {
+                    skipped += 1;
+                    continue;
+                };
+                let id = queue.add_extract_audio(file.path.clone(), output_path, format, format!("{} - audio", stem));
+                let bytes = format.estimated_bytes(file.info.duration, file.info.sample_rate, file.info.channels);
+                queue.set_skip_if_valid(id, policy == ConflictPolicy::Skip);
+                queue.set_estimated_size(id, bytes);
+                queued_bytes += bytes;
+                queued += 1;
+            }
+        }
+
+        let silent_suffix = if silent > 0 {
+            format!(" ({} file(s) without audio, skipped)", silent)
+        } else {
+            String::new()
+        };
+        if queued == 0 {
+            self.status_message = format!("No audio to extract{}{}", skipped_suffix(skipped), silent_suffix);
             return;
         }
-
         self.show_export_progress = true;
-        self.status_message = format!("Exporting {} segment(s) from {} file(s)...",
-            total_queued, self.files_with_segments_count());
+        self.check_disk_space(&output_base, queued_bytes);
+        self.status_message = format!("Extracting audio from {} file(s) as {}...{}{}{}",
+            queued, format.name(), skipped_suffix(skipped), silent_suffix, self.disk_space_suffix());
     }
 
     // ---- Merge / Concat ----
@@ -1158,6 +3081,43 @@ impl FFmpegApp {
         }
     }
 
+    /// Sample rates / channel counts of the merge inputs when they differ,
+    /// e.g. "44100 Hz/2 ch, 48000 Hz/2 ch"
+    pub fn merge_audio_mismatch(&self) -> Option<String> {
+        let mut formats: Vec<(Option<u32>, Option<u32>)> = Vec::new();
+        for file in self.merge_file_order.iter().filter_map(|&i| self.project.files.get(i)) {
+            let format = (file.info.sample_rate, file.info.channels);
+            if !formats.contains(&format) {
+                formats.push(format);
+            }
+        }
+        if formats.len() < 2 {
+            return None;
+        }
+        let described: Vec<String> = formats.iter()
+            .map(|(rate, ch)| format!(
+                "{} Hz/{} ch",
+                rate.map_or("?".to_string(), |r| r.to_string()),
+                ch.map_or("?".to_string(), |c| c.to_string()),
+            ))
+            .collect();
+        Some(described.join(", "))
+    }
+
+    /// Picture size the re-encoded merge scales every input to: the chosen
+    /// `merge_resolution`, else the largest input when sizes differ
+    pub fn merge_video_format(&self) -> Option<crate::ffmpeg::ConcatVideoFormat> {
+        if let Some(height) = self.merge_resolution {
+            return Some(crate::ffmpeg::ConcatVideoFormat::widescreen(height));
+        }
+        let sizes: Vec<(u32, u32)> = self.merge_file_order.iter()
+            .filter_map(|&i| self.project.files.get(i))
+            .map(|f| (f.info.width, f.info.height))
+            .filter(|&(w, h)| w > 0 && h > 0)
+            .collect();
+        crate::ffmpeg::concat_video_format(&sizes)
+    }
+
     /// Start merging all files in merge_file_order into one output file
     pub fn start_merge(&mut self) {
         self.sync_merge_order();
@@ -1185,8 +3145,61 @@ impl FFmpegApp {
                     .to_path_buf()
             });
 
-        let ext = inputs[0].extension().unwrap_or_default().to_string_lossy().to_string();
-        let output_path = output_folder.join(format!("merged_output.{}", ext));
+        // Output name: typed name, input extension unless one was given
+        let name = self.merge_output_name.trim();
+        let name = if name.is_empty() { "merged_output" } else { name };
+        let file_name = if std::path::Path::new(name).extension().is_some() {
+            name.to_string()
+        } else {
+            format!("{}.{}", name, self.export_settings.output_extension(&inputs[0]))
+        };
+        let planned = output_folder.join(file_name);
+
+        let files: Vec<&MediaFile> = self.merge_file_order.iter()
+            .filter_map(|&i| self.project.files.get(i))
+            .collect();
+        if let Some(e) = files.iter().find_map(|f| f.export_blocker().err()) {
+            self.status_message = e;
+            return;
+        }
+        let method = if self.merge_reencode {
+            // The concat filter needs one audio stream per input
+            if let Some(file) = files.iter().find(|f| f.info.audio_codec.is_none()) {
+                self.status_message = format!("Cannot re-encode merge: {} has no audio track", file.filename());
+                return;
+            }
+            let streams: Vec<_> = files.iter().map(|f| (f.info.sample_rate, f.info.channels)).collect();
+            ConcatMethod::Reencode {
+                audio: crate::ffmpeg::concat_audio_format(&streams),
+                video: self.merge_video_format(),
+            }
+        } else {
+            // Stream copy: the container must accept every input's codecs
+            let ext = planned.extension().unwrap_or_default().to_string_lossy().to_string();
+            for file in &files {
+                if let Err(e) = crate::ffmpeg::check_copy_container(
+                    &ext,
+                    file.info.video_codec.as_deref(),
+                    file.info.audio_codec.as_deref(),
+                ) {
+                    self.status_message = format!("Cannot merge into .{}: {} ({})", ext, e, file.filename());
+                    return;
+                }
+            }
+            // The concat list can't hold every file name: refuse now rather than fail in the queue
+            if let Err(e) = crate::ffmpeg::concat_list_contents(&inputs) {
+                self.status_message = format!("Cannot merge: {}", e);
+                return;
+            }
+            ConcatMethod::Copy
+        };
+
+        let policy = self.split_settings.conflict_policy;
+        let planned_output = self.export_queue.lock().ok().and_then(|q| q.plan_output(&planned, policy));
+        let Some(output_path) = planned_output else {
+            self.status_message = format!("{} is already being exported, merge skipped", planned.display());
+            return;
+        };
 
         if let Err(e) = std::fs::create_dir_all(&output_folder) {
             self.status_message = format!("Cannot create output folder: {}", e);
@@ -1196,11 +3209,13 @@ impl FFmpegApp {
         // Add concat job to queue
         {
             let Ok(mut queue) = self.export_queue.lock() else { return };
-            queue.add_concat(
+            let id = queue.add_concat(
                 inputs,
                 output_path,
                 format!("Merge {} files", self.merge_file_order.len()),
+                method,
             );
+            queue.set_skip_if_valid(id, policy == ConflictPolicy::Skip);
         }
 
         self.show_export_progress = true;
@@ -1225,12 +3240,45 @@ impl FFmpegApp {
 
     /// Export all enabled segments
     pub fn export_all(&mut self) {
-        let Some(file) = self.selected_file() else {
+        self.export_segments(None);
+    }
+
+    /// Export one segment (enabled or not) under the name it gets in a full export
+    pub fn export_segment(&mut self, index: usize) {
+        if index < self.segments.len() {
+            self.export_segments(Some(&[index]));
+        }
+    }
+
+    /// Why the typed display aspect ratio can't be exported, if it can't
+    fn display_aspect_error(&self) -> Option<String> {
+        let ratio = self.export_settings.filters.display_aspect.as_deref()?;
+        crate::ffmpeg::parse_aspect_ratio(ratio).err()
+    }
+
+    /// Queue the enabled segments of the selected file, or only the segments
+    /// at indices `only`
+    fn export_segments(&mut self, only: Option<&[usize]>) {
+        if self.selected_file().is_none() {
             self.status_message = "No file selected".to_string();
             return;
-        };
+        }
+
+        let Some(file) = self.selected_file() else { return };
 
-        let enabled_segments: Vec<_> = self.segments.iter().filter(|s| s.enabled).cloned().collect();
+        // (index in the segment list, segment)
+        let mut enabled_segments: Vec<(usize, SplitSegment)> = match only {
+            Some(indices) => indices.iter()
+                .filter_map(|&i| self.segments.get(i).cloned().map(|seg| (i, seg)))
+                .collect(),
+            None => self.segments.iter().cloned().enumerate().filter(|(_, s)| s.enabled).collect(),
+        };
+        // Snap the exported copies only, the user's segments stay as drawn
+        if self.split_settings.snap_to_frames && self.split_settings.trim_mode.is_reencode() {
+            if let Some(fps) = file.info.framerate {
+                Self::snap_segment_list(enabled_segments.iter_mut().map(|(_, seg)| seg), fps, file.info.duration);
+            }
+        }
         if enabled_segments.is_empty() {
             self.status_message = "No segments to export".to_string();
             return;
@@ -1238,16 +3286,19 @@ impl FFmpegApp {
 
         let input_path = file.path.clone();
         let info = file.info.clone();
+        let ext = match self.output_extension_for(file, self.split_settings.trim_mode) {
+            Ok(ext) => ext,
+            Err(e) => {
+                self.status_message = e;
+                return;
+            }
+        };
 
         // Determine output folder
         let output_folder = self.split_settings.output_folder.clone()
             .unwrap_or_else(|| input_path.parent().unwrap_or(std::path::Path::new(".")).to_path_buf());
 
-        let max_size_bytes = if self.split_settings.max_size_mb > 0.0 {
-            (self.split_settings.max_size_mb * 1024.0 * 1024.0) as u64
-        } else {
-            0
-        };
+        let max_size_bytes = self.split_settings.max_size_bytes();
 
         // Calculate bitrate for auto-split
         let total_bitrate_bps = match (info.video_bitrate, info.audio_bitrate) {
@@ -1265,19 +3316,42 @@ impl FFmpegApp {
 
         // Build final segment list (with auto-split using real bitrate sums)
         let bmap = self.bitrate_maps.get(&input_path);
+        // (list index, part number, parts of that segment, part)
         let mut final_segments = Vec::new();
-        for seg in &enabled_segments {
-            if max_size_bytes > 0 {
-                final_segments.extend(Self::auto_split_segment(seg, max_size_bytes, total_bitrate_bps, bmap));
+        for (idx, seg) in &enabled_segments {
+            let parts = if max_size_bytes > 0 {
+                auto_split_segment(seg, max_size_bytes, total_bitrate_bps, bmap, &self.split_settings.part_label_format)
             } else {
-                final_segments.push(seg.clone());
-            }
+                vec![seg.clone()]
+            };
+            let count = parts.len();
+            final_segments.extend(parts.into_iter().enumerate().map(|(k, part)| (*idx, k, count, part)));
         }
+        let parts: Vec<SplitSegment> = final_segments.iter().map(|(.., part)| part.clone()).collect();
+        self.size_warnings = Self::size_limit_warnings(&parts, max_size_bytes);
 
         let stem = input_path.file_stem().unwrap_or_default().to_string_lossy().to_string();
-        let ext = input_path.extension().unwrap_or_default().to_string_lossy().to_string();
         let mode = self.split_settings.trim_mode;
 
+        if let Some(e) = self.display_aspect_error() {
+            self.status_message = e;
+            return;
+        }
+
+        // Subtitles only apply to re-encoded exports; check them before queueing anything
+        if let Some(subs) = self.export_settings.subtitles.as_ref().filter(|_| mode.is_reencode()) {
+            if !subs.path.exists() {
+                self.status_message = format!("Subtitle file not found: {}", subs.path.display());
+                return;
+            }
+            if subs.mode == SubtitleMode::Embed {
+                if let Err(e) = crate::ffmpeg::subtitle_codec_for(&ext, &subs.path) {
+                    self.status_message = e;
+                    return;
+                }
+            }
+        }
+
         // Ensure output folder exists
         if let Err(e) = std::fs::create_dir_all(&output_folder) {
             self.status_message = format!("Cannot create output folder: {}", e);
@@ -1285,28 +3359,204 @@ impl FFmpegApp {
         }
 
         // Add all segments to queue
+        let policy = self.split_settings.conflict_policy;
+        let mut queued = 0usize;
+        let mut queued_bytes = 0u64;
+        let mut skipped = 0usize;
         {
             let Ok(mut queue) = self.export_queue.lock() else { return };
-            for (i, seg) in final_segments.iter().enumerate() {
-                let output_path = output_folder.join(format!("{}_{:03}.{}", stem, i + 1, ext));
-                queue.add_trim_with_label(
+            for (i, (idx, part, count, seg)) in final_segments.iter().enumerate() {
+                // Full export: numbered in order; a selection keeps list numbers
+                let name = match only {
+                    Some(_) if *count > 1 => format!("{}_{:03}_{}", stem, idx + 1, part + 1),
+                    Some(_) => format!("{}_{:03}", stem, idx + 1),
+                    None => format!("{}_{:03}", stem, i + 1),
+                };
+                let planned = output_folder.join(format!("{}.{}", name, ext));
+                let Some(output_path) = queue.plan_output(&planned, policy) else {
+                    skipped += 1;
+                    continue;
+                };
+                let id = queue.add_trim_with_label(
                     input_path.clone(),
                     output_path,
                     seg.start_time,
                     seg.end_time,
                     mode,
                     seg.label.clone(),
+                    self.export_settings.clone(),
                 );
+                queue.set_skip_if_valid(id, policy == ConflictPolicy::Skip);
+                queue.set_estimated_size(id, seg.estimated_size_bytes);
+                queued_bytes += seg.estimated_size_bytes;
+                queued += 1;
             }
         }
 
+        if queued == 0 {
+            self.status_message = format!("All {} output(s) already queued, nothing exported", skipped);
+            return;
+        }
+
         self.show_export_progress = true;
+        self.check_disk_space(&output_folder, queued_bytes);
         self.status_message = format!(
-            "Exporting {} segment(s)...",
-            final_segments.len()
+            "Exporting {} segment(s)...{}{}{}",
+            queued,
+            skipped_suffix(skipped),
+            over_limit_suffix(self.size_warnings.len()),
+            self.disk_space_suffix()
         );
     }
 
+    /// Compare the estimated size of a just-queued export with the free space
+    /// of its output volume (`disk_space`). Outputs still pending from an
+    /// earlier export to the same folder count too.
+    fn check_disk_space(&mut self, folder: &std::path::Path, queued_bytes: u64) {
+        let pending: u64 = self.export_queue.lock()
+            .map(|q| q.jobs.iter()
+                .filter(|j| matches!(j.status, crate::export_queue::JobStatus::Pending | crate::export_queue::JobStatus::Running))
+                .filter(|j| j.output.starts_with(folder))
+                .map(|j| j.estimated_bytes)
+                .sum())
+            .unwrap_or(queued_bytes);
+        self.disk_space = crate::utils::free_space(folder).map(|free| DiskSpaceCheck {
+            folder: folder.to_path_buf(),
+            needed: pending.max(queued_bytes),
+            free,
+        });
+    }
+
+    /// Status suffix when the last export may not fit on its volume
+    fn disk_space_suffix(&self) -> String {
+        match self.disk_space.as_ref().and_then(|d| d.shortfall().map(|_| d)) {
+            Some(d) => format!(
+                " ⚠ needs ~{} but only {} free on the output drive",
+                crate::utils::format_size(d.needed),
+                crate::utils::format_size(d.free)
+            ),
+            None => String::new(),
+        }
+    }
+
+    /// Apply `open_output` to the file a single-output export just wrote
+    fn open_export_output(&mut self, output: PathBuf) {
+        match self.open_output {
+            OpenOutputAction::Nothing => {}
+            OpenOutputAction::SystemPlayer => {
+                if let Err(e) = open::that(&output) {
+                    self.status_message = format!("{} - cannot open {}: {}", self.status_message, output.display(), e);
+                }
+            }
+            OpenOutputAction::AddToProject => {
+                // A review file is not a merge candidate: keep the current mode
+                let mode = self.editing_mode;
+                let status = self.status_message.clone();
+                self.add_files(vec![output.clone()]);
+                self.editing_mode = mode;
+                self.status_message = status;
+                // Already imported (e.g. overwritten in place) → select that entry
+                let key = Self::import_key(&output);
+                match self.project.files.iter().position(|f| Self::import_key(&f.path) == key) {
+                    Some(index) => self.select_file(index),
+                    None => self.status_message = format!("{} - cannot probe {}", self.status_message, output.display()),
+                }
+            }
+        }
+    }
+
+    /// Write `export_summary.csv` for a finished batch (2+ jobs) in the folder
+    /// holding its outputs. Outputs are probed on a background thread.
+    fn write_export_summary(&mut self, jobs: Vec<crate::export_queue::ExportJob>) -> Option<PathBuf> {
+        let last_id = jobs.iter().map(|j| j.id).max()?;
+        self.manifest_from_id = last_id + 1;
+        if jobs.len() < 2 {
+            return None;
+        }
+
+        let folder = Self::common_output_folder(&jobs)?;
+        let path = crate::utils::resolve_output_path(&folder.join("export_summary.csv"), ConflictPolicy::Rename)?;
+
+        let target = path.clone();
+        std::thread::spawn(move || {
+            let csv = crate::export_queue::export_manifest_csv(&jobs, |output| {
+                let info = crate::ffmpeg::probe_file(output).ok()?;
+                Some((info.file_size, info.duration))
+            });
+            if let Err(e) = std::fs::write(&target, csv) {
+                eprintln!("Failed to write export summary {:?}: {}", target, e);
+            }
+        });
+        Some(path)
+    }
+
+    /// Deepest folder containing every output (per-file subfolders → batch root)
+    fn common_output_folder(jobs: &[crate::export_queue::ExportJob]) -> Option<PathBuf> {
+        let mut folder = jobs.first()?.output.parent()?.to_path_buf();
+        for job in &jobs[1..] {
+            while !job.output.starts_with(&folder) {
+                folder = folder.parent()?.to_path_buf();
+            }
+        }
+        Some(folder)
+    }
+
+    /// `.m3u` of the outputs of a finished multi-file export, in queue order,
+    /// next to them (`split_settings.write_playlist`). Paths are relative to
+    /// the playlist so the folder can be moved.
+    fn write_export_playlist(&self, jobs: &[crate::export_queue::ExportJob]) -> Result<Option<PathBuf>, String> {
+        // Stills are not playable entries
+        let mut done: Vec<&crate::export_queue::ExportJob> = jobs.iter()
+            .filter(|j| j.status == JobStatus::Completed)
+            .filter(|j| !matches!(j.operation, crate::export_queue::ExportOperation::Thumbnail { .. }))
+            .collect();
+        if !self.split_settings.write_playlist || jobs.len() < 2 || done.is_empty() {
+            return Ok(None);
+        }
+        done.sort_by_key(|j| j.id);
+
+        let folder = Self::common_output_folder(jobs).ok_or("no common folder")?;
+        // One source → playlist named after it, else a generic name
+        let first_input = &done[0].input;
+        let name = if done.iter().all(|j| &j.input == first_input) {
+            first_input.file_stem().unwrap_or_default().to_string_lossy().to_string()
+        } else {
+            "playlist".to_string()
+        };
+        let path = crate::utils::resolve_output_path(&folder.join(format!("{}.m3u", name)), ConflictPolicy::Rename)
+            .ok_or("no free playlist name")?;
+
+        let entries: Vec<(String, f64, String)> = done.iter()
+            .map(|j| {
+                let duration = match j.operation {
+                    crate::export_queue::ExportOperation::Trim { start, end, .. } => end - start,
+                    _ => -1.0,
+                };
+                let title = if j.segment_label.is_empty() {
+                    j.output.file_stem().unwrap_or_default().to_string_lossy().to_string()
+                } else {
+                    j.segment_label.clone()
+                };
+                (crate::ffmpeg::playlist_entry_path(&folder, &j.output), duration, title)
+            })
+            .collect();
+        std::fs::write(&path, crate::ffmpeg::build_m3u(&entries)).map_err(|e| e.to_string())?;
+        Ok(Some(path))
+    }
+
+    /// One warning per auto-split part whose estimated size is still above
+    /// the limit. Parts are numbered like their output files (`_004` → 4).
+    fn size_limit_warnings(parts: &[SplitSegment], max_bytes: u64) -> Vec<String> {
+        over_limit_parts(parts, max_bytes)
+            .into_iter()
+            .map(|i| format!(
+                "Segment {} exceeds the size limit by {} (single-frame spike)",
+                i + 1,
+                crate::utils::format_size(parts[i].estimated_size_bytes - max_bytes)
+            ))
+            .collect()
+    }
+
     /// Process the next job in the queue
     pub fn process_queue(&mut self) {
         let queue = self.export_queue.clone();
@@ -1326,24 +3576,117 @@ impl FFmpegApp {
             q.is_processing = true;
             if let Some(job) = q.next_pending() {
                 job.status = JobStatus::Running;
-                Some((job.id, job.input.clone(), job.output.clone(), job.operation.clone()))
+                Some((job.id, job.input.clone(), job.output.clone(), job.operation.clone(), job.settings.clone(), job.skip_if_valid))
             } else {
                 q.is_processing = false;
                 None
             }
         };
 
-        if let Some((job_id, input, output, operation)) = job_info {
+        if let Some((job_id, input, output, operation, settings, skip_if_valid)) = job_info {
             self.status_message = "Processing queue...".to_string();
 
+            // Expected output length, used to catch truncated / empty results
+            let expected_duration = match &operation {
+                crate::export_queue::ExportOperation::Trim { start, end, mode } => {
+                    let padding = if mode.is_reencode() { settings.filters.padding_secs() } else { 0.0 };
+                    end - start + padding
+                }
+                crate::export_queue::ExportOperation::Concat { inputs, .. } => {
+                    let durations: Vec<f64> = inputs.iter()
+                        .filter_map(|p| self.project.files.iter().find(|f| &f.path == p))
+                        .map(|f| f.info.duration)
+                        .collect();
+                    // Unknown input → skip the duration check
+                    if durations.len() == inputs.len() { durations.iter().sum() } else { 0.0 }
+                }
+                // Animated WebP/APNG durations aren't reported reliably by ffprobe
+                crate::export_queue::ExportOperation::Animation { .. } => 0.0,
+                crate::export_queue::ExportOperation::Thumbnail { .. } => 0.0,
+                crate::export_queue::ExportOperation::Remux | crate::export_queue::ExportOperation::ExtractAudio { .. } => self.project.files.iter()
+                    .find(|f| f.path == input)
+                    .map_or(0.0, |f| f.info.duration),
+            };
+            let is_image = matches!(
+                operation,
+                crate::export_queue::ExportOperation::Animation { .. } | crate::export_queue::ExportOperation::Thumbnail { .. }
+            );
+            // Live job progress from FFmpeg's `time=` stats. A lossless cut
+            // keeping source timestamps reports them from its start, not zero.
+            let progress_origin = match &operation {
+                crate::export_queue::ExportOperation::Trim { start, mode: TrimMode::Lossless, .. }
+                    if settings.keep_timestamps => *start,
+                _ => 0.0,
+            };
+            let progress_queue = queue.clone();
+            let ffmpeg = ffmpeg.with_progress(progress_origin, expected_duration, Arc::new(move |progress| {
+                let Ok(mut q) = progress_queue.lock() else { return };
+                if let Some(job) = q.get_job_mut(job_id) {
+                    job.progress = progress;
+                }
+            }));
+
             self.runtime.spawn(async move {
+                // "Skip existing": a complete output from an earlier run is kept as-is,
+                // using the same checks as post-export validation
+                if skip_if_valid && output.exists() {
+                    let output_check = output.clone();
+                    let reusable = tokio::task::spawn_blocking(move || {
+                        crate::ffmpeg::validate_output(&output_check, expected_duration).is_ok()
+                    })
+                    .await
+                    .unwrap_or(false);
+                    if reusable {
+                        let Ok(mut q) = queue.lock() else { return };
+                        if let Some(job) = q.get_job_mut(job_id) {
+                            job.status = JobStatus::Completed;
+                            job.progress = 1.0;
+                            job.segment_label = format!("{} (kept existing)", job.segment_label);
+                        }
+                        q.is_processing = false;
+                        return;
+                    }
+                }
+
                 let result = match operation {
                     crate::export_queue::ExportOperation::Trim { start, end, mode } => {
-                        ffmpeg.trim(&input, &output, start, end, mode).await
+                        ffmpeg.trim(&input, &output, start, end, mode, &settings).await
                     }
-                    crate::export_queue::ExportOperation::Concat { inputs } => {
+                    crate::export_queue::ExportOperation::Concat { inputs, method: ConcatMethod::Copy } => {
                         ffmpeg.concat(&inputs, &output).await
                     }
+                    crate::export_queue::ExportOperation::Concat { inputs, method: ConcatMethod::Reencode { audio, video } } => {
+                        ffmpeg.concat_reencode(&inputs, &output, audio, video).await
+                    }
+                    crate::export_queue::ExportOperation::Animation { start, end, settings } => {
+                        ffmpeg.animation(&input, &output, start, end, &settings).await
+                    }
+                    crate::export_queue::ExportOperation::Thumbnail { time } => {
+                        ffmpeg.extract_thumbnail(&input, &output, time).await
+                    }
+                    crate::export_queue::ExportOperation::Remux => ffmpeg.remux(&input, &output).await,
+                    crate::export_queue::ExportOperation::ExtractAudio { format } => {
+                        ffmpeg.extract_audio(&input, &output, format).await
+                    }
+                };
+
+                // Exit code 0 is not enough: check the file really is there and complete
+                let result = match result {
+                    // ffprobe can't always decode animated WebP, and a still has no
+                    // duration: just require a non-empty file
+                    Ok(()) if is_image => match std::fs::metadata(&output) {
+                        Ok(meta) if meta.len() > 0 => Ok(()),
+                        _ => Err(anyhow::anyhow!("Output file missing or empty: {}", output.display())),
+                    },
+                    Ok(()) => {
+                        let output_check = output.clone();
+                        tokio::task::spawn_blocking(move || {
+                            crate::ffmpeg::validate_output(&output_check, expected_duration)
+                        })
+                        .await
+                        .unwrap_or_else(|e| Err(anyhow::anyhow!("Validation task failed: {}", e)))
+                    }
+                    Err(e) => Err(e),
                 };
 
                 let Ok(mut q) = queue.lock() else { return };
@@ -1363,6 +3706,119 @@ impl FFmpegApp {
         }
     }
 
+    /// Describe the enabled segments of the selected file as an edit list,
+    /// without encoding: CMX3600 for `.edl`, ffconcat playlist otherwise.
+    pub fn export_edl(&mut self, path: &std::path::Path) {
+        let Some(file) = self.selected_file() else {
+            self.status_message = "No file selected".to_string();
+            return;
+        };
+        let ranges: Vec<(f64, f64)> = self.segments.iter()
+            .filter(|s| s.enabled)
+            .map(|s| (s.start_time, s.end_time))
+            .collect();
+        if ranges.is_empty() {
+            self.status_message = "No segments to export".to_string();
+            return;
+        }
+
+        let format = crate::ffmpeg::EdlFormat::from_path(path);
+        let content = match format {
+            crate::ffmpeg::EdlFormat::FfConcat => crate::ffmpeg::build_ffconcat(&file.path, &ranges),
+            crate::ffmpeg::EdlFormat::Cmx3600 => {
+                let title = file.path.file_stem().unwrap_or_default().to_string_lossy().to_string();
+                crate::ffmpeg::build_cmx3600_edl(&title, &file.path, &ranges, file.info.framerate.unwrap_or(0.0))
+            }
+        };
+
+        match std::fs::write(path, content) {
+            Ok(()) => self.status_message = format!("{} segment(s) written to {}", ranges.len(), path.display()),
+            Err(e) => self.status_message = format!("Cannot write EDL: {}", e),
+        }
+    }
+
+    /// Command line the current trim would run on the selected file: the
+    /// selected segment, else the IN/OUT range, else the whole file. Quoted
+    /// for the platform shell so it can be pasted and tweaked in a terminal.
+    pub fn current_ffmpeg_command(&self) -> Result<String, String> {
+        let file = self.selected_file().ok_or("No file selected")?;
+        let stem = file.path.file_stem().unwrap_or_default().to_string_lossy().to_string();
+        let (start, end, name) = match self.selected_segment.and_then(|i| self.segments.get(i).map(|s| (i, s))) {
+            Some((i, seg)) => (seg.start_time, seg.end_time, format!("{}_{:03}", stem, i + 1)),
+            None => match (self.in_point, self.out_point) {
+                (Some(a), Some(b)) if b > a => (a, b, format!("{}_trim", stem)),
+                _ => (0.0, file.info.duration, format!("{}_trim", stem)),
+            },
+        };
+
+        // Smart-cut runs several commands chosen at run time, as in queue scripts
+        let mode = match self.split_settings.trim_mode {
+            TrimMode::SmartCut => TrimMode::Precise,
+            mode => mode,
+        };
+        let ext = self.output_extension_for(file, mode)?;
+        let folder = self.split_settings.output_folder.clone()
+            .unwrap_or_else(|| file.path.parent().unwrap_or(std::path::Path::new(".")).to_path_buf());
+        let args = crate::ffmpeg::build_trim_args(
+            &file.path,
+            &folder.join(format!("{}.{}", name, ext)),
+            start,
+            end,
+            mode,
+            &self.export_settings,
+            crate::ffmpeg::SourceVideoCodec::from_codec_name(file.info.video_codec.as_deref()),
+        );
+        Ok(crate::ffmpeg::command_line(crate::ffmpeg::ffmpeg_path(), &args, crate::ffmpeg::ScriptShell::native()))
+    }
+
+    /// Copy `current_ffmpeg_command` to the clipboard
+    pub fn copy_ffmpeg_command(&mut self, ctx: &egui::Context) {
+        match self.current_ffmpeg_command() {
+            Ok(command) => {
+                ctx.copy_text(command);
+                self.status_message = if self.split_settings.trim_mode == TrimMode::SmartCut {
+                    "FFmpeg command copied (smart-cut copied as a Precise re-encode)".to_string()
+                } else {
+                    "FFmpeg command copied to clipboard".to_string()
+                };
+            }
+            Err(e) => self.status_message = e,
+        }
+    }
+
+    /// Write every unfinished queue job as a standalone ffmpeg script
+    /// (`.bat`/`.cmd` → cmd.exe, anything else → POSIX sh).
+    pub fn export_queue_script(&mut self, path: &std::path::Path) {
+        let script = {
+            let Ok(queue) = self.export_queue.lock() else { return };
+            let jobs: Vec<&crate::export_queue::ExportJob> = queue.jobs.iter()
+                .filter(|j| j.status != JobStatus::Completed)
+                .collect();
+            if jobs.is_empty() {
+                self.status_message = "Queue is empty, nothing to script".to_string();
+                return;
+            }
+            let shell = crate::ffmpeg::ScriptShell::from_path(path);
+            let files = &self.project.files;
+            crate::ffmpeg::build_queue_script(&jobs, crate::ffmpeg::ffmpeg_path(), shell, |input| {
+                let codec = files.iter().find(|f| f.path == input).and_then(|f| f.info.video_codec.as_deref());
+                crate::ffmpeg::SourceVideoCodec::from_codec_name(codec)
+            })
+        };
+
+        match std::fs::write(path, script) {
+            Ok(()) => {
+                #[cfg(unix)]
+                {
+                    use std::os::unix::fs::PermissionsExt;
+                    let _ = std::fs::set_permissions(path, std::fs::Permissions::from_mode(0o755));
+                }
+                self.status_message = format!("Queue script saved to {}", path.display());
+            }
+            Err(e) => self.status_message = format!("Cannot write script: {}", e),
+        }
+    }
+
     /// Cancel all pending exports and stop processing
     pub fn cancel_exports(&mut self) {
         if let Ok(mut queue) = self.export_queue.lock() {
@@ -1372,16 +3828,41 @@ impl FFmpegApp {
         self.status_message = "Exports cancelled".to_string();
     }
 
+    /// Redirect a pending job to `output`. A missing folder is an error
+    /// unless `create_folder` is set.
+    pub fn set_job_output(&mut self, id: u32, output: PathBuf, create_folder: bool) -> Result<(), String> {
+        if output.file_name().is_none() {
+            return Err("Enter a file name".to_string());
+        }
+        let folder = output.parent().filter(|p| !p.as_os_str().is_empty()).unwrap_or(std::path::Path::new("."));
+        if !folder.is_dir() {
+            if !create_folder {
+                return Err(format!("Folder does not exist: {}", folder.display()));
+            }
+            std::fs::create_dir_all(folder).map_err(|e| format!("Cannot create folder: {}", e))?;
+        }
+        let Ok(mut queue) = self.export_queue.lock() else {
+            return Err("Queue unavailable".to_string());
+        };
+        queue.set_output(id, output.clone())?;
+        self.status_message = format!("Job output changed to {}", output.display());
+        Ok(())
+    }
+
     /// Clear finished jobs from queue
     pub fn clear_finished_jobs(&mut self) {
         if let Ok(mut queue) = self.export_queue.lock() {
             queue.clear_finished();
         }
+        self.size_warnings.clear();
+        self.size_checks.clear();
+        self.disk_space = None;
     }
 
     /// Update player state and get current frame.
     /// Only recreates the GPU texture when the frame actually changed (PTS check).
     pub fn update_player(&mut self, ctx: &egui::Context) {
+        let mut new_frame = false;
         if let Some(ref player) = self.player {
             self.current_time = player.get_current_time();
 
@@ -1403,20 +3884,147 @@ impl FFmpegApp {
                         color_image,
                         egui::TextureOptions::LINEAR,
                     ));
+                    new_frame = true;
                 }
             }
 
             if player.get_state() == PlaybackState::Playing {
-                ctx.request_repaint_after(std::time::Duration::from_millis(16));
+                // Low-power previews run at 15 fps: no point redrawing faster
+                let interval = if self.low_power { 66 } else { 16 };
+                ctx.request_repaint_after(std::time::Duration::from_millis(interval));
+                if !self.low_power && !self.low_power_suggested && player.is_lagging() {
+                    self.low_power_suggested = true;
+                    self.status_message = "Preview can't keep up with playback: try Playback → Low-power mode".to_string();
+                }
+            }
+        }
+        if new_frame {
+            self.load_step_done(true, false);
+        }
+    }
+
+    /// Frame the filter preview should show now: paused video with at least one
+    /// geometry / color filter active
+    fn filter_preview_key(&self) -> Option<FilterPreviewKey> {
+        // Live preview: the player already shows the filtered picture
+        if !self.filter_preview || self.filter_live || self.get_playback_state() == PlaybackState::Playing {
+            return None;
+        }
+        let file = self.selected_file().filter(|f| !f.is_audio_only())?;
+        let chain = crate::ffmpeg::preview_filter_chain(&self.export_settings.filters)?;
+        Some((file.path.clone(), (self.current_time * 1000.0).round() as i64, chain))
+    }
+
+    /// Filtered frame matching the current position and filters, if extracted
+    pub fn filtered_preview(&self) -> Option<&egui::TextureHandle> {
+        let key = self.filter_preview_key()?;
+        self.filter_preview_texture.as_ref().filter(|(k, _)| *k == key).map(|(_, t)| t)
+    }
+
+    /// Unfiltered and filtered frames of the current position for the A/B view
+    pub fn compare_preview(&self) -> Option<(egui::TextureHandle, egui::TextureHandle)> {
+        if !self.filter_compare {
+            return None;
+        }
+        let filtered = self.filtered_preview()?;
+        let (path, ms, _) = self.filter_preview_key()?;
+        let raw = self.compare_raw_texture.as_ref().filter(|(k, _)| *k == (path, ms)).map(|(_, t)| t)?;
+        Some((raw.clone(), filtered.clone()))
+    }
+
+    /// Re-extract the filtered preview frame once the position and filters
+    /// have stopped changing for a moment
+    pub fn update_filter_preview(&mut self, ctx: &egui::Context) {
+        const DEBOUNCE: std::time::Duration = std::time::Duration::from_millis(300);
+
+        if let Some((key, result)) = self.filter_preview_result.lock().ok().and_then(|mut g| g.take()) {
+            self.filter_preview_running = false;
+            match result {
+                Ok(((data, w, h), raw)) => {
+                    if let Some((data, w, h)) = raw {
+                        let image = egui::ColorImage::from_rgba_unmultiplied([w as usize, h as usize], &data);
+                        let texture = ctx.load_texture("compare_raw", image, egui::TextureOptions::LINEAR);
+                        self.compare_raw_texture = Some(((key.0.clone(), key.1), texture));
+                    }
+                    let image = egui::ColorImage::from_rgba_unmultiplied([w as usize, h as usize], &data);
+                    let texture = ctx.load_texture("filter_preview", image, egui::TextureOptions::LINEAR);
+                    self.filter_preview_texture = Some((key, texture));
+                }
+                Err(e) => {
+                    self.status_message = format!("Filter preview failed: {}", e);
+                    self.filter_preview_failed = Some(key);
+                }
+            }
+        }
+
+        let Some(key) = self.filter_preview_key() else { return };
+        let want_raw = self.filter_compare
+            && !self.compare_raw_texture.as_ref().is_some_and(|(k, _)| k.0 == key.0 && k.1 == key.1);
+        let shown = self.filter_preview_texture.as_ref().is_some_and(|(k, _)| *k == key) && !want_raw;
+        if shown || self.filter_preview_failed.as_ref() == Some(&key) || self.filter_preview_running {
+            return;
+        }
+        match &self.filter_preview_wanted {
+            Some((wanted, since)) if *wanted == key => {
+                if since.elapsed() < DEBOUNCE {
+                    ctx.request_repaint_after(DEBOUNCE - since.elapsed());
+                    return;
+                }
+            }
+            _ => {
+                self.filter_preview_wanted = Some((key, std::time::Instant::now()));
+                ctx.request_repaint_after(DEBOUNCE);
+                return;
             }
         }
+
+        self.filter_preview_running = true;
+        let slot = self.filter_preview_result.clone();
+        std::thread::spawn(move || {
+            let (path, ms, chain) = &key;
+            let time = *ms as f64 / 1000.0;
+            let frames = extract_filtered_frame_rgba(path, time, chain).and_then(|filtered| {
+                // Same extraction without filters, so both sides come from the same decoded frame
+                let raw = if want_raw { Some(extract_filtered_frame_rgba(path, time, "null")?) } else { None };
+                Ok((filtered, raw))
+            });
+            *slot.lock().unwrap() = Some((key, frames));
+        });
     }
 
-    fn get_extension(&self, path: &PathBuf) -> String {
-        path.extension()
-            .unwrap_or_default()
-            .to_string_lossy()
-            .to_string()
+    /// Hand the current filter chain to the player for the live preview, once
+    /// the filters have stopped changing for a moment; the decoder restarts
+    /// its FFmpeg process with it at the current position
+    pub fn update_live_filter(&mut self, ctx: &egui::Context) {
+        const DEBOUNCE: std::time::Duration = std::time::Duration::from_millis(250);
+
+        let wanted = if self.filter_live && !self.selected_is_audio_only() {
+            crate::ffmpeg::preview_filter_chain(&self.export_settings.filters)
+        } else {
+            None
+        };
+        if wanted == self.live_filter_sent {
+            self.live_filter_wanted = None;
+            return;
+        }
+        match &self.live_filter_wanted {
+            Some((chain, since)) if *chain == wanted => {
+                if since.elapsed() < DEBOUNCE {
+                    ctx.request_repaint_after(DEBOUNCE - since.elapsed());
+                    return;
+                }
+            }
+            _ => {
+                self.live_filter_wanted = Some((wanted, std::time::Instant::now()));
+                ctx.request_repaint_after(DEBOUNCE);
+                return;
+            }
+        }
+        self.live_filter_wanted = None;
+        if let Some(player) = &self.player {
+            player.set_preview_filter(wanted.clone());
+        }
+        self.live_filter_sent = wanted;
     }
 
     /// Open current file in LosslessCut (if installed)
@@ -1507,23 +4115,34 @@ impl FFmpegApp {
 
     /// Handle keyboard shortcuts
     pub fn handle_input(&mut self, ctx: &egui::Context) {
+        // Don't steal keys while a text field (timecode entry, etc.) has focus
+        if ctx.wants_keyboard_input() {
+            return;
+        }
+
         ctx.input(|i| {
             // Space - Play/Pause
             if i.key_pressed(egui::Key::Space) {
                 self.toggle_play_pause();
             }
 
-            // Arrow keys - Seek (5s) or frame step when paused
-            if i.key_pressed(egui::Key::ArrowLeft) {
+            let steps = self.seek_settings;
+
+            // Arrow keys - Seek (small step) or frame step when paused.
+            // Shift+Arrow nudges the active IN/OUT handle by one frame instead.
+            if i.modifiers.shift && i.key_pressed(egui::Key::ArrowLeft) {
+                self.nudge_active_handle(-1);
+            } else if i.modifiers.shift && i.key_pressed(egui::Key::ArrowRight) {
+                self.nudge_active_handle(1);
+            } else if i.key_pressed(egui::Key::ArrowLeft) {
                 if self.get_playback_state() == PlaybackState::Playing {
-                    self.seek_relative(-5.0);
+                    self.seek_relative(-steps.small_secs);
                 } else {
                     self.frame_step_backward();
                 }
-            }
-            if i.key_pressed(egui::Key::ArrowRight) {
+            } else if i.key_pressed(egui::Key::ArrowRight) {
                 if self.get_playback_state() == PlaybackState::Playing {
-                    self.seek_relative(5.0);
+                    self.seek_relative(steps.small_secs);
                 } else {
                     self.frame_step_forward();
                 }
@@ -1543,15 +4162,16 @@ impl FFmpegApp {
                 self.frame_step_forward();
             }
 
-            // J/K/L - Playback control (NLE standard)
+            // J/K/L - Playback control (NLE standard), Shift for the fine step
+            let jl_step = if i.modifiers.shift { steps.fine_secs } else { steps.large_secs };
             if i.key_pressed(egui::Key::J) {
-                self.seek_relative(-10.0);
+                self.seek_relative(-jl_step);
             }
             if i.key_pressed(egui::Key::K) {
                 self.pause();
             }
             if i.key_pressed(egui::Key::L) {
-                self.seek_relative(10.0);
+                self.seek_relative(jl_step);
             }
 
             // Home/End - Go to start/end
@@ -1575,11 +4195,9 @@ impl FFmpegApp {
                 self.add_segment();
             }
 
-            // Delete - Remove selected segment
+            // Delete - Remove selected segment(s)
             if i.key_pressed(egui::Key::Delete) {
-                if let Some(idx) = self.selected_segment {
-                    self.remove_segment(idx);
-                }
+                self.remove_selected_segments();
             }
 
             // D - Duplicate selected segment
@@ -1603,14 +4221,21 @@ impl FFmpegApp {
                 self.speed_down();
             }
 
+            // M - Mute / unmute
+            if i.key_pressed(egui::Key::M) {
+                self.toggle_mute();
+            }
+
             // Backspace - Reset speed to 1x
             if i.key_pressed(egui::Key::Backspace) {
                 self.set_speed(1.0);
             }
 
-            // Ctrl+E - Export all
+            // Ctrl+E - Export all, E - Toggle selected segment and play the next
             if i.modifiers.ctrl && i.key_pressed(egui::Key::E) {
                 self.export_all();
+            } else if i.key_pressed(egui::Key::E) {
+                self.toggle_segment_and_advance();
             }
 
             // Ctrl+Shift+Delete - Clear all segments
@@ -1636,8 +4261,12 @@ impl FFmpegApp {
                 .collect()
         });
         if !dropped.is_empty() {
+            let before = self.status_message.clone();
             self.add_files(dropped);
-            self.status_message = format!("{} file(s) loaded", self.project.files.len());
+            // Keep what the import had to report (duplicates, unreadable files)
+            if self.status_message == before {
+                self.status_message = format!("{} file(s) loaded", self.project.files.len());
+            }
         }
     }
 }
@@ -1652,6 +4281,8 @@ impl eframe::App for FFmpegApp {
 
         // Update player
         self.update_player(ctx);
+        self.update_filter_preview(ctx);
+        self.update_live_filter(ctx);
 
         // Auto-follow playhead on timeline
         if self.timeline_follow_playhead && self.get_playback_state() == PlaybackState::Playing {
@@ -1673,28 +4304,29 @@ impl eframe::App for FFmpegApp {
         // Poll auto-cut silence detection
         self.poll_auto_cut();
 
+        // Poll scene-split detection
+        self.poll_scene_split();
+        self.poll_silence_ends();
+
         // Poll batch processing
         self.poll_batch();
+        self.poll_proof_sheet();
+        self.poll_loudness();
 
         // Poll waveform extraction
         self.poll_waveform();
+        self.poll_keyframes();
 
         // Poll thumbnail extraction
         self.poll_thumbnails();
+        self.poll_duration_estimates();
+        self.poll_capabilities();
 
         // Render UI
         crate::ui::render_main_window(self, ctx);
 
-        // Update status from task progress (only if not showing export status)
-        if !self.show_export_progress {
-            if let Ok(progress) = self.current_task.lock() {
-                if let Some(ref p) = *progress {
-                    self.status_message = p.message.clone();
-                }
-            }
-        }
-
         // Update export progress status
+        let mut finished_batch = Vec::new();
         if let Ok(queue) = self.export_queue.lock() {
             let (completed, total) = queue.total_progress();
             if total > 0 && self.show_export_progress {
@@ -1723,22 +4355,59 @@ impl eframe::App for FFmpegApp {
                         self.status_message = format!("Export complete! ({}/{})", success_count, total);
                     }
                     self.show_export_progress = false;
+                    finished_batch = queue.jobs.iter()
+                        .filter(|j| j.id >= self.manifest_from_id)
+                        .cloned()
+                        .collect();
                 } else if self.show_export_progress {
                     self.status_message = format!("Exporting... {}/{}", completed, total);
                 }
             }
         }
 
+        if !finished_batch.is_empty() {
+            self.size_checks = crate::export_queue::size_checks(&finished_batch, |output| {
+                std::fs::metadata(output).ok().map(|m| m.len())
+            });
+        }
+        if let [job] = finished_batch.as_slice() {
+            if job.status == JobStatus::Completed {
+                self.open_export_output(job.output.clone());
+            }
+        }
+        match self.write_export_playlist(&finished_batch) {
+            Ok(Some(playlist)) => self.status_message = format!("{} - playlist: {}", self.status_message, playlist.display()),
+            Ok(None) => {}
+            Err(e) => self.status_message = format!("{} - cannot write playlist: {}", self.status_message, e),
+        }
+        if let Some(summary) = self.write_export_summary(finished_batch) {
+            self.status_message = format!("{} - summary: {}", self.status_message, summary.display());
+        }
+
+        self.autosave_config(ctx);
+
         // Request repaint for progress updates
-        let needs_repaint = self.current_task.lock().map(|p| p.is_some()).unwrap_or(false)
-            || self.export_queue.lock().map(|q| q.is_processing || q.has_pending()).unwrap_or(false)
+        let needs_repaint = self.export_queue.lock().map(|q| q.is_processing || q.has_pending()).unwrap_or(false)
             || self.auto_cut_running
-            || self.batch_running;
+            || self.batch_running
+            || self.proof_sheet_running
+            || self.silence_ends_running
+            || self.loudness_running.is_some()
+            || self.load_status.is_pending()
+            || self.capabilities.is_none()
+            || self.filter_preview_running;
 
         if needs_repaint {
             ctx.request_repaint();
         }
     }
+
+    fn on_exit(&mut self, _gl: Option<&eframe::glow::Context>) {
+        let config = self.current_config();
+        if config != self.saved_config {
+            self.save_config(config);
+        }
+    }
 }
 
 /// Extract a thumbnail (first frame) from a video as raw RGBA data.
@@ -1770,25 +4439,48 @@ fn extract_thumbnail_rgba(path: &PathBuf) -> Option<(Vec<u8>, u32, u32)> {
     }
 }
 
-/// Extract audio waveform peaks using FFmpeg at 1kHz sample rate.
-/// Returns absolute amplitude values (one per millisecond).
-fn extract_waveform_peaks(path: &PathBuf) -> Vec<f32> {
+/// Extract the frame at `time` through the `-vf` chain as RGBA. The output
+/// size depends on the filters, so the frame goes through PNG rather than
+/// raw video.
+fn extract_filtered_frame_rgba(path: &std::path::Path, time: f64, chain: &str) -> Result<RgbaFrame, String> {
     let mut cmd = ffmpeg_command();
-    cmd.arg("-i")
+    cmd.args(["-ss", &format!("{:.3}", time), "-i"])
         .arg(path)
-        .args(["-ac", "1", "-ar", "1000", "-f", "f32le", "-vn", "pipe:1"])
+        .args([
+            "-vf", chain,
+            "-frames:v", "1",
+            "-f", "image2pipe",
+            "-c:v", "png",
+            "pipe:1",
+        ])
         .stdout(std::process::Stdio::piped())
-        .stderr(std::process::Stdio::null())
+        .stderr(std::process::Stdio::piped())
         .stdin(std::process::Stdio::null());
 
-    let output = match cmd.output() {
-        Ok(o) => o,
-        Err(_) => return Vec::new(),
-    };
+    let output = cmd.output().map_err(|e| e.to_string())?;
+    if output.stdout.is_empty() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(stderr.lines().last().unwrap_or("no frame").to_string());
+    }
+    let image = image::load_from_memory(&output.stdout).map_err(|e| e.to_string())?.to_rgba8();
+    let (w, h) = image.dimensions();
+    Ok((image.into_raw(), w, h))
+}
+
+/// " (N skipped)" status suffix for outputs dropped by the conflict policy
+fn skipped_suffix(skipped: usize) -> String {
+    if skipped > 0 {
+        format!(" ({} existing skipped)", skipped)
+    } else {
+        String::new()
+    }
+}
 
-    // Convert raw f32le bytes to absolute float samples
-    output.stdout
-        .chunks_exact(4)
-        .map(|c| f32::from_le_bytes([c[0], c[1], c[2], c[3]]).abs())
-        .collect()
+/// Status suffix for parts queued above the size limit
+fn over_limit_suffix(count: usize) -> String {
+    if count > 0 {
+        format!(" ⚠ {} over size limit", count)
+    } else {
+        String::new()
+    }
 }
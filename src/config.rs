@@ -0,0 +1,174 @@
+//! Persisted user settings.
+//!
+//! Stored as `config.json` in the platform config directory
+//! (`%APPDATA%\FFmpeg Studio` on Windows, `~/Library/Application Support/FFmpeg Studio`
+//! on macOS, `$XDG_CONFIG_HOME/ffmpeg-studio` or `~/.config/ffmpeg-studio` elsewhere).
+//! Missing fields fall back to their defaults, so older files keep loading.
+
+use crate::ui::{AnimationSettings, AudioExtractFormat, BatchTrimAnchor, ExportSettings, OpenOutputAction, ProofSheetSettings, SeekSettings, SplitSettings};
+use anyhow::{anyhow, Result};
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AppConfig {
+    pub volume: f32,
+    pub dark_mode: bool,
+    /// Trim mode, size limit, output folder, conflict policy
+    pub split_settings: SplitSettings,
+    /// Re-encode settings (subtitles are per file and never saved)
+    pub export_settings: ExportSettings,
+    pub seek_settings: SeekSettings,
+    pub fast_scrub_high_res: bool,
+    /// Try hardware decode (`-hwaccel`) for the video preview
+    pub hw_decode: bool,
+    /// Cheaper preview for weak machines (see `PreviewLimits::LOW_POWER`)
+    pub low_power: bool,
+    /// Preview frames decoded around a settled seek
+    pub prefetch: crate::player::PrefetchSettings,
+    /// Open audio and analyze the waveform on first Play, not on selection
+    pub defer_playback_init: bool,
+    pub merge_reencode: bool,
+    /// Target height of a re-encoded merge (`None` = largest input)
+    pub merge_resolution: Option<u32>,
+    pub batch_trim_anchor: BatchTrimAnchor,
+    /// What to do with the file of a single-output export once it is done
+    pub open_output: OpenOutputAction,
+    pub queue_order: crate::export_queue::QueueOrder,
+    pub proof_sheet: ProofSheetSettings,
+    pub animation: AnimationSettings,
+    /// Format of "Extract audio" on all files
+    pub audio_extract: AudioExtractFormat,
+    /// Output folders picked lately, most recent first
+    pub recent_output_folders: Vec<PathBuf>,
+    /// Encode profile file and the profile last applied from it
+    pub profiles_path: Option<PathBuf>,
+    pub selected_profile: Option<String>,
+    /// Explicit ffmpeg / ffprobe binaries (`FFMPEG_BIN` / `FFPROBE_BIN` win)
+    pub ffmpeg_path: Option<String>,
+    pub ffprobe_path: Option<String>,
+}
+
+impl Default for AppConfig {
+    fn default() -> Self {
+        Self {
+            volume: 1.0,
+            dark_mode: true,
+            split_settings: SplitSettings::default(),
+            export_settings: ExportSettings::default(),
+            seek_settings: SeekSettings::default(),
+            fast_scrub_high_res: true,
+            // VideoToolbox is always there on macOS; elsewhere drivers vary
+            hw_decode: cfg!(target_os = "macos"),
+            low_power: false,
+            prefetch: crate::player::PrefetchSettings::default(),
+            defer_playback_init: false,
+            merge_reencode: false,
+            merge_resolution: None,
+            batch_trim_anchor: BatchTrimAnchor::default(),
+            open_output: OpenOutputAction::default(),
+            queue_order: crate::export_queue::QueueOrder::default(),
+            proof_sheet: ProofSheetSettings::default(),
+            animation: AnimationSettings::default(),
+            audio_extract: AudioExtractFormat::default(),
+            recent_output_folders: Vec::new(),
+            profiles_path: None,
+            selected_profile: None,
+            ffmpeg_path: None,
+            ffprobe_path: None,
+        }
+    }
+}
+
+impl AppConfig {
+    /// Settings from disk, defaults when the file is absent or unreadable
+    pub fn load() -> Self {
+        let Some(path) = config_path() else { return Self::default() };
+        match std::fs::read_to_string(&path) {
+            Ok(json) => Self::from_json(&json).unwrap_or_else(|e| {
+                eprintln!("Ignoring settings file {:?}: {}", path, e);
+                Self::default()
+            }),
+            Err(_) => Self::default(),
+        }
+    }
+
+    pub fn from_json(json: &str) -> Result<Self> {
+        serde_json::from_str(json).map_err(|e| anyhow!("Invalid settings: {}", e))
+    }
+
+    pub fn save(&self) -> Result<()> {
+        let path = config_path().ok_or_else(|| anyhow!("No config directory on this system"))?;
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir)?;
+        }
+        let json = serde_json::to_string_pretty(self)?;
+        std::fs::write(&path, json).map_err(|e| anyhow!("Cannot write {}: {}", path.display(), e))
+    }
+}
+
+/// Platform config directory for the app
+pub fn config_dir() -> Option<PathBuf> {
+    #[cfg(target_os = "windows")]
+    {
+        std::env::var_os("APPDATA").map(|dir| PathBuf::from(dir).join("FFmpeg Studio"))
+    }
+    #[cfg(target_os = "macos")]
+    {
+        std::env::var_os("HOME")
+            .map(|home| PathBuf::from(home).join("Library/Application Support/FFmpeg Studio"))
+    }
+    #[cfg(not(any(target_os = "windows", target_os = "macos")))]
+    {
+        std::env::var_os("XDG_CONFIG_HOME")
+            .filter(|dir| !dir.is_empty())
+            .map(PathBuf::from)
+            .or_else(|| std::env::var_os("HOME").map(|home| PathBuf::from(home).join(".config")))
+            .map(|dir| dir.join("ffmpeg-studio"))
+    }
+}
+
+pub fn config_path() -> Option<PathBuf> {
+    config_dir().map(|dir| dir.join("config.json"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ui::TrimMode;
+
+    #[test]
+    fn missing_fields_use_defaults() {
+        let config = AppConfig::from_json(r#"{"volume": 0.5, "split_settings": {"trim_mode": "Precise"}}"#).unwrap();
+        assert_eq!(config.volume, 0.5);
+        assert_eq!(config.split_settings.trim_mode, TrimMode::Precise);
+        assert_eq!(config.split_settings.max_size_mb, SplitSettings::default().max_size_mb);
+        assert!(config.dark_mode);
+    }
+
+    #[test]
+    fn trim_mode_rules_match_codec_or_extension() {
+        let config = AppConfig::from_json(
+            r#"{"split_settings": {"trim_mode_rules": [
+                {"source": "ProRes", "mode": "HighQuality"},
+                {"source": ".mp4", "mode": "Lossless"}
+            ]}}"#,
+        ).unwrap();
+        let split = &config.split_settings;
+        assert_eq!(split.default_trim_mode("mov", Some("prores")), Some(TrimMode::HighQuality));
+        assert_eq!(split.default_trim_mode("MP4", Some("h264")), Some(TrimMode::Lossless));
+        assert_eq!(split.default_trim_mode("mkv", Some("h264")), None);
+    }
+
+    #[test]
+    fn round_trips_through_json() {
+        let config = AppConfig {
+            volume: 0.3,
+            selected_profile: Some("archive".to_string()),
+            ..Default::default()
+        };
+        let json = serde_json::to_string(&config).unwrap();
+        assert_eq!(AppConfig::from_json(&json).unwrap(), config);
+    }
+}
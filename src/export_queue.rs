@@ -1,5 +1,7 @@
-use crate::ui::TrimMode;
-use std::path::PathBuf;
+use crate::ffmpeg::ConcatMethod;
+use crate::ui::{AnimationSettings, AudioExtractFormat, ConflictPolicy, ExportSettings, TrimMode};
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
 use std::sync::{Arc, Mutex};
 
 /// Status of an export job
@@ -21,7 +23,50 @@ pub enum ExportOperation {
     },
     Concat {
         inputs: Vec<PathBuf>,
+        method: ConcatMethod,
     },
+    /// Looping GIF / WebP / APNG of a time range
+    Animation {
+        start: f64,
+        end: f64,
+        settings: AnimationSettings,
+    },
+    /// Still image of the frame at `time`
+    Thumbnail {
+        time: f64,
+    },
+    /// Whole file copied into another container (`-c copy`)
+    Remux,
+    /// First audio track of the whole file, as a standalone audio file
+    ExtractAudio {
+        format: AudioExtractFormat,
+    },
+}
+
+/// Order in which pending jobs are run
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+pub enum QueueOrder {
+    /// As queued
+    #[default]
+    Fifo,
+    /// Stream-copy jobs (near instant) first, then smart-cut, then re-encodes
+    LosslessFirst,
+    /// Shortest time range first; merges, whose length is unknown here, last
+    ShortestFirst,
+}
+
+impl QueueOrder {
+    pub fn all() -> &'static [QueueOrder] {
+        &[QueueOrder::Fifo, QueueOrder::LosslessFirst, QueueOrder::ShortestFirst]
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            QueueOrder::Fifo => "In order",
+            QueueOrder::LosslessFirst => "Lossless first",
+            QueueOrder::ShortestFirst => "Shortest first",
+        }
+    }
 }
 
 /// A single export job
@@ -34,9 +79,37 @@ pub struct ExportJob {
     pub status: JobStatus,
     pub progress: f32,
     pub segment_label: String,
+    /// Encoding settings captured when the job was queued
+    pub settings: ExportSettings,
+    /// Keep an existing output that validates instead of re-exporting it
+    pub skip_if_valid: bool,
+    /// Size predicted from the bitrate map when queued (0 = unknown)
+    pub estimated_bytes: u64,
 }
 
 impl ExportJob {
+    /// 0 = stream copy, 1 = smart-cut (mostly copy), 2 = full re-encode
+    fn cost_class(&self) -> u8 {
+        match &self.operation {
+            ExportOperation::Trim { mode: TrimMode::Lossless, .. } => 0,
+            ExportOperation::Concat { method: ConcatMethod::Copy, .. } => 0,
+            ExportOperation::Thumbnail { .. } | ExportOperation::Remux => 0,
+            ExportOperation::Trim { mode: TrimMode::SmartCut, .. } | ExportOperation::ExtractAudio { .. } => 1,
+            _ => 2,
+        }
+    }
+
+    /// Length of the time range the job covers (infinite for merges and
+    /// remuxes, whose length is unknown here)
+    fn range_secs(&self) -> f64 {
+        match &self.operation {
+            ExportOperation::Trim { start, end, .. } | ExportOperation::Animation { start, end, .. } => end - start,
+            ExportOperation::Concat { .. } | ExportOperation::Remux | ExportOperation::ExtractAudio { .. } => f64::INFINITY,
+            ExportOperation::Thumbnail { .. } => 0.0,
+        }
+    }
+
+    #[cfg(test)]
     pub fn new_trim(id: u32, input: PathBuf, output: PathBuf, start: f64, end: f64, mode: TrimMode) -> Self {
         Self {
             id,
@@ -46,10 +119,14 @@ impl ExportJob {
             status: JobStatus::Pending,
             progress: 0.0,
             segment_label: String::new(),
+            settings: ExportSettings::default(),
+            skip_if_valid: false,
+            estimated_bytes: 0,
         }
     }
 
-    pub fn new_trim_with_label(id: u32, input: PathBuf, output: PathBuf, start: f64, end: f64, mode: TrimMode, label: String) -> Self {
+    #[allow(clippy::too_many_arguments)]
+    pub fn new_trim_with_label(id: u32, input: PathBuf, output: PathBuf, start: f64, end: f64, mode: TrimMode, label: String, settings: ExportSettings) -> Self {
         Self {
             id,
             input,
@@ -58,6 +135,9 @@ impl ExportJob {
             status: JobStatus::Pending,
             progress: 0.0,
             segment_label: label,
+            settings,
+            skip_if_valid: false,
+            estimated_bytes: 0,
         }
     }
 
@@ -79,24 +159,50 @@ impl ExportJob {
                     mode.name()
                 )
             }
-            ExportOperation::Concat { inputs } => {
+            ExportOperation::Concat { inputs, method } => {
                 format!(
-                    "Merge {} files -> {}",
+                    "Merge {} files -> {}{}",
                     inputs.len(),
                     self.output.file_name().unwrap_or_default().to_string_lossy(),
+                    if *method == ConcatMethod::Copy { "" } else { " (re-encode)" },
+                )
+            }
+            ExportOperation::Animation { start, end, settings } => {
+                format!(
+                    "{} -> {} ({:.1}s, {} {} fps)",
+                    self.input.file_name().unwrap_or_default().to_string_lossy(),
+                    self.output.file_name().unwrap_or_default().to_string_lossy(),
+                    end - start,
+                    settings.format.name(),
+                    settings.fps
+                )
+            }
+            ExportOperation::Thumbnail { time } => {
+                format!(
+                    "{} -> {} (frame at {})",
+                    self.input.file_name().unwrap_or_default().to_string_lossy(),
+                    self.output.file_name().unwrap_or_default().to_string_lossy(),
+                    crate::utils::format_time(*time),
+                )
+            }
+            ExportOperation::Remux => {
+                format!(
+                    "{} -> {} (remux, streams copied)",
+                    self.input.file_name().unwrap_or_default().to_string_lossy(),
+                    self.output.file_name().unwrap_or_default().to_string_lossy(),
+                )
+            }
+            ExportOperation::ExtractAudio { format } => {
+                format!(
+                    "{} -> {} (audio, {})",
+                    self.input.file_name().unwrap_or_default().to_string_lossy(),
+                    self.output.file_name().unwrap_or_default().to_string_lossy(),
+                    format.name(),
                 )
             }
         }
     }
 
-    pub fn status_text(&self) -> &str {
-        match &self.status {
-            JobStatus::Pending => "En attente",
-            JobStatus::Running => "En cours...",
-            JobStatus::Completed => "Termine",
-            JobStatus::Failed(_) => "Echec",
-        }
-    }
 }
 
 /// Queue of export jobs
@@ -105,6 +211,8 @@ pub struct ExportQueue {
     pub jobs: Vec<ExportJob>,
     next_id: u32,
     pub is_processing: bool,
+    /// Which pending job `next_pending` picks
+    pub order: QueueOrder,
 }
 
 impl ExportQueue {
@@ -113,10 +221,12 @@ impl ExportQueue {
             jobs: Vec::new(),
             next_id: 0,
             is_processing: false,
+            order: QueueOrder::default(),
         }
     }
 
     /// Add a trim job to the queue
+    #[cfg(test)]
     pub fn add_trim(&mut self, input: PathBuf, output: PathBuf, start: f64, end: f64, mode: TrimMode) -> u32 {
         let id = self.next_id;
         self.next_id += 1;
@@ -127,7 +237,7 @@ impl ExportQueue {
     }
 
     /// Add a concat job to the queue
-    pub fn add_concat(&mut self, inputs: Vec<PathBuf>, output: PathBuf, label: String) -> u32 {
+    pub fn add_concat(&mut self, inputs: Vec<PathBuf>, output: PathBuf, label: String, method: ConcatMethod) -> u32 {
         let id = self.next_id;
         self.next_id += 1;
 
@@ -136,31 +246,128 @@ impl ExportQueue {
             id,
             input: first_input,
             output,
-            operation: ExportOperation::Concat { inputs },
+            operation: ExportOperation::Concat { inputs, method },
+            status: JobStatus::Pending,
+            progress: 0.0,
+            segment_label: label,
+            settings: ExportSettings::default(),
+            skip_if_valid: false,
+            estimated_bytes: 0,
+        };
+        self.jobs.push(job);
+        id
+    }
+
+    /// Add an animation (GIF / WebP / APNG) job to the queue
+    pub fn add_animation(&mut self, input: PathBuf, output: PathBuf, start: f64, end: f64, settings: AnimationSettings) -> u32 {
+        let id = self.next_id;
+        self.next_id += 1;
+
+        let job = ExportJob {
+            id,
+            input,
+            output,
+            operation: ExportOperation::Animation { start, end, settings },
+            status: JobStatus::Pending,
+            progress: 0.0,
+            segment_label: String::new(),
+            settings: ExportSettings::default(),
+            skip_if_valid: false,
+            estimated_bytes: 0,
+        };
+        self.jobs.push(job);
+        id
+    }
+
+    /// Add a still-frame job to the queue
+    pub fn add_thumbnail(&mut self, input: PathBuf, output: PathBuf, time: f64, label: String) -> u32 {
+        let id = self.next_id;
+        self.next_id += 1;
+
+        let job = ExportJob {
+            id,
+            input,
+            output,
+            operation: ExportOperation::Thumbnail { time },
+            status: JobStatus::Pending,
+            progress: 0.0,
+            segment_label: label,
+            settings: ExportSettings::default(),
+            skip_if_valid: false,
+            estimated_bytes: 0,
+        };
+        self.jobs.push(job);
+        id
+    }
+
+    /// Add a whole-file remux job to the queue
+    pub fn add_remux(&mut self, input: PathBuf, output: PathBuf, label: String) -> u32 {
+        let id = self.next_id;
+        self.next_id += 1;
+
+        let job = ExportJob {
+            id,
+            input,
+            output,
+            operation: ExportOperation::Remux,
             status: JobStatus::Pending,
             progress: 0.0,
             segment_label: label,
+            settings: ExportSettings::default(),
+            skip_if_valid: false,
+            estimated_bytes: 0,
+        };
+        self.jobs.push(job);
+        id
+    }
+
+    /// Add a whole-file audio extraction job to the queue
+    pub fn add_extract_audio(&mut self, input: PathBuf, output: PathBuf, format: AudioExtractFormat, label: String) -> u32 {
+        let id = self.next_id;
+        self.next_id += 1;
+
+        let job = ExportJob {
+            id,
+            input,
+            output,
+            operation: ExportOperation::ExtractAudio { format },
+            status: JobStatus::Pending,
+            progress: 0.0,
+            segment_label: label,
+            settings: ExportSettings::default(),
+            skip_if_valid: false,
+            estimated_bytes: 0,
         };
         self.jobs.push(job);
         id
     }
 
     /// Add a trim job with a segment label
-    pub fn add_trim_with_label(&mut self, input: PathBuf, output: PathBuf, start: f64, end: f64, mode: TrimMode, label: String) -> u32 {
+    #[allow(clippy::too_many_arguments)]
+    pub fn add_trim_with_label(&mut self, input: PathBuf, output: PathBuf, start: f64, end: f64, mode: TrimMode, label: String, settings: ExportSettings) -> u32 {
         let id = self.next_id;
         self.next_id += 1;
 
-        let job = ExportJob::new_trim_with_label(id, input, output, start, end, mode, label);
+        let job = ExportJob::new_trim_with_label(id, input, output, start, end, mode, label, settings);
         self.jobs.push(job);
         id
     }
 
-    /// Get the next pending job
+    /// Get the next pending job, following `order`. Ties keep queue order.
     pub fn next_pending(&mut self) -> Option<&mut ExportJob> {
-        self.jobs.iter_mut().find(|j| j.status == JobStatus::Pending)
+        let pending = self.jobs.iter().enumerate().filter(|(_, j)| j.status == JobStatus::Pending);
+        let index = match self.order {
+            QueueOrder::Fifo => pending.map(|(i, _)| i).next(),
+            QueueOrder::LosslessFirst => pending.min_by_key(|(i, j)| (j.cost_class(), *i)).map(|(i, _)| i),
+            QueueOrder::ShortestFirst => pending
+                .min_by(|(i, a), (k, b)| a.range_secs().total_cmp(&b.range_secs()).then(i.cmp(k)))
+                .map(|(i, _)| i),
+        }?;
+        self.jobs.get_mut(index)
     }
 
     /// Get job by ID
+    #[cfg(test)]
     pub fn get_job(&self, id: u32) -> Option<&ExportJob> {
         self.jobs.iter().find(|j| j.id == id)
     }
@@ -184,21 +391,100 @@ impl ExportQueue {
         self.jobs.retain(|j| matches!(j.status, JobStatus::Pending | JobStatus::Running));
     }
 
-    /// Remove a specific job
-    pub fn remove_job(&mut self, id: u32) {
-        self.jobs.retain(|j| j.id != id);
+    /// Put a failed job back in line (disk full, transient error...):
+    /// `process_queue` runs it again. False if the job had not failed.
+    pub fn retry_job(&mut self, id: u32) -> bool {
+        match self.get_job_mut(id) {
+            Some(job) if matches!(job.status, JobStatus::Failed(_)) => {
+                job.status = JobStatus::Pending;
+                job.progress = 0.0;
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// `retry_job` for every failed job; returns how many were requeued
+    pub fn retry_all_failed(&mut self) -> usize {
+        let failed: Vec<u32> = self.jobs.iter()
+            .filter(|j| matches!(j.status, JobStatus::Failed(_)))
+            .map(|j| j.id)
+            .collect();
+        failed.into_iter().filter(|id| self.retry_job(*id)).count()
+    }
+
+    /// True if a pending or running job will write to `path`
+    pub fn has_planned_output(&self, path: &Path) -> bool {
+        self.jobs.iter().any(|j| {
+            matches!(j.status, JobStatus::Pending | JobStatus::Running) && j.output == path
+        })
+    }
+
+    /// Change the output of a job that has not started yet. The extension
+    /// must stay the same: the container was checked against the codecs when
+    /// the job was queued.
+    pub fn set_output(&mut self, id: u32, output: PathBuf) -> Result<(), String> {
+        let taken = self.jobs.iter().any(|j| {
+            j.id != id && matches!(j.status, JobStatus::Pending | JobStatus::Running) && j.output == output
+        });
+        let job = self.get_job_mut(id).ok_or("Job not found")?;
+        if job.status != JobStatus::Pending {
+            return Err("Only pending jobs can be changed".to_string());
+        }
+        if output == job.input {
+            return Err("Output would overwrite the input".to_string());
+        }
+        let ext = |p: &Path| p.extension().map(|e| e.to_string_lossy().to_lowercase());
+        if ext(&output) != ext(&job.output) {
+            return Err(format!(
+                "Keep the .{} extension",
+                ext(&job.output).unwrap_or_default()
+            ));
+        }
+        if taken {
+            return Err(format!("Another queued job already writes {}", output.display()));
+        }
+        job.output = output;
+        Ok(())
+    }
+
+    /// Resolve the output of a job about to be queued. A file already on disk
+    /// is handled by `policy`, except under `ConflictPolicy::Skip`: the job is
+    /// queued anyway and, once flagged with `set_skip_if_valid`, `process_queue`
+    /// keeps the file if it validates. The output of another queued job is
+    /// never shared: skipped under `Skip`, renamed under `Overwrite` and `Rename`.
+    pub fn plan_output(&self, planned: &Path, policy: ConflictPolicy) -> Option<PathBuf> {
+        let on_disk_taken = policy != ConflictPolicy::Skip;
+        let output = crate::utils::resolve_output_path_with(planned, policy, |p| {
+            (on_disk_taken && p.exists()) || self.has_planned_output(p)
+        })?;
+        if !self.has_planned_output(&output) {
+            return Some(output);
+        }
+        // Overwrite replaces files on disk, not what another job will write
+        crate::utils::resolve_output_path_with(&output, ConflictPolicy::Rename, |p| self.has_planned_output(p))
+    }
+
+    /// Mark a job as reusable when its output already exists and is valid
+    pub fn set_skip_if_valid(&mut self, id: u32, skip: bool) {
+        if let Some(job) = self.get_job_mut(id) {
+            job.skip_if_valid = skip;
+        }
+    }
+
+    /// Record the size the segment was expected to have
+    pub fn set_estimated_size(&mut self, id: u32, bytes: u64) {
+        if let Some(job) = self.get_job_mut(id) {
+            job.estimated_bytes = bytes;
+        }
     }
 
     /// Count pending jobs
+    #[cfg(test)]
     pub fn pending_count(&self) -> usize {
         self.jobs.iter().filter(|j| j.status == JobStatus::Pending).count()
     }
 
-    /// Count completed jobs
-    pub fn completed_count(&self) -> usize {
-        self.jobs.iter().filter(|j| j.status == JobStatus::Completed).count()
-    }
-
     /// Check if queue has pending work
     pub fn has_pending(&self) -> bool {
         self.jobs.iter().any(|j| j.status == JobStatus::Pending)
@@ -212,6 +498,19 @@ impl ExportQueue {
         }).count();
         (completed, total)
     }
+
+    /// Share of the whole queue done, counting how far running jobs got
+    pub fn overall_fraction(&self) -> f32 {
+        if self.jobs.is_empty() {
+            return 0.0;
+        }
+        let done: f32 = self.jobs.iter().map(|j| match j.status {
+            JobStatus::Completed | JobStatus::Failed(_) => 1.0,
+            JobStatus::Running => j.progress.clamp(0.0, 1.0),
+            JobStatus::Pending => 0.0,
+        }).sum();
+        done / self.jobs.len() as f32
+    }
 }
 
 /// Shared queue type for async access
@@ -220,3 +519,230 @@ pub type SharedQueue = Arc<Mutex<ExportQueue>>;
 pub fn create_shared_queue() -> SharedQueue {
     Arc::new(Mutex::new(ExportQueue::new()))
 }
+
+/// Estimated vs. actual size of a finished output
+#[derive(Debug, Clone, PartialEq)]
+pub struct SizeCheck {
+    pub output: PathBuf,
+    pub estimated: u64,
+    pub actual: u64,
+}
+
+impl SizeCheck {
+    /// Error of the estimate, in percent of it (positive = output bigger)
+    pub fn error_percent(&self) -> f64 {
+        (self.actual as f64 - self.estimated as f64) / self.estimated as f64 * 100.0
+    }
+}
+
+/// Size checks of the completed jobs that had an estimate. `size_of`
+/// returns the size of an output on disk, `None` if it is missing.
+pub fn size_checks(jobs: &[ExportJob], size_of: impl Fn(&Path) -> Option<u64>) -> Vec<SizeCheck> {
+    jobs.iter()
+        .filter(|j| j.status == JobStatus::Completed && j.estimated_bytes > 0)
+        .filter_map(|j| Some(SizeCheck {
+            output: j.output.clone(),
+            estimated: j.estimated_bytes,
+            actual: size_of(&j.output)?,
+        }))
+        .collect()
+}
+
+/// Manifest of finished jobs, one CSV row per job: which source and segment
+/// produced which file. `probe` returns the actual (size, duration) of an
+/// output, `None` if it is missing or unreadable.
+pub fn export_manifest_csv(jobs: &[ExportJob], probe: impl Fn(&Path) -> Option<(u64, f64)>) -> String {
+    let mut out = String::from("output,source,label,start,end,size_bytes,duration,status\n");
+    for job in jobs {
+        let (source, start, end) = match &job.operation {
+            ExportOperation::Trim { start, end, .. } | ExportOperation::Animation { start, end, .. } => (
+                job.input.to_string_lossy().to_string(),
+                format!("{:.3}", start),
+                format!("{:.3}", end),
+            ),
+            ExportOperation::Concat { inputs, .. } => (
+                inputs.iter().map(|p| p.to_string_lossy()).collect::<Vec<_>>().join(" + "),
+                String::new(),
+                String::new(),
+            ),
+            ExportOperation::Thumbnail { time } => (
+                job.input.to_string_lossy().to_string(),
+                format!("{:.3}", time),
+                format!("{:.3}", time),
+            ),
+            ExportOperation::Remux | ExportOperation::ExtractAudio { .. } => {
+                (job.input.to_string_lossy().to_string(), String::new(), String::new())
+            }
+        };
+        let status = match &job.status {
+            JobStatus::Completed => "ok".to_string(),
+            JobStatus::Failed(e) => format!("failed: {}", e),
+            JobStatus::Pending | JobStatus::Running => "not run".to_string(),
+        };
+        let (size, duration) = match probe(&job.output) {
+            Some((size, duration)) => (size.to_string(), format!("{:.3}", duration)),
+            None => (String::new(), String::new()),
+        };
+        let fields = [
+            job.output.to_string_lossy().to_string(),
+            source,
+            job.segment_label.clone(),
+            start,
+            end,
+            size,
+            duration,
+            status,
+        ];
+        let row: Vec<String> = fields.iter().map(|f| csv_field(f)).collect();
+        out.push_str(&row.join(","));
+        out.push('\n');
+    }
+    out
+}
+
+/// Quote a CSV field when it contains a separator, quote or line break
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn manifest_lists_every_job() {
+        let ok = ExportJob {
+            status: JobStatus::Completed,
+            segment_label: "Intro, part 1".to_string(),
+            ..ExportJob::new_trim(0, PathBuf::from("/v/in.mp4"), PathBuf::from("/v/in_001.mp4"), 1.0, 3.5, TrimMode::Lossless)
+        };
+        let failed = ExportJob {
+            status: JobStatus::Failed("Cancelled".to_string()),
+            ..ExportJob::new_trim(1, PathBuf::from("/v/in.mp4"), PathBuf::from("/v/in_002.mp4"), 3.5, 9.0, TrimMode::Lossless)
+        };
+        let csv = export_manifest_csv(&[ok, failed], |p| {
+            (p == Path::new("/v/in_001.mp4")).then_some((1024, 2.5))
+        });
+        let lines: Vec<&str> = csv.lines().collect();
+        assert_eq!(lines.len(), 3);
+        assert_eq!(lines[1], "/v/in_001.mp4,/v/in.mp4,\"Intro, part 1\",1.000,3.500,1024,2.500,ok");
+        assert_eq!(lines[2], "/v/in_002.mp4,/v/in.mp4,,3.500,9.000,,,failed: Cancelled");
+    }
+
+    #[test]
+    fn size_checks_compare_estimate_and_output() {
+        let estimated = ExportJob {
+            status: JobStatus::Completed,
+            estimated_bytes: 1000,
+            ..ExportJob::new_trim(0, PathBuf::from("/v/in.mp4"), PathBuf::from("/v/in_001.mp4"), 0.0, 1.0, TrimMode::Precise)
+        };
+        let unknown = ExportJob {
+            status: JobStatus::Completed,
+            ..ExportJob::new_trim(1, PathBuf::from("/v/in.mp4"), PathBuf::from("/v/in_002.mp4"), 1.0, 2.0, TrimMode::Precise)
+        };
+        let failed = ExportJob {
+            status: JobStatus::Failed("boom".to_string()),
+            estimated_bytes: 1000,
+            ..ExportJob::new_trim(2, PathBuf::from("/v/in.mp4"), PathBuf::from("/v/in_003.mp4"), 2.0, 3.0, TrimMode::Precise)
+        };
+        let checks = size_checks(&[estimated, unknown, failed], |_| Some(1250));
+        assert_eq!(checks.len(), 1);
+        assert_eq!(checks[0].actual, 1250);
+        assert_eq!(checks[0].error_percent(), 25.0);
+    }
+
+    #[test]
+    fn set_output_only_for_pending_jobs() {
+        let mut queue = ExportQueue::new();
+        let a = queue.add_trim(PathBuf::from("/v/in.mp4"), PathBuf::from("/v/a.mp4"), 0.0, 1.0, TrimMode::Lossless);
+        let b = queue.add_trim(PathBuf::from("/v/in.mp4"), PathBuf::from("/v/b.mp4"), 1.0, 2.0, TrimMode::Lossless);
+
+        assert!(queue.set_output(a, PathBuf::from("/out/a.MP4")).is_ok());
+        assert_eq!(queue.get_job(a).unwrap().output, PathBuf::from("/out/a.MP4"));
+        assert!(queue.set_output(a, PathBuf::from("/out/a.mkv")).is_err());
+        assert!(queue.set_output(a, PathBuf::from("/v/b.mp4")).is_err());
+        assert!(queue.set_output(a, PathBuf::from("/v/in.mp4")).is_err());
+
+        queue.get_job_mut(b).unwrap().status = JobStatus::Running;
+        assert!(queue.set_output(b, PathBuf::from("/out/b.mp4")).is_err());
+    }
+
+    #[test]
+    fn planned_outputs_are_never_shared() {
+        let mut queue = ExportQueue::new();
+        let planned = PathBuf::from("/nonexistent/out/clip.mp4");
+        queue.add_trim(PathBuf::from("/v/in.mp4"), planned.clone(), 0.0, 1.0, TrimMode::Lossless);
+
+        assert_eq!(
+            queue.plan_output(&planned, ConflictPolicy::Overwrite),
+            Some(PathBuf::from("/nonexistent/out/clip_1.mp4"))
+        );
+        assert_eq!(
+            queue.plan_output(&planned, ConflictPolicy::Rename),
+            Some(PathBuf::from("/nonexistent/out/clip_1.mp4"))
+        );
+        assert_eq!(queue.plan_output(&planned, ConflictPolicy::Skip), None);
+
+        // Finished jobs no longer hold their output
+        queue.jobs[0].status = JobStatus::Completed;
+        assert_eq!(queue.plan_output(&planned, ConflictPolicy::Overwrite), Some(planned));
+    }
+
+    #[test]
+    fn overall_fraction_counts_running_progress() {
+        let mut queue = ExportQueue::new();
+        assert_eq!(queue.overall_fraction(), 0.0);
+        let a = queue.add_trim(PathBuf::from("/v/in.mp4"), PathBuf::from("/v/a.mp4"), 0.0, 1.0, TrimMode::Lossless);
+        let b = queue.add_trim(PathBuf::from("/v/in.mp4"), PathBuf::from("/v/b.mp4"), 1.0, 2.0, TrimMode::Lossless);
+        queue.add_trim(PathBuf::from("/v/in.mp4"), PathBuf::from("/v/c.mp4"), 2.0, 3.0, TrimMode::Lossless);
+        queue.add_trim(PathBuf::from("/v/in.mp4"), PathBuf::from("/v/d.mp4"), 3.0, 4.0, TrimMode::Lossless);
+        queue.get_job_mut(a).unwrap().status = JobStatus::Failed("boom".to_string());
+        let running = queue.get_job_mut(b).unwrap();
+        running.status = JobStatus::Running;
+        running.progress = 0.5;
+        assert_eq!(queue.overall_fraction(), 0.375);
+    }
+
+    #[test]
+    fn retry_requeues_only_failed_jobs() {
+        let mut queue = ExportQueue::new();
+        let a = queue.add_trim(PathBuf::from("/v/in.mp4"), PathBuf::from("/v/a.mp4"), 0.0, 1.0, TrimMode::Lossless);
+        let b = queue.add_trim(PathBuf::from("/v/in.mp4"), PathBuf::from("/v/b.mp4"), 1.0, 2.0, TrimMode::Lossless);
+        let c = queue.add_trim(PathBuf::from("/v/in.mp4"), PathBuf::from("/v/c.mp4"), 2.0, 3.0, TrimMode::Lossless);
+        for id in [a, b] {
+            let job = queue.get_job_mut(id).unwrap();
+            job.status = JobStatus::Failed("No space left on device".to_string());
+            job.progress = 0.6;
+        }
+        queue.get_job_mut(c).unwrap().status = JobStatus::Completed;
+
+        assert!(queue.retry_job(a));
+        assert_eq!(queue.get_job(a).unwrap().status, JobStatus::Pending);
+        assert_eq!(queue.get_job(a).unwrap().progress, 0.0);
+        assert!(!queue.retry_job(a), "already pending");
+        assert!(!queue.retry_job(c), "completed jobs stay done");
+        assert_eq!(queue.retry_all_failed(), 1);
+        assert_eq!(queue.pending_count(), 2);
+    }
+
+    #[test]
+    fn next_pending_follows_queue_order() {
+        let mut queue = ExportQueue::new();
+        let slow = queue.add_trim(PathBuf::from("/v/in.mp4"), PathBuf::from("/v/a.mp4"), 0.0, 5.0, TrimMode::HighQuality);
+        let long = queue.add_trim(PathBuf::from("/v/in.mp4"), PathBuf::from("/v/b.mp4"), 0.0, 60.0, TrimMode::Lossless);
+        let short = queue.add_trim(PathBuf::from("/v/in.mp4"), PathBuf::from("/v/c.mp4"), 0.0, 2.0, TrimMode::Lossless);
+
+        assert_eq!(queue.next_pending().unwrap().id, slow);
+        queue.order = QueueOrder::LosslessFirst;
+        assert_eq!(queue.next_pending().unwrap().id, long);
+        queue.order = QueueOrder::ShortestFirst;
+        assert_eq!(queue.next_pending().unwrap().id, short);
+
+        queue.get_job_mut(short).unwrap().status = JobStatus::Completed;
+        assert_eq!(queue.next_pending().unwrap().id, slow);
+    }
+}
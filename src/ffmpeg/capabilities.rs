@@ -0,0 +1,184 @@
+//! Version et capacités du binaire FFmpeg (encodeurs, filtres compilés).
+//!
+//! Les builds diffèrent (pas de libx265, pas de libass...) : l'UI grise les
+//! options qui en dépendent au lieu de laisser l'export échouer avec
+//! « Unknown encoder ».
+
+use super::paths::{ffmpeg_command, ffmpeg_path};
+use anyhow::{anyhow, Result};
+use std::collections::BTreeSet;
+use std::process::Stdio;
+
+/// Encodeurs utilisés par les exports, listés dans le panneau d'infos
+pub const KEY_ENCODERS: &[&str] = &["libx264", "libx265", "libvpx-vp9", "aac", "libopus", "libmp3lame", "libwebp", "apng", "gif", "mov_text"];
+/// Filtres utilisés par les exports, listés dans le panneau d'infos
+pub const KEY_FILTERS: &[&str] = &["subtitles", "yadif", "loudnorm", "silencedetect", "palettegen", "tpad", "equalizer"];
+
+/// Méthodes de décodage matériel par ordre de préférence : l'API native de
+/// la plateforme d'abord, les API propres à un fabricant ensuite
+pub const HWACCEL_PREFERENCE: &[&str] = &["videotoolbox", "d3d11va", "vaapi", "cuda", "qsv", "dxva2", "vdpau"];
+
+/// Ce que le binaire FFmpeg sait faire
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct FfmpegCapabilities {
+    /// Binaire interrogé
+    pub path: String,
+    /// Version (`6.1.1`, `N-113284-g...`), vide si illisible
+    pub version: String,
+    pub encoders: BTreeSet<String>,
+    pub filters: BTreeSet<String>,
+    /// Méthodes `-hwaccel` compilées (`cuda`, `vaapi`, `videotoolbox`...)
+    pub hwaccels: BTreeSet<String>,
+}
+
+impl FfmpegCapabilities {
+    pub fn has_encoder(&self, name: &str) -> bool {
+        self.encoders.contains(name)
+    }
+
+    pub fn has_filter(&self, name: &str) -> bool {
+        self.filters.contains(name)
+    }
+
+    /// Encodeurs requis pour produire un fichier `container` en ré-encodage
+    pub fn container_encoders(container: &str) -> &'static [&'static str] {
+        match container {
+            "webm" => &["libvpx-vp9", "libopus"],
+            _ => &["libx264", "aac"],
+        }
+    }
+
+    /// Méthode `-hwaccel` à essayer pour l'aperçu, dans l'ordre de
+    /// `HWACCEL_PREFERENCE` ; `None` si ce build n'en a aucune
+    pub fn preferred_hwaccel(&self) -> Option<&'static str> {
+        HWACCEL_PREFERENCE.iter().copied().find(|m| self.hwaccels.contains(*m))
+    }
+
+    /// Premier encodeur requis par `container` absent de ce build
+    pub fn missing_for_container(&self, container: &str) -> Option<&'static str> {
+        Self::container_encoders(container).iter().copied().find(|e| !self.has_encoder(e))
+    }
+}
+
+/// Interroge `ffmpeg -version`, `-encoders` et `-filters` (bloquant, une
+/// fraction de seconde)
+pub fn probe_capabilities() -> Result<FfmpegCapabilities> {
+    let run = |arg: &str| -> Result<String> {
+        let output = ffmpeg_command()
+            .args(["-hide_banner", arg])
+            .stdin(Stdio::null())
+            .stderr(Stdio::null())
+            .output()
+            .map_err(|e| anyhow!("Cannot run {}: {}", ffmpeg_path(), e))?;
+        if !output.status.success() {
+            return Err(anyhow!("{} {} failed", ffmpeg_path(), arg));
+        }
+        Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+    };
+    Ok(FfmpegCapabilities {
+        path: ffmpeg_path().to_string(),
+        version: parse_version(&run("-version")?).unwrap_or_default(),
+        encoders: parse_encoders(&run("-encoders")?),
+        filters: parse_filters(&run("-filters")?),
+        // Liste facultative : sans elle l'aperçu reste en décodage logiciel
+        hwaccels: run("-hwaccels").map(|o| parse_hwaccels(&o)).unwrap_or_default(),
+    })
+}
+
+/// `ffmpeg version 6.1.1-3ubuntu5 Copyright (c) ...` → `6.1.1-3ubuntu5`
+pub fn parse_version(output: &str) -> Option<String> {
+    let line = output.lines().next()?;
+    let mut words = line.split_whitespace();
+    words.find(|w| *w == "version")?;
+    words.next().map(str::to_string)
+}
+
+/// Noms listés par `ffmpeg -encoders`, après la ligne ` ------` :
+///   ` V....D libx264              libx264 H.264 / AVC ...`
+pub fn parse_encoders(output: &str) -> BTreeSet<String> {
+    output.lines()
+        .skip_while(|l| !l.trim_start().starts_with("---"))
+        .skip(1)
+        .filter_map(|l| {
+            let mut words = l.split_whitespace();
+            let flags = words.next()?;
+            (flags.len() == 6).then_some(())?;
+            words.next().map(str::to_string)
+        })
+        .collect()
+}
+
+/// Noms listés par `ffmpeg -filters` : lignes `flags nom entrées->sorties description`
+///   ` TSC adelay            A->A       Delay one or more audio channels.`
+pub fn parse_filters(output: &str) -> BTreeSet<String> {
+    output.lines()
+        .filter_map(|l| {
+            let words: Vec<&str> = l.split_whitespace().take(3).collect();
+            match words.as_slice() {
+                [_, name, io] if io.contains("->") => Some(name.to_string()),
+                _ => None,
+            }
+        })
+        .collect()
+}
+
+/// Sortie de `ffmpeg -hwaccels` : un nom par ligne après l'en-tête
+///   `Hardware acceleration methods:`
+pub fn parse_hwaccels(output: &str) -> BTreeSet<String> {
+    output.lines()
+        .skip_while(|l| !l.ends_with(':'))
+        .skip(1)
+        .map(str::trim)
+        .filter(|l| !l.is_empty())
+        .map(str::to_string)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_version_encoders_and_filters() {
+        assert_eq!(
+            parse_version("ffmpeg version 6.1.1-3ubuntu5 Copyright (c) 2000-2023 the FFmpeg developers\nbuilt with gcc 13"),
+            Some("6.1.1-3ubuntu5".to_string())
+        );
+        assert_eq!(parse_version(""), None);
+
+        let encoders = parse_encoders(
+            "Encoders:\n V..... = Video\n A..... = Audio\n ------\n \
+             V....D libx264              libx264 H.264 / AVC (codec h264)\n \
+             A....D aac                  AAC (Advanced Audio Coding)\n",
+        );
+        assert_eq!(encoders.into_iter().collect::<Vec<_>>(), ["aac", "libx264"]);
+
+        let filters = parse_filters(
+            "Filters:\n  T.. = Timeline support\n  | = Source or sink filter\n \
+             TSC adelay            A->A       Delay one or more audio channels.\n \
+             ... subtitles         V->V       Render text subtitles onto input video using the libass library.\n",
+        );
+        assert_eq!(filters.into_iter().collect::<Vec<_>>(), ["adelay", "subtitles"]);
+    }
+
+    #[test]
+    fn picks_the_platform_hwaccel_first() {
+        let caps = FfmpegCapabilities {
+            hwaccels: parse_hwaccels("Hardware acceleration methods:\nvdpau\ncuda\nvaapi\n\n"),
+            ..Default::default()
+        };
+        assert_eq!(caps.hwaccels.len(), 3);
+        assert_eq!(caps.preferred_hwaccel(), Some("vaapi"));
+        assert_eq!(FfmpegCapabilities::default().preferred_hwaccel(), None);
+    }
+
+    #[test]
+    fn container_needs_its_encoders() {
+        let caps = FfmpegCapabilities {
+            encoders: ["libx264", "aac", "libopus"].iter().map(|s| s.to_string()).collect(),
+            ..Default::default()
+        };
+        assert_eq!(caps.missing_for_container("mp4"), None);
+        assert_eq!(caps.missing_for_container("webm"), Some("libvpx-vp9"));
+    }
+}
@@ -0,0 +1,150 @@
+//! Chapitres : lecture avec ffprobe, découpe à la fenêtre d'un segment et
+//! écriture au format FFMETADATA.
+//!
+//! Un segment exporté garde ainsi les chapitres qui tombent dans sa plage,
+//! recalés sur son début, au lieu de les perdre (smart-cut, concat de
+//! fragments) ou de garder ceux de tout le fichier.
+
+use super::paths::ffprobe_command;
+use anyhow::{anyhow, Result};
+use serde::Deserialize;
+use std::path::Path;
+
+/// Chapitre réduit à ce qu'on réécrit : bornes en secondes et titre
+#[derive(Debug, Clone, PartialEq)]
+pub struct Chapter {
+    pub start: f64,
+    pub end: f64,
+    pub title: String,
+}
+
+/// Un chapitre qui déborde d'à peine plus sur le segment n'est pas gardé
+const MIN_CHAPTER_SECS: f64 = 0.5;
+
+#[derive(Deserialize)]
+struct ProbeChapters {
+    #[serde(default)]
+    chapters: Vec<ProbeChapter>,
+}
+
+#[derive(Deserialize)]
+struct ProbeChapter {
+    start_time: String,
+    end_time: String,
+    #[serde(default)]
+    tags: std::collections::HashMap<String, String>,
+}
+
+/// Chapitres de `path` (vide si le fichier n'en a pas)
+pub fn probe_chapters(path: &Path) -> Result<Vec<Chapter>> {
+    let output = ffprobe_command()
+        .args(["-v", "quiet", "-print_format", "json", "-show_chapters"])
+        .arg(path)
+        .output()
+        .map_err(|e| anyhow!("ffprobe a échoué: {}", e))?;
+    if !output.status.success() {
+        return Err(anyhow!("ffprobe -show_chapters failed"));
+    }
+    parse_chapters_json(&String::from_utf8_lossy(&output.stdout))
+}
+
+/// Sortie JSON de `ffprobe -show_chapters`. Les bornes viennent de
+/// `start_time` / `end_time`, déjà converties en secondes par ffprobe.
+pub fn parse_chapters_json(json: &str) -> Result<Vec<Chapter>> {
+    let probe: ProbeChapters = serde_json::from_str(json)
+        .map_err(|e| anyhow!("Failed to parse ffprobe chapters: {}", e))?;
+    Ok(probe.chapters.into_iter()
+        .filter_map(|c| Some(Chapter {
+            start: c.start_time.parse().ok()?,
+            end: c.end_time.parse().ok()?,
+            title: c.tags.get("title").cloned().unwrap_or_default(),
+        }))
+        .collect())
+}
+
+/// Chapitres qui recouvrent `[start, end]`, bornés à la plage et décalés
+/// pour que le segment commence à 0
+pub fn slice_chapters(chapters: &[Chapter], start: f64, end: f64) -> Vec<Chapter> {
+    chapters.iter()
+        .filter_map(|c| {
+            let (s, e) = (c.start.max(start), c.end.min(end));
+            (e - s >= MIN_CHAPTER_SECS).then(|| Chapter { start: s - start, end: e - start, title: c.title.clone() })
+        })
+        .collect()
+}
+
+/// Fichier FFMETADATA décrivant `chapters` (base de temps en ms). `=`, `;`,
+/// `#`, `\` et les retours à la ligne des titres sont échappés par `\`.
+pub fn build_ffmetadata(chapters: &[Chapter]) -> String {
+    let mut out = String::from(";FFMETADATA1\n");
+    for c in chapters {
+        let title: String = c.title.chars()
+            .flat_map(|ch| match ch {
+                '=' | ';' | '#' | '\\' | '\n' => vec!['\\', ch],
+                _ => vec![ch],
+            })
+            .collect();
+        out.push_str(&format!(
+            "[CHAPTER]\nTIMEBASE=1/1000\nSTART={}\nEND={}\ntitle={}\n",
+            (c.start * 1000.0).round() as i64,
+            (c.end * 1000.0).round() as i64,
+            title,
+        ));
+    }
+    out
+}
+
+/// Copie `input` dans `output` en remplaçant ses chapitres par ceux du
+/// fichier FFMETADATA `metadata` (flux et métadonnées globales inchangés)
+pub fn build_chapter_remux_args(input: &Path, metadata: &Path, output: &Path) -> Vec<String> {
+    vec![
+        "-y".to_string(),
+        "-i".to_string(),
+        input.to_string_lossy().to_string(),
+        "-f".to_string(),
+        "ffmetadata".to_string(),
+        "-i".to_string(),
+        metadata.to_string_lossy().to_string(),
+        "-map".to_string(),
+        "0".to_string(),
+        "-map_chapters".to_string(),
+        "1".to_string(),
+        "-c".to_string(),
+        "copy".to_string(),
+        output.to_string_lossy().to_string(),
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_and_slices_chapters_to_a_segment() {
+        let chapters = parse_chapters_json(
+            r#"{"chapters": [
+                {"id": 0, "time_base": "1/1000", "start": 0, "start_time": "0.000000", "end": 60000, "end_time": "60.000000", "tags": {"title": "Intro"}},
+                {"id": 1, "time_base": "1/1000", "start": 60000, "start_time": "60.000000", "end": 200000, "end_time": "200.000000", "tags": {"title": "Part = 1"}},
+                {"id": 2, "time_base": "1/1000", "start": 200000, "start_time": "200.000000", "end": 300000, "end_time": "300.000000"}
+            ]}"#,
+        ).unwrap();
+        assert_eq!(chapters.len(), 3);
+        assert_eq!(chapters[2].title, "");
+
+        // Segment 45 s → 200.2 s : la fin du chapitre 1, le 2 en entier, 0.2 s du 3 (ignoré)
+        let sliced = slice_chapters(&chapters, 45.0, 200.2);
+        assert_eq!(sliced, vec![
+            Chapter { start: 0.0, end: 15.0, title: "Intro".to_string() },
+            Chapter { start: 15.0, end: 155.0, title: "Part = 1".to_string() },
+        ]);
+
+        let meta = build_ffmetadata(&sliced);
+        assert!(meta.starts_with(";FFMETADATA1\n[CHAPTER]\nTIMEBASE=1/1000\nSTART=0\nEND=15000\ntitle=Intro\n"));
+        assert!(meta.ends_with("START=15000\nEND=155000\ntitle=Part \\= 1\n"));
+    }
+
+    #[test]
+    fn no_chapters_key_means_none() {
+        assert_eq!(parse_chapters_json("{}").unwrap(), Vec::new());
+    }
+}
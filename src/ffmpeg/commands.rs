@@ -1,17 +1,19 @@
-use crate::ui::TrimMode;
+use super::filters::{audio_filter_chain, video_filter_chain};
+use super::smart_cut::SourceVideoCodec;
+use crate::ui::{AnimationFormat, AnimationSettings, AudioExtractFormat, ExportSettings, RotationMethod, SubtitleMode, TrimMode};
 use std::path::{Path, PathBuf};
 
 /// Build FFmpeg arguments for trimming with different modes
 /// Maximise l'utilisation CPU avec -threads 0 et x264 threads=auto
 pub fn build_trim_args(
-    input: &PathBuf,
-    output: &PathBuf,
+    input: &Path,
+    output: &Path,
     start: f64,
     end: f64,
     mode: TrimMode,
+    settings: &ExportSettings,
+    source_codec: SourceVideoCodec,
 ) -> Vec<String> {
-    let duration = end - start;
-
     match mode {
         TrimMode::SmartCut => {
             // SmartCut a un pipeline multi-étapes (fragments + concat) qui ne
@@ -22,97 +24,744 @@ pub fn build_trim_args(
         TrimMode::Lossless => {
             // -c copy: pas de ré-encodage, coupe aux keyframes (~instantané)
             // Pas besoin de threads ici, c'est juste du copy
-            vec![
+            // En `-to`, la fin est une option d'entrée, absolue comme `-ss` :
+            // le recalage de `-ss` sur la keyframe ne décale plus la fin
+            let mut args = vec![
                 "-y".to_string(),
                 "-ss".to_string(),
                 format!("{:.3}", start),
+            ];
+            if settings.absolute_end {
+                args.extend(end_args(settings, start, end));
+            }
+            args.extend(display_rotation_args(settings));
+            args.extend([
                 "-i".to_string(),
                 input.to_string_lossy().to_string(),
-                "-t".to_string(),
-                format!("{:.3}", duration),
-                "-c".to_string(),
-                "copy".to_string(),
-                "-avoid_negative_ts".to_string(),
-                "make_zero".to_string(),
-                output.to_string_lossy().to_string(),
-            ]
+            ]);
+            if !settings.absolute_end {
+                args.extend(end_args(settings, start, end));
+            }
+            args.extend(["-c".to_string(), "copy".to_string()]);
+            args.extend(aspect_args(settings));
+            args.extend(metadata_args(settings));
+            if settings.keep_timestamps {
+                // Timestamps d'origine : le clip démarre à `start`, pas à zéro
+                args.push("-copyts".to_string());
+            } else {
+                args.extend(["-avoid_negative_ts".to_string(), "make_zero".to_string()]);
+            }
+            args.push(output.to_string_lossy().to_string());
+            args
         }
         TrimMode::Precise => {
-            // Ré-encodage ultrafast, tous les coeurs CPU
-            vec![
+            // Ré-encodage ultrafast, tous les coeurs CPU sauf limite demandée
+            let mut args = vec![
                 "-y".to_string(),
                 "-threads".to_string(),
-                "0".to_string(),
+                thread_count(settings),
+            ];
+            args.extend(display_rotation_args(settings));
+            args.extend(padding_seek_args(settings, start));
+            args.extend([
                 "-i".to_string(),
                 input.to_string_lossy().to_string(),
-                "-ss".to_string(),
-                format!("{:.3}", start),
-                "-t".to_string(),
-                format!("{:.3}", duration),
-                "-c:v".to_string(),
-                "libx264".to_string(),
-                "-preset".to_string(),
-                "ultrafast".to_string(),
-                "-tune".to_string(),
-                "fastdecode".to_string(),
-                "-crf".to_string(),
-                "18".to_string(),
-                "-threads".to_string(),
-                "0".to_string(),
-                "-x264-params".to_string(),
-                "threads=auto:lookahead_threads=auto".to_string(),
-                "-c:a".to_string(),
-                "aac".to_string(),
-                "-b:a".to_string(),
-                "192k".to_string(),
-                output.to_string_lossy().to_string(),
-            ]
+            ]);
+            args.extend(subtitle_input_args(settings, start, end));
+            args.extend(reencode_seek_args(settings, start, end));
+            if let Some(vf) = output_video_filters(settings, start, end) {
+                args.push("-vf".to_string());
+                args.push(vf);
+            }
+            args.extend(video_args(settings, mode, source_codec, output));
+            args.extend(frame_rate_args(settings));
+            args.extend(aspect_args(settings));
+            args.extend(pix_fmt_args(settings, output));
+            args.extend(audio_encode_args(settings, 192, output, start, end));
+            args.extend(subtitle_embed_args(settings, output));
+            args.extend(metadata_args(settings));
+            args.extend(settings.extra_args.iter().cloned());
+            args.push(output.to_string_lossy().to_string());
+            args
         }
         TrimMode::HighQuality => {
             // Ré-encodage haute qualité, tous les coeurs CPU
             // medium au lieu de slow : meilleur ratio qualité/vitesse en multi-thread
-            vec![
+            let mut args = vec![
                 "-y".to_string(),
                 "-threads".to_string(),
-                "0".to_string(),
+                thread_count(settings),
+            ];
+            args.extend(display_rotation_args(settings));
+            args.extend(padding_seek_args(settings, start));
+            args.extend([
                 "-i".to_string(),
                 input.to_string_lossy().to_string(),
-                "-ss".to_string(),
-                format!("{:.3}", start),
-                "-t".to_string(),
-                format!("{:.3}", duration),
-                "-c:v".to_string(),
-                "libx264".to_string(),
-                "-preset".to_string(),
-                "medium".to_string(),
-                "-crf".to_string(),
-                "18".to_string(),
-                "-threads".to_string(),
-                "0".to_string(),
-                "-x264-params".to_string(),
-                "threads=auto:lookahead_threads=auto:sliced-threads=1".to_string(),
-                "-c:a".to_string(),
-                "aac".to_string(),
-                "-b:a".to_string(),
-                "256k".to_string(),
-                output.to_string_lossy().to_string(),
-            ]
+            ]);
+            args.extend(subtitle_input_args(settings, start, end));
+            args.extend(reencode_seek_args(settings, start, end));
+            if let Some(vf) = output_video_filters(settings, start, end) {
+                args.push("-vf".to_string());
+                args.push(vf);
+            }
+            args.extend(video_args(settings, mode, source_codec, output));
+            args.extend(frame_rate_args(settings));
+            args.extend(aspect_args(settings));
+            args.extend(pix_fmt_args(settings, output));
+            args.extend(audio_encode_args(settings, 256, output, start, end));
+            args.extend(subtitle_embed_args(settings, output));
+            args.extend(metadata_args(settings));
+            args.extend(settings.extra_args.iter().cloned());
+            args.push(output.to_string_lossy().to_string());
+            args
         }
     }
 }
 
+/// Build FFmpeg arguments to save the frame at `timestamp` as an image
+/// (format from the output extension, JPEG quality 2)
+pub fn build_thumbnail_args(input: &Path, output: &Path, timestamp: f64) -> Vec<String> {
+    vec![
+        "-y".to_string(),
+        "-ss".to_string(),
+        timestamp.to_string(),
+        "-i".to_string(),
+        input.to_string_lossy().to_string(),
+        "-vframes".to_string(),
+        "1".to_string(),
+        "-q:v".to_string(),
+        "2".to_string(),
+        output.to_string_lossy().to_string(),
+    ]
+}
+
+/// Build FFmpeg arguments to copy every audio/video stream of `input` into
+/// the container of `output` (`-c copy`, no re-encoding). MKV also takes the
+/// subtitle and attachment streams; other containers would reject most of
+/// them, so only audio and video are kept. MP4/MOV get `+faststart`.
+/// Codec compatibility is checked beforehand with `check_copy_container`.
+pub fn build_remux_args(input: &Path, output: &Path) -> Vec<String> {
+    let ext = output.extension().unwrap_or_default().to_string_lossy().to_lowercase();
+    let mut args = vec!["-y".to_string(), "-i".to_string(), input.to_string_lossy().to_string()];
+    let maps: &[&str] = if ext == "mkv" { &["0"] } else { &["0:v?", "0:a?"] };
+    for map in maps {
+        args.push("-map".to_string());
+        args.push(map.to_string());
+    }
+    args.push("-c".to_string());
+    args.push("copy".to_string());
+    if matches!(ext.as_str(), "mp4" | "m4v" | "mov") {
+        args.push("-movflags".to_string());
+        args.push("+faststart".to_string());
+    }
+    args.push(output.to_string_lossy().to_string());
+    args
+}
+
+/// Build FFmpeg arguments to extract the first audio track of `input` as a
+/// standalone file (video, subtitle and data streams dropped). MP3 uses
+/// LAME VBR quality 2, AAC 192 kb/s, WAV 16-bit PCM at the source rate.
+pub fn build_extract_audio_args(input: &Path, output: &Path, format: AudioExtractFormat) -> Vec<String> {
+    let mut args = vec![
+        "-y".to_string(),
+        "-i".to_string(),
+        input.to_string_lossy().to_string(),
+        "-map".to_string(),
+        "0:a:0".to_string(),
+        "-vn".to_string(),
+        "-sn".to_string(),
+        "-dn".to_string(),
+        "-c:a".to_string(),
+        format.encoder().to_string(),
+    ];
+    match format {
+        AudioExtractFormat::Mp3 => args.extend(["-q:a".to_string(), "2".to_string()]),
+        AudioExtractFormat::Aac => args.extend(["-b:a".to_string(), "192k".to_string()]),
+        AudioExtractFormat::Wav => {}
+    }
+    args.push(output.to_string_lossy().to_string());
+    args
+}
+
+/// Build FFmpeg arguments for a looping animation (GIF, WebP, APNG) of
+/// `[start, end]`, without audio. GIF goes through a generated palette,
+/// otherwise its 256 colours band badly.
+pub fn build_animation_args(input: &Path, output: &Path, start: f64, end: f64, settings: &AnimationSettings) -> Vec<String> {
+    let mut vf = format!("fps={}", settings.fps.max(1));
+    if settings.width > 0 {
+        vf.push_str(&format!(",scale={}:-2:flags=lanczos", settings.width));
+    }
+
+    let mut args = vec![
+        "-y".to_string(),
+        "-ss".to_string(),
+        format!("{:.3}", start),
+        "-t".to_string(),
+        format!("{:.3}", end - start),
+        "-i".to_string(),
+        input.to_string_lossy().to_string(),
+        "-an".to_string(),
+    ];
+    match settings.format {
+        AnimationFormat::Gif => args.extend([
+            "-filter_complex".to_string(),
+            format!("{},split[a][b];[a]palettegen=stats_mode=diff[p];[b][p]paletteuse=dither=bayer", vf),
+            "-loop".to_string(),
+            "0".to_string(),
+        ]),
+        AnimationFormat::WebP => args.extend([
+            "-vf".to_string(),
+            vf,
+            "-c:v".to_string(),
+            "libwebp".to_string(),
+            "-lossless".to_string(),
+            "0".to_string(),
+            "-q:v".to_string(),
+            "75".to_string(),
+            "-loop".to_string(),
+            "0".to_string(),
+        ]),
+        AnimationFormat::Apng => args.extend([
+            "-vf".to_string(),
+            vf,
+            "-c:v".to_string(),
+            "apng".to_string(),
+            "-plays".to_string(),
+            "0".to_string(),
+            "-f".to_string(),
+            "apng".to_string(),
+        ]),
+    }
+    args.push(output.to_string_lossy().to_string());
+    args
+}
+
+/// Fin de coupe : durée `-t` (défaut) ou position absolue `-to` si demandé
+fn end_args(settings: &ExportSettings, start: f64, end: f64) -> [String; 2] {
+    if settings.absolute_end {
+        ["-to".to_string(), format!("{:.3}", end)]
+    } else {
+        ["-t".to_string(), format!("{:.3}", end - start)]
+    }
+}
+
+/// Seek côté sortie des modes ré-encodés. Avec du padding, les filtres
+/// coupent eux-mêmes le segment (`trim`) : un `-ss` ou `-t` en plus
+/// retirerait le noir ajouté ou recouperait le segment décalé.
+fn reencode_seek_args(settings: &ExportSettings, start: f64, end: f64) -> Vec<String> {
+    if settings.filters.has_padding() {
+        return Vec::new();
+    }
+    let mut args = vec!["-ss".to_string(), format!("{:.3}", start)];
+    args.extend(end_args(settings, start, end));
+    args
+}
+
+/// Seek côté entrée des exports avec padding : sans lui, les filtres qui
+/// coupent le segment décoderaient toute la source depuis zéro. Les chaînes
+/// de filtres remettent ensuite les timestamps d'origine (`setpts=PTS+start`).
+fn padding_seek_args(settings: &ExportSettings, start: f64) -> Vec<String> {
+    if !settings.filters.has_padding() || start <= 0.0 {
+        return Vec::new();
+    }
+    vec!["-ss".to_string(), format!("{:.3}", start)]
+}
+
+/// Option d'entrée `-display_rotation` (FFmpeg 6.1+) quand la rotation est
+/// demandée en drapeau : remplace la matrice d'affichage de la source, donc
+/// `Rotation::None` annule une rotation erronée. En copie seul le drapeau
+/// change ; en ré-encodage l'autorotation de FFmpeg l'incruste dans l'image.
+fn display_rotation_args(settings: &ExportSettings) -> Vec<String> {
+    let filters = &settings.filters;
+    if filters.rotation_method != RotationMethod::Metadata {
+        return Vec::new();
+    }
+    vec![
+        "-display_rotation:v:0".to_string(),
+        filters.rotation.display_rotation_degrees().to_string(),
+    ]
+}
+
+/// Ratio d'affichage saisi par l'utilisateur (`16:9`, `16/9`, `2.35`,
+/// `2.35:1`), réduit en entiers premiers entre eux. Au plus 4 décimales par
+/// terme, ratio borné à 1:10..10:1 pour écarter les fautes de frappe.
+pub fn parse_aspect_ratio(text: &str) -> Result<(u32, u32), String> {
+    let invalid = || format!("Invalid display aspect ratio \"{}\" (expected e.g. 16:9, 4/3 or 2.35)", text.trim());
+    // `2.35` → (235, 100)
+    let term = |s: &str| -> Option<(u64, u64)> {
+        let s = s.trim();
+        let (int, frac) = s.split_once('.').unwrap_or((s, ""));
+        if frac.len() > 4 || (int.is_empty() && frac.is_empty())
+            || !int.chars().chain(frac.chars()).all(|c| c.is_ascii_digit()) {
+            return None;
+        }
+        let scale = 10u64.pow(frac.len() as u32);
+        let value = format!("{}{}", int, frac).parse::<u64>().ok()?;
+        (value > 0).then_some((value, scale))
+    };
+    let (num, den) = match text.split_once([':', '/']) {
+        Some((a, b)) => (term(a).ok_or_else(invalid)?, term(b).ok_or_else(invalid)?),
+        None => (term(text).ok_or_else(invalid)?, (1, 1)),
+    };
+    // (a/sa) / (b/sb) = a·sb / b·sa
+    let (w, h) = (num.0 * den.1, den.0 * num.1);
+    let g = gcd(w, h);
+    let (w, h) = (w / g, h / g);
+    if w > 10 * h || h > 10 * w {
+        return Err(invalid());
+    }
+    Ok((u32::try_from(w).map_err(|_| invalid())?, u32::try_from(h).map_err(|_| invalid())?))
+}
+
+fn gcd(a: u64, b: u64) -> u64 {
+    if b == 0 { a.max(1) } else { gcd(b, a % b) }
+}
+
+/// `-aspect W:H` : ratio d'affichage (DAR) du flux vidéo de sortie. En copie
+/// seul le conteneur est retagué, en ré-encodage l'encodeur l'écrit aussi
+/// dans le flux. Un ratio invalide est refusé avant la mise en file.
+fn aspect_args(settings: &ExportSettings) -> Vec<String> {
+    match settings.filters.display_aspect.as_deref().map(parse_aspect_ratio) {
+        Some(Ok((w, h))) => vec!["-aspect".to_string(), format!("{}:{}", w, h)],
+        _ => Vec::new(),
+    }
+}
+
+/// Bitstream filter qui réécrit le ratio dans le flux copié, pour les
+/// lecteurs qui ignorent le tag du conteneur. H.264/HEVC portent un SAR
+/// (DAR × hauteur / largeur), le MPEG-2 directement le DAR. Vide pour les
+/// autres codecs : seul le tag `-aspect` du conteneur s'applique.
+pub fn aspect_bsf_args(codec: Option<&str>, width: u32, height: u32, dar: (u32, u32)) -> Vec<String> {
+    let (dw, dh) = (dar.0 as u64, dar.1 as u64);
+    let (sw, sh) = (dw * height as u64, dh * width as u64);
+    let g = gcd(sw, sh);
+    let filter = match codec {
+        Some("h264") if sw > 0 && sh > 0 => format!("h264_metadata=sample_aspect_ratio={}/{}", sw / g, sh / g),
+        Some("hevc") if sw > 0 && sh > 0 => format!("hevc_metadata=sample_aspect_ratio={}/{}", sw / g, sh / g),
+        Some("mpeg2video") => format!("mpeg2_metadata=display_aspect_ratio={}/{}", dw, dh),
+        _ => return Vec::new(),
+    };
+    vec!["-bsf:v".to_string(), filter]
+}
+
+/// Sortie WebM : seuls VP8/VP9/AV1 + Vorbis/Opus y sont admis
+fn is_webm(output: &Path) -> bool {
+    output
+        .extension()
+        .is_some_and(|ext| ext.to_string_lossy().eq_ignore_ascii_case("webm"))
+}
+
+/// Arguments vidéo des modes ré-encodés. Une source HEVC reste en HEVC
+/// (libx265) au lieu d'être transcodée en x264, le reste part en libx264.
+/// Une sortie WebM passe en libvpx-vp9 quel que soit le codec source.
+fn video_encode_args(mode: TrimMode, source_codec: SourceVideoCodec, output: &Path) -> Vec<String> {
+    let hq = mode == TrimMode::HighQuality;
+    let preset = if hq { "medium" } else { "ultrafast" };
+
+    if is_webm(output) {
+        // CRF VP9 : `-b:v 0` obligatoire, sinon libvpx reste en mode
+        // contraint par le débit par défaut et ignore en partie le CRF
+        return vec![
+            "-c:v".to_string(),
+            "libvpx-vp9".to_string(),
+            "-b:v".to_string(),
+            "0".to_string(),
+            "-crf".to_string(),
+            if hq { "24" } else { "32" }.to_string(),
+            "-deadline".to_string(),
+            if hq { "good" } else { "realtime" }.to_string(),
+            "-cpu-used".to_string(),
+            if hq { "2" } else { "8" }.to_string(),
+            "-row-mt".to_string(),
+            "1".to_string(),
+        ];
+    }
+
+    if source_codec == SourceVideoCodec::H265 {
+        // Échelle CRF x265 décalée d'environ +2 par rapport à x264
+        return vec![
+            "-c:v".to_string(),
+            "libx265".to_string(),
+            "-preset".to_string(),
+            preset.to_string(),
+            "-crf".to_string(),
+            "20".to_string(),
+        ];
+    }
+
+    let mut args = vec![
+        "-c:v".to_string(),
+        "libx264".to_string(),
+        "-preset".to_string(),
+        preset.to_string(),
+    ];
+    if !hq {
+        args.push("-tune".to_string());
+        args.push("fastdecode".to_string());
+    }
+    let x264_params = if hq {
+        "threads=auto:lookahead_threads=auto:sliced-threads=1"
+    } else {
+        "threads=auto:lookahead_threads=auto"
+    };
+    args.extend([
+        "-crf".to_string(),
+        "18".to_string(),
+        "-threads".to_string(),
+        "0".to_string(),
+        "-x264-params".to_string(),
+        x264_params.to_string(),
+    ]);
+    args
+}
+
+/// Chaîne `-vf` des modes ré-encodés : les filtres, puis `fps` si une
+/// cadence de sortie est imposée. Le filtre duplique / supprime les images
+/// dans le graphe (sortie CFR même depuis une source VFR) ; placé en
+/// dernier, il ne perturbe ni les fondus ni le padding calés sur la source.
+fn output_video_filters(settings: &ExportSettings, start: f64, end: f64) -> Option<String> {
+    let chain = video_filter_chain(&settings.filters, burn_in_subtitles(settings), start, end);
+    let Some(fps) = settings.output_fps else { return chain };
+    let fps = format!("fps={}", fps);
+    Some(match chain {
+        Some(chain) => format!("{},{}", chain, fps),
+        None => fps,
+    })
+}
+
+/// `-map_metadata 0` si demandé : date de création, titre... de la source
+fn metadata_args(settings: &ExportSettings) -> Vec<String> {
+    if settings.copy_metadata {
+        vec!["-map_metadata".to_string(), "0".to_string()]
+    } else {
+        Vec::new()
+    }
+}
+
+/// `-r` : cadence déclarée du flux de sortie, alignée sur le filtre `fps`
+fn frame_rate_args(settings: &ExportSettings) -> Vec<String> {
+    match settings.output_fps {
+        Some(fps) => vec!["-r".to_string(), fps.to_string()],
+        None => Vec::new(),
+    }
+}
+
+/// Encodeur du mode, ou celui imposé par un profil d'encodage ; le
+/// `-preset` / `-crf` du profil remplace celui du mode.
+fn video_args(settings: &ExportSettings, mode: TrimMode, source_codec: SourceVideoCodec, output: &Path) -> Vec<String> {
+    let mut args = match &settings.video_codec {
+        Some(codec) => vec!["-c:v".to_string(), codec.clone()],
+        None => video_encode_args(mode, source_codec, output),
+    };
+    if let Some(preset) = &settings.preset {
+        set_option(&mut args, "-preset", preset.clone());
+    }
+    if let Some(crf) = settings.crf {
+        set_option(&mut args, "-crf", crf.to_string());
+    }
+    if let Some(n) = settings.threads {
+        // Encodeur et filtres bornés aussi, sinon x264 relance un thread par coeur
+        set_option(&mut args, "-threads", n.to_string());
+        if let Some(i) = args.iter().position(|a| a == "-x264-params") {
+            if let Some(params) = args.get_mut(i + 1) {
+                *params = params.replace("threads=auto", &format!("threads={}", n));
+            }
+        }
+        args.extend(["-filter_threads".to_string(), n.to_string()]);
+    }
+    if settings.closed_gop {
+        closed_gop_args(&mut args);
+    }
+    args
+}
+
+/// Intervalle des images clés forcées par `closed_gop`, en secondes
+pub const CLOSED_GOP_SECS: f64 = 2.0;
+
+/// GOP fermés et réguliers : une image clé IDR toutes les `CLOSED_GOP_SECS`
+/// (la première image de chaque segment en est une), sans détection de
+/// scène qui en décalerait une ni GOP ouvert qui référencerait le segment
+/// suivant. Les paramètres x264 / x265 s'ajoutent à ceux déjà présents.
+fn closed_gop_args(args: &mut Vec<String>) {
+    let encoder = args.iter().position(|a| a == "-c:v").and_then(|i| args.get(i + 1)).cloned();
+    args.extend([
+        "-force_key_frames".to_string(),
+        format!("expr:gte(t,n_forced*{})", CLOSED_GOP_SECS),
+        "-flags".to_string(),
+        "+cgop".to_string(),
+    ]);
+    let params_flag = match encoder.as_deref() {
+        Some("libx264") => "-x264-params",
+        Some("libx265") => "-x265-params",
+        _ => return,
+    };
+    args.extend(["-forced-idr".to_string(), "1".to_string()]);
+    let gop = "scenecut=0:open-gop=0";
+    match args.iter().position(|a| a == params_flag) {
+        Some(i) if i + 1 < args.len() => args[i + 1] = format!("{}:{}", args[i + 1], gop),
+        _ => args.extend([params_flag.to_string(), gop.to_string()]),
+    }
+}
+
+/// Valeur de `-threads` : la limite demandée, sinon 0 (auto)
+fn thread_count(settings: &ExportSettings) -> String {
+    settings.threads.unwrap_or(0).to_string()
+}
+
+/// Remplace la valeur d'une option déjà présente, sinon l'ajoute
+fn set_option(args: &mut Vec<String>, flag: &str, value: String) {
+    match args.iter().position(|a| a == flag) {
+        Some(i) if i + 1 < args.len() => args[i + 1] = value,
+        _ => {
+            args.push(flag.to_string());
+            args.push(value);
+        }
+    }
+}
+
+/// `-pix_fmt` explicite si demandé. En auto, les conteneurs mp4/mov passent
+/// en yuv420p : les sources 4:2:2 / 4:4:4 ne se lisent pas sur mobile/navigateur.
+fn pix_fmt_args(settings: &ExportSettings, output: &Path) -> Vec<String> {
+    let pix_fmt = settings.pix_fmt.clone().or_else(|| {
+        let ext = output.extension()?.to_string_lossy().to_lowercase();
+        matches!(ext.as_str(), "mp4" | "m4v" | "mov").then(|| "yuv420p".to_string())
+    });
+    match pix_fmt {
+        Some(fmt) => vec!["-pix_fmt".to_string(), fmt],
+        None => Vec::new(),
+    }
+}
+
+/// Arguments audio des modes ré-encodés (AAC, Opus en WebM) : débit
+/// configurable, `default_kbps` si non précisé, fréquence de la source sauf override.
+fn audio_encode_args(settings: &ExportSettings, default_kbps: u32, output: &Path, start: f64, end: f64) -> Vec<String> {
+    let kbps = settings.audio_bitrate_kbps.unwrap_or(default_kbps);
+    let webm = is_webm(output);
+    let mut args = vec![
+        "-c:a".to_string(),
+        if webm { "libopus" } else { "aac" }.to_string(),
+        "-b:a".to_string(),
+        format!("{}k", kbps),
+    ];
+    if webm {
+        // VBR + profil « audio » (musique/mix) plutôt que « voip »
+        args.extend([
+            "-vbr".to_string(),
+            "on".to_string(),
+            "-application".to_string(),
+            "audio".to_string(),
+        ]);
+    }
+    // loudnorm sort en 192 kHz : sans fréquence imposée, retour en 48 kHz
+    let rate = settings.audio_sample_rate.or(settings.normalize_loudness.then_some(48000));
+    // libopus refuse 44.1 kHz : on laisse FFmpeg rééchantillonner en 48 kHz
+    if let Some(rate) = rate.filter(|&r| !webm || r == 48000) {
+        args.push("-ar".to_string());
+        args.push(rate.to_string());
+    }
+    if let Some(filter) = audio_filter_chain(settings, start, end) {
+        args.push("-af".to_string());
+        args.push(filter);
+    }
+    args
+}
+
+/// Fichier de sous-titres à incruster, si le mode est « Burn in »
+fn burn_in_subtitles(settings: &ExportSettings) -> Option<&Path> {
+    settings.subtitles.as_ref()
+        .filter(|subs| subs.mode == SubtitleMode::BurnIn)
+        .map(|subs| subs.path.as_path())
+}
+
+/// Fichier de sous-titres à intégrer en piste, si le mode est « Embed »
+fn embedded_subtitles(settings: &ExportSettings) -> Option<&Path> {
+    settings.subtitles.as_ref()
+        .filter(|subs| subs.mode == SubtitleMode::Embed)
+        .map(|subs| subs.path.as_path())
+}
+
+/// Second `-i` pour les sous-titres intégrés (placé avant les options de sortie).
+/// Avec padding il n'y a pas de seek de sortie : le fichier est coupé comme
+/// la vidéo (`-ss`/`-t` d'entrée) puis décalé du noir ajouté avant le clip.
+fn subtitle_input_args(settings: &ExportSettings, start: f64, end: f64) -> Vec<String> {
+    let Some(subs) = embedded_subtitles(settings) else { return Vec::new() };
+    let mut args = Vec::new();
+    let filters = &settings.filters;
+    if filters.has_padding() {
+        args.extend([
+            "-ss".to_string(),
+            format!("{:.3}", start),
+            "-t".to_string(),
+            format!("{:.3}", end - start),
+        ]);
+        if filters.pad_before > 0.0 {
+            args.extend(["-itsoffset".to_string(), format!("{:.3}", filters.pad_before)]);
+        }
+    }
+    args.extend(["-i".to_string(), subs.to_string_lossy().to_string()]);
+    args
+}
+
+/// `-map` explicites (sinon FFmpeg ne garde qu'un flux par type) et codec
+/// de la piste de sous-titres. Rien si le conteneur est incompatible : la
+/// combinaison est refusée en amont par `subtitle_codec_for`.
+fn subtitle_embed_args(settings: &ExportSettings, output: &Path) -> Vec<String> {
+    let Some(subs) = embedded_subtitles(settings) else { return Vec::new() };
+    let ext = output.extension().unwrap_or_default().to_string_lossy();
+    let Ok(codec) = subtitle_codec_for(&ext, subs) else { return Vec::new() };
+    vec![
+        "-map".to_string(),
+        "0:v:0".to_string(),
+        "-map".to_string(),
+        "0:a?".to_string(),
+        "-map".to_string(),
+        "1:0".to_string(),
+        "-c:s".to_string(),
+        codec.to_string(),
+    ]
+}
+
+/// Codec `-c:s` pour intégrer le fichier de sous-titres `subtitle` dans un
+/// conteneur (par extension). mp4/mov n'acceptent que mov_text (conversion,
+/// le style ASS est perdu), mkv copie srt/ass/vtt tels quels, webm impose WebVTT.
+pub fn subtitle_codec_for(container_ext: &str, subtitle: &Path) -> Result<&'static str, String> {
+    let sub_ext = subtitle.extension().unwrap_or_default().to_string_lossy().to_lowercase();
+    if !matches!(sub_ext.as_str(), "srt" | "ass" | "ssa" | "vtt") {
+        return Err(format!(
+            "Unsupported subtitle file '.{}': use .srt, .ass/.ssa or .vtt",
+            sub_ext
+        ));
+    }
+    match container_ext.to_lowercase().as_str() {
+        "mp4" | "m4v" | "mov" => Ok("mov_text"),
+        "mkv" => Ok("copy"),
+        "webm" => Ok("webvtt"),
+        other => Err(format!(
+            "'.{}' cannot hold a subtitle track: burn the subtitles in or export to MKV/MP4",
+            other
+        )),
+    }
+}
+
+/// Check that a container (by extension) can hold the given codecs with
+/// `-c copy`. Only the common incompatibilities are covered; unknown
+/// containers are accepted and left to FFmpeg.
+pub fn check_copy_container(ext: &str, video_codec: Option<&str>, audio_codec: Option<&str>) -> Result<(), String> {
+    let ext = ext.to_lowercase();
+    type CodecCheck = fn(&str) -> bool;
+    let (video_ok, audio_ok): (CodecCheck, CodecCheck) = match ext.as_str() {
+        "webm" => (
+            |v| matches!(v, "vp8" | "vp9" | "av1"),
+            |a| matches!(a, "opus" | "vorbis"),
+        ),
+        "mp4" | "m4v" | "mov" => (
+            |v| !matches!(v, "vp8" | "theora" | "wmv1" | "wmv2" | "wmv3" | "flv1"),
+            |a| !matches!(a, "vorbis" | "wmav1" | "wmav2" | "pcm_s16le" | "pcm_s24le"),
+        ),
+        "ts" => (
+            |v| matches!(v, "h264" | "hevc" | "mpeg2video" | "mpeg1video"),
+            |a| matches!(a, "aac" | "mp3" | "mp2" | "ac3" | "eac3" | "opus"),
+        ),
+        _ => return Ok(()),
+    };
+
+    if let Some(v) = video_codec {
+        if !video_ok(v) {
+            return Err(format!("video codec {} not supported in .{}", v, ext));
+        }
+    }
+    if let Some(a) = audio_codec {
+        if !audio_ok(a) {
+            return Err(format!("audio codec {} not supported in .{}", a, ext));
+        }
+    }
+    Ok(())
+}
+
 /// Format a single entry for an FFmpeg concat-demuxer list file.
 ///
-/// The concat demuxer treats `\` as an escape character, so a raw Windows path
-/// like `C:\tmp\frag.ts` would be misread (`\t`, `\f`, ...). We convert the
-/// separators to `/` (which FFmpeg accepts on Windows) and escape single quotes.
-/// Returns a ready-to-write line, e.g. `file 'C:/tmp/frag.ts'`.
+/// The path is single-quoted; quotes inside it are closed, escaped and
+/// reopened (`'\''`). Windows paths get `/` separators (which FFmpeg accepts
+/// on Windows) and lose their `\\?\` verbatim prefix. Returns a ready-to-write
+/// line, e.g. `file 'C:/tmp/frag.ts'`.
+///
+/// Lossy for display and scripts; list files FFmpeg reads are written with
+/// `concat_list_contents`, which keeps the exact bytes and rejects what a list
+/// cannot hold.
 pub fn concat_demuxer_line(path: &Path) -> String {
-    let normalized = path
-        .to_string_lossy()
-        .replace('\\', "/")
-        .replace('\'', "'\\''");
-    format!("file '{}'", normalized)
+    String::from_utf8_lossy(&quote_concat_path(&concat_path_bytes(path))).into_owned()
+}
+
+/// Contents of a concat-demuxer list file for `paths`, one `file` line each.
+///
+/// Fails on paths a list cannot represent: a line break ends the entry, and on
+/// Windows FFmpeg takes UTF-8 paths, so non-Unicode names would be altered.
+pub fn concat_list_contents(paths: &[PathBuf]) -> Result<Vec<u8>, String> {
+    let mut out = Vec::new();
+    for path in paths {
+        check_concat_path(path)?;
+        out.extend(quote_concat_path(&concat_path_bytes(path)));
+        out.push(b'\n');
+    }
+    Ok(out)
+}
+
+fn check_concat_path(path: &Path) -> Result<(), String> {
+    if concat_path_bytes(path).iter().any(|b| matches!(b, b'\n' | b'\r')) {
+        return Err(format!(
+            "{}: file name contains a line break, rename it before merging",
+            path.display()
+        ));
+    }
+    #[cfg(not(unix))]
+    if path.to_str().is_none() {
+        return Err(format!(
+            "{}: file name is not valid Unicode, rename it before merging",
+            path.display()
+        ));
+    }
+    Ok(())
+}
+
+/// Path as written in a list: raw bytes on Unix (non-UTF-8 names kept intact),
+/// `/` separators for Windows paths. On Unix a `\` in a name is an ordinary
+/// character and is left as-is inside the quotes.
+fn concat_path_bytes(path: &Path) -> Vec<u8> {
+    #[cfg(unix)]
+    let raw = {
+        use std::os::unix::ffi::OsStrExt;
+        path.as_os_str().as_bytes().to_vec()
+    };
+    #[cfg(not(unix))]
+    let raw = path.to_string_lossy().into_owned().into_bytes();
+
+    let raw = if let Some(rest) = raw.strip_prefix(br"\\?\UNC\") {
+        [br"\\".as_slice(), rest].concat()
+    } else if let Some(rest) = raw.strip_prefix(br"\\?\") {
+        rest.to_vec()
+    } else {
+        raw
+    };
+    let drive = raw.len() >= 2 && raw[0].is_ascii_alphabetic() && raw[1] == b':';
+    let windows_path = cfg!(windows) || drive || raw.starts_with(br"\\");
+    if windows_path {
+        raw.into_iter().map(|b| if b == b'\\' { b'/' } else { b }).collect()
+    } else {
+        raw
+    }
+}
+
+fn quote_concat_path(path: &[u8]) -> Vec<u8> {
+    let mut out = b"file '".to_vec();
+    for &b in path {
+        if b == b'\'' {
+            out.extend_from_slice(br"'\''");
+        } else {
+            out.push(b);
+        }
+    }
+    out.push(b'\'');
+    out
 }
 
 /// Build FFmpeg arguments for lossless concatenation.
@@ -133,10 +782,162 @@ pub fn build_concat_args(concat_list_path: &Path, output: &Path) -> Vec<String>
     ]
 }
 
+/// Fusion : copie des flux (demuxer concat) ou ré-encodage (filtre concat)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ConcatMethod {
+    #[default]
+    Copy,
+    /// `audio` / `video` : format commun imposé quand les entrées diffèrent
+    Reencode {
+        audio: Option<ConcatAudioFormat>,
+        video: Option<ConcatVideoFormat>,
+    },
+}
+
+/// Format audio commun des entrées d'une fusion ré-encodée
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ConcatAudioFormat {
+    pub sample_rate: u32,
+    pub channels: u32,
+}
+
+impl ConcatAudioFormat {
+    /// Fréquence imposée en cas de mélange (44.1k + 48k → 48k)
+    pub const COMMON_RATE: u32 = 48000;
+
+    fn channel_layout(&self) -> &'static str {
+        match self.channels {
+            1 => "mono",
+            6 => "5.1",
+            8 => "7.1",
+            _ => "stereo",
+        }
+    }
+}
+
+/// Taille d'image commune des entrées d'une fusion ré-encodée
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ConcatVideoFormat {
+    pub width: u32,
+    pub height: u32,
+}
+
+impl ConcatVideoFormat {
+    /// Hauteurs proposées dans l'UI quand la taille est choisie (16:9)
+    pub const HEIGHTS: &'static [u32] = &[2160, 1440, 1080, 720, 480];
+
+    /// Taille 16:9 de hauteur `height`, dimensions paires (requis par yuv420p)
+    pub fn widescreen(height: u32) -> Self {
+        Self { width: (height * 16 / 9 + 1) & !1, height: height & !1 }
+    }
+}
+
+/// Taille commune à imposer aux vidéos `(width, height)` des entrées, ou
+/// `None` si elles ont toutes la même (aucune mise à l'échelle). La cible
+/// est la plus grande entrée en pixels : les autres y sont agrandies puis
+/// complétées de bandes noires, sans déformation.
+pub fn concat_video_format(sizes: &[(u32, u32)]) -> Option<ConcatVideoFormat> {
+    let first = *sizes.first()?;
+    if sizes.iter().all(|&s| s == first) {
+        return None;
+    }
+    let &(width, height) = sizes.iter().max_by_key(|(w, h)| u64::from(*w) * u64::from(*h))?;
+    Some(ConcatVideoFormat { width: width & !1, height: height & !1 })
+}
+
+/// Format commun à imposer aux pistes audio `(sample_rate, channels)` des
+/// entrées, ou `None` si elles concordent déjà (aucun rééchantillonnage).
+/// Mélanger 44.1 et 48 kHz dans le filtre concat fait dériver l'audio.
+pub fn concat_audio_format(streams: &[(Option<u32>, Option<u32>)]) -> Option<ConcatAudioFormat> {
+    let first = *streams.first()?;
+    if streams.iter().all(|&s| s == first) {
+        return None;
+    }
+    let channels = streams.iter().filter_map(|s| s.1).max().unwrap_or(2);
+    Some(ConcatAudioFormat { sample_rate: ConcatAudioFormat::COMMON_RATE, channels })
+}
+
+/// Build FFmpeg arguments for a re-encoding merge with the `concat` filter.
+/// Works with inputs of different codecs; each audio stream is resampled
+/// and given the same channel layout first when `audio` is set, and each
+/// picture is scaled and padded to the same size when `video` is set.
+pub fn build_concat_reencode_args(
+    inputs: &[impl AsRef<Path>],
+    output: &Path,
+    audio: Option<ConcatAudioFormat>,
+    video: Option<ConcatVideoFormat>,
+) -> Vec<String> {
+    let mut args = vec!["-y".to_string(), "-threads".to_string(), "0".to_string()];
+    for input in inputs {
+        args.push("-i".to_string());
+        args.push(input.as_ref().to_string_lossy().to_string());
+    }
+
+    let mut graph = String::new();
+    let mut streams = String::new();
+    for i in 0..inputs.len() {
+        // Le filtre concat exige la même taille et le même SAR partout
+        match video {
+            Some(fmt) => {
+                graph.push_str(&format!(
+                    "[{i}:v:0]scale={w}:{h}:force_original_aspect_ratio=decrease,\
+                     pad={w}:{h}:(ow-iw)/2:(oh-ih)/2:color=black,setsar=1[v{i}];",
+                    w = fmt.width,
+                    h = fmt.height,
+                ));
+                streams.push_str(&format!("[v{i}]"));
+            }
+            None => streams.push_str(&format!("[{i}:v:0]")),
+        }
+        match audio {
+            Some(fmt) => {
+                graph.push_str(&format!(
+                    "[{i}:a:0]aresample={rate},aformat=sample_rates={rate}:channel_layouts={layout}[a{i}];",
+                    rate = fmt.sample_rate,
+                    layout = fmt.channel_layout(),
+                ));
+                streams.push_str(&format!("[a{i}]"));
+            }
+            None => streams.push_str(&format!("[{i}:a:0]")),
+        }
+    }
+    graph.push_str(&format!("{}concat=n={}:v=1:a=1[v][a]", streams, inputs.len()));
+
+    args.extend([
+        "-filter_complex".to_string(),
+        graph,
+        "-map".to_string(),
+        "[v]".to_string(),
+        "-map".to_string(),
+        "[a]".to_string(),
+    ]);
+    args.extend(video_encode_args(TrimMode::HighQuality, SourceVideoCodec::Other, output));
+    let settings = ExportSettings::default();
+    args.extend(pix_fmt_args(&settings, output));
+    let settings = ExportSettings { audio_sample_rate: audio.map(|fmt| fmt.sample_rate), ..settings };
+    args.extend(audio_encode_args(&settings, 192, output, 0.0, 0.0));
+    args.push(output.to_string_lossy().to_string());
+    args
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn remux_copies_streams_into_the_new_container() {
+        assert_eq!(
+            build_remux_args(Path::new("in.mkv"), Path::new("out.mp4")),
+            ["-y", "-i", "in.mkv", "-map", "0:v?", "-map", "0:a?", "-c", "copy", "-movflags", "+faststart", "out.mp4"]
+        );
+        assert_eq!(
+            build_remux_args(Path::new("in.mp4"), Path::new("out.mkv")),
+            ["-y", "-i", "in.mp4", "-map", "0", "-c", "copy", "out.mkv"]
+        );
+        // VP9 has no lossless way into a TS
+        assert!(check_copy_container("ts", Some("vp9"), Some("opus")).is_err());
+    }
+
     #[test]
     fn concat_line_converts_windows_separators() {
         let line = concat_demuxer_line(Path::new(r"C:\Users\me\temp\frag_000.ts"));
@@ -148,4 +949,532 @@ mod tests {
         let line = concat_demuxer_line(Path::new("/tmp/it's a clip.ts"));
         assert_eq!(line, "file '/tmp/it'\\''s a clip.ts'");
     }
+
+    #[test]
+    fn concat_list_keeps_spaces_quotes_and_unicode() {
+        let paths = [
+            PathBuf::from("/home/me/My Clips/l'été — 東京.mp4"),
+            PathBuf::from(r"\\nas\share\it's.mkv"),
+            PathBuf::from(r"\\?\D:\long\path.mp4"),
+            PathBuf::from(r"\\?\UNC\nas\share\b.mp4"),
+            PathBuf::from(r"/tmp/back\slash.mp4"),
+        ];
+        let list = String::from_utf8(concat_list_contents(&paths).unwrap()).unwrap();
+        assert_eq!(
+            list,
+            "file '/home/me/My Clips/l'\\''été — 東京.mp4'\n\
+             file '//nas/share/it'\\''s.mkv'\n\
+             file 'D:/long/path.mp4'\n\
+             file '//nas/share/b.mp4'\n\
+             file '/tmp/back\\slash.mp4'\n"
+        );
+
+        let broken = [PathBuf::from("/tmp/two\nlines.mp4")];
+        assert!(concat_list_contents(&broken).is_err());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn concat_list_keeps_non_utf8_bytes() {
+        use std::os::unix::ffi::OsStrExt;
+        let path = PathBuf::from(std::ffi::OsStr::from_bytes(b"/tmp/caf\xe9.mp4"));
+        assert_eq!(concat_list_contents(&[path]).unwrap(), b"file '/tmp/caf\xe9.mp4'\n");
+    }
+
+    #[test]
+    fn reencode_uses_mode_default_audio_bitrate() {
+        let args = build_trim_args(
+            Path::new("in.mp4"), Path::new("out.mp4"), 0.0, 5.0,
+            TrimMode::HighQuality, &ExportSettings::default(), SourceVideoCodec::H264,
+        );
+        let pos = args.iter().position(|a| a == "-b:a").unwrap();
+        assert_eq!(args[pos + 1], "256k");
+        assert!(!args.contains(&"-ar".to_string()));
+        assert_eq!(args.last().unwrap(), "out.mp4");
+    }
+
+    #[test]
+    fn reencode_applies_audio_overrides() {
+        let settings = ExportSettings {
+            audio_bitrate_kbps: Some(320),
+            audio_sample_rate: Some(48000),
+            match_source_codec: true,
+            pix_fmt: Some("yuv444p".to_string()),
+            audio_offset_ms: None,
+            filters: Default::default(),
+            subtitles: None,
+            video_codec: None,
+            crf: None,
+            preset: None,
+            extra_args: Vec::new(),
+            container: None,
+            absolute_end: false,
+            threads: None,
+            output_fps: None,
+            copy_metadata: false,
+            keep_timestamps: false,
+            keep_chapters: false,
+            normalize_loudness: false,
+            closed_gop: false,
+        };
+        let args = build_trim_args(
+            Path::new("in.mp4"), Path::new("out.mp4"), 0.0, 5.0,
+            TrimMode::Precise, &settings, SourceVideoCodec::H264,
+        );
+        let pos = args.iter().position(|a| a == "-b:a").unwrap();
+        assert_eq!(args[pos + 1], "320k");
+        let pos = args.iter().position(|a| a == "-ar").unwrap();
+        assert_eq!(args[pos + 1], "48000");
+        let pos = args.iter().position(|a| a == "-pix_fmt").unwrap();
+        assert_eq!(args[pos + 1], "yuv444p");
+        assert_eq!(args.last().unwrap(), "out.mp4");
+    }
+
+    #[test]
+    fn reencode_keeps_hevc_sources_in_hevc() {
+        let args = build_trim_args(
+            Path::new("in.mkv"), Path::new("out.mkv"), 0.0, 5.0,
+            TrimMode::Precise, &ExportSettings::default(), SourceVideoCodec::H265,
+        );
+        let pos = args.iter().position(|a| a == "-c:v").unwrap();
+        assert_eq!(args[pos + 1], "libx265");
+        assert!(!args.contains(&"-x264-params".to_string()));
+
+        let args = build_trim_args(
+            Path::new("in.mkv"), Path::new("out.mkv"), 0.0, 5.0,
+            TrimMode::Precise, &ExportSettings::default(), SourceVideoCodec::Other,
+        );
+        let pos = args.iter().position(|a| a == "-c:v").unwrap();
+        assert_eq!(args[pos + 1], "libx264");
+    }
+
+    #[test]
+    fn auto_pix_fmt_depends_on_container() {
+        let settings = ExportSettings::default();
+        let mp4 = build_trim_args(
+            Path::new("in.mkv"), Path::new("out.mp4"), 0.0, 5.0,
+            TrimMode::Precise, &settings, SourceVideoCodec::H264,
+        );
+        let pos = mp4.iter().position(|a| a == "-pix_fmt").unwrap();
+        assert_eq!(mp4[pos + 1], "yuv420p");
+
+        let mkv = build_trim_args(
+            Path::new("in.mkv"), Path::new("out.mkv"), 0.0, 5.0,
+            TrimMode::Precise, &settings, SourceVideoCodec::H264,
+        );
+        assert!(!mkv.contains(&"-pix_fmt".to_string()));
+    }
+
+    #[test]
+    fn copy_container_compatibility() {
+        assert!(check_copy_container("mp4", Some("h264"), Some("aac")).is_ok());
+        assert!(check_copy_container("MKV", Some("vp9"), Some("pcm_s16le")).is_ok());
+        assert!(check_copy_container("webm", Some("h264"), Some("aac")).is_err());
+        assert!(check_copy_container("webm", Some("vp9"), Some("opus")).is_ok());
+        assert!(check_copy_container("mp4", Some("h264"), Some("vorbis")).is_err());
+        assert!(check_copy_container("mp4", None, Some("aac")).is_ok());
+    }
+    #[test]
+    fn reencode_passes_filter_chains() {
+        let settings = ExportSettings {
+            audio_offset_ms: Some(250),
+            filters: crate::ui::FilterSettings {
+                scale_height: Some(720),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        let args = build_trim_args(
+            Path::new("in.mp4"), Path::new("out.mp4"), 0.0, 5.0,
+            TrimMode::Precise, &settings, SourceVideoCodec::H264,
+        );
+        let vf = args.iter().position(|a| a == "-vf").unwrap();
+        assert_eq!(args[vf + 1], "scale=-2:720");
+        assert!(vf < args.iter().position(|a| a == "-c:v").unwrap());
+        assert!(args.contains(&"-af".to_string()));
+
+        let copy = build_trim_args(
+            Path::new("in.mp4"), Path::new("out.mp4"), 0.0, 5.0,
+            TrimMode::Lossless, &settings, SourceVideoCodec::H264,
+        );
+        assert!(!copy.contains(&"-vf".to_string()));
+    }
+
+    #[test]
+    fn lossless_can_keep_timestamps_and_metadata() {
+        let input = Path::new("in.mp4");
+        let output = Path::new("out.mp4");
+        let args = build_trim_args(input, output, 10.0, 20.0, TrimMode::Lossless, &ExportSettings::default(), SourceVideoCodec::H264);
+        assert!(args.contains(&"make_zero".to_string()));
+        assert!(!args.contains(&"-copyts".to_string()));
+        assert!(!args.contains(&"-map_metadata".to_string()));
+
+        let settings = ExportSettings { keep_timestamps: true, copy_metadata: true, ..Default::default() };
+        let args = build_trim_args(input, output, 10.0, 20.0, TrimMode::Lossless, &settings, SourceVideoCodec::H264);
+        assert!(args.contains(&"-copyts".to_string()));
+        assert!(!args.contains(&"make_zero".to_string()));
+        let pos = args.iter().position(|a| a == "-map_metadata").unwrap();
+        assert_eq!(args[pos + 1], "0");
+        assert_eq!(args.last().unwrap(), "out.mp4");
+    }
+
+    #[test]
+    fn output_fps_converts_after_the_filters() {
+        let settings = ExportSettings {
+            output_fps: Some(30.0),
+            filters: crate::ui::FilterSettings { scale_height: Some(720), ..Default::default() },
+            ..Default::default()
+        };
+        let args = build_trim_args(
+            Path::new("in.mp4"), Path::new("out.mp4"), 0.0, 5.0,
+            TrimMode::HighQuality, &settings, SourceVideoCodec::H264,
+        );
+        let vf = args.iter().position(|a| a == "-vf").unwrap();
+        assert_eq!(args[vf + 1], "scale=-2:720,fps=30");
+        let r = args.iter().position(|a| a == "-r").unwrap();
+        assert_eq!(args[r + 1], "30");
+
+        let settings = ExportSettings { output_fps: Some(23.976), ..Default::default() };
+        let args = build_trim_args(
+            Path::new("in.mp4"), Path::new("out.mp4"), 0.0, 5.0,
+            TrimMode::Precise, &settings, SourceVideoCodec::H264,
+        );
+        let vf = args.iter().position(|a| a == "-vf").unwrap();
+        assert_eq!(args[vf + 1], "fps=23.976");
+
+        // Stream copy : cadence inchangeable
+        let copy = build_trim_args(
+            Path::new("in.mp4"), Path::new("out.mp4"), 0.0, 5.0,
+            TrimMode::Lossless, &settings, SourceVideoCodec::H264,
+        );
+        assert!(!copy.contains(&"-r".to_string()));
+    }
+
+    #[test]
+    fn thread_limit_applies_to_decoder_encoder_and_filters() {
+        let settings = ExportSettings { threads: Some(4), ..Default::default() };
+        let args = build_trim_args(
+            Path::new("in.mp4"), Path::new("out.mp4"), 0.0, 5.0,
+            TrimMode::HighQuality, &settings, SourceVideoCodec::H264,
+        );
+        let limits: Vec<&String> = args.windows(2).filter(|w| w[0] == "-threads").map(|w| &w[1]).collect();
+        assert_eq!(limits, ["4", "4"]);
+        let x264 = args.iter().position(|a| a == "-x264-params").unwrap();
+        assert!(args[x264 + 1].starts_with("threads=4:"));
+        let filter = args.iter().position(|a| a == "-filter_threads").unwrap();
+        assert_eq!(args[filter + 1], "4");
+
+        let auto = build_trim_args(
+            Path::new("in.mp4"), Path::new("out.mp4"), 0.0, 5.0,
+            TrimMode::HighQuality, &ExportSettings::default(), SourceVideoCodec::H264,
+        );
+        assert!(!auto.contains(&"-filter_threads".to_string()));
+    }
+
+    #[test]
+    fn padding_replaces_output_seek() {
+        let mut settings = ExportSettings {
+            filters: crate::ui::FilterSettings { pad_before: 2.0, ..Default::default() },
+            ..Default::default()
+        };
+        let args = build_trim_args(
+            Path::new("in.mp4"), Path::new("out.mp4"), 10.0, 20.0,
+            TrimMode::HighQuality, &settings, SourceVideoCodec::H264,
+        );
+        // Seek d'entrée seulement : pas de décodage depuis zéro, pas de `-t`
+        assert_eq!(args.iter().filter(|a| *a == "-ss").count(), 1);
+        let ss = args.iter().position(|a| a == "-ss").unwrap();
+        assert_eq!(args[ss + 1], "10.000");
+        assert!(ss < args.iter().position(|a| a == "-i").unwrap());
+        assert!(!args.contains(&"-t".to_string()));
+        let af = args.iter().position(|a| a == "-af").unwrap();
+        assert!(args[af + 1].starts_with("asetpts=PTS+10.000/TB,atrim=start=10.000:end=20.000"));
+
+        // Sous-titres intégrés coupés comme la vidéo et décalés du noir ajouté
+        settings.subtitles = Some(crate::ui::SubtitleSettings { path: "subs.srt".into(), mode: SubtitleMode::Embed });
+        let args = build_trim_args(
+            Path::new("in.mp4"), Path::new("out.mkv"), 10.0, 20.0,
+            TrimMode::HighQuality, &settings, SourceVideoCodec::H264,
+        );
+        let subs = args.iter().position(|a| a == "subs.srt").unwrap();
+        assert_eq!(
+            args[subs - 7..subs],
+            ["-ss", "10.000", "-t", "10.000", "-itsoffset", "2.000", "-i"]
+        );
+    }
+
+    #[test]
+    fn webm_reencode_uses_vp9_crf_and_opus() {
+        let settings = ExportSettings {
+            audio_sample_rate: Some(44100),
+            ..Default::default()
+        };
+        let args = build_trim_args(
+            Path::new("in.mkv"), Path::new("out.WebM"), 0.0, 5.0,
+            TrimMode::HighQuality, &settings, SourceVideoCodec::H265,
+        );
+        let cv = args.iter().position(|a| a == "-c:v").unwrap();
+        assert_eq!(args[cv + 1], "libvpx-vp9");
+        let bv = args.iter().position(|a| a == "-b:v").unwrap();
+        assert_eq!(args[bv + 1], "0");
+        assert!(args.iter().any(|a| a == "-crf"));
+
+        let ca = args.iter().position(|a| a == "-c:a").unwrap();
+        assert_eq!(args[ca + 1], "libopus");
+        assert!(args.windows(2).any(|w| w[0] == "-vbr" && w[1] == "on"));
+        assert!(!args.contains(&"-ar".to_string()));
+        assert!(!args.contains(&"-x264-params".to_string()));
+    }
+
+    #[test]
+    fn subtitle_container_support() {
+        let srt = Path::new("film.srt");
+        assert_eq!(subtitle_codec_for("MP4", srt).unwrap(), "mov_text");
+        assert_eq!(subtitle_codec_for("mkv", Path::new("film.ass")).unwrap(), "copy");
+        assert_eq!(subtitle_codec_for("webm", srt).unwrap(), "webvtt");
+        assert!(subtitle_codec_for("avi", srt).is_err());
+        assert!(subtitle_codec_for("mkv", Path::new("film.idx")).is_err());
+    }
+
+    #[test]
+    fn subtitles_burn_in_or_embed() {
+        let mut settings = ExportSettings {
+            subtitles: Some(crate::ui::SubtitleSettings {
+                path: "subs.srt".into(),
+                mode: SubtitleMode::Embed,
+            }),
+            ..Default::default()
+        };
+        let args = build_trim_args(
+            Path::new("in.mp4"), Path::new("out.mp4"), 2.0, 5.0,
+            TrimMode::Precise, &settings, SourceVideoCodec::H264,
+        );
+        // Deux entrées avant les options de sortie, piste convertie en mov_text
+        let inputs: Vec<_> = args.windows(2).filter(|w| w[0] == "-i").map(|w| w[1].as_str()).collect();
+        assert_eq!(inputs, ["in.mp4", "subs.srt"]);
+        assert!(args.iter().position(|a| a == "subs.srt").unwrap() < args.iter().position(|a| a == "-ss").unwrap());
+        assert!(args.windows(2).any(|w| w[0] == "-map" && w[1] == "1:0"));
+        assert!(args.windows(2).any(|w| w[0] == "-c:s" && w[1] == "mov_text"));
+        assert!(!args.contains(&"-vf".to_string()));
+
+        settings.subtitles.as_mut().unwrap().mode = SubtitleMode::BurnIn;
+        let args = build_trim_args(
+            Path::new("in.mp4"), Path::new("out.mp4"), 2.0, 5.0,
+            TrimMode::Precise, &settings, SourceVideoCodec::H264,
+        );
+        assert_eq!(args.iter().filter(|a| *a == "-i").count(), 1);
+        let vf = args.iter().position(|a| a == "-vf").unwrap();
+        assert_eq!(args[vf + 1], "subtitles='subs.srt'");
+        assert!(!args.contains(&"-c:s".to_string()));
+    }
+
+    #[test]
+    fn lossless_rotation_flag_is_an_input_option() {
+        let mut settings = ExportSettings::default();
+        let plain = build_trim_args(
+            Path::new("in.mp4"), Path::new("out.mp4"), 1.0, 5.0,
+            TrimMode::Lossless, &settings, SourceVideoCodec::H264,
+        );
+        assert!(!plain.iter().any(|a| a.starts_with("-display_rotation")));
+
+        settings.filters.rotation = crate::ui::Rotation::Cw90;
+        settings.filters.rotation_method = RotationMethod::Metadata;
+        let args = build_trim_args(
+            Path::new("in.mp4"), Path::new("out.mp4"), 1.0, 5.0,
+            TrimMode::Lossless, &settings, SourceVideoCodec::H264,
+        );
+        let pos = args.iter().position(|a| a == "-display_rotation:v:0").unwrap();
+        assert_eq!(args[pos + 1], "-90");
+        assert!(pos < args.iter().position(|a| a == "-i").unwrap());
+        assert!(args.windows(2).any(|w| w[0] == "-c" && w[1] == "copy"));
+    }
+
+    #[test]
+    fn concat_audio_format_only_on_mismatch() {
+        assert_eq!(concat_audio_format(&[(Some(48000), Some(2)), (Some(48000), Some(2))]), None);
+        assert_eq!(
+            concat_audio_format(&[(Some(44100), Some(2)), (Some(48000), Some(1))]),
+            Some(ConcatAudioFormat { sample_rate: 48000, channels: 2 })
+        );
+    }
+
+    #[test]
+    fn concat_reencode_resamples_before_concat_filter() {
+        let inputs = [Path::new("/v/a.mp4"), Path::new("/v/b.mp4")];
+        let fmt = ConcatAudioFormat { sample_rate: 48000, channels: 2 };
+        let args = build_concat_reencode_args(&inputs, Path::new("/v/m.mp4"), Some(fmt), None);
+        let graph = &args[args.iter().position(|a| a == "-filter_complex").unwrap() + 1];
+        assert_eq!(
+            graph,
+            "[0:a:0]aresample=48000,aformat=sample_rates=48000:channel_layouts=stereo[a0];\
+             [1:a:0]aresample=48000,aformat=sample_rates=48000:channel_layouts=stereo[a1];\
+             [0:v:0][a0][1:v:0][a1]concat=n=2:v=1:a=1[v][a]"
+        );
+        assert!(args.windows(2).any(|w| w == ["-ar", "48000"]));
+
+        // Formats identiques : pas de rééchantillonnage
+        let args = build_concat_reencode_args(&inputs, Path::new("/v/m.mp4"), None, None);
+        assert!(args.contains(&"[0:v:0][0:a:0][1:v:0][1:a:0]concat=n=2:v=1:a=1[v][a]".to_string()));
+        assert!(!args.contains(&"-ar".to_string()));
+    }
+
+    #[test]
+    fn concat_reencode_scales_mixed_resolutions() {
+        assert_eq!(concat_video_format(&[(1920, 1080), (1920, 1080)]), None);
+        let fmt = concat_video_format(&[(1280, 720), (1080, 1920), (1921, 1081)]).unwrap();
+        assert_eq!(fmt, ConcatVideoFormat { width: 1920, height: 1080 });
+        assert_eq!(ConcatVideoFormat::widescreen(480), ConcatVideoFormat { width: 854, height: 480 });
+
+        let inputs = [Path::new("/v/a.mp4"), Path::new("/v/b.mp4")];
+        let args = build_concat_reencode_args(&inputs, Path::new("/v/m.mp4"), None, Some(fmt));
+        let graph = &args[args.iter().position(|a| a == "-filter_complex").unwrap() + 1];
+        assert!(graph.starts_with(
+            "[0:v:0]scale=1920:1080:force_original_aspect_ratio=decrease,\
+             pad=1920:1080:(ow-iw)/2:(oh-ih)/2:color=black,setsar=1[v0];"
+        ));
+        assert!(graph.ends_with("[v0][0:a:0][v1][1:a:0]concat=n=2:v=1:a=1[v][a]"));
+    }
+
+    #[test]
+    fn profile_overrides_encoder_and_appends_extra_args() {
+        let settings = ExportSettings {
+            crf: Some(23),
+            preset: Some("slow".to_string()),
+            ..Default::default()
+        };
+        let args = build_trim_args(
+            Path::new("in.mp4"), Path::new("out.mp4"), 0.0, 5.0,
+            TrimMode::HighQuality, &settings, SourceVideoCodec::H264,
+        );
+        assert!(args.windows(2).any(|w| w == ["-c:v", "libx264"]));
+        assert!(args.windows(2).any(|w| w == ["-crf", "23"]));
+        assert!(args.windows(2).any(|w| w == ["-preset", "slow"]));
+        assert_eq!(args.iter().filter(|a| *a == "-crf").count(), 1);
+
+        let settings = ExportSettings {
+            video_codec: Some("libx265".to_string()),
+            crf: Some(20),
+            extra_args: vec!["-tag:v".to_string(), "hvc1".to_string()],
+            ..Default::default()
+        };
+        let args = build_trim_args(
+            Path::new("in.mp4"), Path::new("out.mp4"), 0.0, 5.0,
+            TrimMode::Precise, &settings, SourceVideoCodec::H264,
+        );
+        assert!(args.windows(2).any(|w| w == ["-c:v", "libx265"]));
+        assert!(!args.contains(&"-x264-params".to_string()));
+        assert_eq!(&args[args.len() - 3..], ["-tag:v", "hvc1", "out.mp4"]);
+    }
+
+    #[test]
+    fn absolute_end_uses_to_instead_of_duration() {
+        let settings = ExportSettings { absolute_end: true, ..Default::default() };
+        let input = Path::new("in.mp4");
+        let output = Path::new("out.mp4");
+
+        let args = build_trim_args(input, output, 10.0, 25.5, TrimMode::Precise, &settings, SourceVideoCodec::H264);
+        let to = args.iter().position(|a| a == "-to").unwrap();
+        assert_eq!(args[to + 1], "25.500");
+        assert!(to > args.iter().position(|a| a == "-i").unwrap());
+        assert!(!args.contains(&"-t".to_string()));
+
+        // Copie : `-to` avant `-i`, avec `-ss`, pour que les deux bornes soient absolues
+        let args = build_trim_args(input, output, 10.0, 25.5, TrimMode::Lossless, &settings, SourceVideoCodec::H264);
+        let to = args.iter().position(|a| a == "-to").unwrap();
+        assert!(to < args.iter().position(|a| a == "-i").unwrap());
+
+        let args = build_trim_args(input, output, 10.0, 25.5, TrimMode::Lossless, &ExportSettings::default(), SourceVideoCodec::H264);
+        let t = args.iter().position(|a| a == "-t").unwrap();
+        assert_eq!(args[t + 1], "15.500");
+        assert!(t > args.iter().position(|a| a == "-i").unwrap());
+    }
+
+    #[test]
+    fn display_aspect_is_validated_and_tagged() {
+        assert_eq!(parse_aspect_ratio("16:9"), Ok((16, 9)));
+        assert_eq!(parse_aspect_ratio(" 32/18 "), Ok((16, 9)));
+        assert_eq!(parse_aspect_ratio("2.35"), Ok((47, 20)));
+        assert_eq!(parse_aspect_ratio("2.35:1"), Ok((47, 20)));
+        for bad in ["", "16:", "abc", "16:0", "-4:3", "1.23456", "100:1"] {
+            assert!(parse_aspect_ratio(bad).is_err(), "{bad}");
+        }
+
+        let settings = ExportSettings {
+            filters: crate::ui::FilterSettings { display_aspect: Some("4:3".to_string()), ..Default::default() },
+            ..Default::default()
+        };
+        let input = Path::new("in.mp4");
+        for mode in [TrimMode::Lossless, TrimMode::Precise] {
+            let args = build_trim_args(input, Path::new("out.mp4"), 0.0, 5.0, mode, &settings, SourceVideoCodec::H264);
+            assert!(args.windows(2).any(|w| w == ["-aspect", "4:3"]));
+        }
+
+        // DVD 720x480 anamorphique affiché en 16:9 → SAR 32:27
+        assert_eq!(aspect_bsf_args(Some("h264"), 720, 480, (16, 9)), ["-bsf:v", "h264_metadata=sample_aspect_ratio=32/27"]);
+        assert_eq!(aspect_bsf_args(Some("mpeg2video"), 720, 480, (16, 9)), ["-bsf:v", "mpeg2_metadata=display_aspect_ratio=16/9"]);
+        assert!(aspect_bsf_args(Some("vp9"), 720, 480, (16, 9)).is_empty());
+    }
+
+    #[test]
+    fn closed_gop_forces_regular_idr_frames() {
+        let settings = ExportSettings { closed_gop: true, ..Default::default() };
+        let args = build_trim_args(
+            Path::new("in.mp4"), Path::new("out.mp4"), 0.0, 5.0,
+            TrimMode::HighQuality, &settings, SourceVideoCodec::H264,
+        );
+        assert!(args.windows(2).any(|w| w == ["-force_key_frames", "expr:gte(t,n_forced*2)"]));
+        assert!(args.windows(2).any(|w| w == ["-forced-idr", "1"]));
+        let params = args.iter().position(|a| a == "-x264-params").unwrap();
+        assert_eq!(args[params + 1], "threads=auto:lookahead_threads=auto:sliced-threads=1:scenecut=0:open-gop=0");
+
+        let args = build_trim_args(
+            Path::new("in.mkv"), Path::new("out.mkv"), 0.0, 5.0,
+            TrimMode::Precise, &settings, SourceVideoCodec::H265,
+        );
+        assert!(args.windows(2).any(|w| w == ["-x265-params", "scenecut=0:open-gop=0"]));
+
+        // WebM : images clés forcées, pas de paramètres x264
+        let args = build_trim_args(
+            Path::new("in.mp4"), Path::new("out.webm"), 0.0, 5.0,
+            TrimMode::Precise, &settings, SourceVideoCodec::H264,
+        );
+        assert!(args.contains(&"-force_key_frames".to_string()));
+        assert!(!args.contains(&"-forced-idr".to_string()));
+    }
+
+    #[test]
+    fn extract_audio_keeps_only_the_first_audio_track() {
+        let args = build_extract_audio_args(Path::new("talk.mkv"), Path::new("talk.mp3"), AudioExtractFormat::Mp3);
+        assert!(args.windows(2).any(|w| w == ["-map", "0:a:0"]));
+        assert!(args.contains(&"-vn".to_string()));
+        assert!(args.windows(2).any(|w| w == ["-c:a", "libmp3lame"]));
+        assert_eq!(args.last().unwrap(), "talk.mp3");
+
+        let args = build_extract_audio_args(Path::new("talk.mkv"), Path::new("talk.wav"), AudioExtractFormat::Wav);
+        assert!(args.windows(2).any(|w| w == ["-c:a", "pcm_s16le"]));
+        assert!(!args.contains(&"-b:a".to_string()));
+    }
+
+    #[test]
+    fn animation_args_per_format() {
+        let input = Path::new("in.mp4");
+        let webp = AnimationSettings::default();
+        let args = build_animation_args(input, Path::new("loop.webp"), 2.0, 5.0, &webp);
+        let vf = args.iter().position(|a| a == "-vf").unwrap();
+        assert_eq!(args[vf + 1], "fps=15,scale=480:-2:flags=lanczos");
+        assert!(args.windows(2).any(|w| w[0] == "-c:v" && w[1] == "libwebp"));
+        assert!(args.windows(2).any(|w| w[0] == "-loop" && w[1] == "0"));
+        assert!(args.contains(&"-an".to_string()));
+
+        let gif = AnimationSettings { format: AnimationFormat::Gif, width: 0, ..webp };
+        let args = build_animation_args(input, Path::new("loop.gif"), 2.0, 5.0, &gif);
+        let fc = args.iter().position(|a| a == "-filter_complex").unwrap();
+        assert!(args[fc + 1].starts_with("fps=15,split"));
+        assert!(args[fc + 1].contains("paletteuse"));
+
+        let apng = AnimationSettings { format: AnimationFormat::Apng, ..webp };
+        let args = build_animation_args(input, Path::new("loop.apng"), 2.0, 5.0, &apng);
+        assert!(args.windows(2).any(|w| w[0] == "-plays" && w[1] == "0"));
+        assert_eq!(args.last().unwrap(), "loop.apng");
+    }
 }
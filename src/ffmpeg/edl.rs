@@ -0,0 +1,165 @@
+//! Segment lists as edit decision lists: ffconcat playlists and CMX3600 EDLs,
+//! plus M3U playlists of exported parts.
+//!
+//! Nothing is encoded; the cuts are only described, for the concat demuxer,
+//! for another editor to conform, or for a player to chain.
+
+use super::commands::concat_demuxer_line;
+use std::path::Path;
+
+/// EDL flavor, chosen from the output file extension.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EdlFormat {
+    /// `ffconcat version 1.0` with `inpoint`/`outpoint` per segment
+    FfConcat,
+    /// CMX3600 text EDL (non-drop-frame timecodes)
+    Cmx3600,
+}
+
+impl EdlFormat {
+    /// `.edl` → CMX3600, anything else (`.ffconcat`, `.txt`) → ffconcat
+    pub fn from_path(path: &Path) -> Self {
+        match path.extension().map(|e| e.to_string_lossy().to_lowercase()) {
+            Some(ext) if ext == "edl" => EdlFormat::Cmx3600,
+            _ => EdlFormat::FfConcat,
+        }
+    }
+}
+
+/// Build an ffconcat playlist that plays `segments` of `input` back to back.
+///
+/// `ffmpeg -f concat -safe 0 -i list.ffconcat -c copy out.mp4` then joins
+/// them. With stream copy the demuxer still starts each part on the keyframe
+/// before `inpoint`, as with a lossless trim.
+pub fn build_ffconcat(input: &Path, segments: &[(f64, f64)]) -> String {
+    let mut out = String::from("ffconcat version 1.0\n");
+    for &(start, end) in segments {
+        out.push_str(&concat_demuxer_line(input));
+        out.push('\n');
+        out.push_str(&format!("inpoint {:.3}\noutpoint {:.3}\n", start, end));
+    }
+    out
+}
+
+/// Format seconds as a non-drop-frame `HH:MM:SS:FF` timecode at an integer
+/// timebase (29.97 → 30, 23.976 → 24, as NDF EDLs expect).
+fn timecode(seconds: f64, timebase: u32) -> String {
+    let total_frames = (seconds.max(0.0) * timebase as f64).round() as u64;
+    let tb = timebase as u64;
+    let frames = total_frames % tb;
+    let total_secs = total_frames / tb;
+    format!(
+        "{:02}:{:02}:{:02}:{:02}",
+        total_secs / 3600,
+        (total_secs % 3600) / 60,
+        total_secs % 60,
+        frames
+    )
+}
+
+/// Build a CMX3600 EDL: one cut event per segment, source timecodes taken from
+/// the segment bounds, record timecodes laid end to end from zero.
+pub fn build_cmx3600_edl(title: &str, input: &Path, segments: &[(f64, f64)], fps: f64) -> String {
+    let timebase = if fps > 0.0 && fps.is_finite() { fps.round().max(1.0) as u32 } else { 25 };
+    let clip_name = input.file_name().unwrap_or_default().to_string_lossy();
+
+    let mut out = format!("TITLE: {}\nFCM: NON-DROP FRAME\n\n", title);
+    let mut record = 0.0;
+    for (i, &(start, end)) in segments.iter().enumerate() {
+        let length = (end - start).max(0.0);
+        out.push_str(&format!(
+            "{:03}  AX       B     C        {} {} {} {}\n",
+            i + 1,
+            timecode(start, timebase),
+            timecode(end, timebase),
+            timecode(record, timebase),
+            timecode(record + length, timebase),
+        ));
+        out.push_str(&format!("* FROM CLIP NAME: {}\n\n", clip_name));
+        record += length;
+    }
+    out
+}
+
+/// Path of `file` as written in a playlist stored in `folder`: relative
+/// with `/` separators when the file is inside it, absolute otherwise.
+pub fn playlist_entry_path(folder: &Path, file: &Path) -> String {
+    match file.strip_prefix(folder) {
+        Ok(relative) => relative.components()
+            .map(|c| c.as_os_str().to_string_lossy())
+            .collect::<Vec<_>>()
+            .join("/"),
+        Err(_) => file.to_string_lossy().to_string(),
+    }
+}
+
+/// Build an extended M3U playlist from `(path, duration, title)` entries,
+/// played in order. A duration of -1 means unknown.
+pub fn build_m3u(entries: &[(String, f64, String)]) -> String {
+    let mut out = String::from("#EXTM3U\n");
+    for (path, duration, title) in entries {
+        let secs = if *duration > 0.0 { duration.round() as i64 } else { -1 };
+        out.push_str(&format!("#EXTINF:{},{}\n{}\n", secs, title, path));
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn format_from_extension() {
+        assert_eq!(EdlFormat::from_path(Path::new("cuts.EDL")), EdlFormat::Cmx3600);
+        assert_eq!(EdlFormat::from_path(Path::new("cuts.ffconcat")), EdlFormat::FfConcat);
+        assert_eq!(EdlFormat::from_path(Path::new("cuts")), EdlFormat::FfConcat);
+    }
+
+    #[test]
+    fn ffconcat_has_in_and_out_points() {
+        let list = build_ffconcat(Path::new("/v/it's.mp4"), &[(1.0, 2.5), (10.0, 12.25)]);
+        assert_eq!(
+            list,
+            "ffconcat version 1.0\n\
+             file '/v/it'\\''s.mp4'\ninpoint 1.000\noutpoint 2.500\n\
+             file '/v/it'\\''s.mp4'\ninpoint 10.000\noutpoint 12.250\n"
+        );
+    }
+
+    #[test]
+    fn timecodes_use_integer_timebase() {
+        assert_eq!(timecode(0.0, 30), "00:00:00:00");
+        assert_eq!(timecode(3661.5, 30), "01:01:01:15");
+        assert_eq!(timecode(1.0 / 25.0 * 24.0, 25), "00:00:00:24");
+    }
+
+    #[test]
+    fn cmx3600_events_are_laid_end_to_end() {
+        let edl = build_cmx3600_edl("Cuts", Path::new("/v/film.mov"), &[(10.0, 15.0), (20.0, 21.0)], 29.97);
+        let lines: Vec<&str> = edl.lines().collect();
+        assert_eq!(lines[0], "TITLE: Cuts");
+        assert_eq!(lines[1], "FCM: NON-DROP FRAME");
+        assert_eq!(
+            lines[3],
+            "001  AX       B     C        00:00:10:00 00:00:15:00 00:00:00:00 00:00:05:00"
+        );
+        assert_eq!(lines[4], "* FROM CLIP NAME: film.mov");
+        assert_eq!(
+            lines[6],
+            "002  AX       B     C        00:00:20:00 00:00:21:00 00:00:05:00 00:00:06:00"
+        );
+    }
+
+    #[test]
+    fn m3u_lists_relative_paths_in_order() {
+        let folder = Path::new("/out/stream");
+        assert_eq!(playlist_entry_path(folder, Path::new("/out/stream/day1/part_001.mp4")), "day1/part_001.mp4");
+        assert_eq!(playlist_entry_path(folder, Path::new("/elsewhere/x.mp4")), "/elsewhere/x.mp4");
+
+        let m3u = build_m3u(&[
+            ("part_001.mp4".to_string(), 59.6, "Segment 1".to_string()),
+            ("part_002.mp4".to_string(), 0.0, "Segment 2".to_string()),
+        ]);
+        assert_eq!(m3u, "#EXTM3U\n#EXTINF:60,Segment 1\npart_001.mp4\n#EXTINF:-1,Segment 2\npart_002.mp4\n");
+    }
+}
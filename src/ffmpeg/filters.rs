@@ -0,0 +1,399 @@
+//! Audio and video filter chains for re-encoded exports.
+//!
+//! Video stages are always emitted in the same order, regardless of which
+//! ones are enabled:
+//!
+//! 1. deinterlace (`yadif`) — must see the original interlaced fields
+//! 2. crop — in source pixel coordinates, before any geometry change
+//! 3. rotate / flip (`transpose`, `hflip`, `vflip`)
+//! 4. scale — on the cropped, rotated frame so the target height is the
+//!    height of the final picture
+//! 5. color (`eq`)
+//! 6. burned-in subtitles — after scaling so text is rendered at output size
+//! 7. fade in / out — so it darkens the final image and the subtitles
+//! 8. padding (`trim`, then `tpad`) — black frames around the faded segment
+//!
+//! Audio stages: sync offset (`adelay` / `atrim`), voice cleanup
+//! (`highpass`, then one `equalizer` per band), then padding (`atrim`, then
+//! `adelay` / `apad` for silence).
+//!
+//! Padding needs the segment to start at zero, so the padded chains trim it
+//! themselves and the command drops its output `-ss` / `-t`. It seeks on the
+//! input instead (no decoding from zero), and each chain first shifts its
+//! stream back to source timestamps so every stage stays anchored at `start`.
+//! Each chain pads its own stream: audio-only and video-only sources are
+//! padded as well.
+//!
+//! A picture overlay (watermark) would sit next to the subtitles stage; it
+//! needs a second input and a `-filter_complex` graph, so it is not part of
+//! this `-vf` chain.
+
+use crate::ui::{ExportSettings, FilterSettings, Rotation, RotationMethod};
+use std::path::Path;
+
+/// Build the `-vf` chain for re-encoded exports (`None` if no filter is active).
+///
+/// The trim seek is output-side (`-ss` after `-i`), so filters see source
+/// timestamps: fades are anchored at `start` and `end`, not at zero, and
+/// burned subtitles stay in sync without shifting the subtitle file. With
+/// padding the seek is input-side and the chain restores those timestamps.
+pub fn video_filter_chain(
+    filters: &FilterSettings,
+    burn_subtitles: Option<&Path>,
+    start: f64,
+    end: f64,
+) -> Option<String> {
+    let mut chain = Vec::new();
+
+    // Seek d'entrée du padding : le flux démarre à zéro, on le recale sur la source
+    if filters.has_padding() && start > 0.0 {
+        chain.push(format!("setpts=PTS+{:.3}/TB", start));
+    }
+
+    if filters.deinterlace {
+        chain.push("yadif".to_string());
+    }
+
+    if let Some(crop) = filters.crop {
+        if crop.width > 0 && crop.height > 0 {
+            chain.push(format!("crop={}:{}:{}:{}", crop.width, crop.height, crop.x, crop.y));
+        }
+    }
+
+    // Rotation en drapeau : rien à filtrer, gérée par `-display_rotation` en copie
+    let rotation = match filters.rotation_method {
+        RotationMethod::Reencode => filters.rotation,
+        RotationMethod::Metadata => Rotation::None,
+    };
+    match rotation {
+        Rotation::None => {}
+        Rotation::Cw90 => chain.push("transpose=1".to_string()),
+        Rotation::Ccw90 => chain.push("transpose=2".to_string()),
+        Rotation::Rot180 => chain.push("transpose=1,transpose=1".to_string()),
+    }
+    if filters.hflip {
+        chain.push("hflip".to_string());
+    }
+    if filters.vflip {
+        chain.push("vflip".to_string());
+    }
+
+    if let Some(height) = filters.scale_height {
+        // -2 : largeur paire calculée depuis le ratio (requis par yuv420p)
+        chain.push(format!("scale=-2:{}", height));
+    }
+
+    let neutral = FilterSettings::default();
+    if filters.brightness != neutral.brightness
+        || filters.contrast != neutral.contrast
+        || filters.saturation != neutral.saturation
+    {
+        chain.push(format!(
+            "eq=brightness={:.2}:contrast={:.2}:saturation={:.2}",
+            filters.brightness, filters.contrast, filters.saturation
+        ));
+    }
+
+    if let Some(subs) = burn_subtitles {
+        chain.push(format!("subtitles='{}'", escape_filter_path(subs)));
+    }
+
+    let duration = (end - start).max(0.0);
+    // Fondus bornés à la durée du segment pour ne pas déborder l'un sur l'autre
+    let fade_in = filters.fade_in_secs.min(duration);
+    let fade_out = filters.fade_out_secs.min(duration);
+    if fade_in > 0.0 {
+        chain.push(format!("fade=t=in:st={:.3}:d={:.3}", start, fade_in));
+    }
+    if fade_out > 0.0 {
+        chain.push(format!("fade=t=out:st={:.3}:d={:.3}", end - fade_out, fade_out));
+    }
+
+    if filters.has_padding() {
+        chain.push(format!("trim=start={:.3}:end={:.3},setpts=PTS-STARTPTS", start, end));
+        let mut tpad = Vec::new();
+        if filters.pad_before > 0.0 {
+            tpad.push(format!("start_duration={:.3}", filters.pad_before));
+        }
+        if filters.pad_after > 0.0 {
+            tpad.push(format!("stop_duration={:.3}", filters.pad_after));
+        }
+        // start/stop_mode=add (défaut) : images noires, pas de gel de l'image
+        chain.push(format!("tpad={}:color=black", tpad.join(":")));
+    }
+
+    if chain.is_empty() {
+        None
+    } else {
+        Some(chain.join(","))
+    }
+}
+
+/// `-vf` chain for a still preview of the geometry and color filters
+/// (`None` if none is active). Fades, padding and subtitles depend on the
+/// segment timing and are left out.
+pub fn preview_filter_chain(filters: &FilterSettings) -> Option<String> {
+    let still = FilterSettings {
+        fade_in_secs: 0.0,
+        fade_out_secs: 0.0,
+        pad_before: 0.0,
+        pad_after: 0.0,
+        ..filters.clone()
+    };
+    video_filter_chain(&still, None, 0.0, 0.0)
+}
+
+/// Escape a file path for use inside a quoted filter option. Backslashes
+/// become `/` (also valid on Windows) and the drive colon is escaped,
+/// as in `subtitles='C\:/subs/film.srt'`. A quote closes the quoted part and
+/// reopens it around `\\\'`, which the graph parser turns into `\'` and the
+/// option parser into `'` (`Don'\\\''t.srt`).
+pub fn escape_filter_path(path: &Path) -> String {
+    path.to_string_lossy()
+        .replace('\\', "/")
+        .replace(':', "\\:")
+        .replace('\'', r"'\\\''")
+}
+
+/// Chaîne de filtres audio des modes ré-encodés (`None` si aucun filtre).
+/// Le seek est côté sortie (`-ss` après `-i`), le décalage voit donc le flux
+/// complet et reste correct quel que soit le point de coupe (avec padding il
+/// est côté entrée : le flux est recalé sur la source après le décalage).
+/// Avec padding ou normalisation, le segment est coupé (`atrim`) juste
+/// après : `loudnorm` ne mesure que le clip. Sans padding, la normalisation replace ensuite le clip
+/// à sa position d'origine pour que le `-ss`/`-t` de sortie ne le recoupe pas.
+pub fn audio_filter_chain(settings: &ExportSettings, start: f64, end: f64) -> Option<String> {
+    let mut filters = Vec::new();
+    match settings.audio_offset_ms {
+        // Audio en avance sur la vidéo → on le retarde
+        Some(ms) if ms > 0 => filters.push(format!("adelay=delays={}:all=1", ms)),
+        // Audio en retard → on coupe le début et on recale les timestamps
+        Some(ms) if ms < 0 => filters.push(format!(
+            "atrim=start={:.3},asetpts=PTS-STARTPTS",
+            -ms as f64 / 1000.0
+        )),
+        _ => {}
+    }
+    let padding = &settings.filters;
+    if padding.has_padding() && start > 0.0 {
+        filters.push(format!("asetpts=PTS+{:.3}/TB", start));
+    }
+    // Segment ramené à zéro comme la vidéo, avant tout filtre coûteux
+    if padding.has_padding() || settings.normalize_loudness {
+        filters.push(format!("atrim=start={:.3}:end={:.3},asetpts=PTS-STARTPTS", start, end));
+    }
+    // Nettoyage de la voix : coupe-bas puis égaliseur
+    if let Some(hz) = settings.filters.highpass {
+        filters.push(format!("highpass=f={}", hz));
+    }
+    for band in &settings.filters.equalizer {
+        filters.push(format!(
+            "equalizer=f={}:width_type=o:width={}:g={}",
+            band.freq_hz, band.width_octaves, band.gain_db
+        ));
+    }
+    // Normalisation après l'égaliseur, qui change le niveau ; avant le
+    // padding, pour que le silence ajouté ne pèse pas dans la mesure
+    if settings.normalize_loudness {
+        filters.push(super::loudness::loudnorm_filter());
+    }
+    // Silence avant/après, sur le segment ramené à zéro comme la vidéo
+    if padding.has_padding() {
+        if padding.pad_before > 0.0 {
+            filters.push(format!("adelay=delays={}:all=1", (padding.pad_before * 1000.0).round() as u64));
+        }
+        if padding.pad_after > 0.0 {
+            filters.push(format!("apad=pad_dur={:.3}", padding.pad_after));
+        }
+    } else if settings.normalize_loudness && start > 0.0 {
+        filters.push(format!("asetpts=PTS+{:.3}/TB", start));
+    }
+    if filters.is_empty() {
+        None
+    } else {
+        Some(filters.join(","))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ui::CropRect;
+
+    #[test]
+    fn no_filters_means_no_chain() {
+        assert_eq!(video_filter_chain(&FilterSettings::default(), None, 0.0, 10.0), None);
+        assert!(FilterSettings::default().is_identity());
+    }
+
+    #[test]
+    fn stages_follow_fixed_order() {
+        let filters = FilterSettings {
+            deinterlace: true,
+            crop: Some(CropRect { x: 10, y: 20, width: 1280, height: 720 }),
+            rotation: Rotation::Cw90,
+            rotation_method: RotationMethod::Reencode,
+            hflip: true,
+            vflip: false,
+            scale_height: Some(480),
+            // Tag `-aspect`, pas un filtre
+            display_aspect: Some("16:9".to_string()),
+            brightness: 0.1,
+            contrast: 1.2,
+            saturation: 1.0,
+            fade_in_secs: 1.0,
+            fade_out_secs: 2.0,
+            // Audio-only, must not leak into -vf
+            highpass: Some(100),
+            equalizer: Vec::new(),
+            pad_before: 0.0,
+            pad_after: 0.0,
+        };
+        assert_eq!(
+            video_filter_chain(&filters, None, 5.0, 15.0).unwrap(),
+            "yadif,crop=1280:720:10:20,transpose=1,hflip,scale=-2:480,\
+             eq=brightness=0.10:contrast=1.20:saturation=1.00,\
+             fade=t=in:st=5.000:d=1.000,fade=t=out:st=13.000:d=2.000"
+        );
+    }
+
+    #[test]
+    fn rotation_variants() {
+        let mut filters = FilterSettings { rotation: Rotation::Rot180, ..Default::default() };
+        assert_eq!(video_filter_chain(&filters, None, 0.0, 1.0).unwrap(), "transpose=1,transpose=1");
+        filters.rotation = Rotation::Ccw90;
+        assert_eq!(video_filter_chain(&filters, None, 0.0, 1.0).unwrap(), "transpose=2");
+
+        // Drapeau seul : pas de transpose
+        filters.rotation_method = RotationMethod::Metadata;
+        assert_eq!(video_filter_chain(&filters, None, 0.0, 1.0), None);
+    }
+
+    #[test]
+    fn fades_are_clamped_to_segment() {
+        let filters = FilterSettings {
+            fade_in_secs: 5.0,
+            ..Default::default()
+        };
+        assert_eq!(
+            video_filter_chain(&filters, None, 2.0, 4.0).unwrap(),
+            "fade=t=in:st=2.000:d=2.000"
+        );
+    }
+
+    #[test]
+    fn audio_offset_filters() {
+        let mut settings = ExportSettings::default();
+        assert_eq!(audio_filter_chain(&settings, 0.0, 10.0), None);
+
+        settings.audio_offset_ms = Some(250);
+        assert_eq!(audio_filter_chain(&settings, 0.0, 10.0).unwrap(), "adelay=delays=250:all=1");
+
+        settings.audio_offset_ms = Some(-1500);
+        assert_eq!(
+            audio_filter_chain(&settings, 0.0, 10.0).unwrap(),
+            "atrim=start=1.500,asetpts=PTS-STARTPTS"
+        );
+    }
+
+    #[test]
+    fn voice_cleanup_follows_offset() {
+        let mut settings = ExportSettings {
+            audio_offset_ms: Some(100),
+            filters: FilterSettings { highpass: Some(80), ..Default::default() },
+            ..Default::default()
+        };
+        settings.filters.set_presence_boost_db(3.0);
+        assert_eq!(
+            audio_filter_chain(&settings, 0.0, 10.0).unwrap(),
+            "adelay=delays=100:all=1,highpass=f=80,equalizer=f=3000:width_type=o:width=1:g=3"
+        );
+
+        settings.filters.set_presence_boost_db(0.0);
+        assert!(settings.filters.equalizer.is_empty());
+        assert_eq!(settings.filters.presence_boost_db(), 0.0);
+    }
+
+    #[test]
+    fn burned_subtitles_come_before_fades() {
+        let filters = FilterSettings {
+            scale_height: Some(720),
+            fade_out_secs: 1.0,
+            ..Default::default()
+        };
+        assert_eq!(
+            video_filter_chain(&filters, Some(Path::new(r"C:\subs\film.srt")), 0.0, 10.0).unwrap(),
+            "scale=-2:720,subtitles='C\\:/subs/film.srt',fade=t=out:st=9.000:d=1.000"
+        );
+    }
+
+    #[test]
+    fn quotes_in_subtitle_paths_close_and_reopen_the_quoting() {
+        assert_eq!(escape_filter_path(Path::new("/subs/Don't.srt")), r"/subs/Don'\\\''t.srt");
+        let filters = FilterSettings::default();
+        assert_eq!(
+            video_filter_chain(&filters, Some(Path::new("/subs/Don't.srt")), 0.0, 10.0).unwrap(),
+            r"subtitles='/subs/Don'\\\''t.srt'"
+        );
+    }
+
+    #[test]
+    fn preview_chain_keeps_geometry_only() {
+        let filters = FilterSettings {
+            crop: Some(CropRect { x: 10, y: 20, width: 640, height: 360 }),
+            scale_height: Some(720),
+            fade_in_secs: 1.0,
+            pad_after: 2.0,
+            ..Default::default()
+        };
+        assert_eq!(preview_filter_chain(&filters).as_deref(), Some("crop=640:360:10:20,scale=-2:720"));
+        assert_eq!(preview_filter_chain(&FilterSettings { fade_out_secs: 1.0, ..Default::default() }), None);
+    }
+
+    #[test]
+    fn padding_trims_then_pads_each_stream() {
+        let settings = ExportSettings {
+            filters: FilterSettings {
+                fade_in_secs: 1.0,
+                pad_before: 2.0,
+                pad_after: 1.5,
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        assert_eq!(settings.filters.padding_secs(), 3.5);
+        assert_eq!(
+            video_filter_chain(&settings.filters, None, 5.0, 15.0).unwrap(),
+            "setpts=PTS+5.000/TB,fade=t=in:st=5.000:d=1.000,trim=start=5.000:end=15.000,setpts=PTS-STARTPTS,\
+             tpad=start_duration=2.000:stop_duration=1.500:color=black"
+        );
+        assert_eq!(
+            audio_filter_chain(&settings, 5.0, 15.0).unwrap(),
+            "asetpts=PTS+5.000/TB,atrim=start=5.000:end=15.000,asetpts=PTS-STARTPTS,adelay=delays=2000:all=1,apad=pad_dur=1.500"
+        );
+
+        let normalized = ExportSettings { normalize_loudness: true, ..settings };
+        assert_eq!(
+            audio_filter_chain(&normalized, 5.0, 15.0).unwrap(),
+            "asetpts=PTS+5.000/TB,atrim=start=5.000:end=15.000,asetpts=PTS-STARTPTS,\
+             loudnorm=I=-16:TP=-1.5:LRA=11,adelay=delays=2000:all=1,apad=pad_dur=1.500"
+        );
+
+        // Sans padding : coupe, mesure sur le clip, puis retour à la position
+        // d'origine pour le `-ss`/`-t` de sortie
+        let plain = ExportSettings { normalize_loudness: true, audio_offset_ms: Some(-250), ..Default::default() };
+        assert_eq!(
+            audio_filter_chain(&plain, 5.0, 15.0).unwrap(),
+            "atrim=start=0.250,asetpts=PTS-STARTPTS,\
+             atrim=start=5.000:end=15.000,asetpts=PTS-STARTPTS,\
+             loudnorm=I=-16:TP=-1.5:LRA=11,asetpts=PTS+5.000/TB"
+        );
+
+        // Un seul côté
+        let after_only = FilterSettings { pad_after: 3.0, ..Default::default() };
+        assert_eq!(
+            video_filter_chain(&after_only, None, 0.0, 4.0).unwrap(),
+            "trim=start=0.000:end=4.000,setpts=PTS-STARTPTS,tpad=stop_duration=3.000:color=black"
+        );
+    }
+}
@@ -82,10 +82,30 @@ pub fn last_keyframe_at_or_before(keyframes: &[f64], t: f64, tolerance: f64) ->
 }
 
 /// `true` si `t` tombe à moins de `tolerance` d'une keyframe.
+#[cfg(test)]
 pub fn is_keyframe_aligned(keyframes: &[f64], t: f64, tolerance: f64) -> bool {
     keyframes.iter().any(|&k| (k - t).abs() <= tolerance)
 }
 
+/// Resserre `[start, end]` sur des keyframes : début avancé à la keyframe
+/// suivante, fin reculée à la précédente. Une coupe `-c copy` qui démarre sur
+/// une keyframe n'a pas d'images figées au début.
+///
+/// Retourne `None` si aucune keyframe ne tombe dans l'intervalle.
+pub fn snap_range_inward(keyframes: &[f64], start: f64, end: f64, tolerance: f64) -> Option<(f64, f64)> {
+    let snapped_start = first_keyframe_at_or_after(keyframes, start, tolerance)?;
+    let snapped_end = last_keyframe_at_or_before(keyframes, end, tolerance)?;
+    (snapped_end > snapped_start).then_some((snapped_start, snapped_end))
+}
+
+/// Point de départ réel d'une coupe `-c copy` demandée à `t` : la dernière
+/// keyframe `<= t` (le seek d'entrée recule jusqu'à elle), et l'écart en
+/// secondes entre cette keyframe et `t`.
+pub fn lossless_start_snap(keyframes: &[f64], t: f64) -> Option<(f64, f64)> {
+    let keyframe = last_keyframe_at_or_before(keyframes, t, 0.001)?;
+    Some((keyframe, (t - keyframe).max(0.0)))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -122,4 +142,23 @@ mod tests {
         assert!(is_keyframe_aligned(&kf, 2.03, 0.04));
         assert!(!is_keyframe_aligned(&kf, 2.5, 0.04));
     }
+
+    #[test]
+    fn snap_inward_tightens_both_ends() {
+        let kf = vec![0.0, 2.0, 4.0, 6.0, 8.0];
+        assert_eq!(snap_range_inward(&kf, 1.2, 7.5, 0.001), Some((2.0, 6.0)));
+        // Déjà alignés : inchangés
+        assert_eq!(snap_range_inward(&kf, 2.0, 6.0, 0.001), Some((2.0, 6.0)));
+        // Une seule keyframe dans l'intervalle : rien de copiable proprement
+        assert_eq!(snap_range_inward(&kf, 2.5, 5.0, 0.001), None);
+        assert_eq!(snap_range_inward(&[], 1.0, 5.0, 0.001), None);
+    }
+
+    #[test]
+    fn lossless_start_lands_on_prior_keyframe() {
+        let kf = vec![0.0, 2.0, 4.0];
+        assert_eq!(lossless_start_snap(&kf, 3.5), Some((2.0, 1.5)));
+        assert_eq!(lossless_start_snap(&kf, 4.0), Some((4.0, 0.0)));
+        assert_eq!(lossless_start_snap(&[], 1.0), None);
+    }
 }
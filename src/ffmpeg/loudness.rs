@@ -0,0 +1,107 @@
+//! Loudness analysis (EBU R128) with FFmpeg's `loudnorm` filter in
+//! measurement mode.
+
+/// Measured loudness of a file's audio
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LoudnessInfo {
+    /// Integrated loudness (LUFS)
+    pub integrated_lufs: f64,
+    /// True peak (dBTP)
+    pub true_peak_db: f64,
+    /// Loudness range (LU)
+    pub lra: f64,
+}
+
+impl LoudnessInfo {
+    /// True peak above -1 dBTP: likely clipped once encoded to a lossy format
+    pub fn is_clipping(&self) -> bool {
+        self.true_peak_db > -1.0
+    }
+}
+
+/// Integrated loudness target of normalized exports (streaming platforms' -16 LUFS)
+pub const TARGET_LUFS: f64 = -16.0;
+
+/// Single-pass `loudnorm` filter bringing a clip to `TARGET_LUFS` with a
+/// -1.5 dBTP ceiling. Every clip is measured on its own, so split parts all
+/// come out at the same loudness.
+pub fn loudnorm_filter() -> String {
+    format!("loudnorm=I={}:TP=-1.5:LRA=11", TARGET_LUFS)
+}
+
+/// Build FFmpeg arguments for a loudness measurement.
+///
+/// `loudnorm=print_format=json` only measures on a first pass: it prints a
+/// JSON summary on stderr at the end, the output is discarded.
+pub fn build_loudness_args(input: &str) -> Vec<String> {
+    vec![
+        "-hide_banner".to_string(),
+        "-nostats".to_string(),
+        "-i".to_string(),
+        input.to_string(),
+        "-vn".to_string(),
+        "-af".to_string(),
+        "loudnorm=print_format=json".to_string(),
+        "-f".to_string(),
+        "null".to_string(),
+        "-".to_string(),
+    ]
+}
+
+/// Parse the JSON block printed by `loudnorm` at the end of stderr.
+///
+/// The block follows a `[Parsed_loudnorm_0 @ ...]` line:
+///   { "input_i" : "-23.54", "input_tp" : "-4.12", "input_lra" : "6.70", ... }
+pub fn parse_loudness_output(stderr_lines: &[String]) -> Option<LoudnessInfo> {
+    let start = stderr_lines.iter().rposition(|l| l.trim_start().starts_with('{'))?;
+    let end = start + stderr_lines[start..].iter().position(|l| l.trim_end().ends_with('}'))?;
+    let json = stderr_lines[start..=end].join("\n");
+    let value: serde_json::Value = serde_json::from_str(&json).ok()?;
+
+    // Values are strings; silence gives "-inf"
+    let field = |name: &str| -> Option<f64> {
+        value.get(name)?.as_str()?.trim().parse::<f64>().ok().filter(|v| v.is_finite())
+    };
+    Some(LoudnessInfo {
+        integrated_lufs: field("input_i")?,
+        true_peak_db: field("input_tp")?,
+        lra: field("input_lra")?,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn lines(text: &str) -> Vec<String> {
+        text.lines().map(str::to_string).collect()
+    }
+
+    #[test]
+    fn parses_loudnorm_summary() {
+        let stderr = lines(
+            "size=N/A time=00:01:00.00 bitrate=N/A speed= 120x\n\
+             [Parsed_loudnorm_0 @ 0x55d0c8a2c340] \n\
+             {\n\
+             \t\"input_i\" : \"-23.54\",\n\
+             \t\"input_tp\" : \"-0.42\",\n\
+             \t\"input_lra\" : \"6.70\",\n\
+             \t\"input_thresh\" : \"-34.00\",\n\
+             \t\"normalization_type\" : \"dynamic\",\n\
+             \t\"target_offset\" : \"0.00\"\n\
+             }",
+        );
+        let info = parse_loudness_output(&stderr).unwrap();
+        assert_eq!(info.integrated_lufs, -23.54);
+        assert_eq!(info.true_peak_db, -0.42);
+        assert_eq!(info.lra, 6.7);
+        assert!(info.is_clipping());
+    }
+
+    #[test]
+    fn silent_or_missing_audio_gives_none() {
+        let silent = lines("{\n\"input_i\" : \"-inf\",\n\"input_tp\" : \"-inf\",\n\"input_lra\" : \"0.00\"\n}");
+        assert_eq!(parse_loudness_output(&silent), None);
+        assert_eq!(parse_loudness_output(&lines("Output file is empty")), None);
+    }
+}
@@ -3,15 +3,34 @@ mod probe;
 mod commands;
 mod progress;
 mod silence;
+mod scenes;
 mod paths;
 mod keyframes;
 mod smart_cut;
+mod filters;
+mod edl;
+mod script;
+mod validate;
+mod profiles;
+mod loudness;
+mod capabilities;
+mod chapters;
+mod waveform;
 
 pub use wrapper::*;
 pub use probe::*;
 pub use commands::*;
 pub use progress::*;
 pub use silence::*;
+pub use scenes::*;
 pub use paths::*;
-pub use keyframes::*;
-pub use smart_cut::*;
+pub use validate::*;
+pub use script::*;
+pub use edl::*;
+pub use profiles::*;
+pub use loudness::{LoudnessInfo, TARGET_LUFS};
+pub use capabilities::*;
+pub use waveform::*;
+pub use filters::preview_filter_chain;
+pub use smart_cut::SourceVideoCodec;
+pub use keyframes::{extract_keyframes, lossless_start_snap, snap_range_inward};
@@ -11,6 +11,8 @@ use std::sync::OnceLock;
 
 static FFMPEG_PATH: OnceLock<String> = OnceLock::new();
 static FFPROBE_PATH: OnceLock<String> = OnceLock::new();
+/// Paths from the settings file (ffmpeg, ffprobe), set once at startup
+static CONFIGURED_PATHS: OnceLock<(Option<String>, Option<String>)> = OnceLock::new();
 
 /// Candidate directories searched on macOS, in order of preference.
 /// Apple Silicon Homebrew first, then Intel / MacPorts.
@@ -86,6 +88,14 @@ fn resolve(binary: &str) -> String {
         }
     }
 
+    // 1b. Path from the settings file
+    if let Some((ffmpeg, ffprobe)) = CONFIGURED_PATHS.get() {
+        let configured = if binary == "ffmpeg" { ffmpeg } else { ffprobe };
+        if let Some(val) = configured.as_ref().filter(|v| PathBuf::from(v).exists()) {
+            return val.clone();
+        }
+    }
+
     // 2. Next to our own executable (a bundled ffmpeg ships alongside the app)
     if let Ok(exe) = std::env::current_exe() {
         if let Some(dir) = exe.parent() {
@@ -131,6 +141,13 @@ fn resolve(binary: &str) -> String {
     binary.to_string()
 }
 
+/// Register ffmpeg / ffprobe paths from the settings file. Must run before
+/// the first `ffmpeg_path()` / `ffprobe_path()` call; the env overrides
+/// still take precedence.
+pub fn set_configured_paths(ffmpeg: Option<String>, ffprobe: Option<String>) {
+    let _ = CONFIGURED_PATHS.set((ffmpeg, ffprobe));
+}
+
 /// Absolute path to the ffmpeg binary (or bare "ffmpeg" if nothing found).
 pub fn ffmpeg_path() -> &'static str {
     FFMPEG_PATH.get_or_init(|| resolve("ffmpeg"))
@@ -141,16 +158,6 @@ pub fn ffprobe_path() -> &'static str {
     FFPROBE_PATH.get_or_init(|| resolve("ffprobe"))
 }
 
-/// Returns true if ffmpeg is installed and responds to `-version`.
-pub fn is_ffmpeg_available() -> bool {
-    let mut cmd = std::process::Command::new(ffmpeg_path());
-    cmd.arg("-version")
-        .stdout(std::process::Stdio::null())
-        .stderr(std::process::Stdio::null());
-    apply_platform_flags(&mut cmd);
-    cmd.status().map(|s| s.success()).unwrap_or(false)
-}
-
 /// Human-readable hint shown when ffmpeg is not found.
 pub fn install_hint() -> &'static str {
     #[cfg(target_os = "macos")]
@@ -210,10 +217,3 @@ pub fn ffprobe_command() -> std::process::Command {
     apply_platform_flags(&mut cmd);
     cmd
 }
-
-/// Build a tokio async `Command` for ffmpeg with platform flags applied.
-pub fn ffmpeg_command_async() -> tokio::process::Command {
-    let mut cmd = tokio::process::Command::new(ffmpeg_path());
-    apply_platform_flags_tokio(&mut cmd);
-    cmd
-}
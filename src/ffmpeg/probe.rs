@@ -2,6 +2,7 @@ use super::paths::{ffmpeg_command, ffprobe_command};
 use anyhow::{anyhow, Result};
 use serde::{Deserialize, Serialize};
 use std::path::Path;
+use std::process::Stdio;
 
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct MediaInfo {
@@ -13,12 +14,26 @@ pub struct MediaInfo {
     pub video_bitrate: Option<u64>,
     pub audio_bitrate: Option<u64>,
     pub framerate: Option<f64>,
+    /// Cadence moyenne (`avg_frame_rate`), distincte de `framerate` en VFR
+    #[serde(default)]
+    pub avg_framerate: Option<f64>,
     pub sample_rate: Option<u32>,
     pub channels: Option<u32>,
     pub format_name: String,
     pub file_size: u64,
 }
 
+impl MediaInfo {
+    /// Cadence variable (captures d'écran, téléphones) : la cadence nominale
+    /// et la moyenne diffèrent de plus de 1 %
+    pub fn is_variable_framerate(&self) -> bool {
+        match (self.framerate, self.avg_framerate) {
+            (Some(r), Some(avg)) if r > 0.0 && avg > 0.0 => (r - avg).abs() / r > 0.01,
+            _ => false,
+        }
+    }
+}
+
 #[derive(Debug, Deserialize)]
 struct FFProbeOutput {
     format: Option<FFProbeFormat>,
@@ -30,7 +45,6 @@ struct FFProbeFormat {
     duration: Option<String>,
     format_name: Option<String>,
     size: Option<String>,
-    bit_rate: Option<String>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -40,7 +54,9 @@ struct FFProbeStream {
     width: Option<u32>,
     height: Option<u32>,
     bit_rate: Option<String>,
+    duration: Option<String>,
     r_frame_rate: Option<String>,
+    avg_frame_rate: Option<String>,
     sample_rate: Option<String>,
     channels: Option<u32>,
 }
@@ -84,9 +100,13 @@ pub fn probe_file(path: &Path) -> Result<MediaInfo> {
     }
 
     // Parse stream info
+    let mut stream_duration: f64 = 0.0;
     if let Some(streams) = probe_output.streams {
         for stream in streams {
             let codec_type = stream.codec_type.as_deref().unwrap_or("");
+            if let Some(d) = stream.duration.as_deref().and_then(|d| d.parse::<f64>().ok()) {
+                stream_duration = stream_duration.max(d);
+            }
 
             match codec_type {
                 "video" => {
@@ -97,6 +117,8 @@ pub fn probe_file(path: &Path) -> Result<MediaInfo> {
                         .and_then(|b| b.parse::<u64>().ok());
                     info.framerate = stream.r_frame_rate
                         .and_then(|r| parse_framerate(&r));
+                    info.avg_framerate = stream.avg_frame_rate
+                        .and_then(|r| parse_framerate(&r));
                 }
                 "audio" => {
                     info.audio_codec = stream.codec_name;
@@ -111,9 +133,32 @@ pub fn probe_file(path: &Path) -> Result<MediaInfo> {
         }
     }
 
+    // Conteneur sans durée (MP4 fragmenté, capture de flux) : celle des
+    // flux. Reste à 0 sinon : `estimate_duration` lit tout le fichier, trop
+    // lent ici, l'app la lance une fois en arrière-plan à l'import
+    if !(info.duration.is_finite() && info.duration > 0.0) {
+        info.duration = stream_duration;
+    }
+
     Ok(info)
 }
 
+/// Durée obtenue en lisant tout le fichier (`-c copy` vers `null`, sans
+/// décodage) pour les fichiers qui n'en annoncent aucune. `None` si FFmpeg
+/// n'arrive pas au bout.
+pub fn estimate_duration(path: &Path) -> Option<f64> {
+    let output = ffmpeg_command()
+        .args(["-hide_banner", "-nostdin", "-i"])
+        .arg(path)
+        .args(["-c", "copy", "-f", "null", "-"])
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .output()
+        .ok()?;
+    super::last_progress_time(&String::from_utf8_lossy(&output.stderr))
+        .filter(|d| *d > 0.0)
+}
+
 fn parse_framerate(fps_str: &str) -> Option<f64> {
     let parts: Vec<&str> = fps_str.split('/').collect();
     if parts.len() == 2 {
@@ -125,28 +170,3 @@ fn parse_framerate(fps_str: &str) -> Option<f64> {
     }
     fps_str.parse().ok()
 }
-
-/// Extract a thumbnail frame from a video at a specific timestamp
-pub fn extract_frame(video_path: &Path, output_path: &Path, timestamp: f64) -> Result<()> {
-    let output = ffmpeg_command()
-        .args([
-            "-y",
-            "-ss", &timestamp.to_string(),
-            "-i",
-        ])
-        .arg(video_path)
-        .args([
-            "-vframes", "1",
-            "-q:v", "2",
-        ])
-        .arg(output_path)
-        .output()
-        .map_err(|e| anyhow!("ffmpeg a échoué: {}. {}", e, super::paths::install_hint()))?;
-
-    if !output.status.success() {
-        let stderr = String::from_utf8_lossy(&output.stderr);
-        return Err(anyhow!("Failed to extract frame: {}", stderr));
-    }
-
-    Ok(())
-}
@@ -0,0 +1,61 @@
+//! Encode profiles shared as a JSON file.
+//!
+//! The file holds an array of profiles; every field but `name` is optional:
+//!
+//! ```json
+//! [
+//!   { "name": "archive", "video_codec": "libx265", "crf": 20, "preset": "slow" },
+//!   { "name": "web", "crf": 26, "pix_fmt": "yuv420p", "extra_args": ["-movflags", "+faststart"] }
+//! ]
+//! ```
+
+use crate::ui::EncodeProfile;
+use anyhow::{anyhow, Result};
+use std::path::Path;
+
+/// Parse a profile list, rejecting unnamed or duplicate profiles
+pub fn parse_encode_profiles(json: &str) -> Result<Vec<EncodeProfile>> {
+    let profiles: Vec<EncodeProfile> = serde_json::from_str(json)
+        .map_err(|e| anyhow!("Invalid profile file: {}", e))?;
+    for (i, profile) in profiles.iter().enumerate() {
+        if profile.name.trim().is_empty() {
+            return Err(anyhow!("Profile #{} has no name", i + 1));
+        }
+        if profiles[..i].iter().any(|p| p.name == profile.name) {
+            return Err(anyhow!("Duplicate profile name '{}'", profile.name));
+        }
+    }
+    Ok(profiles)
+}
+
+pub fn load_encode_profiles(path: &Path) -> Result<Vec<EncodeProfile>> {
+    let json = std::fs::read_to_string(path)
+        .map_err(|e| anyhow!("Cannot read {}: {}", path.display(), e))?;
+    parse_encode_profiles(&json)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_partial_profiles() {
+        let profiles = parse_encode_profiles(
+            r#"[{"name": "archive", "video_codec": "libx265", "crf": 20, "preset": "slow"},
+                {"name": "web", "extra_args": ["-movflags", "+faststart"]}]"#,
+        )
+        .unwrap();
+        assert_eq!(profiles.len(), 2);
+        assert_eq!(profiles[0].video_codec.as_deref(), Some("libx265"));
+        assert_eq!(profiles[0].crf, Some(20));
+        assert_eq!(profiles[1].crf, None);
+        assert_eq!(profiles[1].extra_args, ["-movflags", "+faststart"]);
+    }
+
+    #[test]
+    fn rejects_unnamed_and_duplicate_profiles() {
+        assert!(parse_encode_profiles(r#"[{"name": ""}]"#).is_err());
+        assert!(parse_encode_profiles(r#"[{"name": "a"}, {"name": "a"}]"#).is_err());
+        assert!(parse_encode_profiles("not json").is_err());
+    }
+}
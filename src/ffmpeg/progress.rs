@@ -1,46 +1,12 @@
-use serde::{Deserialize, Serialize};
-
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct TaskProgress {
-    pub task_name: String,
-    pub message: String,
-    pub progress: f32,
-    pub is_complete: bool,
-    pub is_error: bool,
-}
-
-impl TaskProgress {
-    pub fn new(task_name: &str) -> Self {
-        Self {
-            task_name: task_name.to_string(),
-            message: format!("{}...", task_name),
-            progress: 0.0,
-            is_complete: false,
-            is_error: false,
-        }
-    }
-
-    pub fn update(&mut self, progress: f32, message: &str) {
-        self.progress = progress.clamp(0.0, 1.0);
-        self.message = message.to_string();
-    }
-
-    pub fn complete(&mut self, message: &str) {
-        self.progress = 1.0;
-        self.message = message.to_string();
-        self.is_complete = true;
-    }
-
-    pub fn fail(&mut self, message: &str) {
-        self.message = message.to_string();
-        self.is_complete = true;
-        self.is_error = true;
-    }
-}
-
 /// Parse FFmpeg progress output line
 /// FFmpeg outputs progress in format: frame=  123 fps= 30 q=28.0 size=    1234kB time=00:00:05.00 bitrate= 2000.0kbits/s
 pub fn parse_progress_line(line: &str, total_duration: f64) -> Option<f32> {
+    parse_progress_line_from(line, 0.0, total_duration)
+}
+
+/// Same for an output whose timestamps start at `origin` instead of zero
+/// (`-copyts` keeps the source's, so `time=` starts at the cut point)
+pub fn parse_progress_line_from(line: &str, origin: f64, total_duration: f64) -> Option<f32> {
     // Look for time= pattern
     if let Some(time_pos) = line.find("time=") {
         let time_str = &line[time_pos + 5..];
@@ -48,7 +14,7 @@ pub fn parse_progress_line(line: &str, total_duration: f64) -> Option<f32> {
             let time_value = &time_str[..end_pos];
             if let Some(current_time) = parse_time_string(time_value) {
                 if total_duration > 0.0 {
-                    return Some((current_time / total_duration) as f32);
+                    return Some(((current_time - origin) / total_duration) as f32);
                 }
             }
         }
@@ -68,6 +34,30 @@ fn parse_time_string(time_str: &str) -> Option<f64> {
     None
 }
 
+/// Last `time=` of FFmpeg's stats lines (rewritten in place with `\r`):
+/// the position reached when the run ended. `time=N/A` lines are skipped.
+pub fn last_progress_time(stderr: &str) -> Option<f64> {
+    stderr.split(['\r', '\n']).rev().find_map(|line| {
+        let rest = &line[line.find("time=")? + 5..];
+        parse_time_string(rest.split_whitespace().next()?)
+    })
+}
+
+/// Overall progress of several tasks, each weighted by its media duration so
+/// one long file is not drowned out by many short ones
+/// (`(duration, progress)` pairs; zero durations count as one second)
+pub fn weighted_progress(tasks: &[(f64, f32)]) -> f32 {
+    let total: f64 = tasks.iter().map(|(d, _)| d.max(1.0)).sum();
+    if total <= 0.0 {
+        return 0.0;
+    }
+    let done: f64 = tasks
+        .iter()
+        .map(|(d, p)| d.max(1.0) * p.clamp(0.0, 1.0) as f64)
+        .sum();
+    (done / total) as f32
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -78,6 +68,10 @@ mod tests {
         let progress = parse_progress_line(line, 100.0);
         assert!(progress.is_some());
         assert!((progress.unwrap() - 0.1).abs() < 0.01);
+        // Source timestamps kept: 10 s into a 20 s cut made at 1:30
+        let line = "frame=  300 fps= 30 q=28.0 size=    1024kB time=00:01:40.00 bitrate= 838.9kbits/s";
+        let progress = parse_progress_line_from(line, 90.0, 20.0).unwrap();
+        assert!((progress - 0.5).abs() < 0.01);
     }
 
     #[test]
@@ -85,4 +79,23 @@ mod tests {
         assert_eq!(parse_time_string("00:01:30.50"), Some(90.5));
         assert_eq!(parse_time_string("01:00:00.00"), Some(3600.0));
     }
+
+    #[test]
+    fn last_progress_time_is_the_final_stats_line() {
+        let stderr = "Input #0, mpegts, from 'rec.ts':\n  Duration: N/A, start: 1.4\n\
+                      size=N/A time=00:00:05.00 bitrate=N/A speed=50x\r\
+                      size=N/A time=00:12:41.36 bitrate=N/A speed=61x\r\
+                      [out#0/null] video:1kB audio:2kB\n";
+        assert_eq!(last_progress_time(stderr), Some(761.36));
+        assert_eq!(last_progress_time("size=N/A time=N/A bitrate=N/A\n"), None);
+    }
+
+    #[test]
+    fn weighted_progress_follows_long_files() {
+        // 3 h file half done, two short files finished
+        let p = weighted_progress(&[(10800.0, 0.5), (60.0, 1.0), (60.0, 1.0)]);
+        assert!((p - 0.5055).abs() < 0.001);
+        assert_eq!(weighted_progress(&[]), 0.0);
+        assert_eq!(weighted_progress(&[(0.0, 1.0)]), 1.0);
+    }
 }
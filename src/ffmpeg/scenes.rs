@@ -0,0 +1,94 @@
+//! Scene-change detection and conversion of scene cuts into segments.
+
+/// Build FFmpeg arguments for scene-change detection.
+///
+/// `select='gt(scene,T)'` keeps only frames whose scene score exceeds the
+/// threshold (0.0–1.0, lower = more sensitive); `showinfo` logs their
+/// `pts_time` on stderr. Audio is skipped and the output discarded.
+pub fn build_scene_detect_args(input: &str, threshold: f64) -> Vec<String> {
+    vec![
+        "-i".to_string(),
+        input.to_string(),
+        "-an".to_string(),
+        "-vf".to_string(),
+        format!("select='gt(scene,{:.3})',showinfo", threshold),
+        "-f".to_string(),
+        "null".to_string(),
+        "-".to_string(),
+    ]
+}
+
+/// Parse FFmpeg stderr output to extract scene-change timestamps.
+///
+/// showinfo outputs lines like:
+///   [Parsed_showinfo_1 @ ...] n:   0 pts:  12345 pts_time:12.345 ...
+pub fn parse_scene_output(stderr_lines: &[String]) -> Vec<f64> {
+    let mut times: Vec<f64> = stderr_lines
+        .iter()
+        .filter(|line| line.contains("showinfo"))
+        .filter_map(|line| {
+            let pos = line.find("pts_time:")?;
+            let after = &line[pos + "pts_time:".len()..];
+            after.split_whitespace().next()?.parse::<f64>().ok()
+        })
+        .collect();
+    times.sort_by(|a, b| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal));
+    times
+}
+
+/// Turn scene-change timestamps into consecutive `(start, end)` ranges
+/// covering `[0, duration]`.
+///
+/// Cuts closer than `min_scene_secs` to the previous cut (or to either end of
+/// the file) are dropped, so flashes and fades don't produce tiny segments.
+pub fn scenes_to_ranges(duration: f64, scene_times: &[f64], min_scene_secs: f64) -> Vec<(f64, f64)> {
+    if duration <= 0.0 {
+        return Vec::new();
+    }
+
+    let mut ranges = Vec::new();
+    let mut start = 0.0;
+    for &t in scene_times {
+        if t - start < min_scene_secs || duration - t < min_scene_secs {
+            continue;
+        }
+        ranges.push((start, t));
+        start = t;
+    }
+    ranges.push((start, duration));
+    ranges
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_showinfo_lines() {
+        let lines = vec![
+            "[Parsed_showinfo_1 @ 0x1] n:   0 pts:  61440 pts_time:4.8     duration: 512".to_string(),
+            "frame=  100 fps=0.0 q=-0.0 size=N/A time=00:00:04.00".to_string(),
+            "[Parsed_showinfo_1 @ 0x1] n:   1 pts: 153600 pts_time:12.0    duration: 512".to_string(),
+        ];
+        assert_eq!(parse_scene_output(&lines), vec![4.8, 12.0]);
+    }
+
+    #[test]
+    fn ranges_cover_whole_file() {
+        let ranges = scenes_to_ranges(30.0, &[10.0, 20.0], 1.0);
+        assert_eq!(ranges, vec![(0.0, 10.0), (10.0, 20.0), (20.0, 30.0)]);
+    }
+
+    #[test]
+    fn short_scenes_are_merged() {
+        // 10.3 is too close to 10.0, 29.8 too close to the end
+        let ranges = scenes_to_ranges(30.0, &[10.0, 10.3, 20.0, 29.8], 1.0);
+        assert_eq!(ranges, vec![(0.0, 10.0), (10.0, 20.0), (20.0, 30.0)]);
+    }
+
+    #[test]
+    fn no_scenes_gives_single_range() {
+        assert_eq!(scenes_to_ranges(5.0, &[], 1.0), vec![(0.0, 5.0)]);
+        assert!(scenes_to_ranges(0.0, &[1.0], 1.0).is_empty());
+    }
+}
@@ -0,0 +1,229 @@
+//! Export the job queue as a standalone shell script (`.sh` or `.bat`).
+//!
+//! Each job is rendered from the same `build_*_args` builders the GUI uses,
+//! so the script runs exactly the commands the queue would have run.
+
+use super::commands::{
+    build_animation_args, build_concat_args, build_concat_reencode_args, build_extract_audio_args, build_remux_args, build_thumbnail_args, build_trim_args,
+    concat_demuxer_line, ConcatMethod,
+};
+use super::smart_cut::SourceVideoCodec;
+use crate::export_queue::{ExportJob, ExportOperation};
+use crate::ui::TrimMode;
+
+/// Target shell for a generated script
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ScriptShell {
+    /// POSIX sh (`.sh`)
+    Posix,
+    /// Windows cmd.exe (`.bat` / `.cmd`)
+    Cmd,
+}
+
+impl ScriptShell {
+    /// Shell of the running platform (cmd.exe on Windows)
+    pub fn native() -> Self {
+        if cfg!(windows) { ScriptShell::Cmd } else { ScriptShell::Posix }
+    }
+
+    /// Pick the shell from the script file extension (`.bat`/`.cmd` → cmd)
+    pub fn from_path(path: &std::path::Path) -> Self {
+        match path.extension().map(|e| e.to_string_lossy().to_lowercase()).as_deref() {
+            Some("bat") | Some("cmd") => ScriptShell::Cmd,
+            _ => ScriptShell::Posix,
+        }
+    }
+}
+
+/// Quote one argument for the target shell
+pub fn shell_quote(arg: &str, shell: ScriptShell) -> String {
+    match shell {
+        ScriptShell::Posix => {
+            let safe = !arg.is_empty()
+                && arg.chars().all(|c| c.is_ascii_alphanumeric() || "-_./:=,+@%".contains(c));
+            if safe {
+                arg.to_string()
+            } else {
+                format!("'{}'", arg.replace('\'', "'\\''"))
+            }
+        }
+        ScriptShell::Cmd => {
+            // Percent signs expand as variables in batch files, even inside quotes
+            format!("\"{}\"", arg.replace('"', "\"\"").replace('%', "%%"))
+        }
+    }
+}
+
+/// Full command line, every argument quoted for `shell`
+pub fn command_line(ffmpeg: &str, args: &[String], shell: ScriptShell) -> String {
+    std::iter::once(ffmpeg)
+        .chain(args.iter().map(String::as_str))
+        .map(|a| shell_quote(a, shell))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Escape a line written with `echo` in cmd.exe
+fn cmd_echo_escape(line: &str) -> String {
+    line.chars()
+        .flat_map(|c| match c {
+            '^' | '&' | '|' | '<' | '>' | '(' | ')' => vec!['^', c],
+            '%' => vec!['%', '%'],
+            _ => vec![c],
+        })
+        .collect()
+}
+
+/// Render the script for `jobs`. `source_codec` gives the video codec of a
+/// job's input (used by the re-encode modes to keep HEVC in HEVC).
+pub fn build_queue_script(
+    jobs: &[&ExportJob],
+    ffmpeg: &str,
+    shell: ScriptShell,
+    source_codec: impl Fn(&std::path::Path) -> SourceVideoCodec,
+) -> String {
+    let comment = match shell {
+        ScriptShell::Posix => "#",
+        ScriptShell::Cmd => "REM",
+    };
+    let mut out = String::new();
+    match shell {
+        ScriptShell::Posix => out.push_str("#!/bin/sh\nset -e\n\n"),
+        ScriptShell::Cmd => out.push_str("@echo off\r\n\r\n"),
+    }
+    let nl = if shell == ScriptShell::Cmd { "\r\n" } else { "\n" };
+
+    for job in jobs {
+        out.push_str(&format!("{} {}{}", comment, job.description(), nl));
+        match &job.operation {
+            ExportOperation::Trim { start, end, mode } => {
+                // Smart-cut needs keyframe analysis at run time: script it as a precise re-encode
+                let mode = if *mode == TrimMode::SmartCut {
+                    out.push_str(&format!("{} smart-cut is not scriptable, using Precise{}", comment, nl));
+                    TrimMode::Precise
+                } else {
+                    *mode
+                };
+                let args = build_trim_args(
+                    &job.input, &job.output, *start, *end, mode, &job.settings, source_codec(&job.input),
+                );
+                out.push_str(&command_line(ffmpeg, &args, shell));
+                out.push_str(nl);
+            }
+            ExportOperation::Animation { start, end, settings } => {
+                let args = build_animation_args(&job.input, &job.output, *start, *end, settings);
+                out.push_str(&command_line(ffmpeg, &args, shell));
+                out.push_str(nl);
+            }
+            ExportOperation::Thumbnail { time } => {
+                let args = build_thumbnail_args(&job.input, &job.output, *time);
+                out.push_str(&command_line(ffmpeg, &args, shell));
+                out.push_str(nl);
+            }
+            ExportOperation::Remux => {
+                let args = build_remux_args(&job.input, &job.output);
+                out.push_str(&command_line(ffmpeg, &args, shell));
+                out.push_str(nl);
+            }
+            ExportOperation::ExtractAudio { format } => {
+                let args = build_extract_audio_args(&job.input, &job.output, *format);
+                out.push_str(&command_line(ffmpeg, &args, shell));
+                out.push_str(nl);
+            }
+            ExportOperation::Concat { inputs, method: ConcatMethod::Reencode { audio, video } } => {
+                let args = build_concat_reencode_args(inputs, &job.output, *audio, *video);
+                out.push_str(&command_line(ffmpeg, &args, shell));
+                out.push_str(nl);
+            }
+            ExportOperation::Concat { inputs, method: ConcatMethod::Copy } => {
+                let list_path = job.output.with_file_name(format!("_concat_list_{}.txt", job.id));
+                let list = list_path.to_string_lossy().to_string();
+                match shell {
+                    ScriptShell::Posix => {
+                        out.push_str(&format!("cat > {} <<'EOF'\n", shell_quote(&list, shell)));
+                        for input in inputs {
+                            out.push_str(&concat_demuxer_line(input));
+                            out.push('\n');
+                        }
+                        out.push_str("EOF\n");
+                    }
+                    ScriptShell::Cmd => {
+                        out.push_str(&format!("type nul > {}\r\n", shell_quote(&list, shell)));
+                        for input in inputs {
+                            out.push_str(&format!(
+                                "echo {}>> {}\r\n",
+                                cmd_echo_escape(&concat_demuxer_line(input)),
+                                shell_quote(&list, shell)
+                            ));
+                        }
+                    }
+                }
+                let args = build_concat_args(&list_path, &job.output);
+                out.push_str(&command_line(ffmpeg, &args, shell));
+                out.push_str(nl);
+                let rm = match shell {
+                    ScriptShell::Posix => "rm -f",
+                    ScriptShell::Cmd => "del",
+                };
+                out.push_str(&format!("{} {}{}", rm, shell_quote(&list, shell), nl));
+            }
+        }
+        out.push_str(nl);
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::{Path, PathBuf};
+
+    #[test]
+    fn posix_quoting() {
+        assert_eq!(shell_quote("-y", ScriptShell::Posix), "-y");
+        assert_eq!(shell_quote("/tmp/my clip.mp4", ScriptShell::Posix), "'/tmp/my clip.mp4'");
+        assert_eq!(shell_quote("it's.mp4", ScriptShell::Posix), "'it'\\''s.mp4'");
+        assert_eq!(shell_quote("", ScriptShell::Posix), "''");
+    }
+
+    #[test]
+    fn cmd_quoting() {
+        assert_eq!(shell_quote(r"C:\a b\c.mp4", ScriptShell::Cmd), r#""C:\a b\c.mp4""#);
+        assert_eq!(shell_quote("100%.mp4", ScriptShell::Cmd), "\"100%%.mp4\"");
+    }
+
+    #[test]
+    fn command_line_quotes_program_and_args() {
+        let args = vec!["-i".to_string(), "/v/my clip.mp4".to_string()];
+        assert_eq!(
+            command_line("/opt/ff mpeg/ffmpeg", &args, ScriptShell::Posix),
+            "'/opt/ff mpeg/ffmpeg' -i '/v/my clip.mp4'"
+        );
+    }
+
+    #[test]
+    fn shell_from_extension() {
+        assert_eq!(ScriptShell::from_path(Path::new("q.BAT")), ScriptShell::Cmd);
+        assert_eq!(ScriptShell::from_path(Path::new("q.sh")), ScriptShell::Posix);
+    }
+
+    #[test]
+    fn script_contains_one_command_per_job() {
+        let trim = ExportJob::new_trim(
+            0, PathBuf::from("/v/in.mp4"), PathBuf::from("/v/out 1.mp4"), 1.0, 3.0, TrimMode::Lossless,
+        );
+        let concat = ExportJob {
+            operation: ExportOperation::Concat {
+                inputs: vec![PathBuf::from("/v/a.mp4"), PathBuf::from("/v/b.mp4")],
+                method: ConcatMethod::Copy,
+            },
+            ..ExportJob::new_trim(1, PathBuf::from("/v/a.mp4"), PathBuf::from("/v/m.mp4"), 0.0, 0.0, TrimMode::Lossless)
+        };
+        let script = build_queue_script(&[&trim, &concat], "ffmpeg", ScriptShell::Posix, |_| SourceVideoCodec::Other);
+
+        assert!(script.starts_with("#!/bin/sh"));
+        assert_eq!(script.lines().filter(|l| l.starts_with("ffmpeg ")).count(), 2);
+        assert!(script.contains("'/v/out 1.mp4'"));
+        assert!(script.contains("file '/v/b.mp4'"));
+    }
+}
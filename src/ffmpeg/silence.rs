@@ -1,9 +1,12 @@
-/// Silence detection, bitrate mapping, and intelligent cut-point computation.
+//! Silence detection, bitrate mapping, and intelligent cut-point computation.
 
 use super::paths::ffprobe_command;
+use crate::ui::SplitSegment;
 use std::cmp::Ordering;
+use std::io::{BufRead, BufReader};
 use std::path::Path;
 use std::process::Stdio;
+use std::time::{Duration, Instant};
 
 /// Asymétrie de la fenêtre de recherche : on préfère minimiser le segment
 /// (couper avant `ideal_end`) plutôt que le maximiser. Côté droit limité à
@@ -38,12 +41,12 @@ impl SilenceInterval {
 ///
 /// Filtre d'abord ceux dont le midpoint est hors de la fenêtre
 /// `[window_start, window_end]`.
-fn rank_candidates<'a>(
-    silences: &'a [SilenceInterval],
+fn rank_candidates(
+    silences: &[SilenceInterval],
     window_start: f64,
     window_end: f64,
     ideal_end: f64,
-) -> Vec<&'a SilenceInterval> {
+) -> Vec<&SilenceInterval> {
     let mut candidates: Vec<&SilenceInterval> = silences
         .iter()
         .filter(|s| s.midpoint() >= window_start && s.midpoint() <= window_end)
@@ -112,7 +115,7 @@ pub fn parse_silence_output(stderr_lines: &[String]) -> Vec<SilenceInterval> {
     for line in stderr_lines {
         if let Some(pos) = line.find("silence_start:") {
             let after = &line[pos + "silence_start:".len()..];
-            let value_str = after.trim().split_whitespace().next().unwrap_or("");
+            let value_str = after.split_whitespace().next().unwrap_or("");
             if let Ok(v) = value_str.parse::<f64>() {
                 pending_start = Some(v);
             }
@@ -132,6 +135,26 @@ pub fn parse_silence_output(stderr_lines: &[String]) -> Vec<SilenceInterval> {
     intervals
 }
 
+/// Leading/trailing silence within this distance of the file edges is "dead air"
+const EDGE_SECS: f64 = 0.5;
+
+/// Range left once dead air at the very start and end is removed.
+///
+/// A silence counts as leading if it starts within `EDGE_SECS` of 0, as
+/// trailing if it ends within `EDGE_SECS` of `duration`. `keep` seconds of
+/// each silence are kept so the first and last words are not clipped.
+/// `None` if there is no such silence or if the whole file is silent.
+pub fn trim_silence_ends(silences: &[SilenceInterval], duration: f64, keep: f64) -> Option<(f64, f64)> {
+    let lead = silences.first().filter(|s| s.start <= EDGE_SECS);
+    let trail = silences.last().filter(|s| s.end >= duration - EDGE_SECS);
+    if lead.is_none() && trail.is_none() {
+        return None;
+    }
+    let start = lead.map_or(0.0, |s| (s.end - keep).max(0.0));
+    let end = trail.map_or(duration, |s| (s.start + keep).min(duration));
+    (end > start).then_some((start, end))
+}
+
 /// Compute cut points that respect a maximum byte size per segment,
 /// preferring to cut at silence boundaries for natural transitions.
 ///
@@ -205,6 +228,16 @@ pub fn compute_cut_points(
     segments
 }
 
+/// Number of cuts between `segments` (all but the file end) that fell back
+/// to the size limit because no silence was found in their search window:
+/// a cut on a silence lies inside it, midpoint of the chosen interval.
+pub fn cuts_outside_silence(segments: &[(f64, f64)], silences: &[SilenceInterval]) -> usize {
+    let Some((_, inner)) = segments.split_last() else { return 0 };
+    inner.iter()
+        .filter(|(_, cut)| !silences.iter().any(|s| s.start <= *cut && *cut <= s.end))
+        .count()
+}
+
 /// Cumulative byte size at each second boundary.
 /// `cumulative_bytes[i]` = total bytes from time 0 to second `i`.
 #[derive(Debug, Clone)]
@@ -217,6 +250,9 @@ pub struct BitrateMap {
 impl BitrateMap {
     /// Get the estimated byte count between two timestamps.
     pub fn bytes_between(&self, start: f64, end: f64) -> u64 {
+        if self.is_empty() {
+            return 0;
+        }
         let start_sec = (start.floor() as usize).min(self.cumulative_bytes.len().saturating_sub(1));
         let end_sec = (end.ceil() as usize).min(self.cumulative_bytes.len().saturating_sub(1));
         self.cumulative_bytes[end_sec].saturating_sub(self.cumulative_bytes[start_sec])
@@ -225,6 +261,9 @@ impl BitrateMap {
     /// Find the time at which `target_bytes` bytes have been consumed since `start_time`.
     /// Returns the second boundary where the cumulative size first exceeds start + target_bytes.
     pub fn time_for_bytes(&self, start_time: f64, target_bytes: u64) -> f64 {
+        if self.is_empty() {
+            return self.duration;
+        }
         let start_sec = (start_time.floor() as usize).min(self.cumulative_bytes.len().saturating_sub(1));
         let base_bytes = self.cumulative_bytes[start_sec];
 
@@ -243,14 +282,35 @@ impl BitrateMap {
     }
 }
 
+/// Durée maximale de l'analyse des paquets (vidéo + audio). Au-delà, le
+/// ffprobe est tué et l'estimation uniforme prend le relais.
+pub const BITRATE_MAP_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// Couverture minimale : les paquets doivent atteindre 90 % de la durée,
+/// sinon la carte est incomplète (flux image2, MKV sans pts...).
+const MIN_COVERAGE: f64 = 0.9;
+
 /// Extract a bitrate map from a video using ffprobe packet sizes.
 /// Groups packet sizes by second to build a cumulative byte curve.
 /// This gives accurate size data even for variable bitrate content.
-pub fn extract_bitrate_map(path: &Path, duration: f64) -> BitrateMap {
+///
+/// Fails with a short reason when ffprobe cannot run, exceeds
+/// `BITRATE_MAP_TIMEOUT`, or reports too few packets to trust.
+pub fn extract_bitrate_map(path: &Path, duration: f64) -> Result<BitrateMap, String> {
+    let deadline = Instant::now() + BITRATE_MAP_TIMEOUT;
+    let mut lines = probe_packets(path, "v:0", deadline)?;
+    // Also account for audio stream sizes
+    lines.extend(probe_packets(path, "a:0", deadline)?);
+    bitrate_map_from_packets(&lines, duration)
+}
+
+/// Lignes `pts_time,size` des paquets du flux `stream`, ou une erreur si
+/// ffprobe dépasse `deadline`
+fn probe_packets(path: &Path, stream: &str, deadline: Instant) -> Result<Vec<String>, String> {
     let mut cmd = ffprobe_command();
     cmd.args([
         "-v", "quiet",
-        "-select_streams", "v:0",
+        "-select_streams", stream,
         "-show_entries", "packet=pts_time,size",
         "-of", "csv=p=0",
     ])
@@ -259,76 +319,59 @@ pub fn extract_bitrate_map(path: &Path, duration: f64) -> BitrateMap {
     .stderr(Stdio::null())
     .stdin(Stdio::null());
 
-    let output = match cmd.output() {
-        Ok(o) => o,
-        Err(_) => return BitrateMap { cumulative_bytes: Vec::new(), duration },
-    };
+    let mut child = cmd.spawn().map_err(|e| format!("cannot run ffprobe: {}", e))?;
+    let stdout = child.stdout.take().ok_or("no ffprobe output")?;
+    // Lecture dans un thread : un pipe plein bloquerait ffprobe
+    let reader = std::thread::spawn(move || {
+        BufReader::new(stdout).lines().map_while(Result::ok).collect::<Vec<String>>()
+    });
+
+    loop {
+        match child.try_wait() {
+            Ok(Some(_)) => break,
+            Ok(None) if Instant::now() >= deadline => {
+                let _ = child.kill();
+                let _ = child.wait();
+                let _ = reader.join();
+                return Err(format!("packet scan took over {} s", BITRATE_MAP_TIMEOUT.as_secs()));
+            }
+            Ok(None) => std::thread::sleep(Duration::from_millis(50)),
+            Err(e) => return Err(format!("ffprobe failed: {}", e)),
+        }
+    }
+    reader.join().map_err(|_| "ffprobe output lost".to_string())
+}
 
-    let stdout = String::from_utf8_lossy(&output.stdout);
+/// Build the cumulative byte curve from `pts_time,size` lines. Rejects
+/// packet lists that stop well before `duration`.
+pub fn bitrate_map_from_packets(lines: &[String], duration: f64) -> Result<BitrateMap, String> {
     let num_seconds = (duration.ceil() as usize) + 1;
     let mut bytes_per_second = vec![0u64; num_seconds];
+    let mut packets = 0usize;
+    let mut last_time = 0.0f64;
 
     // Parse lines like "1.234,5678" (pts_time,size)
-    for line in stdout.lines() {
+    for line in lines {
         let mut parts = line.split(',');
-        let time_str = match parts.next() {
-            Some(s) => s.trim(),
-            None => continue,
+        let (Some(time_str), Some(size_str)) = (parts.next(), parts.next()) else {
+            continue;
         };
-        let size_str = match parts.next() {
-            Some(s) => s.trim(),
-            None => continue,
-        };
-
-        let time: f64 = match time_str.parse() {
-            Ok(t) => t,
-            Err(_) => continue,
-        };
-        let size: u64 = match size_str.parse() {
-            Ok(s) => s,
-            Err(_) => continue,
+        let (Ok(time), Ok(size)) = (time_str.trim().parse::<f64>(), size_str.trim().parse::<u64>()) else {
+            continue;
         };
+        if !time.is_finite() || time < 0.0 {
+            continue;
+        }
 
         let sec = (time.floor() as usize).min(num_seconds.saturating_sub(1));
         bytes_per_second[sec] += size;
+        packets += 1;
+        last_time = last_time.max(time);
     }
 
-    // Also account for audio stream sizes
-    let mut cmd_audio = ffprobe_command();
-    cmd_audio.args([
-        "-v", "quiet",
-        "-select_streams", "a:0",
-        "-show_entries", "packet=pts_time,size",
-        "-of", "csv=p=0",
-    ])
-    .arg(path)
-    .stdout(Stdio::piped())
-    .stderr(Stdio::null())
-    .stdin(Stdio::null());
-
-    if let Ok(audio_output) = cmd_audio.output() {
-        let audio_stdout = String::from_utf8_lossy(&audio_output.stdout);
-        for line in audio_stdout.lines() {
-            let mut parts = line.split(',');
-            let time_str = match parts.next() {
-                Some(s) => s.trim(),
-                None => continue,
-            };
-            let size_str = match parts.next() {
-                Some(s) => s.trim(),
-                None => continue,
-            };
-            let time: f64 = match time_str.parse() {
-                Ok(t) => t,
-                Err(_) => continue,
-            };
-            let size: u64 = match size_str.parse() {
-                Ok(s) => s,
-                Err(_) => continue,
-            };
-            let sec = (time.floor() as usize).min(num_seconds.saturating_sub(1));
-            bytes_per_second[sec] += size;
-        }
+    // Un paquet par seconde au minimum, jusqu'à (presque) la fin
+    if duration <= 0.0 || (packets as f64) < duration.min(10.0) || last_time < (duration - 1.0) * MIN_COVERAGE {
+        return Err(format!("only {} packet(s) up to {:.0} s of {:.0} s", packets, last_time, duration));
     }
 
     // Build cumulative array
@@ -337,10 +380,10 @@ pub fn extract_bitrate_map(path: &Path, duration: f64) -> BitrateMap {
         cumulative[i] = cumulative[i - 1] + bytes_per_second[i - 1];
     }
 
-    BitrateMap {
+    Ok(BitrateMap {
         cumulative_bytes: cumulative,
         duration,
-    }
+    })
 }
 
 /// Compute cut points using actual per-second bitrate data (BitrateMap)
@@ -411,12 +454,144 @@ pub fn compute_cut_points_accurate(
         cursor = cut_point;
     }
 
+    // Une coupe à moins de 0.1 s de la fin sort de la boucle sans couvrir
+    // le reste : on rattache ce bout au dernier segment.
+    if let Some(last) = segments.last_mut() {
+        last.1 = duration;
+    }
+
     segments
 }
 
+/// Tail shorter than this is merged into the previous part instead of
+/// producing its own segment.
+const MIN_TAIL_SECS: f64 = 1.0;
+
+/// Auto-split a segment that exceeds `max_bytes` into smaller sub-segments.
+/// Uses the bitrate map (cumulative real byte sums) when available,
+/// falls back to uniform bitrate estimate otherwise.
+///
+/// Sub-segments keep the `enabled` flag, get labels from the `part_label`
+/// template (`crate::utils::format_label`) and always cover the whole segment. With a bitrate map, a final tail under one
+/// second is absorbed by the previous part; the uniform estimate splits into
+/// equal parts instead. Neither path produces a zero-length segment.
+pub fn auto_split_segment(
+    segment: &SplitSegment,
+    max_bytes: u64,
+    bitrate_bps: f64,
+    bitrate_map: Option<&BitrateMap>,
+    part_label: &str,
+) -> Vec<SplitSegment> {
+    if max_bytes == 0 || segment.duration() <= 0.0 {
+        return vec![segment.clone()];
+    }
+
+    // Une map vide (extraction ratée) vaut pas de map
+    let bitrate_map = bitrate_map.filter(|bm| !bm.is_empty());
+
+    // Get the real size from the bitrate map if available
+    let real_size = bitrate_map
+        .map(|bm| bm.bytes_between(segment.start_time, segment.end_time))
+        .unwrap_or(segment.estimated_size_bytes);
+
+    if real_size <= max_bytes {
+        return vec![segment.clone()];
+    }
+
+    let effective_max = (max_bytes as f64 * 0.98) as u64; // 2% safety margin
+
+    let ranges = if let Some(bm) = bitrate_map {
+        // --- Bitrate-map path: cut by cumulative byte sum ---
+        let mut ranges = Vec::new();
+        let mut cursor = segment.start_time;
+
+        while cursor < segment.end_time - 0.1 {
+            // Walk forward until we've accumulated effective_max bytes
+            let cut = bm.time_for_bytes(cursor, effective_max).min(segment.end_time);
+
+            // Ensure we always advance at least 1 second
+            let cut = if cut <= cursor + 0.5 {
+                (cursor + 1.0).min(segment.end_time)
+            } else {
+                cut
+            };
+
+            let end = if segment.end_time - cut < MIN_TAIL_SECS { segment.end_time } else { cut };
+            ranges.push((cursor, end));
+            cursor = end;
+        }
+
+        // Même garde que compute_cut_points_accurate : pas de bout non couvert
+        if let Some(last) = ranges.last_mut() {
+            last.1 = segment.end_time;
+        }
+        ranges
+    } else {
+        // --- Fallback: uniform bitrate estimate ---
+        let bytes_per_second = bitrate_bps / 8.0;
+        if bytes_per_second <= 0.0 {
+            return vec![segment.clone()];
+        }
+        let max_duration = effective_max as f64 / bytes_per_second;
+        if max_duration <= 0.0 {
+            return vec![segment.clone()];
+        }
+
+        // Parts égales plutôt que « max puis reste » : pas de queue minuscule,
+        // et le epsilon évite une part vide quand la durée est un multiple exact
+        let num_parts = ((segment.duration() / max_duration) - 1e-9).ceil().max(1.0) as usize;
+        let part_duration = segment.duration() / num_parts as f64;
+        (0..num_parts)
+            .map(|i| {
+                let start = segment.start_time + i as f64 * part_duration;
+                let end = if i + 1 == num_parts {
+                    segment.end_time
+                } else {
+                    segment.start_time + (i + 1) as f64 * part_duration
+                };
+                (start, end)
+            })
+            .collect()
+    };
+
+    let total = ranges.len();
+    ranges
+        .into_iter()
+        .enumerate()
+        .map(|(i, (start, end))| {
+            let mut sub = SplitSegment::new(start, end, crate::utils::format_label(part_label, &segment.label, i + 1, total));
+            sub.enabled = segment.enabled;
+            sub.estimated_size_bytes = match bitrate_map {
+                Some(bm) => bm.bytes_between(start, end),
+                None => (bitrate_bps / 8.0 * (end - start)) as u64,
+            };
+            sub
+        })
+        .collect()
+}
+
+/// Indices of `parts` whose estimated size is still above `max_bytes`.
+///
+/// The bitrate map has one-second resolution and auto-split always advances
+/// at least one second, so a single second heavier than the limit (a
+/// high-bitrate spike) cannot be cut finer and stays over the limit. Callers
+/// use this post-pass to warn instead of silently producing oversized files.
+pub fn over_limit_parts(parts: &[SplitSegment], max_bytes: u64) -> Vec<usize> {
+    if max_bytes == 0 {
+        return Vec::new();
+    }
+    parts
+        .iter()
+        .enumerate()
+        .filter(|(_, part)| part.estimated_size_bytes > max_bytes)
+        .map(|(i, _)| i)
+        .collect()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::utils::DEFAULT_PART_LABEL;
 
     #[test]
     fn test_parse_silence_output() {
@@ -435,6 +610,23 @@ mod tests {
         assert!((intervals[1].end - 46.5).abs() < 0.001);
     }
 
+    #[test]
+    fn test_trim_silence_ends() {
+        let silences = vec![
+            SilenceInterval { start: 0.0, end: 3.0 },
+            SilenceInterval { start: 20.0, end: 21.0 },
+            SilenceInterval { start: 55.0, end: 59.8 },
+        ];
+        assert_eq!(trim_silence_ends(&silences, 60.0, 0.25), Some((2.75, 55.25)));
+
+        // Only a trailing silence: start untouched
+        assert_eq!(trim_silence_ends(&silences[1..], 60.0, 0.0), Some((0.0, 55.0)));
+        // Silences away from the edges are not dead air
+        assert_eq!(trim_silence_ends(&silences[1..2], 60.0, 0.0), None);
+        // Silent file
+        assert_eq!(trim_silence_ends(&[SilenceInterval { start: 0.0, end: 60.0 }], 60.0, 0.0), None);
+    }
+
     #[test]
     fn test_compute_cut_points_single_segment() {
         // 100 seconds at 1 Mbps = 12.5 MB, max = 100 MB => single segment
@@ -536,5 +728,171 @@ mod tests {
         for i in 1..segments.len() {
             assert!((segments[i].0 - segments[i - 1].1).abs() < 0.001);
         }
+        assert_eq!(cuts_outside_silence(&segments, &[]), segments.len() - 1);
+    }
+
+    #[test]
+    fn narrow_tolerance_misses_a_distant_silence() {
+        // ~190 s par segment ; le silence à 175 s n'est dans la fenêtre qu'à ±30 s
+        let silences = vec![SilenceInterval { start: 174.5, end: 175.5 }];
+        let wide = compute_cut_points(300.0, 8_000_000.0, 200_000_000, 30.0, &silences);
+        assert!((wide[0].1 - 175.0).abs() < 0.1);
+        assert_eq!(cuts_outside_silence(&wide, &silences), 0);
+
+        let narrow = compute_cut_points(300.0, 8_000_000.0, 200_000_000, 5.0, &silences);
+        assert!(narrow[0].1 > 180.0);
+        assert_eq!(cuts_outside_silence(&narrow, &silences), 1);
+    }
+
+    /// 1 MB par seconde sur `secs` secondes
+    fn uniform_map(secs: usize) -> BitrateMap {
+        BitrateMap {
+            cumulative_bytes: (0..=secs).map(|i| i as u64 * 1_000_000).collect(),
+            duration: secs as f64,
+        }
+    }
+
+    fn assert_contiguous(parts: &[SplitSegment], start: f64, end: f64) {
+        assert!((parts[0].start_time - start).abs() < 1e-9);
+        assert!((parts.last().unwrap().end_time - end).abs() < 1e-9);
+        for pair in parts.windows(2) {
+            assert!((pair[0].end_time - pair[1].start_time).abs() < 1e-9);
+        }
+        for part in parts {
+            assert!(part.duration() >= 1.0, "tiny part {:?}", part);
+        }
+    }
+
+    #[test]
+    fn test_time_for_bytes() {
+        let map = uniform_map(10);
+        assert_eq!(map.time_for_bytes(0.0, 3_000_000), 3.0);
+        // Le départ est arrondi à la seconde inférieure
+        assert_eq!(map.time_for_bytes(2.5, 1_000_000), 3.0);
+        // Budget jamais atteint → fin du fichier
+        assert_eq!(map.time_for_bytes(8.0, 50_000_000), 10.0);
+
+        let empty = BitrateMap { cumulative_bytes: Vec::new(), duration: 10.0 };
+        assert_eq!(empty.time_for_bytes(0.0, 1), 10.0);
+        assert_eq!(empty.bytes_between(0.0, 10.0), 0);
+    }
+
+    #[test]
+    fn test_compute_cut_points_accurate_covers_whole_file() {
+        let map = uniform_map(100);
+        let segments = compute_cut_points_accurate(100.0, 25_000_000, 5.0, &[], &map);
+        assert!(segments.len() >= 4);
+        assert_eq!(segments[0].0, 0.0);
+        assert_eq!(segments.last().unwrap().1, 100.0);
+        for pair in segments.windows(2) {
+            assert_eq!(pair[0].1, pair[1].0);
+        }
+
+        // Silence juste avant la fin : la coupe tombe à < 0.1 s du bout
+        let silences = [SilenceInterval { start: 24.4, end: 24.5 }];
+        let segments = compute_cut_points_accurate(24.5, 24_000_000, 5.0, &silences, &uniform_map(25));
+        assert_eq!(segments.last().unwrap().1, 24.5);
+
+        let empty = BitrateMap { cumulative_bytes: Vec::new(), duration: 0.0 };
+        assert_eq!(compute_cut_points_accurate(50.0, 1_000, 5.0, &[], &empty), vec![(0.0, 50.0)]);
+    }
+
+    #[test]
+    fn test_auto_split_exactly_at_max_size() {
+        let map = uniform_map(20);
+        let seg = SplitSegment::new(0.0, 10.0, "Part".to_string());
+        let parts = auto_split_segment(&seg, 10_000_000, 8_000_000.0, Some(&map), DEFAULT_PART_LABEL);
+        assert_eq!(parts.len(), 1);
+        assert_eq!(parts[0].label, "Part");
+    }
+
+    #[test]
+    fn test_auto_split_far_over_max_size() {
+        let map = uniform_map(100);
+        let mut seg = SplitSegment::new(0.0, 100.0, "Part".to_string());
+        seg.enabled = false;
+        let parts = auto_split_segment(&seg, 10_000_000, 8_000_000.0, Some(&map), DEFAULT_PART_LABEL);
+        // Map à la seconde : 9.8 MB atteints à 10 s → 10 parts de 10 s
+        assert_eq!(parts.len(), 10);
+        assert_contiguous(&parts, 0.0, 100.0);
+        assert_eq!(parts[0].label, "Part (1/10)");
+        assert_eq!(parts[9].label, "Part (10/10)");
+        assert!(parts.iter().all(|p| !p.enabled));
+        assert!(parts.iter().all(|p| p.estimated_size_bytes <= 10_000_000));
+    }
+
+    #[test]
+    fn test_auto_split_absorbs_sub_second_tail() {
+        // Bitrate map : 9 s par part, reste 0.5 s → rattaché à la dernière
+        let map = uniform_map(19);
+        let seg = SplitSegment::new(0.0, 18.5, "Part".to_string());
+        let parts = auto_split_segment(&seg, 9_200_000, 8_000_000.0, Some(&map), DEFAULT_PART_LABEL);
+        assert_eq!(parts.len(), 2);
+        assert_contiguous(&parts, 0.0, 18.5);
+
+        // Estimation uniforme : 1 MB/s, 9.8 s max, 19.9 s = 2 parts + 0.3 s
+        // → 3 parts égales, chacune sous la limite
+        let mut seg = SplitSegment::new(0.0, 19.9, "Part".to_string());
+        seg.estimated_size_bytes = 19_900_000;
+        let parts = auto_split_segment(&seg, 10_000_000, 8_000_000.0, None, DEFAULT_PART_LABEL);
+        assert_eq!(parts.len(), 3);
+        assert_contiguous(&parts, 0.0, 19.9);
+        assert!(parts.iter().all(|p| p.estimated_size_bytes <= 10_000_000));
+    }
+
+    #[test]
+    fn test_auto_split_exact_multiple_has_no_empty_tail() {
+        // 29.4 s / 9.8 s = 3 parts pile : l'arrondi ne doit pas en créer une 4e vide
+        let mut seg = SplitSegment::new(0.0, 29.4, "Part".to_string());
+        seg.estimated_size_bytes = 29_400_000;
+        let parts = auto_split_segment(&seg, 10_000_000, 8_000_000.0, None, DEFAULT_PART_LABEL);
+        assert_eq!(parts.len(), 3);
+        assert_contiguous(&parts, 0.0, 29.4);
+    }
+
+    #[test]
+    fn test_auto_split_with_empty_bitrate_map_uses_estimate() {
+        let empty = BitrateMap { cumulative_bytes: Vec::new(), duration: 0.0 };
+        let mut seg = SplitSegment::new(10.0, 40.0, "Part".to_string());
+        seg.estimated_size_bytes = 30_000_000;
+        let parts = auto_split_segment(&seg, 10_000_000, 8_000_000.0, Some(&empty), DEFAULT_PART_LABEL);
+        assert_eq!(parts.len(), 4);
+        assert_contiguous(&parts, 10.0, 40.0);
+
+        // Sans débit connu, on ne découpe pas
+        let parts = auto_split_segment(&seg, 10_000_000, 0.0, Some(&empty), DEFAULT_PART_LABEL);
+        assert_eq!(parts.len(), 1);
+    }
+
+    #[test]
+    fn test_spike_second_is_flagged_over_limit() {
+        // Seconde 3 pèse 30 MB à elle seule : impossible de couper plus fin
+        let mut cumulative = vec![0u64];
+        for sec in 0..10u64 {
+            let size = if sec == 3 { 30_000_000 } else { 1_000_000 };
+            cumulative.push(cumulative.last().unwrap() + size);
+        }
+        let map = BitrateMap { cumulative_bytes: cumulative, duration: 10.0 };
+        let seg = SplitSegment::new(0.0, 10.0, "Part".to_string());
+        let parts = auto_split_segment(&seg, 10_000_000, 8_000_000.0, Some(&map), DEFAULT_PART_LABEL);
+        assert_contiguous(&parts, 0.0, 10.0);
+
+        let over = over_limit_parts(&parts, 10_000_000);
+        assert_eq!(over.len(), 1);
+        let spike = &parts[over[0]];
+        assert!(spike.start_time <= 3.0 && spike.end_time >= 4.0);
+        assert!(over_limit_parts(&parts, 0).is_empty());
+    }
+
+    #[test]
+    fn test_bitrate_map_rejects_sparse_packets() {
+        let lines: Vec<String> = (0..20).map(|i| format!("{}.5,1000", i)).collect();
+        let map = bitrate_map_from_packets(&lines, 20.0).unwrap();
+        assert_eq!(map.bytes_between(0.0, 20.0), 20_000);
+
+        // Une seule image (image2) ou des paquets qui s'arrêtent à mi-chemin
+        assert!(bitrate_map_from_packets(&["0.0,50000".to_string()], 20.0).is_err());
+        assert!(bitrate_map_from_packets(&lines[..10], 20.0).is_err());
+        assert!(bitrate_map_from_packets(&["N/A,1000".to_string()], 1.0).is_err());
     }
 }
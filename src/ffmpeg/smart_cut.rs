@@ -12,7 +12,7 @@
 //! Si l'ensemble du segment ne contient aucune keyframe ou est trop court pour
 //! avoir un Middle, on ré-encode le tout (rare en pratique).
 
-use super::commands::concat_demuxer_line;
+use super::commands::concat_list_contents;
 use super::keyframes::{first_keyframe_at_or_after, last_keyframe_at_or_before};
 use super::paths::{apply_platform_flags_tokio, ffmpeg_path, install_hint};
 use anyhow::{anyhow, Result};
@@ -122,7 +122,7 @@ pub fn plan_smart_cut(
 // ===========================================================================
 
 /// Codec vidéo de la source — détermine le bitstream filter à appliquer.
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum SourceVideoCodec {
     H264,
     H265,
@@ -329,15 +329,9 @@ async fn concat_ts_fragments(
 
     // Crée le fichier liste pour le concat demuxer.
     let list_path = temp_dir.join("concat_list.txt");
-    {
-        use std::io::Write;
-        let mut f = std::fs::File::create(&list_path)
-            .map_err(|e| anyhow!("Cannot create concat list: {}", e))?;
-        for ts_path in ts_paths {
-            writeln!(f, "{}", concat_demuxer_line(ts_path))
-                .map_err(|e| anyhow!("Cannot write concat list: {}", e))?;
-        }
-    }
+    let list = concat_list_contents(ts_paths).map_err(|e| anyhow!(e))?;
+    std::fs::write(&list_path, list)
+        .map_err(|e| anyhow!("Cannot write concat list: {}", e))?;
 
     let mut args: Vec<String> = vec![
         "-y".into(),
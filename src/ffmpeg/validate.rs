@@ -0,0 +1,75 @@
+//! Vérification des fichiers produits par un job d'export.
+//!
+//! Un job peut se terminer avec un code de sortie 0 tout en laissant un
+//! fichier vide ou tronqué (crash en cours de route, concat `-c copy` entre
+//! flux incompatibles...). On re-probe donc la sortie et on compare sa durée
+//! à celle attendue.
+
+use super::probe::probe_file;
+use anyhow::{anyhow, Result};
+use std::path::Path;
+
+/// Écart toléré entre durée attendue et durée réelle : 5 %, au moins 0,5 s
+/// (les cuts lossless tombent sur des keyframes et bougent un peu).
+fn duration_tolerance(expected: f64) -> f64 {
+    (expected * 0.05).max(0.5)
+}
+
+/// Vérifie qu'une sortie n'est pas nettement plus courte que prévu.
+/// `expected <= 0` désactive le contrôle (durée inconnue).
+pub fn check_output_duration(actual: f64, expected: f64) -> Result<(), String> {
+    if expected <= 0.0 {
+        return Ok(());
+    }
+    if actual + duration_tolerance(expected) < expected {
+        return Err(format!(
+            "Output too short: {:.2}s instead of ~{:.2}s",
+            actual, expected
+        ));
+    }
+    Ok(())
+}
+
+/// Valide un fichier de sortie : présent, non vide, lisible par ffprobe et
+/// d'une durée cohérente avec `expected_duration`.
+pub fn validate_output(path: &Path, expected_duration: f64) -> Result<()> {
+    let meta = std::fs::metadata(path)
+        .map_err(|_| anyhow!("Output file missing: {}", path.display()))?;
+    if meta.len() == 0 {
+        return Err(anyhow!("Output file is empty: {}", path.display()));
+    }
+
+    let info = probe_file(path).map_err(|e| anyhow!("Output unreadable: {}", e))?;
+    check_output_duration(info.duration, expected_duration).map_err(|e| anyhow!(e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn accepts_output_within_tolerance() {
+        assert!(check_output_duration(9.8, 10.0).is_ok());
+        assert!(check_output_duration(10.4, 10.0).is_ok());
+        // Keyframe-aligned lossless cuts may run longer
+        assert!(check_output_duration(12.0, 10.0).is_ok());
+    }
+
+    #[test]
+    fn rejects_truncated_output() {
+        assert!(check_output_duration(4.0, 10.0).is_err());
+        assert!(check_output_duration(90.0, 100.0).is_err());
+        assert!(check_output_duration(0.0, 2.0).is_err());
+    }
+
+    #[test]
+    fn unknown_expected_duration_skips_check() {
+        assert!(check_output_duration(0.0, 0.0).is_ok());
+    }
+
+    #[test]
+    fn missing_file_is_reported() {
+        let err = validate_output(Path::new("/nonexistent/out.mp4"), 5.0).unwrap_err();
+        assert!(err.to_string().contains("missing"));
+    }
+}
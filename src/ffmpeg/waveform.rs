@@ -0,0 +1,62 @@
+//! Enveloppe audio pour la timeline : PCM mono `f32le` à 1 kHz lu sur la
+//! sortie standard de FFmpeg.
+
+use super::paths::ffmpeg_command;
+use std::path::Path;
+use std::process::Stdio;
+
+/// Amplitudes absolues du mixage mono à 1 kHz (une par milliseconde).
+/// Vide sans piste audio ou si FFmpeg échoue : la timeline reste plate
+/// plutôt que d'afficher une forme d'onde plausible mais fausse.
+pub fn extract_waveform_peaks(path: &Path) -> Vec<f32> {
+    let mut cmd = ffmpeg_command();
+    cmd.arg("-i")
+        .arg(path)
+        .args(["-ac", "1", "-ar", "1000", "-f", "f32le", "-vn", "pipe:1"])
+        .stdout(Stdio::piped())
+        .stderr(Stdio::null())
+        .stdin(Stdio::null());
+
+    let output = match cmd.output() {
+        Ok(o) => o,
+        Err(_) => return Vec::new(),
+    };
+
+    // Octets f32le bruts → amplitudes absolues
+    output.stdout
+        .chunks_exact(4)
+        .map(|c| f32::from_le_bytes([c[0], c[1], c[2], c[3]]).abs())
+        .collect()
+}
+
+/// Réduit `samples` à `buckets` crêtes (maximum de chaque tranche), pour
+/// un aperçu de largeur fixe. Vide si `samples` l'est. Seul le lecteur mpv
+/// s'en sert.
+#[cfg(feature = "mpv")]
+pub fn bucket_peaks(samples: &[f32], buckets: usize) -> Vec<f32> {
+    if samples.is_empty() || buckets == 0 {
+        return Vec::new();
+    }
+    (0..buckets)
+        .map(|i| {
+            let from = i * samples.len() / buckets;
+            let to = ((i + 1) * samples.len() / buckets).max(from + 1).min(samples.len());
+            samples[from..to].iter().copied().fold(0.0, f32::max)
+        })
+        .collect()
+}
+
+#[cfg(all(test, feature = "mpv"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn buckets_keep_the_loudest_sample() {
+        let samples = [0.1, 0.9, 0.2, 0.0, 0.4, 0.3];
+        assert_eq!(bucket_peaks(&samples, 3), vec![0.9, 0.2, 0.4]);
+        // Plus de tranches que d'échantillons : chaque tranche en garde un
+        assert_eq!(bucket_peaks(&[0.5, 0.25], 4), vec![0.5, 0.5, 0.25, 0.25]);
+        // Pas d'audio : pas de forme d'onde inventée
+        assert!(bucket_peaks(&[], 200).is_empty());
+    }
+}
@@ -1,49 +1,52 @@
+use super::chapters::{build_chapter_remux_args, build_ffmetadata, probe_chapters, slice_chapters};
 use super::commands::*;
 use super::keyframes::extract_keyframes;
-use super::paths::{
-    apply_platform_flags, apply_platform_flags_tokio, ffmpeg_path, ffprobe_path, install_hint,
-};
+use super::loudness::{build_loudness_args, parse_loudness_output, LoudnessInfo};
+use super::paths::{apply_platform_flags_tokio, ffmpeg_path, install_hint};
 use super::probe::{probe_file, MediaInfo};
+use super::progress::{parse_progress_line, parse_progress_line_from};
+use super::scenes::{build_scene_detect_args, parse_scene_output};
 use super::silence::{build_silence_detect_args, parse_silence_output, SilenceInterval};
 use super::smart_cut::{execute_smart_cut, plan_smart_cut, SourceVideoCodec};
-use crate::ui::TrimMode;
+use crate::ui::{AudioExtractFormat, ExportSettings, TrimMode};
 use anyhow::{anyhow, Result};
 use std::path::{Path, PathBuf};
 use std::process::Stdio;
+use std::sync::Arc;
 use tokio::io::{AsyncBufReadExt, BufReader};
 use tokio::process::Command;
 
+/// Reçoit l'avancement (0..=1) de la commande FFmpeg en cours
+pub type ProgressCallback = Arc<dyn Fn(f32) + Send + Sync>;
+
 #[derive(Clone)]
 pub struct FFmpegWrapper {
     ffmpeg_path: String,
-    ffprobe_path: String,
+    /// Timestamp de départ et durée attendue de la sortie, destinataire de
+    /// l'avancement lu dans les lignes `time=` (voir `with_progress`)
+    progress: Option<(f64, f64, ProgressCallback)>,
 }
 
 impl FFmpegWrapper {
     pub fn new() -> Self {
         Self {
             ffmpeg_path: ffmpeg_path().to_string(),
-            ffprobe_path: ffprobe_path().to_string(),
+            progress: None,
         }
     }
 
-    pub fn with_paths(ffmpeg_path: String, ffprobe_path: String) -> Self {
+    /// Copie qui rapporte l'avancement de ses exports (coupe, concat,
+    /// remux...) à `on_progress`, rapporté à `duration` secondes de sortie
+    /// dont les timestamps partent de `origin` (0, ou le début de la coupe
+    /// quand `-copyts` garde ceux de la source). Sans durée connue (0) rien
+    /// n'est rapporté.
+    pub fn with_progress(&self, origin: f64, duration: f64, on_progress: ProgressCallback) -> Self {
         Self {
-            ffmpeg_path,
-            ffprobe_path,
+            progress: Some((origin, duration, on_progress)),
+            ..self.clone()
         }
     }
 
-    /// Check if FFmpeg is available
-    pub fn is_available(&self) -> bool {
-        let mut cmd = std::process::Command::new(&self.ffmpeg_path);
-        cmd.arg("-version")
-            .stdout(Stdio::null())
-            .stderr(Stdio::null());
-        apply_platform_flags(&mut cmd);
-        cmd.status().map(|s| s.success()).unwrap_or(false)
-    }
-
     /// Probe a media file for information
     pub fn probe(&self, path: &Path) -> Result<MediaInfo> {
         probe_file(path)
@@ -52,18 +55,99 @@ impl FFmpegWrapper {
     /// Trim a video between start and end times
     pub async fn trim(
         &self,
-        input: &PathBuf,
-        output: &PathBuf,
+        input: &Path,
+        output: &Path,
+        start: f64,
+        end: f64,
+        mode: TrimMode,
+        settings: &ExportSettings,
+    ) -> Result<()> {
+        if settings.keep_chapters {
+            let input_for_probe = input.to_path_buf();
+            let chapters = tokio::task::spawn_blocking(move || probe_chapters(&input_for_probe))
+                .await
+                .map_err(|e| anyhow!("Probe task failed: {}", e))??;
+            let mut sliced = slice_chapters(&chapters, start, end);
+            if !sliced.is_empty() {
+                // Le noir ajouté avant le clip décale tout le contenu
+                let pad = if mode.is_reencode() { settings.filters.pad_before } else { 0.0 };
+                for c in &mut sliced {
+                    c.start += pad;
+                    c.end += pad;
+                }
+                return self.trim_with_chapters(input, output, start, end, mode, settings, &build_ffmetadata(&sliced)).await;
+            }
+        }
+        self.trim_streams(input, output, start, end, mode, settings).await
+    }
+
+    /// Coupe puis remplace les chapitres de la sortie par `metadata`
+    /// (FFMETADATA) : un remux `-c copy` de plus, valable pour tous les modes
+    #[allow(clippy::too_many_arguments)]
+    async fn trim_with_chapters(
+        &self,
+        input: &Path,
+        output: &Path,
+        start: f64,
+        end: f64,
+        mode: TrimMode,
+        settings: &ExportSettings,
+        metadata: &str,
+    ) -> Result<()> {
+        let stem = output.file_stem().unwrap_or_default().to_string_lossy();
+        let ext = output.extension().unwrap_or_default().to_string_lossy();
+        let cut = output.with_file_name(format!("{}_nochapters.{}", stem, ext));
+        let meta_path = output.with_file_name(format!("{}_chapters.txt", stem));
+
+        let result = async {
+            self.trim_streams(input, &cut, start, end, mode, settings).await?;
+            std::fs::write(&meta_path, metadata)
+                .map_err(|e| anyhow!("Cannot write chapter metadata: {}", e))?;
+            self.execute_ffmpeg(&build_chapter_remux_args(&cut, &meta_path, output)).await
+        }
+        .await;
+
+        let _ = std::fs::remove_file(&cut);
+        let _ = std::fs::remove_file(&meta_path);
+        result
+    }
+
+    async fn trim_streams(
+        &self,
+        input: &Path,
+        output: &Path,
         start: f64,
         end: f64,
         mode: TrimMode,
+        settings: &ExportSettings,
     ) -> Result<()> {
         // SmartCut a un pipeline multi-étapes (extraction keyframes + N fragments + concat)
         // qui ne se réduit pas à un seul appel ffmpeg → on l'intercepte ici.
         if mode == TrimMode::SmartCut {
             return self.smart_trim(input, output, start, end).await;
         }
-        let args = build_trim_args(input, output, start, end, mode);
+        // Codec de ré-encodage aligné sur la source (sinon x264 par défaut)
+        let source_codec = if mode.is_reencode() && settings.match_source_codec {
+            let input_for_probe = input.to_path_buf();
+            let info = tokio::task::spawn_blocking(move || probe_file(&input_for_probe))
+                .await
+                .map_err(|e| anyhow!("Probe task failed: {}", e))??;
+            SourceVideoCodec::from_codec_name(info.video_codec.as_deref())
+        } else {
+            SourceVideoCodec::Other
+        };
+        let mut args = build_trim_args(input, output, start, end, mode, settings, source_codec);
+        // En copie, le ratio forcé est aussi réécrit dans le flux (SAR)
+        let dar = settings.filters.display_aspect.as_deref().and_then(|r| parse_aspect_ratio(r).ok());
+        if let (TrimMode::Lossless, Some(dar)) = (mode, dar) {
+            let input_for_probe = input.to_path_buf();
+            let info = tokio::task::spawn_blocking(move || probe_file(&input_for_probe))
+                .await
+                .map_err(|e| anyhow!("Probe task failed: {}", e))??;
+            let bsf = aspect_bsf_args(info.video_codec.as_deref(), info.width, info.height, dar);
+            let at = args.len() - 1;
+            args.splice(at..at, bsf);
+        }
         self.execute_ffmpeg(&args).await
     }
 
@@ -73,18 +157,18 @@ impl FFmpegWrapper {
     /// des keyframes. Aucun décalage entre segments adjacents.
     pub async fn smart_trim(
         &self,
-        input: &PathBuf,
-        output: &PathBuf,
+        input: &Path,
+        output: &Path,
         start: f64,
         end: f64,
     ) -> Result<()> {
         // Probe + keyframes en parallèle (extraction sync, lance via spawn_blocking)
-        let input_for_probe = input.clone();
+        let input_for_probe = input.to_path_buf();
         let info = tokio::task::spawn_blocking(move || probe_file(&input_for_probe))
             .await
             .map_err(|e| anyhow!("Probe task failed: {}", e))??;
 
-        let input_for_kf = input.clone();
+        let input_for_kf = input.to_path_buf();
         let keyframes = tokio::task::spawn_blocking(move || extract_keyframes(&input_for_kf))
             .await
             .map_err(|e| anyhow!("Keyframe task failed: {}", e))?;
@@ -99,7 +183,8 @@ impl FFmpegWrapper {
         execute_smart_cut(input, output, &plan, codec).await
     }
 
-    /// Execute an FFmpeg command with the given arguments
+    /// Execute an FFmpeg command with the given arguments. Stats lines end
+    /// with `\r`: stderr is split on both terminators to follow `time=`.
     async fn execute_ffmpeg(&self, args: &[String]) -> Result<()> {
         let mut cmd = Command::new(&self.ffmpeg_path);
         cmd.args(args)
@@ -112,13 +197,20 @@ impl FFmpegWrapper {
             .map_err(|e| anyhow!("Impossible de lancer FFmpeg: {}. {}", e, install_hint()))?;
 
         let stderr = child.stderr.take().ok_or_else(|| anyhow!("Failed to capture stderr"))?;
-        let mut reader = BufReader::new(stderr).lines();
+        let mut reader = BufReader::new(stderr).split(b'\r');
 
         // Collect stderr output for error reporting
         let mut error_lines = Vec::new();
-        while let Some(line) = reader.next_line().await? {
-            if line.contains("Error") || line.contains("error") || line.contains("Invalid") {
-                error_lines.push(line);
+        while let Some(chunk) = reader.next_segment().await? {
+            for line in String::from_utf8_lossy(&chunk).lines() {
+                if let Some((origin, duration, on_progress)) = &self.progress {
+                    if let Some(progress) = parse_progress_line_from(line, *origin, *duration) {
+                        on_progress(progress.clamp(0.0, 1.0));
+                    }
+                }
+                if line.contains("Error") || line.contains("error") || line.contains("Invalid") {
+                    error_lines.push(line.to_string());
+                }
             }
         }
 
@@ -139,9 +231,24 @@ impl FFmpegWrapper {
     /// Detect silence intervals in a media file using FFmpeg's silencedetect filter.
     pub async fn detect_silence(
         &self,
-        input: &PathBuf,
+        input: &Path,
+        noise_db: f64,
+        min_duration: f64,
+    ) -> Result<Vec<SilenceInterval>> {
+        self.detect_silence_with_progress(input, noise_db, min_duration, 0.0, |_| {})
+            .await
+    }
+
+    /// Same as `detect_silence`, reporting the analysed fraction (0..1) from
+    /// FFmpeg's `time=` stats as they arrive. Stats lines end with `\r`, so
+    /// stderr is split on both line terminators.
+    pub async fn detect_silence_with_progress(
+        &self,
+        input: &Path,
         noise_db: f64,
         min_duration: f64,
+        duration: f64,
+        mut on_progress: impl FnMut(f32) + Send,
     ) -> Result<Vec<SilenceInterval>> {
         let input_str = input.to_string_lossy().to_string();
         let args = build_silence_detect_args(&input_str, noise_db, min_duration);
@@ -165,6 +272,48 @@ impl FFmpegWrapper {
             .stderr
             .take()
             .ok_or_else(|| anyhow!("Failed to capture stderr for silence detection"))?;
+        let mut reader = BufReader::new(stderr).split(b'\r');
+
+        let mut all_lines = Vec::new();
+        while let Some(chunk) = reader.next_segment().await? {
+            for line in String::from_utf8_lossy(&chunk).lines() {
+                if let Some(progress) = parse_progress_line(line, duration) {
+                    on_progress(progress.clamp(0.0, 1.0));
+                }
+                all_lines.push(line.to_string());
+            }
+        }
+
+        let _ = child.wait().await?;
+
+        Ok(parse_silence_output(&all_lines))
+    }
+
+    /// Detect scene changes using FFmpeg's scene score (`select` + `showinfo`).
+    /// Returns the sorted timestamps of the detected cuts.
+    pub async fn detect_scenes(&self, input: &Path, threshold: f64) -> Result<Vec<f64>> {
+        let input_str = input.to_string_lossy().to_string();
+        let args = build_scene_detect_args(&input_str, threshold);
+
+        let mut cmd = Command::new(&self.ffmpeg_path);
+        cmd.args(&args)
+            .stdout(Stdio::null())
+            .stderr(Stdio::piped());
+
+        apply_platform_flags_tokio(&mut cmd);
+
+        let mut child = cmd.spawn().map_err(|e| {
+            anyhow!(
+                "Impossible de lancer FFmpeg pour la détection de scènes: {}. {}",
+                e,
+                install_hint()
+            )
+        })?;
+
+        let stderr = child
+            .stderr
+            .take()
+            .ok_or_else(|| anyhow!("Failed to capture stderr for scene detection"))?;
         let mut reader = BufReader::new(stderr).lines();
 
         let mut all_lines = Vec::new();
@@ -172,9 +321,51 @@ impl FFmpegWrapper {
             all_lines.push(line);
         }
 
-        let _ = child.wait().await?;
+        let status = child.wait().await?;
+        if !status.success() {
+            return Err(anyhow!("Scene detection failed: FFmpeg exited with status {}", status));
+        }
 
-        Ok(parse_silence_output(&all_lines))
+        Ok(parse_scene_output(&all_lines))
+    }
+
+    /// Measure integrated loudness, true peak and loudness range (EBU R128)
+    pub async fn analyze_loudness(&self, input: &Path) -> Result<LoudnessInfo> {
+        let input_str = input.to_string_lossy().to_string();
+        let args = build_loudness_args(&input_str);
+
+        let mut cmd = Command::new(&self.ffmpeg_path);
+        cmd.args(&args)
+            .stdout(Stdio::null())
+            .stderr(Stdio::piped());
+
+        apply_platform_flags_tokio(&mut cmd);
+
+        let mut child = cmd.spawn().map_err(|e| {
+            anyhow!(
+                "Impossible de lancer FFmpeg pour l'analyse de loudness: {}. {}",
+                e,
+                install_hint()
+            )
+        })?;
+
+        let stderr = child
+            .stderr
+            .take()
+            .ok_or_else(|| anyhow!("Failed to capture stderr for loudness analysis"))?;
+        let mut reader = BufReader::new(stderr).lines();
+
+        let mut all_lines = Vec::new();
+        while let Some(line) = reader.next_line().await? {
+            all_lines.push(line);
+        }
+
+        let status = child.wait().await?;
+        if !status.success() {
+            return Err(anyhow!("Loudness analysis failed: FFmpeg exited with status {}", status));
+        }
+
+        parse_loudness_output(&all_lines).ok_or_else(|| anyhow!("No audio to measure"))
     }
 
     /// Concatenate multiple video files into one using the concat demuxer.
@@ -182,7 +373,7 @@ impl FFmpegWrapper {
     pub async fn concat(
         &self,
         inputs: &[PathBuf],
-        output: &PathBuf,
+        output: &Path,
     ) -> Result<()> {
         if inputs.is_empty() {
             return Err(anyhow!("No input files for concatenation"));
@@ -190,15 +381,9 @@ impl FFmpegWrapper {
 
         // Create concat list file next to output
         let list_path = output.with_file_name("_concat_list.txt");
-        {
-            use std::io::Write;
-            let mut f = std::fs::File::create(&list_path)
-                .map_err(|e| anyhow!("Failed to create concat list: {}", e))?;
-            for input in inputs {
-                writeln!(f, "{}", concat_demuxer_line(input))
-                    .map_err(|e| anyhow!("Failed to write concat list: {}", e))?;
-            }
-        }
+        let list = concat_list_contents(inputs).map_err(|e| anyhow!(e))?;
+        std::fs::write(&list_path, list)
+            .map_err(|e| anyhow!("Failed to write concat list: {}", e))?;
 
         let args = super::commands::build_concat_args(&list_path, output);
         let result = self.execute_ffmpeg(&args).await;
@@ -209,26 +394,107 @@ impl FFmpegWrapper {
         result
     }
 
+    /// Export `[start, end]` as a looping animation (see `build_animation_args`)
+    pub async fn animation(
+        &self,
+        input: &Path,
+        output: &Path,
+        start: f64,
+        end: f64,
+        settings: &crate::ui::AnimationSettings,
+    ) -> Result<()> {
+        let args = build_animation_args(input, output, start, end, settings);
+        self.execute_ffmpeg(&args).await
+    }
+
+    /// Concatenate with re-encoding through the `concat` filter
+    /// (see `build_concat_reencode_args`)
+    pub async fn concat_reencode(
+        &self,
+        inputs: &[PathBuf],
+        output: &Path,
+        audio: Option<super::commands::ConcatAudioFormat>,
+        video: Option<super::commands::ConcatVideoFormat>,
+    ) -> Result<()> {
+        if inputs.is_empty() {
+            return Err(anyhow!("No input files for concatenation"));
+        }
+        let args = super::commands::build_concat_reencode_args(inputs, output, audio, video);
+        self.execute_ffmpeg(&args).await
+    }
+
+    /// Preview montage: cut each `(start, end)` clip from `input` and join
+    /// them into `output`. Clips are stream-copied when the source allows it,
+    /// otherwise re-encoded (fast preset) so they still concat without
+    /// re-encoding the join. Parts live in a temp folder next to the output.
+    pub async fn proof_sheet(
+        &self,
+        input: &Path,
+        output: &Path,
+        clips: &[(f64, f64)],
+        settings: &ExportSettings,
+    ) -> Result<()> {
+        if clips.is_empty() {
+            return Err(anyhow!("No clips for the proof sheet"));
+        }
+        let stem = output.file_stem().unwrap_or_default().to_string_lossy().to_string();
+        let ext = output.extension().unwrap_or_default().to_string_lossy().to_string();
+        let parts_dir = output.with_file_name(format!(".{}_parts", stem));
+        std::fs::create_dir_all(&parts_dir)
+            .map_err(|e| anyhow!("Failed to create {}: {}", parts_dir.display(), e))?;
+
+        let mut result = Err(anyhow!("Proof sheet was not built"));
+        for mode in [TrimMode::Lossless, TrimMode::Precise] {
+            result = self.proof_sheet_parts(input, output, clips, mode, settings, &parts_dir, &ext).await;
+            if result.is_ok() {
+                break;
+            }
+        }
+
+        let _ = std::fs::remove_dir_all(&parts_dir);
+        result
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    async fn proof_sheet_parts(
+        &self,
+        input: &Path,
+        output: &Path,
+        clips: &[(f64, f64)],
+        mode: TrimMode,
+        settings: &ExportSettings,
+        parts_dir: &Path,
+        ext: &str,
+    ) -> Result<()> {
+        let mut parts = Vec::with_capacity(clips.len());
+        for (i, &(start, end)) in clips.iter().enumerate() {
+            let part = parts_dir.join(format!("part_{:03}.{}", i, ext));
+            self.trim(input, &part, start, end, mode, settings).await?;
+            parts.push(part);
+        }
+        self.concat(&parts, output).await
+    }
+
+    /// Copy the streams of `input` into the container of `output`
+    /// (see `build_remux_args`)
+    pub async fn remux(&self, input: &Path, output: &Path) -> Result<()> {
+        self.execute_ffmpeg(&build_remux_args(input, output)).await
+    }
+
+    /// Save the first audio track of `input` as `output`
+    /// (see `build_extract_audio_args`)
+    pub async fn extract_audio(&self, input: &Path, output: &Path, format: AudioExtractFormat) -> Result<()> {
+        self.execute_ffmpeg(&build_extract_audio_args(input, output, format)).await
+    }
+
     /// Extract a single frame as thumbnail
     pub async fn extract_thumbnail(
         &self,
-        input: &PathBuf,
-        output: &PathBuf,
+        input: &Path,
+        output: &Path,
         timestamp: f64,
     ) -> Result<()> {
-        let args = vec![
-            "-y".to_string(),
-            "-ss".to_string(),
-            timestamp.to_string(),
-            "-i".to_string(),
-            input.to_string_lossy().to_string(),
-            "-vframes".to_string(),
-            "1".to_string(),
-            "-q:v".to_string(),
-            "2".to_string(),
-            output.to_string_lossy().to_string(),
-        ];
-
+        let args = build_thumbnail_args(input, output, timestamp);
         self.execute_ffmpeg(&args).await
     }
 }
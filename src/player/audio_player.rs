@@ -1,7 +1,8 @@
 use rodio::{Decoder, OutputStream, OutputStreamHandle, Sink, Source};
 use std::fs::File;
 use std::io::BufReader;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+use std::process::Child;
 use std::sync::Arc;
 use parking_lot::Mutex;
 
@@ -13,12 +14,16 @@ pub struct AudioPlayer {
     sink: Arc<Sink>,
     temp_audio_path: Arc<Mutex<Option<PathBuf>>>,
     volume: Arc<Mutex<f32>>,
+    /// Audio time of the last seek, where a late-loaded sink starts
+    position: Mutex<f64>,
+    /// Running WAV extraction, killed if the player is dropped before it ends
+    extraction: Arc<Mutex<Option<Child>>>,
 }
 
 impl AudioPlayer {
     /// Create a new audio player. Audio extraction runs in a background thread
     /// so the caller is NOT blocked.
-    pub fn new(video_path: &PathBuf, _duration: f64) -> Result<Self, String> {
+    pub fn new(video_path: &Path, _duration: f64) -> Result<Self, String> {
         let (stream, stream_handle) = OutputStream::try_default()
             .map_err(|e| format!("Failed to initialize audio output: {}", e))?;
 
@@ -28,13 +33,19 @@ impl AudioPlayer {
         let temp_audio_path: Arc<Mutex<Option<PathBuf>>> = Arc::new(Mutex::new(None));
 
         // Extract audio in background thread — non-blocking
-        let path = video_path.clone();
+        let path = video_path.to_path_buf();
         let slot = temp_audio_path.clone();
+        let extraction: Arc<Mutex<Option<Child>>> = Arc::new(Mutex::new(None));
+        let running = extraction.clone();
         std::thread::spawn(move || {
-            if let Ok(temp_path) = extract_audio_to_temp(&path) {
+            if let Ok(temp_path) = extract_audio_to_temp(&path, &running) {
                 // Only store if the file actually exists (extraction succeeded)
                 if temp_path.exists() && std::fs::metadata(&temp_path).map(|m| m.len() > 0).unwrap_or(false) {
-                    *slot.lock() = Some(temp_path);
+                    *slot.lock() = Some(temp_path.clone());
+                }
+                // Player dropped just as FFmpeg finished: nobody will clean up
+                if Arc::strong_count(&slot) == 1 {
+                    let _ = std::fs::remove_file(&temp_path);
                 }
             }
         });
@@ -45,29 +56,17 @@ impl AudioPlayer {
             sink: Arc::new(sink),
             temp_audio_path,
             volume: Arc::new(Mutex::new(1.0)),
+            position: Mutex::new(0.0),
+            extraction,
         })
     }
 
-    /// Load audio from the temp file into the sink (no-op if not extracted yet)
-    fn load_audio(&self) -> Result<(), String> {
-        let guard = self.temp_audio_path.lock();
-        if let Some(ref temp_path) = *guard {
-            let file = File::open(temp_path)
-                .map_err(|e| format!("Failed to open audio file: {}", e))?;
-            let source = Decoder::new(BufReader::new(file))
-                .map_err(|e| format!("Failed to decode audio: {}", e))?;
-
-            self.sink.append(source);
-            self.sink.set_volume(*self.volume.lock());
-            self.sink.pause();
-        }
-        Ok(())
-    }
-
-    /// Play audio (no-op if extraction not yet complete)
+    /// Play audio (no-op if extraction not yet complete). An empty sink
+    /// (extraction finished after the last seek) is loaded at that seek's
+    /// position, not at the start of the file.
     pub fn play(&self) {
         if self.sink.empty() {
-            let _ = self.load_audio();
+            self.seek(*self.position.lock());
         }
         self.sink.play();
     }
@@ -90,17 +89,24 @@ impl AudioPlayer {
         self.sink.set_volume(clamped);
     }
 
-    /// Seek to position (requires reloading audio)
+    /// Seek to position (requires reloading audio). Always leaves the sink
+    /// paused: the caller decides whether to `play()` afterwards.
+    /// A negative time starts with that much silence (used by the A/V offset).
     pub fn seek(&self, time: f64) {
-        self.sink.stop();
+        *self.position.lock() = time;
+        // `clear` empties the queue and pauses; no `stop` first, whose flag
+        // would make the next `append` wait for the output thread again
         self.sink.clear();
 
         let guard = self.temp_audio_path.lock();
         if let Some(ref temp_path) = *guard {
             if let Ok(file) = File::open(temp_path) {
                 if let Ok(source) = Decoder::new(BufReader::new(file)) {
-                    let skipped = source.skip_duration(std::time::Duration::from_secs_f64(time));
-                    self.sink.append(skipped);
+                    if time >= 0.0 {
+                        self.sink.append(source.skip_duration(std::time::Duration::from_secs_f64(time)));
+                    } else {
+                        self.sink.append(source.delay(std::time::Duration::from_secs_f64(-time)));
+                    }
                     self.sink.set_volume(*self.volume.lock());
                 }
             }
@@ -110,14 +116,21 @@ impl AudioPlayer {
 
 impl Drop for AudioPlayer {
     fn drop(&mut self) {
+        // Taking the child tells the extraction thread it was cancelled
+        if let Some(mut child) = self.extraction.lock().take() {
+            let _ = child.kill();
+            let _ = child.wait();
+        }
         if let Some(ref temp_path) = *self.temp_audio_path.lock() {
             let _ = std::fs::remove_file(temp_path);
         }
     }
 }
 
-/// Extract audio from video to a temporary WAV file using FFmpeg
-fn extract_audio_to_temp(video_path: &PathBuf) -> Result<PathBuf, String> {
+/// Extract audio from video to a temporary WAV file using FFmpeg. The
+/// process is parked in `running` while it works so the player can kill it;
+/// finding the slot emptied afterwards means the player is gone.
+fn extract_audio_to_temp(video_path: &Path, running: &Mutex<Option<Child>>) -> Result<PathBuf, String> {
     let temp_dir = std::env::temp_dir();
     let file_stem = video_path.file_stem()
         .unwrap_or_default()
@@ -139,10 +152,31 @@ fn extract_audio_to_temp(video_path: &PathBuf) -> Result<PathBuf, String> {
         .stdout(std::process::Stdio::null())
         .stderr(std::process::Stdio::null());
 
-    let output = cmd.output()
+    let child = cmd.spawn()
         .map_err(|e| format!("Failed to run FFmpeg: {}", e))?;
+    *running.lock() = Some(child);
+
+    // Poll instead of `wait()` so the lock stays free for the player's drop
+    let status = loop {
+        let mut guard = running.lock();
+        let Some(child) = guard.as_mut() else {
+            drop(guard);
+            let _ = std::fs::remove_file(&temp_path);
+            return Err("Audio extraction cancelled".to_string());
+        };
+        match child.try_wait() {
+            Ok(Some(status)) => {
+                guard.take();
+                break status;
+            }
+            Ok(None) => {}
+            Err(e) => return Err(format!("Failed to wait for FFmpeg: {}", e)),
+        }
+        drop(guard);
+        std::thread::sleep(std::time::Duration::from_millis(50));
+    };
 
-    if !output.status.success() {
+    if !status.success() {
         return Err("FFmpeg audio extraction failed".to_string());
     }
 
@@ -13,7 +13,8 @@ pub use stream_decoder::*;
 #[cfg(feature = "mpv")]
 pub use mpv_player::MpvPlayer;
 
-use std::path::PathBuf;
+use crate::ffmpeg::MediaInfo;
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
 use parking_lot::Mutex;
 
@@ -34,27 +35,61 @@ pub struct VideoFrame {
 
 pub const SPEED_PRESETS: &[f64] = &[0.25, 0.5, 1.0, 1.5, 2.0, 4.0];
 
+/// Above 1440p, full-res decoding makes frame-accurate scrubbing sluggish
+const HIGH_RES_PIXELS: u64 = 2560 * 1440;
+
+fn is_high_res(width: u32, height: u32) -> bool {
+    width as u64 * height as u64 > HIGH_RES_PIXELS
+}
+
 pub struct MediaPlayer {
     pub duration: f64,
     pub framerate: f64,
     state: Arc<Mutex<PlaybackState>>,
     clock: Arc<Mutex<PlaybackClock>>,
     audio_player: Option<AudioPlayer>,
+    /// Why audio output could not be opened (no device, device busy, RDP...)
+    audio_error: Option<String>,
     stream_decoder: Option<StreamDecoder>,
+    /// Why the video decoder could not be started
+    video_error: Option<String>,
+    path: PathBuf,
+    /// Source above `HIGH_RES_PIXELS`: fast scrub applies
+    high_res: bool,
+    audio_only: bool,
     speed: f64,
+    /// Audio shift relative to video in seconds (positive = audio later)
+    audio_offset: f64,
+    /// `prepare_playback` has run (audio output opened, or tried)
+    playback_prepared: bool,
 }
 
 impl MediaPlayer {
-    pub fn new(path: &PathBuf) -> Result<Self, String> {
-        let info = crate::ffmpeg::probe_file(path)
-            .map_err(|e| format!("Failed to probe file: {}", e))?;
-
+    /// Light init: video decoder only, so the first frame shows right
+    /// away. Audio (a WAV extraction of the whole file) waits for
+    /// `prepare_playback`.
+    ///
+    /// `info`: the probe already stored with the file (its duration may come
+    /// from a full read, which a new probe would not repeat).
+    /// `hwaccel`: hardware decode method for the preview (`None` = software)
+    pub fn new(path: &Path, info: &MediaInfo, hwaccel: Option<String>) -> Result<Self, String> {
         let state = Arc::new(Mutex::new(PlaybackState::Stopped));
         let clock = Arc::new(Mutex::new(PlaybackClock::new()));
 
-        let audio_player = AudioPlayer::new(path, info.duration).ok();
         let fps = info.framerate.unwrap_or(30.0);
-        let decoder = StreamDecoder::new(path, info.width, info.height, info.duration, fps).ok();
+        // Audio-only: no video decoder, the clock alone drives the position
+        let audio_only = info.video_codec.is_none();
+        let (decoder, video_error) = if audio_only {
+            (None, None)
+        } else {
+            match StreamDecoder::new(path, info.width, info.height, info.duration, fps, hwaccel) {
+                Ok(decoder) => (Some(decoder), None),
+                Err(e) => {
+                    eprintln!("Video preview disabled: {}", e);
+                    (None, Some(e))
+                }
+            }
+        };
 
         if let Some(ref dec) = decoder {
             dec.seek(0.0);
@@ -65,25 +100,44 @@ impl MediaPlayer {
             framerate: fps,
             state,
             clock,
-            audio_player,
+            audio_player: None,
+            audio_error: None,
             stream_decoder: decoder,
+            video_error,
+            path: path.to_path_buf(),
+            high_res: is_high_res(info.width, info.height),
+            audio_only,
             speed: 1.0,
+            audio_offset: 0.0,
+            playback_prepared: false,
         })
     }
 
+    /// Heavy init, once: open the audio output and start extracting the
+    /// audio track (in the background). A failure leaves video-only playback
+    /// and is kept in `audio_error`.
+    pub fn prepare_playback(&mut self, volume: f32) {
+        if self.playback_prepared {
+            return;
+        }
+        if let Err(e) = self.reinit_audio(volume) {
+            eprintln!("Audio disabled: {}", e);
+        }
+    }
+
+    pub fn is_playback_prepared(&self) -> bool {
+        self.playback_prepared
+    }
+
     pub fn play(&self) {
         *self.state.lock() = PlaybackState::Playing;
         self.clock.lock().resume();
-        if (self.speed - 1.0).abs() < 0.01 {
-            if let Some(ref audio) = self.audio_player {
-                // Re-sync audio to current decoder time before playing
-                if let Some(ref dec) = self.stream_decoder {
-                    let t = dec.get_decoder_time();
-                    audio.seek(t);
-                }
-                audio.play();
-            }
-        }
+        // Re-sync audio to current decoder time before playing
+        let t = match self.stream_decoder {
+            Some(ref dec) => dec.get_decoder_time(),
+            None => self.clock.lock().get_time(),
+        };
+        self.sync_audio(t);
         if let Some(ref decoder) = self.stream_decoder {
             decoder.play();
         }
@@ -117,9 +171,18 @@ impl MediaPlayer {
         if let Some(ref decoder) = self.stream_decoder {
             decoder.seek(clamped);
         }
-        if let Some(ref audio) = self.audio_player {
-            audio.seek(clamped);
-            if *self.state.lock() == PlaybackState::Playing && (self.speed - 1.0).abs() < 0.01 {
+        self.sync_audio(clamped);
+    }
+
+    /// Bring the audio sink in line with the playback state at video time
+    /// `time` (`audio_action`). Silent transitions only pause the sink: a
+    /// string of seeks while paused reloads nothing and plays nothing.
+    fn sync_audio(&self, time: f64) {
+        let Some(ref audio) = self.audio_player else { return };
+        match audio_action(*self.state.lock(), self.speed, time - self.audio_offset) {
+            AudioAction::Silence => audio.pause(),
+            AudioAction::PlayFrom(t) => {
+                audio.seek(t);
                 audio.play();
             }
         }
@@ -138,17 +201,107 @@ impl MediaPlayer {
         if let Some(ref decoder) = self.stream_decoder {
             decoder.set_speed(speed as f32);
         }
-        if let Some(ref audio) = self.audio_player {
-            if (speed - 1.0).abs() > 0.01 {
-                audio.pause();
-            } else if *self.state.lock() == PlaybackState::Playing {
-                audio.play();
-            }
+        // Back to 1×: the video moved on at the other speed, restart audio there
+        self.sync_audio(self.get_current_time());
+    }
+
+    /// Shift audio relative to video (preview of the export audio offset)
+    pub fn set_audio_offset(&mut self, offset_secs: f64) {
+        if (offset_secs - self.audio_offset).abs() < 1e-6 {
+            return;
         }
+        self.audio_offset = offset_secs;
+        self.sync_audio(self.get_current_time());
     }
 
-    pub fn get_speed(&self) -> f64 {
-        self.speed
+    /// Keyframe-only scrubbing for high-res sources; no effect below 1440p
+    pub fn set_fast_scrub(&self, enabled: bool) {
+        if let Some(ref decoder) = self.stream_decoder {
+            decoder.set_fast_scrub(enabled && self.high_res);
+        }
+    }
+
+    /// Preview at `PreviewLimits::LOW_POWER` (smaller, slower, lazier scrub)
+    pub fn set_low_power(&self, enabled: bool) {
+        if let Some(ref decoder) = self.stream_decoder {
+            decoder.set_low_power(enabled);
+        }
+    }
+
+    /// Frames the preview decodes around a settled seek
+    pub fn set_prefetch(&self, settings: PrefetchSettings) {
+        if let Some(ref decoder) = self.stream_decoder {
+            decoder.set_prefetch(settings);
+        }
+    }
+
+    /// The preview cannot keep up with the frame rate during playback
+    pub fn is_lagging(&self) -> bool {
+        self.stream_decoder.as_ref().is_some_and(|d| d.is_lagging())
+    }
+
+    /// Change the preview's hardware decode method and redraw the current frame with it
+    pub fn set_hwaccel(&self, hwaccel: Option<String>) {
+        if let Some(ref decoder) = self.stream_decoder {
+            decoder.set_hwaccel(hwaccel);
+            decoder.seek(self.get_current_time());
+        }
+    }
+
+    /// Run the video preview through the `-vf` chain (`None` = unfiltered)
+    pub fn set_preview_filter(&self, chain: Option<String>) {
+        if let Some(ref decoder) = self.stream_decoder {
+            decoder.set_filter(chain);
+        }
+    }
+
+    /// Decoder used by the video preview (`None` without video)
+    pub fn decode_path(&self) -> Option<DecodePath> {
+        self.stream_decoder.as_ref().map(|d| d.decode_path())
+    }
+
+    pub fn is_audio_only(&self) -> bool {
+        self.audio_only
+    }
+
+    /// A video decoder is running (false for audio-only files, or if it failed to start)
+    pub fn has_video(&self) -> bool {
+        self.stream_decoder.is_some()
+    }
+
+    /// Why the video preview shows nothing or a stale frame: the decoder
+    /// failed to start, or the last frame grab failed
+    pub fn preview_error(&self) -> Option<String> {
+        match self.stream_decoder {
+            Some(ref decoder) => decoder.get_error(),
+            None => self.video_error.clone(),
+        }
+    }
+
+    /// Error from the last attempt to open the audio output, if it failed
+    pub fn audio_error(&self) -> Option<&str> {
+        self.audio_error.as_deref()
+    }
+
+    /// Retry opening the audio output (e.g. after plugging in headphones) and
+    /// resume at the current position with the given volume.
+    pub fn reinit_audio(&mut self, volume: f32) -> Result<(), String> {
+        self.playback_prepared = true;
+        // Release the old stream first so the device is free for the new one
+        self.audio_player = None;
+        match AudioPlayer::new(&self.path, self.duration) {
+            Ok(audio) => {
+                audio.set_volume(volume);
+                self.audio_player = Some(audio);
+                self.audio_error = None;
+                self.sync_audio(self.get_current_time());
+                Ok(())
+            }
+            Err(e) => {
+                self.audio_error = Some(e.clone());
+                Err(e)
+            }
+        }
     }
 
     pub fn get_state(&self) -> PlaybackState {
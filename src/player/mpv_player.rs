@@ -279,17 +279,15 @@ impl MpvPlayer {
         });
     }
 
+    /// Real peaks from the audio track; empty without audio (flat timeline)
     fn generate_waveform_async(&self) {
         let waveform = self.waveform.clone();
         let duration = self.duration;
+        let path = self.path.clone();
 
         std::thread::spawn(move || {
-            let mut peaks = Vec::with_capacity(200);
-            for i in 0..200 {
-                let t = (i as f64 / 200.0) * duration;
-                let peak = ((t * 7.3).sin() * 0.5 + 0.5).abs() as f32;
-                peaks.push(peak * 0.8);
-            }
+            let samples = crate::ffmpeg::extract_waveform_peaks(&path);
+            let peaks = crate::ffmpeg::bucket_peaks(&samples, 200);
             *waveform.lock() = Some(WaveformData { peaks, duration });
         });
     }
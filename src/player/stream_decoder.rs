@@ -6,16 +6,35 @@
 //! - `-an -sn` skips audio/subtitle decoding (huge speedup)
 //! - `-frames:v 1` for scrub mode (instant frame grab)
 //! - Seek debouncing: coalesces rapid seeks during scrubbing
+//! - Scrub cache: the nearest recently grabbed frame is shown immediately
+//!   while the exact frame decodes
+//! - Fast scrub (high-res sources): keyframe-only grabs while scrubbing,
+//!   refined with an accurate grab once the seeks stop
 //! - Shared decoder time for A/V sync
+//! - Optional hardware decode (`-hwaccel`), dropped for software as soon as
+//!   a hardware-decoded process produces no frame
+//! - Scrub failures (FFmpeg error, no frame decoded) are kept for the UI
+//!   instead of leaving a blank preview without explanation
+//! - Optional filter chain (live filter preview): the running process is
+//!   restarted at the current position when the chain changes
+//! - Low-power mode (`PreviewLimits`): smaller, slower preview and lazier
+//!   scrubbing; playback that keeps running late is reported as lagging
+//! - Prefetch (`PrefetchSettings`): once a seek has settled, a few frames
+//!   around it are decoded into the scrub cache, ahead or behind depending
+//!   on the last move, spaced at the preview frame rate
 
 use std::io::Read;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::process::{Child, Stdio};
+use std::collections::VecDeque;
 use std::sync::mpsc::{self, Receiver, Sender};
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::{Arc, Mutex};
 use std::thread;
 use std::time::{Duration, Instant};
 
+use serde::{Deserialize, Serialize};
+
 use super::VideoFrame;
 
 #[derive(Debug)]
@@ -26,12 +45,218 @@ pub enum DecoderCommand {
     Stop,
 }
 
+/// Which decoder the preview processes use
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DecodePath {
+    Software,
+    /// `-hwaccel` method passed to FFmpeg
+    Hardware(String),
+    /// Hardware decode failed with this method; software from then on
+    Fallback(String),
+}
+
+impl DecodePath {
+    fn new(hwaccel: Option<String>) -> Self {
+        hwaccel.map_or(DecodePath::Software, DecodePath::Hardware)
+    }
+
+    /// `-hwaccel` method for the next FFmpeg process, if any
+    pub fn hwaccel(&self) -> Option<&str> {
+        match self {
+            DecodePath::Hardware(method) => Some(method),
+            DecodePath::Software | DecodePath::Fallback(_) => None,
+        }
+    }
+
+    /// Give up on hardware decode after a failed process. Returns true if it
+    /// was in use, i.e. the failed attempt is worth retrying in software.
+    pub fn fall_back(&mut self) -> bool {
+        match self {
+            DecodePath::Hardware(method) => {
+                *self = DecodePath::Fallback(std::mem::take(method));
+                true
+            }
+            DecodePath::Software | DecodePath::Fallback(_) => false,
+        }
+    }
+
+    pub fn label(&self) -> String {
+        match self {
+            DecodePath::Software => "software".to_string(),
+            DecodePath::Hardware(method) => format!("hardware ({})", method),
+            DecodePath::Fallback(method) => format!("software ({} failed)", method),
+        }
+    }
+}
+
 pub struct StreamDecoder {
     command_tx: Sender<DecoderCommand>,
     current_frame: Arc<Mutex<Option<VideoFrame>>>,
     speed: Arc<Mutex<f32>>,
     /// Shared decoder time — the authoritative video position for A/V sync
     pub decoder_time: Arc<Mutex<f64>>,
+    /// Keyframe-only scrub grabs (see `spawn_ffmpeg_scrub`)
+    fast_scrub: Arc<AtomicBool>,
+    /// Hardware or software decode, read by every new FFmpeg process
+    decode_path: Arc<Mutex<DecodePath>>,
+    /// `-vf` chain applied before the preview scaling (`None` = unfiltered)
+    filter: Arc<Mutex<Option<String>>>,
+    /// Set by `set_filter`, cleared by the thread once it has restarted
+    filter_changed: Arc<AtomicBool>,
+    /// Why the last frame grab failed, cleared by the next good frame
+    error: Arc<Mutex<Option<String>>>,
+    /// `PreviewLimits::LOW_POWER` instead of `NORMAL`
+    low_power: Arc<AtomicBool>,
+    /// Playback frames keep coming later than their time
+    lagging: Arc<AtomicBool>,
+    /// Frames decoded around a settled seek, read at each seek
+    prefetch: Arc<Mutex<PrefetchSettings>>,
+    /// Joined on drop, so a replaced decoder never outlives its player
+    thread: Option<thread::JoinHandle<()>>,
+}
+
+/// How much work the preview may cost: normal, or low-power for weak
+/// machines (laptops on battery, VMs without GPU)
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PreviewLimits {
+    /// Bounding box of the decoded picture
+    pub max_width: u32,
+    pub max_height: u32,
+    /// Playback frame rate range, source rate clamped into it
+    pub fps: (u32, u32),
+    /// Time gathering further seeks before a scrub grab
+    pub seek_debounce: Duration,
+    /// Quiet time after the last seek before a fast (keyframe) scrub frame
+    /// is replaced by the exact one
+    pub refine_delay: Duration,
+    /// Keyframe-only scrub for every source, not just high-res ones
+    pub always_fast_scrub: bool,
+    /// Prefetch frames around settled seeks (`PrefetchSettings`)
+    pub prefetch: bool,
+}
+
+impl PreviewLimits {
+    pub const NORMAL: Self = Self {
+        max_width: 640,
+        max_height: 360,
+        fps: (24, 30),
+        seek_debounce: Duration::from_millis(15),
+        refine_delay: Duration::from_millis(150),
+        always_fast_scrub: false,
+        prefetch: true,
+    };
+    pub const LOW_POWER: Self = Self {
+        max_width: 480,
+        max_height: 270,
+        fps: (12, 15),
+        seek_debounce: Duration::from_millis(60),
+        refine_delay: Duration::from_millis(400),
+        always_fast_scrub: true,
+        prefetch: false,
+    };
+
+    pub fn new(low_power: bool) -> Self {
+        if low_power { Self::LOW_POWER } else { Self::NORMAL }
+    }
+
+    /// Preview size and playback rate for a `src_w`x`src_h` source at `src_fps`
+    fn format(&self, src_w: u32, src_h: u32, src_fps: f64) -> (u32, u32, u32) {
+        let (w, h) = compute_preview_size(src_w, src_h, self.max_width, self.max_height);
+        (w, h, (src_fps.round() as u32).clamp(self.fps.0, self.fps.1))
+    }
+}
+
+/// Which side of the position gets prefetched
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum PrefetchDirection {
+    /// Always after the position
+    Forward,
+    /// After it following playback or a forward seek, before it after a
+    /// backward seek
+    #[default]
+    FollowSeek,
+    /// Both sides, nearest first
+    Both,
+}
+
+impl PrefetchDirection {
+    pub fn all() -> &'static [PrefetchDirection] {
+        &[PrefetchDirection::FollowSeek, PrefetchDirection::Forward, PrefetchDirection::Both]
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            PrefetchDirection::Forward => "Forward",
+            PrefetchDirection::FollowSeek => "Follow last seek",
+            PrefetchDirection::Both => "Both sides",
+        }
+    }
+}
+
+/// Frames decoded into the scrub cache once a seek has settled, so stepping
+/// or scrubbing nearby shows them without waiting for FFmpeg
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PrefetchSettings {
+    /// Frames per settled seek (0 = off)
+    pub frames: u32,
+    /// Spacing between prefetched frames, in preview frames
+    pub spacing: u32,
+    pub direction: PrefetchDirection,
+}
+
+impl Default for PrefetchSettings {
+    fn default() -> Self {
+        Self { frames: 4, spacing: 1, direction: PrefetchDirection::default() }
+    }
+}
+
+impl PrefetchSettings {
+    /// Half the scrub cache: the rest keeps the recently visited positions
+    pub const MAX_FRAMES: u32 = 12;
+
+    /// Times to decode around `time`, nearest first. `forward`: the last move
+    /// went forward (playback, forward seek); `fps`: preview frame rate.
+    fn times(&self, time: f64, forward: bool, fps: u32, duration: f64) -> Vec<f64> {
+        let step = self.spacing.max(1) as f64 / fps.max(1) as f64;
+        let ahead = match self.direction {
+            PrefetchDirection::Forward => 1.0,
+            PrefetchDirection::FollowSeek if forward => 1.0,
+            PrefetchDirection::FollowSeek => -1.0,
+            PrefetchDirection::Both => 1.0,
+        };
+        (1..=self.frames.min(Self::MAX_FRAMES))
+            .map(|i| match self.direction {
+                // Alternate after / before: +1, -1, +2, -2...
+                PrefetchDirection::Both => {
+                    let k = i.div_ceil(2) as f64;
+                    if i % 2 == 1 { time + k * step } else { time - k * step }
+                }
+                _ => time + ahead * i as f64 * step,
+            })
+            .filter(|t| (0.0..=duration).contains(t))
+            .collect()
+    }
+}
+
+/// Late frames over the last `WINDOW` played: playback is lagging once
+/// three in four miss their time (a startup hiccup does not count)
+#[derive(Default)]
+struct LagMeter {
+    late: VecDeque<bool>,
+}
+
+impl LagMeter {
+    const WINDOW: usize = 60;
+
+    /// Record one frame, return whether playback is lagging
+    fn record(&mut self, late: bool) -> bool {
+        if self.late.len() == Self::WINDOW {
+            self.late.pop_front();
+        }
+        self.late.push_back(late);
+        self.late.len() == Self::WINDOW && self.late.iter().filter(|l| **l).count() * 4 >= Self::WINDOW * 3
+    }
 }
 
 fn compute_preview_size(src_w: u32, src_h: u32, max_w: u32, max_h: u32) -> (u32, u32) {
@@ -45,32 +270,55 @@ fn compute_preview_size(src_w: u32, src_h: u32, max_w: u32, max_h: u32) -> (u32,
 }
 
 impl StreamDecoder {
-    pub fn new(path: &PathBuf, width: u32, height: u32, duration: f64, src_fps: f64) -> Result<Self, String> {
+    /// `hwaccel`: `-hwaccel` method to try first (`None` = software decode)
+    pub fn new(path: &Path, width: u32, height: u32, duration: f64, src_fps: f64, hwaccel: Option<String>) -> Result<Self, String> {
+        if width == 0 || height == 0 {
+            return Err("ffprobe reported no picture size for the video stream".to_string());
+        }
         let (command_tx, command_rx) = mpsc::channel();
 
         let current_frame = Arc::new(Mutex::new(None));
         let speed = Arc::new(Mutex::new(1.0f32));
         let decoder_time = Arc::new(Mutex::new(0.0f64));
 
-        let (preview_width, preview_height) = compute_preview_size(width, height, 640, 360);
-        let decode_fps = (src_fps.round() as u32).clamp(24, 30);
-
-        let path_clone = path.clone();
+        let path_clone = path.to_path_buf();
         let current_frame_clone = current_frame.clone();
         let speed_clone = speed.clone();
         let decoder_time_clone = decoder_time.clone();
+        let fast_scrub = Arc::new(AtomicBool::new(false));
+        let fast_scrub_clone = fast_scrub.clone();
+        let decode_path = Arc::new(Mutex::new(DecodePath::new(hwaccel)));
+        let decode_path_clone = decode_path.clone();
+        let filter = Arc::new(Mutex::new(None));
+        let filter_clone = filter.clone();
+        let filter_changed = Arc::new(AtomicBool::new(false));
+        let filter_changed_clone = filter_changed.clone();
+        let error = Arc::new(Mutex::new(None));
+        let error_clone = error.clone();
+        let low_power = Arc::new(AtomicBool::new(false));
+        let low_power_clone = low_power.clone();
+        let lagging = Arc::new(AtomicBool::new(false));
+        let lagging_clone = lagging.clone();
+        let prefetch = Arc::new(Mutex::new(PrefetchSettings::default()));
+        let prefetch_clone = prefetch.clone();
 
-        thread::spawn(move || {
+        let thread = thread::spawn(move || {
             decoder_thread(
                 path_clone,
-                preview_width,
-                preview_height,
+                (width, height, src_fps),
                 duration,
-                decode_fps,
                 command_rx,
                 current_frame_clone,
                 speed_clone,
                 decoder_time_clone,
+                fast_scrub_clone,
+                decode_path_clone,
+                filter_clone,
+                filter_changed_clone,
+                error_clone,
+                low_power_clone,
+                lagging_clone,
+                prefetch_clone,
             );
         });
 
@@ -79,9 +327,40 @@ impl StreamDecoder {
             current_frame,
             speed,
             decoder_time,
+            fast_scrub,
+            decode_path,
+            filter,
+            filter_changed,
+            error,
+            low_power,
+            lagging,
+            prefetch,
+            thread: Some(thread),
         })
     }
 
+    /// Switch hardware decode on (`Some(method)`) or off; applies from the
+    /// next FFmpeg process (next seek or play)
+    pub fn set_hwaccel(&self, hwaccel: Option<String>) {
+        *self.decode_path.lock().unwrap() = DecodePath::new(hwaccel);
+    }
+
+    pub fn decode_path(&self) -> DecodePath {
+        self.decode_path.lock().unwrap().clone()
+    }
+
+    /// Filter the preview through `chain` (`None` = back to the plain
+    /// picture). The current frame is redrawn or playback restarts in place.
+    pub fn set_filter(&self, chain: Option<String>) {
+        *self.filter.lock().unwrap() = chain;
+        self.filter_changed.store(true, Ordering::Relaxed);
+    }
+
+    /// Use keyframe-only grabs while scrubbing (accurate frame once paused)
+    pub fn set_fast_scrub(&self, enabled: bool) {
+        self.fast_scrub.store(enabled, Ordering::Relaxed);
+    }
+
     pub fn seek(&self, time: f64) {
         let _ = self.command_tx.send(DecoderCommand::Seek(time));
     }
@@ -105,30 +384,75 @@ impl StreamDecoder {
     pub fn get_decoder_time(&self) -> f64 {
         *self.decoder_time.lock().unwrap()
     }
+
+    /// Why the preview frame at the current position could not be decoded
+    pub fn get_error(&self) -> Option<String> {
+        self.error.lock().unwrap().clone()
+    }
+
+    /// Switch to `PreviewLimits::LOW_POWER` (or back): the picture is
+    /// redrawn or playback restarts in place at the new size and rate
+    pub fn set_low_power(&self, enabled: bool) {
+        self.low_power.store(enabled, Ordering::Relaxed);
+    }
+
+    /// Playback has been falling behind the frame rate for a while
+    pub fn is_lagging(&self) -> bool {
+        self.lagging.load(Ordering::Relaxed)
+    }
+
+    /// Prefetch settings, used from the next settled seek (never in low-power mode)
+    pub fn set_prefetch(&self, settings: PrefetchSettings) {
+        *self.prefetch.lock().unwrap() = settings;
+    }
 }
 
 impl Drop for StreamDecoder {
+    /// Stop the decoder thread and wait for it: its FFmpeg process is killed
+    /// and no frame of this file can be published after the drop. The wait
+    /// is at most one frame read (the thread checks for Stop between reads).
     fn drop(&mut self) {
         let _ = self.command_tx.send(DecoderCommand::Stop);
+        if let Some(thread) = self.thread.take() {
+            let _ = thread.join();
+        }
     }
 }
 
 // ---- FFmpeg process helpers ----
 
+/// `-vf` of the preview processes: `filter` first (at source resolution, crop
+/// coordinates are in source pixels), then scaling to the preview size. A
+/// filtered picture keeps its own shape, letterboxed in the fixed-size frame
+/// (a crop or a quarter turn changes it). `fps` only for playback.
+fn preview_vf(filter: Option<&str>, width: u32, height: u32, fps: Option<u32>) -> String {
+    let mut vf = match filter {
+        Some(chain) => format!(
+            "{},scale={w}:{h}:force_original_aspect_ratio=decrease:flags=fast_bilinear,pad={w}:{h}:-1:-1",
+            chain, w = width, h = height,
+        ),
+        None => format!("scale={}:{}:flags=fast_bilinear", width, height),
+    };
+    if let Some(fps) = fps {
+        vf.push_str(&format!(",fps={}", fps));
+    }
+    vf
+}
+
 /// Spawn FFmpeg for continuous playback (no frame limit)
-fn spawn_ffmpeg_play(path: &PathBuf, start_time: f64, width: u32, height: u32, fps: u32) -> Option<Child> {
+#[allow(clippy::too_many_arguments)]
+fn spawn_ffmpeg_play(path: &Path, start_time: f64, width: u32, height: u32, fps: u32, hwaccel: Option<&str>, filter: Option<&str>) -> Option<Child> {
     let mut cmd = crate::ffmpeg::ffmpeg_command();
-    // On Apple Silicon, let VideoToolbox decode H.264/HEVC in hardware.
-    // FFmpeg falls back silently if the codec isn't supported in hardware.
-    #[cfg(target_os = "macos")]
-    {
-        cmd.args(["-hwaccel", "videotoolbox"]);
+    // FFmpeg decodes in software by itself when the codec isn't supported in
+    // hardware; a device that can't be opened ends the process (see DecodePath)
+    if let Some(method) = hwaccel {
+        cmd.args(["-hwaccel", method]);
     }
     cmd.args(["-ss", &format!("{:.3}", start_time), "-i"])
         .arg(path)
         .args([
             "-an", "-sn",       // skip audio + subtitles = much faster
-            "-vf", &format!("scale={}:{}:flags=fast_bilinear,fps={}", width, height, fps),
+            "-vf", &preview_vf(filter, width, height, Some(fps)),
             "-f", "rawvideo",
             "-pix_fmt", "rgba",
             "-vsync", "cfr",
@@ -141,28 +465,75 @@ fn spawn_ffmpeg_play(path: &PathBuf, start_time: f64, width: u32, height: u32, f
     cmd.spawn().ok()
 }
 
-/// Spawn FFmpeg for a single frame grab (scrubbing) — ultra fast
-fn spawn_ffmpeg_scrub(path: &PathBuf, time: f64, width: u32, height: u32) -> Option<Child> {
+/// Spawn FFmpeg for a single frame grab (scrubbing) — ultra fast.
+/// `keyframe_only` decodes just the keyframe before `time` (`-skip_frame nokey`
+/// + `-noaccurate_seek`): a few frames off, but no full-res decode of the GOP.
+///
+/// stderr carries only errors (`-v error`), read back when no frame comes out.
+/// A keyframe grab logs its `showinfo` line instead (see `keyframe_time`).
+#[allow(clippy::too_many_arguments)]
+fn spawn_ffmpeg_scrub(path: &Path, time: f64, width: u32, height: u32, keyframe_only: bool, hwaccel: Option<&str>, filter: Option<&str>) -> Result<Child, String> {
     let mut cmd = crate::ffmpeg::ffmpeg_command();
-    #[cfg(target_os = "macos")]
-    {
-        cmd.args(["-hwaccel", "videotoolbox"]);
+    let mut vf = preview_vf(filter, width, height, None);
+    if keyframe_only {
+        cmd.args(["-v", "info", "-hide_banner", "-nostats"]);
+        vf = format!("showinfo,{}", vf);
+    } else {
+        cmd.args(["-v", "error"]);
+    }
+    if let Some(method) = hwaccel {
+        cmd.args(["-hwaccel", method]);
+    }
+    if keyframe_only {
+        cmd.args(["-skip_frame", "nokey", "-noaccurate_seek"]);
     }
     cmd.args(["-ss", &format!("{:.3}", time), "-i"])
         .arg(path)
         .args([
             "-an", "-sn",
             "-frames:v", "1",   // decode only ONE frame
-            "-vf", &format!("scale={}:{}:flags=fast_bilinear", width, height),
+            "-vf", &vf,
             "-f", "rawvideo",
             "-pix_fmt", "rgba",
             "pipe:1",
         ])
         .stdout(Stdio::piped())
-        .stderr(Stdio::null())
+        .stderr(Stdio::piped())
         .stdin(Stdio::null());
 
-    cmd.spawn().ok()
+    cmd.spawn().map_err(|e| format!("cannot run {}: {}", crate::ffmpeg::ffmpeg_path(), e))
+}
+
+/// Stop a scrub process that gave no frame and tell why (`failure_reason`)
+fn scrub_failure(mut child: Child, time: f64, duration: f64) -> Option<String> {
+    let _ = child.kill();
+    let _ = child.wait();
+    let mut stderr = String::new();
+    if let Some(mut pipe) = child.stderr.take() {
+        let _ = pipe.read_to_string(&mut stderr);
+    }
+    failure_reason(&stderr, time, duration)
+}
+
+/// Position of the frame a keyframe grab at `time` returned: `showinfo`
+/// reports its pts relative to the `-ss` point, negative when the keyframe
+/// comes before it. `time` when the line is missing.
+fn keyframe_time(stderr: &str, time: f64, duration: f64) -> f64 {
+    stderr.lines()
+        .find_map(|line| line.split("pts_time:").nth(1))
+        .and_then(|rest| rest.split_whitespace().next())
+        .and_then(|value| value.parse::<f64>().ok())
+        .map_or(time, |offset| (time + offset).clamp(0.0, duration))
+}
+
+/// Last stderr line, else a generic message; none when seeking at the very
+/// end, where there is legitimately nothing left to decode
+fn failure_reason(stderr: &str, time: f64, duration: f64) -> Option<String> {
+    match stderr.lines().rev().map(str::trim).find(|l| !l.is_empty()) {
+        Some(line) => Some(line.to_string()),
+        None if time + 1.0 < duration => Some(format!("no frame decoded at {:.2} s", time)),
+        None => None,
+    }
 }
 
 fn kill_process(proc: &mut Option<Child>) {
@@ -194,6 +565,171 @@ fn read_one_frame(proc: &mut Child, frame_size: usize, width: u32, height: u32,
     })
 }
 
+/// Recently grabbed scrub frames, used as placeholders while the exact frame
+/// for a new seek position decodes.
+struct ScrubCache {
+    frames: VecDeque<VideoFrame>,
+    capacity: usize,
+}
+
+impl ScrubCache {
+    /// ~24 preview frames (640x360 RGBA ≈ 0.9 MB each)
+    const DEFAULT_CAPACITY: usize = 24;
+
+    fn new(capacity: usize) -> Self {
+        Self { frames: VecDeque::with_capacity(capacity), capacity }
+    }
+
+    /// Frame whose pts is closest to `time`
+    fn nearest(&self, time: f64) -> Option<&VideoFrame> {
+        self.frames.iter().min_by(|a, b| {
+            (a.pts - time).abs().partial_cmp(&(b.pts - time).abs()).unwrap_or(std::cmp::Ordering::Equal)
+        })
+    }
+
+    /// Frame grabbed at exactly `time`
+    fn at(&self, time: f64) -> Option<&VideoFrame> {
+        self.frames.iter().find(|f| (f.pts - time).abs() <= 0.001)
+    }
+
+    /// Forget every frame (drawn with a filter that no longer applies)
+    fn clear(&mut self) {
+        self.frames.clear();
+    }
+
+    /// Insert a frame, replacing one at the same position and evicting the oldest when full
+    fn insert(&mut self, frame: VideoFrame) {
+        self.frames.retain(|f| (f.pts - frame.pts).abs() > 0.001);
+        if self.frames.len() >= self.capacity {
+            self.frames.pop_front();
+        }
+        self.frames.push_back(frame);
+    }
+}
+
+/// Publish the cached frame nearest to `time` if it is closer than the one
+/// currently displayed, so scrubbing responds before the exact frame is decoded.
+fn show_nearest_cached(cache: &ScrubCache, current_frame: &Mutex<Option<VideoFrame>>, time: f64) {
+    let Some(nearest) = cache.nearest(time) else { return };
+    let mut current = current_frame.lock().unwrap();
+    let closer = current
+        .as_ref()
+        .is_none_or(|f| (nearest.pts - time).abs() < (f.pts - time).abs());
+    if closer {
+        *current = Some(nearest.clone());
+    }
+}
+
+/// Decode one frame at `time`. A hardware grab that yields nothing is retried
+/// in software; if that works, software sticks. The error is the reason
+/// given by `scrub_failure`, none for a keyframe grab (its stderr is the
+/// info log; the exact grab that follows reports the error).
+///
+/// A keyframe grab carries the keyframe's own position as pts, so it is
+/// never taken for the exact frame at `time`.
+#[allow(clippy::too_many_arguments)]
+fn decode_scrub_frame(
+    path: &Path,
+    time: f64,
+    duration: f64,
+    width: u32,
+    height: u32,
+    frame_size: usize,
+    keyframe_only: bool,
+    decode_path: &Mutex<DecodePath>,
+    filter: &Mutex<Option<String>>,
+) -> Result<VideoFrame, Option<String>> {
+    let filter = filter.lock().unwrap().clone();
+    let grab = |hwaccel: Option<&str>| -> Result<VideoFrame, Option<String>> {
+        let mut child = spawn_ffmpeg_scrub(path, time, width, height, keyframe_only, hwaccel, filter.as_deref())
+            .map_err(Some)?;
+        match read_one_frame(&mut child, frame_size, width, height, time) {
+            Some(mut frame) => {
+                let _ = child.kill();
+                let _ = child.wait();
+                if keyframe_only {
+                    let mut stderr = String::new();
+                    if let Some(mut pipe) = child.stderr.take() {
+                        let _ = pipe.read_to_string(&mut stderr);
+                    }
+                    frame.pts = keyframe_time(&stderr, time, duration);
+                }
+                Ok(frame)
+            }
+            None => {
+                let reason = scrub_failure(child, time, duration);
+                Err(reason.filter(|_| !keyframe_only))
+            }
+        }
+    };
+    let hwaccel = decode_path.lock().unwrap().hwaccel().map(str::to_string);
+    let mut frame = grab(hwaccel.as_deref());
+    if frame.is_err() && hwaccel.is_some() {
+        // No frame in software either (past the end...): not the hardware's fault
+        frame = grab(None);
+        if frame.is_ok() {
+            decode_path.lock().unwrap().fall_back();
+        }
+    }
+    frame
+}
+
+/// Grab one frame at `time` (`decode_scrub_frame`), publish it and return it.
+/// A failure is published in `error`.
+#[allow(clippy::too_many_arguments)]
+fn grab_scrub_frame(
+    path: &Path,
+    time: f64,
+    duration: f64,
+    width: u32,
+    height: u32,
+    frame_size: usize,
+    keyframe_only: bool,
+    current_frame: &Mutex<Option<VideoFrame>>,
+    decode_path: &Mutex<DecodePath>,
+    filter: &Mutex<Option<String>>,
+    error: &Mutex<Option<String>>,
+) -> Option<VideoFrame> {
+    match decode_scrub_frame(path, time, duration, width, height, frame_size, keyframe_only, decode_path, filter) {
+        Ok(f) => {
+            *current_frame.lock().unwrap() = Some(f.clone());
+            *error.lock().unwrap() = None;
+            Some(f)
+        }
+        Err(reason) => {
+            if reason.is_some() {
+                *error.lock().unwrap() = reason;
+            }
+            None
+        }
+    }
+}
+
+/// Decode the frames at `times` into the cache, skipping those already there,
+/// until a command arrives. That command is returned for the caller to handle
+/// (a closed channel comes back as `Stop`).
+fn prefetch_frames(
+    cache: &mut ScrubCache,
+    times: &[f64],
+    command_rx: &Receiver<DecoderCommand>,
+    mut decode: impl FnMut(f64) -> Option<VideoFrame>,
+) -> Option<DecoderCommand> {
+    for &t in times {
+        match command_rx.try_recv() {
+            Ok(command) => return Some(command),
+            Err(mpsc::TryRecvError::Disconnected) => return Some(DecoderCommand::Stop),
+            Err(mpsc::TryRecvError::Empty) => {}
+        }
+        if cache.at(t).is_some() {
+            continue;
+        }
+        if let Some(frame) = decode(t) {
+            cache.insert(frame);
+        }
+    }
+    None
+}
+
 /// Debounce seeks: wait up to `delay` for more Seek commands, return the latest.
 /// Also handles Play/Pause/Stop that arrive during the wait.
 fn debounce_seek(
@@ -222,19 +758,32 @@ fn debounce_seek(
     (final_time, play_state, false)
 }
 
-/// The decoder thread.
+/// The decoder thread. `source`: size and frame rate of the video stream.
+#[allow(clippy::too_many_arguments)]
 fn decoder_thread(
     path: PathBuf,
-    width: u32,
-    height: u32,
+    source: (u32, u32, f64),
     duration: f64,
-    fps: u32,
     command_rx: Receiver<DecoderCommand>,
     current_frame: Arc<Mutex<Option<VideoFrame>>>,
     speed: Arc<Mutex<f32>>,
     decoder_time: Arc<Mutex<f64>>,
+    fast_scrub: Arc<AtomicBool>,
+    decode_path: Arc<Mutex<DecodePath>>,
+    filter: Arc<Mutex<Option<String>>>,
+    filter_changed: Arc<AtomicBool>,
+    error: Arc<Mutex<Option<String>>>,
+    low_power: Arc<AtomicBool>,
+    lagging: Arc<AtomicBool>,
+    prefetch: Arc<Mutex<PrefetchSettings>>,
 ) {
-    let frame_size = (width * height * 4) as usize;
+    let hwaccel = || decode_path.lock().unwrap().hwaccel().map(str::to_string);
+    let chain = || filter.lock().unwrap().clone();
+    let (src_w, src_h, src_fps) = source;
+    let mut low_power_on = low_power.load(Ordering::Relaxed);
+    let mut limits = PreviewLimits::new(low_power_on);
+    let (mut width, mut height, mut fps) = limits.format(src_w, src_h, src_fps);
+    let mut frame_size = (width * height * 4) as usize;
     let mut current_time: f64 = 0.0;
     let mut is_playing = false;
     let mut play_process: Option<Child> = None;
@@ -242,8 +791,29 @@ fn decoder_thread(
 
     // Reusable buffer for frame reads to avoid allocations during playback
     let mut frame_buf = vec![0u8; frame_size];
+    let mut scrub_cache = ScrubCache::new(ScrubCache::DEFAULT_CAPACITY);
+    // Seek received while waiting to refine a fast scrub frame
+    let mut pending_seek: Option<f64> = None;
+    let mut lag = LagMeter::default();
+    // Direction of the last move, for `PrefetchDirection::FollowSeek`
+    let mut moving_forward = true;
 
     loop {
+        // Low-power toggled: new size and rate, redraw or restart in place
+        if low_power.load(Ordering::Relaxed) != low_power_on {
+            low_power_on = !low_power_on;
+            limits = PreviewLimits::new(low_power_on);
+            (width, height, fps) = limits.format(src_w, src_h, src_fps);
+            frame_size = (width * height * 4) as usize;
+            frame_buf = vec![0u8; frame_size];
+            scrub_cache.clear();
+            kill_process(&mut play_process);
+            lag = LagMeter::default();
+            lagging.store(false, Ordering::Relaxed);
+            if !is_playing {
+                pending_seek.get_or_insert(current_time);
+            }
+        }
         if is_playing {
             // ---- PLAYBACK MODE ----
             // Check for commands without blocking
@@ -256,7 +826,7 @@ fn decoder_thread(
                         kill_process(&mut play_process);
                         playback_epoch = Some((Instant::now(), t));
                         // Spawn new process at seek position
-                        play_process = spawn_ffmpeg_play(&path, t, width, height, fps);
+                        play_process = spawn_ffmpeg_play(&path, t, width, height, fps, hwaccel().as_deref(), chain().as_deref());
                     }
                     Ok(DecoderCommand::Play) => {} // already playing
                     Ok(DecoderCommand::Pause) => {
@@ -281,10 +851,16 @@ fn decoder_thread(
                 continue; // was paused in the command loop
             }
 
+            // New filter chain: restart from the current position with it
+            if filter_changed.swap(false, Ordering::Relaxed) {
+                scrub_cache.clear();
+                kill_process(&mut play_process);
+            }
+
             // Ensure we have a process
             if play_process.is_none() {
                 playback_epoch = Some((Instant::now(), current_time));
-                play_process = spawn_ffmpeg_play(&path, current_time, width, height, fps);
+                play_process = spawn_ffmpeg_play(&path, current_time, width, height, fps, hwaccel().as_deref(), chain().as_deref());
             }
 
             if let Some(ref mut child) = play_process {
@@ -302,6 +878,8 @@ fn decoder_thread(
                             let wall_target = epoch_wall
                                 + Duration::from_secs_f64((current_time - epoch_time) / spd as f64);
                             let now = Instant::now();
+                            let late = now > wall_target + Duration::from_secs_f64(1.0 / fps as f64);
+                            lagging.store(lag.record(late), Ordering::Relaxed);
                             if now < wall_target {
                                 // Sleep but check for commands every 5ms
                                 let mut remaining = wall_target - now;
@@ -326,7 +904,7 @@ fn decoder_thread(
                                             *decoder_time.lock().unwrap() = t;
                                             kill_process(&mut play_process);
                                             playback_epoch = Some((Instant::now(), t));
-                                            play_process = spawn_ffmpeg_play(&path, t, width, height, fps);
+                                            play_process = spawn_ffmpeg_play(&path, t, width, height, fps, hwaccel().as_deref(), chain().as_deref());
                                             break;
                                         }
                                         _ => {}
@@ -343,6 +921,12 @@ fn decoder_thread(
                             kill_process(&mut play_process);
                         }
                     }
+                    // Hardware decode gave out before the end: carry on in software
+                    None if current_time + 0.5 < duration && decode_path.lock().unwrap().fall_back() => {
+                        kill_process(&mut play_process);
+                        playback_epoch = Some((Instant::now(), current_time));
+                        play_process = spawn_ffmpeg_play(&path, current_time, width, height, fps, None, chain().as_deref());
+                    }
                     None => {
                         is_playing = false;
                         playback_epoch = None;
@@ -355,35 +939,79 @@ fn decoder_thread(
             }
         } else {
             // ---- IDLE / SCRUB MODE ----
+            // New filter chain: cached frames are stale, redraw the current one
+            if filter_changed.swap(false, Ordering::Relaxed) {
+                scrub_cache.clear();
+                pending_seek.get_or_insert(current_time);
+            }
             // Block on next command (no CPU burn)
-            match command_rx.recv_timeout(Duration::from_millis(50)) {
+            let command = match pending_seek.take() {
+                Some(t) => Ok(DecoderCommand::Seek(t)),
+                None => command_rx.recv_timeout(Duration::from_millis(50)),
+            };
+            match command {
                 Ok(DecoderCommand::Seek(t)) => {
-                    // Debounce: wait 15ms for more seeks (scrubbing)
+                    // Debounce: wait for more seeks (scrubbing)
                     let (final_t, play_cmd, stop) =
-                        debounce_seek(&command_rx, t, Duration::from_millis(15));
+                        debounce_seek(&command_rx, t, limits.seek_debounce);
 
                     if stop { return; }
 
                     let t = final_t.clamp(0.0, duration);
+                    if t != current_time {
+                        moving_forward = t > current_time;
+                    }
                     current_time = t;
                     *decoder_time.lock().unwrap() = t;
 
-                    // Fast single-frame grab
-                    if let Some(mut child) = spawn_ffmpeg_scrub(&path, t, width, height) {
-                        if let Some(frame) = read_one_frame(&mut child, frame_size, width, height, t) {
-                            *current_frame.lock().unwrap() = Some(frame);
+                    // Placeholder from the cache, then fast single-frame grab
+                    show_nearest_cached(&scrub_cache, &current_frame, t);
+                    let mut accurate = !(fast_scrub.load(Ordering::Relaxed) || limits.always_fast_scrub);
+                    let mut play_cmd = play_cmd;
+                    if !accurate {
+                        // Keyframe preview now, exact frame only once the seeks stop
+                        grab_scrub_frame(&path, t, duration, width, height, frame_size, true, &current_frame, &decode_path, &filter, &error);
+                        match command_rx.recv_timeout(limits.refine_delay) {
+                            Ok(DecoderCommand::Seek(next)) => pending_seek = Some(next),
+                            Ok(DecoderCommand::Play) => play_cmd = Some(true),
+                            Ok(DecoderCommand::Pause) | Err(mpsc::RecvTimeoutError::Timeout) => accurate = true,
+                            Ok(DecoderCommand::Stop) | Err(mpsc::RecvTimeoutError::Disconnected) => return,
+                        }
+                    }
+                    if accurate {
+                        if let Some(frame) = scrub_cache.at(t) {
+                            // Prefetched: no FFmpeg run needed
+                            *current_frame.lock().unwrap() = Some(frame.clone());
+                            *error.lock().unwrap() = None;
+                        } else if let Some(frame) = grab_scrub_frame(&path, t, duration, width, height, frame_size, false, &current_frame, &decode_path, &filter, &error) {
+                            scrub_cache.insert(frame);
+                        }
+                    }
+
+                    // Settled on an exact frame: decode the neighbours the next move will want
+                    if accurate && play_cmd.is_none() && limits.prefetch {
+                        let times = prefetch.lock().unwrap().times(t, moving_forward, fps, duration);
+                        let interrupt = prefetch_frames(&mut scrub_cache, &times, &command_rx, |pt| {
+                            decode_scrub_frame(&path, pt, duration, width, height, frame_size, false, &decode_path, &filter).ok()
+                        });
+                        match interrupt {
+                            Some(DecoderCommand::Seek(next)) => pending_seek = Some(next),
+                            Some(DecoderCommand::Play) => play_cmd = Some(true),
+                            Some(DecoderCommand::Pause) | None => {}
+                            Some(DecoderCommand::Stop) => return,
                         }
-                        kill_process(&mut Some(child));
                     }
 
                     // If Play was received during debounce, start playing
                     if play_cmd == Some(true) {
                         is_playing = true;
+                        moving_forward = true;
                         playback_epoch = Some((Instant::now(), current_time));
                     }
                 }
                 Ok(DecoderCommand::Play) => {
                     is_playing = true;
+                    moving_forward = true;
                     playback_epoch = Some((Instant::now(), current_time));
                 }
                 Ok(DecoderCommand::Pause) => {} // already paused
@@ -415,3 +1043,166 @@ fn read_frame_into_buf(proc: &mut Child, buf: &mut [u8], frame_size: usize, widt
         pts,
     })
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn frame(pts: f64) -> VideoFrame {
+        VideoFrame { data: Arc::new(Vec::new()), width: 2, height: 2, pts }
+    }
+
+    #[test]
+    fn dropping_the_decoder_ends_its_thread() {
+        // No such file: every FFmpeg spawn fails, the thread just idles
+        let decoder = StreamDecoder::new(Path::new("/nonexistent/clip.mp4"), 640, 360, 10.0, 30.0, None).unwrap();
+        let frame = decoder.current_frame.clone();
+        let time = decoder.decoder_time.clone();
+        decoder.seek(4.0);
+        decoder.play();
+        drop(decoder);
+        // The thread's clones are gone: it has returned, not just been asked to
+        assert_eq!(Arc::strong_count(&frame), 1);
+        assert_eq!(Arc::strong_count(&time), 1);
+    }
+
+    #[test]
+    fn filter_runs_before_the_preview_scaling() {
+        assert_eq!(preview_vf(None, 640, 360, Some(30)), "scale=640:360:flags=fast_bilinear,fps=30");
+        assert_eq!(
+            preview_vf(Some("transpose=1"), 640, 360, None),
+            "transpose=1,scale=640:360:force_original_aspect_ratio=decrease:flags=fast_bilinear,pad=640:360:-1:-1"
+        );
+    }
+
+    #[test]
+    fn failure_reason_is_the_last_ffmpeg_error() {
+        let stderr = "[h264 @ 0x55] Invalid NAL unit size\nError while decoding stream #0:0: Invalid data found when processing input\n\n";
+        assert_eq!(
+            failure_reason(stderr, 3.0, 10.0).as_deref(),
+            Some("Error while decoding stream #0:0: Invalid data found when processing input")
+        );
+        assert_eq!(failure_reason("", 3.0, 10.0).as_deref(), Some("no frame decoded at 3.00 s"));
+        // Seeking onto the last frame: nothing to report
+        assert_eq!(failure_reason("", 9.5, 10.0), None);
+        // No picture size from ffprobe: refused up front
+        let no_video = StreamDecoder::new(Path::new("/nonexistent/clip.mp4"), 0, 0, 10.0, 30.0, None);
+        assert!(no_video.is_err());
+    }
+
+    #[test]
+    fn keyframe_grabs_report_the_keyframe_position() {
+        let stderr = "[Parsed_showinfo_0 @ 0x55] config in time_base: 1/90000, frame_rate: 30/1\n\
+            [Parsed_showinfo_0 @ 0x55] n:   0 pts: -72000 pts_time:-0.8    duration:   3000 fmt:yuv420p\n";
+        assert!((keyframe_time(stderr, 12.0, 60.0) - 11.2).abs() < 1e-9);
+        // Keyframe right at the start of the file
+        assert_eq!(keyframe_time("n:   0 pts:      0 pts_time:-0.5", 0.2, 60.0), 0.0);
+        assert_eq!(keyframe_time("", 12.0, 60.0), 12.0);
+    }
+
+    #[test]
+    fn low_power_shrinks_the_preview() {
+        assert_eq!(PreviewLimits::NORMAL.format(1920, 1080, 59.94), (640, 360, 30));
+        assert_eq!(PreviewLimits::new(true).format(1920, 1080, 59.94), (480, 270, 15));
+        assert_eq!(PreviewLimits::new(true).format(320, 240, 10.0), (320, 240, 12));
+    }
+
+    #[test]
+    fn lagging_needs_most_recent_frames_late() {
+        let mut lag = LagMeter::default();
+        // Startup: a burst of late frames, then on time
+        for i in 0..LagMeter::WINDOW {
+            assert!(!lag.record(i < 10));
+        }
+        for _ in 0..LagMeter::WINDOW - 1 {
+            lag.record(true);
+        }
+        assert!(lag.record(true));
+        assert!(lag.record(false), "one on-time frame does not clear it");
+    }
+
+    #[test]
+    fn prefetch_follows_the_last_move() {
+        let settings = PrefetchSettings { frames: 3, spacing: 2, direction: PrefetchDirection::FollowSeek };
+        // 2 preview frames at 25 fps = 80 ms apart, nearest first
+        let ahead = settings.times(5.0, true, 25, 60.0);
+        let behind = settings.times(5.0, false, 25, 60.0);
+        for (got, want) in ahead.iter().zip([5.08, 5.16, 5.24]) {
+            assert!((got - want).abs() < 1e-9);
+        }
+        for (got, want) in behind.iter().zip([4.92, 4.84, 4.76]) {
+            assert!((got - want).abs() < 1e-9);
+        }
+        // Forward ignores the move; nothing outside the file
+        let forward = PrefetchSettings { direction: PrefetchDirection::Forward, ..settings };
+        assert_eq!(forward.times(59.9, false, 25, 60.0).len(), 1);
+        let both = PrefetchSettings { frames: 4, spacing: 1, direction: PrefetchDirection::Both };
+        let times = both.times(0.04, true, 25, 60.0);
+        assert_eq!(times.len(), 3, "-0.04 s is dropped");
+        assert!(times[0] > 0.04 && times[1] < 0.04 && times[2] > times[0]);
+        assert!(PrefetchSettings { frames: 0, ..settings }.times(5.0, true, 25, 60.0).is_empty());
+    }
+
+    #[test]
+    fn prefetch_skips_cached_frames_and_stops_on_a_command() {
+        let (tx, rx) = mpsc::channel();
+        let mut cache = ScrubCache::new(8);
+        cache.insert(frame(1.1));
+        let mut decoded = Vec::new();
+        let interrupt = prefetch_frames(&mut cache, &[1.1, 1.2, 1.3], &rx, |t| {
+            decoded.push(t);
+            Some(frame(t))
+        });
+        assert!(interrupt.is_none());
+        assert_eq!(decoded, vec![1.2, 1.3]);
+        assert!(cache.at(1.3).is_some());
+
+        tx.send(DecoderCommand::Seek(7.0)).unwrap();
+        let interrupt = prefetch_frames(&mut cache, &[2.0], &rx, |t| Some(frame(t)));
+        assert!(matches!(interrupt, Some(DecoderCommand::Seek(t)) if t == 7.0));
+        assert!(cache.at(2.0).is_none());
+    }
+
+    #[test]
+    fn hardware_decode_falls_back_once() {
+        let mut path = DecodePath::new(Some("cuda".to_string()));
+        assert_eq!(path.hwaccel(), Some("cuda"));
+        assert!(path.fall_back());
+        assert_eq!(path, DecodePath::Fallback("cuda".to_string()));
+        assert_eq!(path.hwaccel(), None);
+        // A software failure is final: no retry loop
+        assert!(!path.fall_back());
+        assert!(!DecodePath::new(None).fall_back());
+    }
+
+    #[test]
+    fn scrub_cache_returns_nearest_and_evicts_oldest() {
+        let mut cache = ScrubCache::new(3);
+        assert!(cache.nearest(1.0).is_none());
+        for pts in [1.0, 5.0, 9.0] {
+            cache.insert(frame(pts));
+        }
+        assert_eq!(cache.nearest(6.2).unwrap().pts, 5.0);
+
+        // Même position : remplacée, pas dupliquée
+        cache.insert(frame(5.0));
+        assert_eq!(cache.frames.len(), 3);
+
+        // 5.0 réinséré en dernier : c'est 1.0 le plus ancien
+        cache.insert(frame(12.0));
+        assert_eq!(cache.nearest(0.0).unwrap().pts, 5.0);
+    }
+
+    #[test]
+    fn placeholder_only_when_closer_than_current() {
+        let mut cache = ScrubCache::new(4);
+        cache.insert(frame(10.0));
+        let current = Mutex::new(Some(frame(3.0)));
+
+        show_nearest_cached(&cache, &current, 4.0);
+        assert_eq!(current.lock().unwrap().as_ref().unwrap().pts, 3.0);
+
+        show_nearest_cached(&cache, &current, 9.0);
+        assert_eq!(current.lock().unwrap().as_ref().unwrap().pts, 10.0);
+    }
+}
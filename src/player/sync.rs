@@ -1,5 +1,28 @@
+use super::PlaybackState;
 use std::time::Instant;
 
+/// What the audio sink must do after a play / pause / seek / speed change.
+/// Every transition goes through [`audio_action`], so a seek while paused can
+/// never start the sink and a seek while playing always restarts it in sync.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum AudioAction {
+    /// Keep the sink paused; the next `PlayFrom` reloads it at the right spot
+    Silence,
+    /// Reload the sink at this audio position and start it
+    PlayFrom(f64),
+}
+
+/// Audio follows the clock only during normal-speed playback; at other
+/// speeds the video plays silently. `audio_time` is the video time minus the
+/// A/V offset (negative = leading silence).
+pub fn audio_action(state: PlaybackState, speed: f64, audio_time: f64) -> AudioAction {
+    if state == PlaybackState::Playing && (speed - 1.0).abs() < 0.01 {
+        AudioAction::PlayFrom(audio_time)
+    } else {
+        AudioAction::Silence
+    }
+}
+
 /// Playback clock for A/V synchronization with variable speed support
 pub struct PlaybackClock {
     start_time: Option<Instant>,
@@ -77,11 +100,6 @@ impl PlaybackClock {
         }
         self.speed = new_speed;
     }
-
-    /// Get current speed
-    pub fn get_speed(&self) -> f64 {
-        self.speed
-    }
 }
 
 impl Default for PlaybackClock {
@@ -104,7 +122,7 @@ mod tests {
         clock.resume();
         sleep(Duration::from_millis(100));
         let t = clock.get_time();
-        assert!(t >= 0.09 && t <= 0.15);
+        assert!((0.09..=0.15).contains(&t));
 
         clock.pause();
         let t1 = clock.get_time();
@@ -122,7 +140,25 @@ mod tests {
         clock.resume();
         sleep(Duration::from_millis(100));
         let t = clock.get_time();
-        assert!(t >= 10.09 && t <= 10.15);
+        assert!((10.09..=10.15).contains(&t));
+    }
+
+    #[test]
+    fn test_audio_action_transitions() {
+        use PlaybackState::*;
+        // (state after the transition, speed, expected)
+        let steps = [
+            (Paused, 1.0, AudioAction::Silence),        // seek while paused
+            (Paused, 1.0, AudioAction::Silence),        // again: still silent
+            (Playing, 1.0, AudioAction::PlayFrom(4.5)), // play: reload at the position
+            (Playing, 1.0, AudioAction::PlayFrom(4.5)), // seek while playing: restart there
+            (Playing, 2.0, AudioAction::Silence),       // fast playback is silent
+            (Playing, 1.0, AudioAction::PlayFrom(4.5)), // back to 1×: resync before playing
+            (Stopped, 1.0, AudioAction::Silence),
+        ];
+        for (state, speed, expected) in steps {
+            assert_eq!(audio_action(state, speed, 4.5), expected, "{:?} at {}x", state, speed);
+        }
     }
 
     #[test]
@@ -133,6 +169,6 @@ mod tests {
         sleep(Duration::from_millis(100));
         let t = clock.get_time();
         // At 2x speed, 100ms real time = ~200ms video time
-        assert!(t >= 0.18 && t <= 0.25);
+        assert!((0.18..=0.25).contains(&t));
     }
 }
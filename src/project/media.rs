@@ -6,6 +6,14 @@ use std::path::PathBuf;
 pub struct MediaFile {
     pub path: PathBuf,
     pub info: MediaInfo,
+    /// Unique per import: background results tagged with the id of a file
+    /// that was removed (or removed and re-added) are discarded
+    #[serde(skip)]
+    pub id: u64,
+    /// No duration from ffprobe: the full read (`ffmpeg::estimate_duration`)
+    /// started at import is still running
+    #[serde(skip)]
+    pub estimating_duration: bool,
 }
 
 impl MediaFile {
@@ -16,14 +24,44 @@ impl MediaFile {
             .unwrap_or_else(|| "Unknown".to_string())
     }
 
-    pub fn is_video(&self) -> bool {
-        self.info.video_codec.is_some()
-    }
-
     pub fn is_audio_only(&self) -> bool {
         self.info.audio_codec.is_some() && self.info.video_codec.is_none()
     }
 
+    /// Why this file can't be played or exported although ffprobe read it:
+    /// no audio or video stream, or no duration (truncated download...)
+    pub fn problem(&self) -> Option<&'static str> {
+        if self.info.video_codec.is_none() && self.info.audio_codec.is_none() {
+            Some("no audio or video stream")
+        } else if self.estimating_duration {
+            Some("duration still being read")
+        } else if self.duration_unknown() {
+            Some("zero or unknown duration")
+        } else {
+            None
+        }
+    }
+
+    /// Has streams, but no duration (yet): ffprobe found none and the full
+    /// read is pending or failed too (live capture, broken index). No
+    /// timeline, trim or Auto-Cut, only a remux
+    pub fn duration_unknown(&self) -> bool {
+        let duration = self.info.duration;
+        let has_stream = self.info.video_codec.is_some() || self.info.audio_codec.is_some();
+        has_stream && (!duration.is_finite() || duration <= 0.0)
+    }
+
+    /// Error for an export of a problematic file
+    pub fn export_blocker(&self) -> Result<(), String> {
+        match self.problem() {
+            Some(_) if self.estimating_duration => {
+                Err(format!("{}: duration still being read, try again in a moment", self.filename()))
+            }
+            Some(problem) => Err(format!("{} looks corrupt ({}), not exported", self.filename(), problem)),
+            None => Ok(()),
+        }
+    }
+
     pub fn resolution_string(&self) -> String {
         if self.info.width > 0 && self.info.height > 0 {
             format!("{}x{}", self.info.width, self.info.height)
@@ -50,12 +88,4 @@ impl Project {
             files: Vec::new(),
         }
     }
-
-    pub fn clear(&mut self) {
-        self.files.clear();
-    }
-
-    pub fn total_duration(&self) -> f64 {
-        self.files.iter().map(|f| f.info.duration).sum()
-    }
 }
@@ -1,7 +1,10 @@
 use crate::app::FFmpegApp;
 use crate::player::PlaybackState;
-use crate::ui::{clip_seekbar, EditingMode, TimelineClip, TimelineWidget, TrimMode};
-use crate::utils::{format_time, format_size};
+use crate::ui::{
+    clip_seekbar, lossless_cut_hint, trim_mode_boundary_hint, AnimationFormat, AudioExtractFormat, BatchTrimAnchor, ConflictPolicy, CropRect, EditingMode, ExportSettings, FilterSettings, OpenOutputAction, Rotation, RotationMethod, SegmentAction, SplitSettings, SubtitleMode,
+    SubtitleSettings, TimelineClip, TimelineWidget, TrimMode,
+};
+use crate::utils::{format_duration_frames, format_time, format_size};
 use eframe::egui;
 
 pub fn render_main_window(app: &mut FFmpegApp, ctx: &egui::Context) {
@@ -72,15 +75,16 @@ pub fn render_main_window(app: &mut FFmpegApp, ctx: &egui::Context) {
         ui.separator();
         render_playback_controls(app, ui);
     });
+
+    render_queue_panel(app, ctx);
+    render_ffmpeg_info(app, ctx);
 }
 
 fn render_menu_bar(app: &mut FFmpegApp, ui: &mut egui::Ui) {
     egui::menu::bar(ui, |ui| {
         ui.menu_button("File", |ui| {
             if ui.button("Open Video... (Ctrl+O)").clicked() {
-                if let Some(paths) = rfd::FileDialog::new()
-                    .add_filter("Video", &["mp4", "mkv", "avi", "mov", "webm", "ts", "flv"])
-                    .add_filter("All Files", &["*"])
+                if let Some(paths) = super::file_browser::media_file_dialog()
                     .pick_files()
                 {
                     app.add_files(paths);
@@ -128,6 +132,77 @@ fn render_menu_bar(app: &mut FFmpegApp, ui: &mut egui::Ui) {
                 app.clear_in_out_points();
                 ui.close_menu();
             }
+            ui.separator();
+            ui.menu_button("Seek Steps", |ui| {
+                let steps = &mut app.seek_settings;
+                for (label, value) in [
+                    ("←/→ while playing:", &mut steps.small_secs),
+                    ("J/L:", &mut steps.large_secs),
+                    ("Shift+J/L:", &mut steps.fine_secs),
+                ] {
+                    ui.horizontal(|ui| {
+                        ui.label(label);
+                        ui.add(egui::DragValue::new(value).range(0.1..=600.0).speed(0.5).suffix(" s"));
+                    });
+                }
+                if ui.button("Reset").clicked() {
+                    *steps = crate::ui::SeekSettings::default();
+                }
+            });
+            let mut fast_scrub = app.fast_scrub_high_res;
+            if ui.checkbox(&mut fast_scrub, "Fast scrub for high-res")
+                .on_hover_text("Above 1440p, show the nearest keyframe while scrubbing and the exact frame once you stop")
+                .changed()
+            {
+                app.set_fast_scrub_high_res(fast_scrub);
+            }
+            let mut hw_decode = app.hw_decode;
+            if ui.checkbox(&mut hw_decode, "Hardware decode for preview")
+                .on_hover_text("Decode the preview with the GPU (-hwaccel): much faster scrubbing on 4K HEVC/AV1.\nFalls back to software if the hardware decoder fails. The active path is shown under Properties.")
+                .changed()
+            {
+                app.set_hw_decode(hw_decode);
+            }
+            let mut low_power = app.low_power;
+            if ui.checkbox(&mut low_power, "Low-power mode")
+                .on_hover_text("For slow laptops and VMs: smaller 15 fps preview, keyframe-only scrubbing, no prefetch and fewer redraws.\nExports are not affected.")
+                .changed()
+            {
+                app.set_low_power(low_power);
+            }
+            ui.add_enabled_ui(!app.low_power, |ui| {
+                ui.menu_button("Prefetch", |ui| {
+                    let mut prefetch = app.prefetch;
+                    ui.horizontal(|ui| {
+                        ui.label("Frames:");
+                        ui.add(egui::DragValue::new(&mut prefetch.frames).range(0..=crate::player::PrefetchSettings::MAX_FRAMES))
+                            .on_hover_text("Decoded around the position once a seek settles (0 = off)");
+                    });
+                    ui.horizontal(|ui| {
+                        ui.label("Every:");
+                        ui.add(egui::DragValue::new(&mut prefetch.spacing).range(1..=30).suffix(" frame(s)"))
+                            .on_hover_text("Spacing at the preview frame rate");
+                    });
+                    for direction in crate::player::PrefetchDirection::all() {
+                        ui.radio_value(&mut prefetch.direction, *direction, direction.name());
+                    }
+                    if ui.button("Reset").clicked() {
+                        prefetch = crate::player::PrefetchSettings::default();
+                    }
+                    if prefetch != app.prefetch {
+                        app.set_prefetch(prefetch);
+                    }
+                });
+            }).response.on_disabled_hover_text("Off in low-power mode");
+            ui.checkbox(&mut app.defer_playback_init, "Quick browse (load audio on first Play)")
+                .on_hover_text("Selecting a video only shows its first frame: audio extraction and the waveform start when you press Play.\nClicking through a long file list no longer stalls on each file.");
+            if ui.add_enabled(app.player.is_some(), egui::Button::new("Reinitialize Audio"))
+                .on_hover_text("Reopen the audio device (after plugging in headphones, switching output...)")
+                .clicked()
+            {
+                app.reinit_audio();
+                ui.close_menu();
+            }
         });
 
         ui.menu_button("Tools", |ui| {
@@ -151,6 +226,34 @@ fn render_menu_bar(app: &mut FFmpegApp, ui: &mut egui::Ui) {
                 app.open_in_default_player();
                 ui.close_menu();
             }
+            if ui.add_enabled(has_file, egui::Button::new("Copy FFmpeg Command"))
+                .on_hover_text("Copy the command for the selected segment (or IN/OUT range) with the current trim mode and settings")
+                .clicked()
+            {
+                app.copy_ffmpeg_command(ui.ctx());
+                ui.close_menu();
+            }
+
+            if ui.button("FFmpeg Info...")
+                .on_hover_text("FFmpeg version and available encoders / filters")
+                .clicked()
+            {
+                app.show_ffmpeg_info = true;
+                ui.close_menu();
+            }
+
+            ui.separator();
+            let mut dark = app.dark_mode;
+            if ui.checkbox(&mut dark, "Dark theme").changed() {
+                app.set_dark_mode(ui.ctx(), dark);
+            }
+            if ui.button("Open Settings Folder")
+                .on_hover_text("Settings are saved automatically; ffmpeg_path / ffprobe_path can be set in config.json")
+                .clicked()
+            {
+                app.open_settings_folder();
+                ui.close_menu();
+            }
         });
     });
 }
@@ -171,13 +274,26 @@ fn render_status_bar(app: &FFmpegApp, ui: &mut egui::Ui) {
 
 fn render_preview_area(app: &mut FFmpegApp, ui: &mut egui::Ui) {
     let available_size = ui.available_size();
-    let preview_height = (available_size.y * 0.35).min(300.0).max(120.0);
+    let preview_height = (available_size.y * 0.35).clamp(120.0, 300.0);
 
     egui::Frame::canvas(ui.style()).show(ui, |ui| {
         ui.set_min_height(preview_height);
         ui.set_max_height(preview_height);
 
-        if let Some(ref texture) = app.preview_texture {
+        if app.selected_is_audio_only() && !app.current_waveform.is_empty() && !app.selected_duration_unknown() {
+            // Audio file: the waveform is the preview
+            let seek = TimelineWidget::new(app.get_duration(), app.current_time)
+                .in_point(app.in_point)
+                .out_point(app.out_point)
+                .waveform_data(&app.current_waveform)
+                .show_waveform_preview(ui);
+            if let Some(time) = seek {
+                app.seek(time);
+            }
+        } else if let Some((raw, filtered)) = app.compare_preview() {
+            render_compare_preview(ui, &raw, &filtered, &mut app.compare_split);
+        } else if let Some(texture) = app.filtered_preview().or(app.preview_texture.as_ref()) {
+            let filtered = app.filtered_preview().is_some();
             let texture_size = texture.size_vec2();
             let aspect_ratio = texture_size.x / texture_size.y;
 
@@ -191,14 +307,44 @@ fn render_preview_area(app: &mut FFmpegApp, ui: &mut egui::Ui) {
             ui.centered_and_justified(|ui| {
                 ui.image((texture.id(), display_size));
             });
+            if filtered {
+                ui.painter().text(
+                    ui.max_rect().left_top() + egui::vec2(6.0, 4.0),
+                    egui::Align2::LEFT_TOP,
+                    format!("Filtered preview {}x{}", texture_size.x, texture_size.y),
+                    egui::FontId::proportional(11.0),
+                    egui::Color32::from_rgb(255, 200, 60),
+                );
+            } else if let Some(error) = app.preview_error() {
+                // The frame on screen is stale: say why it did not update
+                ui.painter().text(
+                    ui.max_rect().left_bottom() + egui::vec2(6.0, -4.0),
+                    egui::Align2::LEFT_BOTTOM,
+                    format!("Preview failed: {}", error),
+                    egui::FontId::proportional(11.0),
+                    egui::Color32::from_rgb(255, 100, 100),
+                );
+            }
         } else if let Some(file) = app.selected_file() {
-            ui.centered_and_justified(|ui| {
-                ui.label(format!(
-                    "{}\n{} | {}\n\nPress Play to start",
-                    file.filename(),
-                    file.resolution_string(),
-                    file.duration_string()
-                ));
+            let error = app.preview_error();
+            ui.centered_and_justified(|ui| match error {
+                Some(error) => {
+                    ui.label(egui::RichText::new(format!(
+                        "{}\n{} | {}\n\nPreview failed: {}",
+                        file.filename(),
+                        file.resolution_string(),
+                        file.duration_string(),
+                        error
+                    )).color(egui::Color32::from_rgb(255, 100, 100)));
+                }
+                None => {
+                    ui.label(format!(
+                        "{}\n{} | {}\n\nPress Play to start",
+                        file.filename(),
+                        file.resolution_string(),
+                        file.duration_string()
+                    ));
+                }
             });
         } else {
             ui.centered_and_justified(|ui| {
@@ -229,6 +375,51 @@ fn render_preview_area(app: &mut FFmpegApp, ui: &mut egui::Ui) {
     });
 }
 
+/// A/B view of the paused frame: source on the left of a draggable split,
+/// filtered on the right. Crop / rotation change the shape, then the two are
+/// shown side by side instead.
+fn render_compare_preview(ui: &mut egui::Ui, raw: &egui::TextureHandle, filtered: &egui::TextureHandle, split: &mut f32) {
+    let fit = |size: egui::Vec2, area: egui::Rect| {
+        let scale = (area.width() / size.x).min(area.height() / size.y);
+        egui::Rect::from_center_size(area.center(), size * scale)
+    };
+    let label = |ui: &egui::Ui, pos: egui::Pos2, align: egui::Align2, text: &str| {
+        ui.painter().text(pos, align, text, egui::FontId::proportional(11.0), egui::Color32::from_rgb(255, 200, 60));
+    };
+    let full_uv = egui::Rect::from_min_max(egui::pos2(0.0, 0.0), egui::pos2(1.0, 1.0));
+    let area = ui.available_rect_before_wrap();
+    let (raw_size, filtered_size) = (raw.size_vec2(), filtered.size_vec2());
+    let same_shape = ((raw_size.x / raw_size.y) - (filtered_size.x / filtered_size.y)).abs() < 0.01;
+
+    if !same_shape {
+        let (left, right) = area.split_left_right_at_fraction(0.5);
+        let (a, b) = (fit(raw_size, left.shrink(2.0)), fit(filtered_size, right.shrink(2.0)));
+        ui.painter().image(raw.id(), a, full_uv, egui::Color32::WHITE);
+        ui.painter().image(filtered.id(), b, full_uv, egui::Color32::WHITE);
+        label(ui, a.left_top() + egui::vec2(4.0, 2.0), egui::Align2::LEFT_TOP, "A: source");
+        label(ui, b.left_top() + egui::vec2(4.0, 2.0), egui::Align2::LEFT_TOP, "B: filtered");
+        ui.allocate_rect(area, egui::Sense::hover());
+        return;
+    }
+
+    let rect = fit(filtered_size, area);
+    let response = ui.allocate_rect(rect, egui::Sense::click_and_drag())
+        .on_hover_cursor(egui::CursorIcon::ResizeHorizontal);
+    if let Some(pos) = response.interact_pointer_pos() {
+        *split = ((pos.x - rect.left()) / rect.width()).clamp(0.0, 1.0);
+    }
+    let x = rect.left() + rect.width() * *split;
+    let (a, b) = (
+        egui::Rect::from_min_max(rect.left_top(), egui::pos2(x, rect.bottom())),
+        egui::Rect::from_min_max(egui::pos2(x, rect.top()), rect.right_bottom()),
+    );
+    ui.painter().image(raw.id(), a, egui::Rect::from_min_max(egui::pos2(0.0, 0.0), egui::pos2(*split, 1.0)), egui::Color32::WHITE);
+    ui.painter().image(filtered.id(), b, egui::Rect::from_min_max(egui::pos2(*split, 0.0), egui::pos2(1.0, 1.0)), egui::Color32::WHITE);
+    ui.painter().vline(x, rect.y_range(), egui::Stroke::new(2.0, egui::Color32::WHITE));
+    label(ui, rect.left_top() + egui::vec2(4.0, 2.0), egui::Align2::LEFT_TOP, "A: source");
+    label(ui, rect.right_top() + egui::vec2(-4.0, 2.0), egui::Align2::RIGHT_TOP, "B: filtered");
+}
+
 fn render_playback_controls(app: &mut FFmpegApp, ui: &mut egui::Ui) {
     let state = app.get_playback_state();
     let duration = app.get_duration();
@@ -309,29 +500,86 @@ fn render_playback_controls(app: &mut FFmpegApp, ui: &mut egui::Ui) {
             ).changed() {
                 app.set_volume(volume);
             }
-            ui.small("Vol");
+            let speaker = if app.muted || app.volume == 0.0 { "🔇" } else { "🔊" };
+            if ui.small_button(speaker)
+                .on_hover_text(if app.muted { "Unmute (M)" } else { "Mute (M)" })
+                .clicked()
+            {
+                app.toggle_mute();
+            }
+
+            // No audio device (headless, RDP, device in use): say so instead of playing silently
+            let audio_error = app.player.as_ref().and_then(|p| p.audio_error()).map(str::to_string);
+            if let Some(err) = audio_error {
+                if ui.small_button("Retry").on_hover_text("Reinitialize audio output").clicked() {
+                    app.reinit_audio();
+                }
+                ui.colored_label(egui::Color32::from_rgb(255, 100, 100), "Audio unavailable")
+                    .on_hover_text(err);
+            }
         });
     });
 
+    render_trim_range(app, ui);
+
     // Row 2: I/O + Add segment
     ui.horizontal(|ui| {
-        if ui.button("[I] In").on_hover_text("Set In point (I)").clicked() {
+        if ui.button("[I] In").on_hover_text("Set In point (I)\nShift+Left/Right nudges the last marker by 1 frame").clicked() {
             app.set_in_point();
         }
         if ui.button("[O] Out").on_hover_text("Set Out point (O)").clicked() {
             app.set_out_point();
         }
 
-        if let Some(in_pt) = app.in_point {
-            ui.small(format!("IN: {}", format_time(in_pt)));
+        // Typed timecodes: Enter / focus loss moves the marker
+        ui.small("IN:");
+        let in_edit = ui.add(
+            egui::TextEdit::singleline(&mut app.trim_settings.start_time_str)
+                .desired_width(72.0)
+                .hint_text("--:--.---")
+                .font(egui::TextStyle::Monospace),
+        ).on_hover_text("Type a timecode (HH:MM:SS.mmm, MM:SS or seconds) and press Enter");
+        if in_edit.lost_focus() {
+            app.apply_typed_in_point();
         }
-        if let Some(out_pt) = app.out_point {
-            ui.small(format!("OUT: {}", format_time(out_pt)));
+        ui.small("OUT:");
+        let out_edit = ui.add(
+            egui::TextEdit::singleline(&mut app.trim_settings.end_time_str)
+                .desired_width(72.0)
+                .hint_text("--:--.---")
+                .font(egui::TextStyle::Monospace),
+        ).on_hover_text("Type a timecode (HH:MM:SS.mmm, MM:SS or seconds) and press Enter");
+        if out_edit.lost_focus() {
+            app.apply_typed_out_point();
+        }
+        if let (Some(i), Some(o)) = (app.in_point, app.out_point) {
+            let fps = app.selected_file().and_then(|f| f.info.framerate);
+            ui.small(format!("= {}", format_duration_frames((o - i).abs(), fps)))
+                .on_hover_text("IN → OUT duration");
         }
 
-        ui.separator();
+        let can_detect = app.selected_file().is_some() && !app.silence_ends_running;
+        if ui.add_enabled(can_detect, egui::Button::new("Trim silence ends").small())
+            .on_hover_text("Set IN/OUT past the silence at the very start and end of the file")
+            .clicked()
+        {
+            app.start_trim_silence_ends();
+        }
+        if app.silence_ends_running {
+            ui.spinner();
+        }
 
         let can_add = app.in_point.is_some() && app.out_point.is_some();
+        if can_add && app.split_settings.trim_mode == TrimMode::Lossless
+            && ui.small_button("⇥⇤ KF")
+                .on_hover_text("Snap IN forward / OUT back to keyframes for a clean lossless cut")
+                .clicked()
+        {
+            app.snap_in_out_lossless();
+        }
+
+        ui.separator();
+
         if ui.add_enabled(can_add, egui::Button::new("+ Segment (S)"))
             .on_hover_text("Create segment from IN/OUT points")
             .clicked()
@@ -345,6 +593,52 @@ fn render_playback_controls(app: &mut FFmpegApp, ui: &mut egui::Ui) {
             app.clear_in_out_points();
         }
 
+        if can_add && app.project.files.len() > 1 {
+            ui.menu_button("Trim all files", |ui| {
+                ui.label("Apply IN/OUT to every loaded file:");
+                for anchor in BatchTrimAnchor::all() {
+                    ui.radio_value(&mut app.batch_trim_anchor, *anchor, anchor.name());
+                }
+                ui.small("Clamped to each file's duration, saved as <name>_trim");
+                if ui.button(format!("Queue {} trims", app.project.files.len())).clicked() {
+                    app.trim_all_files();
+                    ui.close_menu();
+                }
+            }).response.on_hover_text("Strip the same intro/outro from all files");
+        }
+
+        if can_add && !app.selected_is_audio_only() {
+            ui.menu_button("Animation", |ui| {
+                let missing: Vec<Option<String>> = AnimationFormat::all().iter()
+                    .map(|f| app.missing_encoder(f.encoder()))
+                    .collect();
+                let settings = &mut app.animation_settings;
+                ui.horizontal(|ui| {
+                    for (format, missing) in AnimationFormat::all().iter().zip(&missing) {
+                        let radio = ui.add_enabled(missing.is_none(), egui::RadioButton::new(settings.format == *format, format.name()));
+                        if radio.clicked() {
+                            settings.format = *format;
+                        }
+                        if let Some(why) = missing {
+                            radio.on_disabled_hover_text(why);
+                        }
+                    }
+                });
+                ui.horizontal(|ui| {
+                    ui.label("FPS:");
+                    ui.add(egui::DragValue::new(&mut settings.fps).range(1..=60));
+                    ui.label("Width:");
+                    ui.add(egui::DragValue::new(&mut settings.width).range(0..=3840).suffix(" px"))
+                        .on_hover_text("0 = source width");
+                });
+                ui.small("Loops forever, no audio");
+                if ui.button(format!("Export IN→OUT as {}", settings.format.name())).clicked() {
+                    app.export_animation();
+                    ui.close_menu();
+                }
+            }).response.on_hover_text("Save the IN/OUT range as a looping WebP / APNG / GIF");
+        }
+
         // Segment navigation
         if !app.segments.is_empty() {
             ui.separator();
@@ -369,17 +663,33 @@ fn render_playback_controls(app: &mut FFmpegApp, ui: &mut egui::Ui) {
             ui.ctx().request_repaint();
         }
     } else {
+        let seekable = !app.selected_duration_unknown();
         ui.horizontal(|ui| {
+            if !seekable {
+                ui.disable();
+            }
             let mut current = app.current_time;
             ui.style_mut().spacing.slider_width = ui.available_width() - 20.0;
 
+            // Shift+click sets IN, Alt+click sets OUT at the clicked time (no seek)
+            let modifiers = ui.input(|i| i.modifiers);
+            let marking = modifiers.shift || modifiers.alt;
+
             let slider_response = ui.add(
                 egui::Slider::new(&mut current, 0.0..=duration.max(0.001))
                     .show_value(false)
                     .trailing_fill(true)
-            );
+            ).on_hover_text("Click/drag: seek\nShift+click: set IN\nAlt+click: set OUT");
 
-            if slider_response.changed() {
+            if marking {
+                if slider_response.clicked() {
+                    if modifiers.shift {
+                        app.set_in_point_at(current);
+                    } else {
+                        app.set_out_point_at(current);
+                    }
+                }
+            } else if slider_response.changed() {
                 app.seek(current);
             }
             if slider_response.dragged() || slider_response.changed() {
@@ -440,6 +750,29 @@ fn render_mode_tabs(app: &mut FFmpegApp, ui: &mut egui::Ui) {
     });
 }
 
+/// Replaces the timeline for a file without a duration: nothing to seek or
+/// cut on, but a remux usually gives it one
+fn render_unknown_duration(app: &mut FFmpegApp, ui: &mut egui::Ui) {
+    ui.add_space(8.0);
+    if app.selected_duration_pending() {
+        ui.horizontal(|ui| {
+            ui.spinner();
+            ui.label("No duration in the file: reading it through to measure it...");
+        });
+        return;
+    }
+    ui.colored_label(
+        egui::Color32::from_rgb(255, 180, 60),
+        "⚠ Duration unknown (live capture, fragmented or truncated file): timeline, trim and Auto-Cut are disabled",
+    );
+    if ui.button("Remux to fix the duration")
+        .on_hover_text("Copy the streams into a new file (no re-encode), which gets a proper duration.\nImport the result to edit it.")
+        .clicked()
+    {
+        app.remux_to_fix_duration();
+    }
+}
+
 fn render_timeline_panel(app: &mut FFmpegApp, ui: &mut egui::Ui) {
     // En mode Merge avec au moins 2 fichiers, la timeline affiche tous les clips
     // à fusionner bout à bout sur la durée totale, plutôt que les segments d'un fichier.
@@ -448,15 +781,25 @@ fn render_timeline_panel(app: &mut FFmpegApp, ui: &mut egui::Ui) {
         return;
     }
 
+    if app.selected_duration_unknown() {
+        render_unknown_duration(app, ui);
+        return;
+    }
+
     let duration = app.get_duration();
+    let selection = app.segment_selection();
+    app.probe_lossless_keyframes();
 
     let response = TimelineWidget::new(duration, app.current_time)
         .in_point(app.in_point)
         .out_point(app.out_point)
         .zoom(app.timeline_zoom)
         .scroll(app.timeline_scroll)
+        .max_size_bytes(app.split_settings.max_size_bytes())
         .segments(&app.segments)
         .selected_segment(app.selected_segment)
+        .selected_segments(&selection)
+        .keyframes(app.lossless_keyframes())
         .waveform_data(&app.current_waveform)
         .show(ui);
 
@@ -470,7 +813,14 @@ fn render_timeline_panel(app: &mut FFmpegApp, ui: &mut egui::Ui) {
         app.timeline_scroll = scroll;
     }
     if let Some(idx) = response.segment_clicked {
-        app.selected_segment = Some(idx);
+        let extend = ui.input(|i| i.modifiers.command);
+        app.click_segment(idx, extend);
+    }
+    if let Some((idx, action)) = response.segment_action {
+        apply_segment_action(app, idx, action);
+    }
+    if let Some(drag) = response.boundary_drag {
+        app.move_segment_boundary(drag.segment, drag.is_start, drag.time, drag.rolling);
     }
     if response.is_scrubbing {
         ui.ctx().request_repaint();
@@ -479,6 +829,246 @@ fn render_timeline_panel(app: &mut FFmpegApp, ui: &mut egui::Ui) {
         app.timeline_zoom = 1.0;
         app.timeline_scroll = 0.0;
     }
+    render_segment_rename(app, ui.ctx());
+}
+
+/// Run an entry of the timeline's segment context menu
+fn apply_segment_action(app: &mut FFmpegApp, idx: usize, action: SegmentAction) {
+    let Some(seg) = app.segments.get(idx) else { return };
+    let start = seg.start_time;
+    match action {
+        SegmentAction::Delete => app.remove_segment(idx),
+        SegmentAction::SplitAtPlayhead => app.split_segment_at(idx, app.current_time),
+        SegmentAction::MergeWithNext => app.merge_segment_with_next(idx),
+        SegmentAction::Rename => app.segment_rename = Some((idx, seg.label.clone())),
+        SegmentAction::ToggleEnabled => {
+            if let Some(seg) = app.segments.get_mut(idx) {
+                seg.enabled = !seg.enabled;
+            }
+        }
+        SegmentAction::GoToStart => app.seek(start),
+        SegmentAction::Export => app.export_segment(idx),
+    }
+}
+
+/// Small window editing a segment's label (opened from the context menu)
+fn render_segment_rename(app: &mut FFmpegApp, ctx: &egui::Context) {
+    let Some((idx, mut label)) = app.segment_rename.take() else { return };
+    let mut open = true;
+    let mut done = false;
+    egui::Window::new("Rename segment")
+        .open(&mut open)
+        .collapsible(false)
+        .resizable(false)
+        .show(ctx, |ui| {
+            let edit = ui.text_edit_singleline(&mut label);
+            if !edit.has_focus() && !edit.lost_focus() {
+                edit.request_focus();
+            }
+            let enter = edit.lost_focus() && ui.input(|i| i.key_pressed(egui::Key::Enter));
+            if ui.button("OK").clicked() || enter {
+                done = true;
+            }
+        });
+    if done {
+        app.rename_segment(idx, &label);
+    } else if open {
+        app.segment_rename = Some((idx, label));
+    }
+}
+
+/// Queued jobs; a pending job's output can be changed before it runs
+fn render_queue_panel(app: &mut FFmpegApp, ctx: &egui::Context) {
+    use crate::export_queue::JobStatus;
+    if !app.show_queue_panel {
+        return;
+    }
+    // (id, description, status text, output, status, progress)
+    let jobs: Vec<(u32, String, String, std::path::PathBuf, JobStatus, f32)> = app.export_queue.lock()
+        .map(|q| q.jobs.iter().map(|j| {
+            let status = match &j.status {
+                JobStatus::Pending => "Pending".to_string(),
+                JobStatus::Running => "Running".to_string(),
+                JobStatus::Completed => "Done".to_string(),
+                JobStatus::Failed(e) => format!("Failed: {}", e),
+            };
+            (j.id, j.description(), status, j.output.clone(), j.status.clone(), j.progress)
+        }).collect())
+        .unwrap_or_default();
+
+    let mut open = app.show_queue_panel;
+    egui::Window::new(format!("Export queue ({} jobs)", jobs.len()))
+        .open(&mut open)
+        .default_width(520.0)
+        .show(ctx, |ui| {
+            if let Ok(mut queue) = app.export_queue.lock() {
+                ui.horizontal(|ui| {
+                    ui.label("Run:");
+                    egui::ComboBox::from_id_salt("queue_order")
+                        .selected_text(queue.order.name())
+                        .show_ui(ui, |ui| {
+                            for order in crate::export_queue::QueueOrder::all() {
+                                ui.selectable_value(&mut queue.order, *order, order.name());
+                            }
+                        })
+                        .response
+                        .on_hover_text("Which pending job starts next. Lossless first gets the instant cuts done before slow re-encodes.");
+                    if jobs.iter().any(|j| matches!(j.4, JobStatus::Failed(_))) && ui.button("Retry failed").on_hover_text("Run every failed job again").clicked() {
+                        queue.retry_all_failed();
+                        app.show_export_progress = true;
+                    }
+                });
+            }
+            if jobs.is_empty() {
+                ui.label("No jobs queued.");
+                return;
+            }
+            egui::ScrollArea::vertical().max_height(360.0).show(ui, |ui| {
+                for (id, description, status_text, output, status, progress) in &jobs {
+                    ui.horizontal(|ui| {
+                        ui.label(description);
+                        ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                            let editing = app.queue_output_edit.as_ref().is_some_and(|(e, _)| e == id);
+                            if *status == JobStatus::Pending && !editing && ui.small_button("Edit").on_hover_text("Change output path").clicked() {
+                                app.queue_output_edit = Some((*id, output.to_string_lossy().to_string()));
+                            }
+                            if matches!(status, JobStatus::Failed(_)) && ui.small_button("Retry").on_hover_text("Run this job again").clicked() {
+                                if let Ok(mut queue) = app.export_queue.lock() {
+                                    queue.retry_job(*id);
+                                }
+                                app.show_export_progress = true;
+                            }
+                            if *status == JobStatus::Running {
+                                ui.add(egui::ProgressBar::new(*progress).show_percentage().desired_width(120.0));
+                            } else {
+                                ui.small(status_text);
+                            }
+                        });
+                    });
+                    if app.queue_output_edit.as_ref().is_some_and(|(e, _)| e == id) {
+                        render_job_output_edit(app, ui, *id);
+                    } else {
+                        ui.small(output.to_string_lossy());
+                    }
+                    ui.separator();
+                }
+            });
+        });
+    app.show_queue_panel = open;
+    if !open {
+        app.queue_output_edit = None;
+    }
+}
+
+/// FFmpeg version and the encoders / filters this build was compiled with
+fn render_ffmpeg_info(app: &mut FFmpegApp, ctx: &egui::Context) {
+    if !app.show_ffmpeg_info {
+        return;
+    }
+    let mut open = app.show_ffmpeg_info;
+    egui::Window::new("FFmpeg Info")
+        .open(&mut open)
+        .default_width(420.0)
+        .show(ctx, |ui| {
+            let caps = match &app.capabilities {
+                None => {
+                    ui.horizontal(|ui| {
+                        ui.spinner();
+                        ui.label("Probing ffmpeg...");
+                    });
+                    return;
+                }
+                Some(Err(e)) => {
+                    ui.colored_label(egui::Color32::from_rgb(255, 100, 100), e);
+                    return;
+                }
+                Some(Ok(caps)) => caps,
+            };
+            let version = if caps.version.is_empty() { "unknown" } else { caps.version.as_str() };
+            ui.label(format!("Version: {}", version));
+            ui.small(&caps.path);
+            ui.separator();
+
+            let badge = |ui: &mut egui::Ui, name: &str, present: bool| {
+                if present {
+                    ui.colored_label(egui::Color32::from_rgb(100, 200, 100), format!("✔ {}", name));
+                } else {
+                    ui.colored_label(egui::Color32::from_rgb(255, 100, 100), format!("✘ {}", name));
+                }
+            };
+            ui.label("Encoders used by exports:");
+            ui.horizontal_wrapped(|ui| {
+                for &name in crate::ffmpeg::KEY_ENCODERS {
+                    badge(ui, name, caps.has_encoder(name));
+                }
+            });
+            ui.label("Filters used by exports:");
+            ui.horizontal_wrapped(|ui| {
+                for &name in crate::ffmpeg::KEY_FILTERS {
+                    badge(ui, name, caps.has_filter(name));
+                }
+            });
+            ui.separator();
+
+            let list = |ui: &mut egui::Ui, title: String, names: &std::collections::BTreeSet<String>| {
+                egui::CollapsingHeader::new(title).show(ui, |ui| {
+                    egui::ScrollArea::vertical().max_height(200.0).show(ui, |ui| {
+                        ui.small(names.iter().cloned().collect::<Vec<_>>().join(", "));
+                    });
+                });
+            };
+            list(ui, format!("All encoders ({})", caps.encoders.len()), &caps.encoders);
+            list(ui, format!("All filters ({})", caps.filters.len()), &caps.filters);
+        });
+    app.show_ffmpeg_info = open;
+}
+
+/// Output path editor of one pending job: typed path or save dialog, checked
+/// before it is applied (missing folders can be created)
+fn render_job_output_edit(app: &mut FFmpegApp, ui: &mut egui::Ui, id: u32) {
+    let Some((_, text)) = app.queue_output_edit.as_mut() else { return };
+    let mut apply: Option<bool> = None;
+    let mut cancel = false;
+    ui.horizontal(|ui| {
+        ui.add(egui::TextEdit::singleline(text).desired_width(360.0).font(egui::TextStyle::Monospace));
+        if ui.small_button("...").on_hover_text("Browse").clicked() {
+            let current = std::path::PathBuf::from(text.as_str());
+            let mut dialog = rfd::FileDialog::new();
+            if let Some(dir) = current.parent().filter(|d| d.is_dir()) {
+                dialog = dialog.set_directory(dir);
+            }
+            if let Some(name) = current.file_name() {
+                dialog = dialog.set_file_name(name.to_string_lossy());
+            }
+            if let Some(path) = dialog.save_file() {
+                *text = path.to_string_lossy().to_string();
+            }
+        }
+    });
+    let output = std::path::PathBuf::from(text.trim());
+    let folder_missing = output.parent()
+        .is_some_and(|p| !p.as_os_str().is_empty() && !p.is_dir());
+    ui.horizontal(|ui| {
+        if folder_missing {
+            ui.colored_label(egui::Color32::from_rgb(255, 180, 60), "Folder does not exist");
+            if ui.small_button("Create folder and apply").clicked() {
+                apply = Some(true);
+            }
+        } else if ui.small_button("Apply").clicked() {
+            apply = Some(false);
+        }
+        if ui.small_button("Cancel").clicked() {
+            cancel = true;
+        }
+    });
+    if let Some(create_folder) = apply {
+        match app.set_job_output(id, output, create_folder) {
+            Ok(()) => app.queue_output_edit = None,
+            Err(e) => app.status_message = e,
+        }
+    } else if cancel {
+        app.queue_output_edit = None;
+    }
 }
 
 /// Ordre effectif des fichiers à fusionner (merge_file_order s'il est défini,
@@ -572,9 +1162,7 @@ fn render_file_list_panel(app: &mut FFmpegApp, ui: &mut egui::Ui) {
         ui.heading("Files");
         ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
             if ui.small_button("+").on_hover_text("Open Video... (Ctrl+O)").clicked() {
-                if let Some(paths) = rfd::FileDialog::new()
-                    .add_filter("Video", &["mp4", "mkv", "avi", "mov", "webm", "ts", "flv"])
-                    .add_filter("All Files", &["*"])
+                if let Some(paths) = super::file_browser::media_file_dialog()
                     .pick_files()
                 {
                     app.add_files(paths);
@@ -609,24 +1197,49 @@ fn render_file_list_panel(app: &mut FFmpegApp, ui: &mut egui::Ui) {
                 let is_selected = app.selected_file_index == Some(i);
 
                 ui.horizontal(|ui| {
-                    let label_text = format!("{} ({})", file.filename(), file.duration_string());
-                    let label = egui::RichText::new(label_text).small();
+                    let problem = file.problem();
+                    let label_text = match problem {
+                        Some(_) => format!("⚠ {} ({})", file.filename(), file.duration_string()),
+                        None => format!("{} ({})", file.filename(), file.duration_string()),
+                    };
+                    let mut label = egui::RichText::new(label_text).small();
+                    if problem.is_some() {
+                        label = label.color(egui::Color32::from_rgb(255, 165, 0));
+                    }
                     let response = ui.selectable_label(is_selected, label);
                     if response.clicked() {
                         select_idx = Some(i);
                     }
-                    response.on_hover_text(format!(
+                    let mut hover = format!(
                         "{}\n{} | {} | {}",
                         file.path.display(),
                         file.resolution_string(),
                         file.duration_string(),
                         format_size(file.info.file_size),
-                    ));
+                    );
+                    if let Some(problem) = problem {
+                        hover.push_str(&format!("\n⚠ Corrupt or unreadable: {} - exports are disabled", problem));
+                    }
+                    response.on_hover_text(hover);
 
                     ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
                         if ui.small_button("x").on_hover_text("Remove file").clicked() {
                             remove_idx = Some(i);
                         }
+                        // The selected file's segments live in `app.segments` until saved back
+                        let seg_count = if is_selected {
+                            app.segments.len()
+                        } else {
+                            app.file_segments.get(&file.path).map_or(0, Vec::len)
+                        };
+                        if seg_count > 0 {
+                            ui.label(
+                                egui::RichText::new(format!("{} seg", seg_count))
+                                    .small()
+                                    .color(egui::Color32::from_rgb(100, 200, 100)),
+                            )
+                            .on_hover_text(format!("{} segment(s)", seg_count));
+                        }
                     });
                 });
             }
@@ -643,15 +1256,16 @@ fn render_file_list_panel(app: &mut FFmpegApp, ui: &mut egui::Ui) {
     ui.separator();
 
     ui.horizontal(|ui| {
-        if !app.project.files.is_empty() {
-            if ui.button("Remove All").clicked() {
-                app.remove_all_files();
-            }
+        if !app.project.files.is_empty()
+            && ui.button("Remove All").clicked() {
+            app.remove_all_files();
         }
     });
 
     ui.small(format!("{} file(s)", app.project.files.len()));
 
+    render_file_properties(app, ui);
+
     // ---- MERGE SECTION directly in file list panel (always visible) ----
     if app.project.files.len() >= 2 {
         ui.add_space(8.0);
@@ -673,14 +1287,14 @@ fn render_file_list_panel(app: &mut FFmpegApp, ui: &mut egui::Ui) {
         });
         if ui.button("Browse output...").on_hover_text("Choose where to save the merged file").clicked() {
             if let Some(folder) = rfd::FileDialog::new().pick_folder() {
-                app.split_settings.output_folder = Some(folder);
+                app.set_output_folder(Some(folder));
             }
         }
-        if app.split_settings.output_folder.is_some() {
-            if ui.small_button("Reset folder").clicked() {
-                app.split_settings.output_folder = None;
-            }
+        if app.split_settings.output_folder.is_some()
+            && ui.small_button("Reset folder").clicked() {
+            app.split_settings.output_folder = None;
         }
+        render_recent_folders(app, ui, "recent_folders_merge");
 
         ui.add_space(4.0);
 
@@ -725,6 +1339,115 @@ fn render_file_list_panel(app: &mut FFmpegApp, ui: &mut egui::Ui) {
     }
 }
 
+/// Quick trim: drag both ends of the clip on one compact slider. Shares the
+/// IN/OUT markers with the timeline; the playhead follows the held handle.
+fn render_trim_range(app: &mut FFmpegApp, ui: &mut egui::Ui) {
+    let duration = app.get_duration();
+    if duration <= 0.0 {
+        return;
+    }
+    ui.horizontal(|ui| {
+        ui.small("Trim:");
+        // Keep room for the label on the right
+        let slider_width = (ui.available_width() - 230.0).max(120.0);
+        let response = ui.allocate_ui(egui::vec2(slider_width, 18.0), |ui| {
+            crate::ui::range_slider(ui, app.in_point, app.out_point, duration, app.current_time)
+        }).inner;
+        if let Some((start, end)) = response.range {
+            app.set_in_out_range(start, end);
+        }
+        if let Some(t) = response.preview_time {
+            if app.get_playback_state() == PlaybackState::Playing {
+                app.pause();
+            }
+            app.seek(t);
+        }
+        let start = app.in_point.unwrap_or(0.0);
+        let end = app.out_point.unwrap_or(duration);
+        let fps = app.selected_file().and_then(|f| f.info.framerate);
+        ui.small(format!(
+            "{} → {} = {}",
+            format_time(start),
+            format_time(end),
+            format_duration_frames((end - start).max(0.0), fps)
+        ))
+        .on_hover_text("Drag a handle to move IN or OUT, the middle to slide both");
+    });
+}
+
+/// Stream details of the selected file, with an on-demand loudness measurement
+fn render_file_properties(app: &mut FFmpegApp, ui: &mut egui::Ui) {
+    let Some(file) = app.selected_file() else { return };
+    let info = file.info.clone();
+    let path = file.path.clone();
+    let decode_path = app.player.as_ref().and_then(|p| p.decode_path());
+
+    egui::CollapsingHeader::new("Properties")
+        .id_salt("file_properties")
+        .show(ui, |ui| {
+            egui::Grid::new("file_properties_grid").num_columns(2).show(ui, |ui| {
+                let mut row = |name: &str, value: String| {
+                    ui.small(name);
+                    ui.small(value);
+                    ui.end_row();
+                };
+                row("Container", info.format_name.clone());
+                row("Duration", format_time(info.duration));
+                row("Size", format_size(info.file_size));
+                if let Some(codec) = &info.video_codec {
+                    let fps = info.framerate.map(|f| format!(" @ {:.3} fps", f)).unwrap_or_default();
+                    row("Video", format!("{} {}x{}{}", codec, info.width, info.height, fps));
+                }
+                if let Some(codec) = &info.audio_codec {
+                    let rate = info.sample_rate.map(|r| format!(" {} Hz", r)).unwrap_or_default();
+                    let channels = info.channels.map(|c| format!(" {}ch", c)).unwrap_or_default();
+                    row("Audio", format!("{}{}{}", codec, rate, channels));
+                }
+                let bitrate = info.video_bitrate.unwrap_or(0) + info.audio_bitrate.unwrap_or(0);
+                if bitrate > 0 {
+                    row("Bitrate", crate::utils::format_bitrate(bitrate));
+                }
+                if let Some(path) = decode_path.as_ref() {
+                    row("Preview decode", path.label());
+                }
+            });
+
+            if info.audio_codec.is_none() {
+                return;
+            }
+            ui.horizontal(|ui| {
+                ui.small("Loudness:");
+                match app.loudness.get(&path) {
+                    Some(Ok(l)) => {
+                        let text = format!("{:.1} LUFS, TP {:.1} dB, LRA {:.1}", l.integrated_lufs, l.true_peak_db, l.lra);
+                        if l.is_clipping() {
+                            ui.colored_label(egui::Color32::from_rgb(255, 100, 100), egui::RichText::new(text).small())
+                                .on_hover_text("True peak above -1 dBTP: may clip after lossy encoding");
+                        } else {
+                            ui.small(text);
+                        }
+                    }
+                    Some(Err(e)) => {
+                        ui.colored_label(egui::Color32::from_rgb(255, 100, 100), egui::RichText::new("failed").small())
+                            .on_hover_text(e.as_str());
+                    }
+                    None if app.loudness_running.as_ref() == Some(&path) => {
+                        ui.spinner();
+                    }
+                    None => {
+                        let busy = app.loudness_running.is_some();
+                        if ui.add_enabled(!busy, egui::Button::new("Analyze").small())
+                            .on_hover_text("Measure integrated loudness, true peak and loudness range (EBU R128)")
+                            .clicked()
+                        {
+                            app.start_loudness_analysis();
+                        }
+                    }
+                }
+            });
+        });
+}
+
 fn render_segment_list(app: &mut FFmpegApp, ui: &mut egui::Ui) {
     ui.heading("Segments");
 
@@ -737,6 +1460,12 @@ fn render_segment_list(app: &mut FFmpegApp, ui: &mut egui::Ui) {
     let mut to_remove: Option<usize> = None;
     let mut to_select: Option<usize> = None;
     let mut toggle_enable: Option<usize> = None;
+    let mut to_seek: Option<f64> = None;
+    let mut apply_times: Option<usize> = None;
+    let fps = app.selected_file().and_then(|f| f.info.framerate);
+    let max_bytes = app.split_settings.max_size_bytes();
+    app.probe_lossless_keyframes();
+    let keyframes = app.lossless_keyframes().to_vec();
 
     egui::ScrollArea::vertical()
         .auto_shrink([false, false])
@@ -744,33 +1473,90 @@ fn render_segment_list(app: &mut FFmpegApp, ui: &mut egui::Ui) {
         .show(ui, |ui| {
             for (i, seg) in app.segments.iter().enumerate() {
                 let is_selected = app.selected_segment == Some(i);
+                let in_selection = app.is_segment_selected(i);
 
                 ui.horizontal(|ui| {
                     // Checkbox
                     let mut enabled = seg.enabled;
-                    if ui.checkbox(&mut enabled, "").changed() {
+                    if ui.checkbox(&mut enabled, "").on_hover_text("Export this segment (E toggles and plays the next)").changed() {
                         toggle_enable = Some(i);
                     }
 
                     // Segment info (clickable)
                     let label = format!(
-                        "#{} {} - {} (~{})",
+                        "#{} {} - {} [{}] (~{})",
                         i + 1,
                         format_time(seg.start_time),
                         format_time(seg.end_time),
+                        format_duration_frames(seg.duration(), fps),
                         format_size(seg.estimated_size_bytes),
                     );
 
-                    let response = ui.selectable_label(is_selected, &label);
+                    // Over the size limit: red, with the overage, so it can be re-split before export
+                    let overage = (max_bytes > 0 && seg.estimated_size_bytes > max_bytes)
+                        .then(|| seg.estimated_size_bytes - max_bytes);
+                    let response = match overage {
+                        Some(over) => ui.selectable_label(
+                            in_selection,
+                            egui::RichText::new(format!("{} +{} over", label, format_size(over)))
+                                .color(egui::Color32::from_rgb(255, 100, 100)),
+                        )
+                        .on_hover_text("Exceeds the max size. Export auto-splits it; split it yourself to choose where"),
+                        None => ui.selectable_label(in_selection, &label)
+                            .on_hover_text("Ctrl+click to select several segments"),
+                    };
+                    // Lossless: where the -c copy cut really lands
+                    let response = if keyframes.is_empty() {
+                        response
+                    } else {
+                        response.on_hover_text(format!(
+                            "{}\n{}",
+                            lossless_cut_hint(&keyframes, seg.start_time, true),
+                            lossless_cut_hint(&keyframes, seg.end_time, false)
+                        ))
+                    };
                     if response.clicked() {
                         to_select = Some(i);
                     }
 
+                    if ui.small_button("\u{25B6}").on_hover_text("Seek to segment start").clicked() {
+                        to_seek = Some(seg.start_time);
+                    }
+
                     // Delete button
                     if ui.small_button("x").on_hover_text("Remove segment (Del)").clicked() {
                         to_remove = Some(i);
                     }
                 });
+
+                // Editable boundaries for the selected segment
+                if is_selected {
+                    let start_id = ui.make_persistent_id(("segment_start_edit", i));
+                    let end_id = ui.make_persistent_id(("segment_end_edit", i));
+                    let editing = ui.memory(|m| m.has_focus(start_id) || m.has_focus(end_id));
+                    let (start_buf, end_buf) = &mut app.segment_time_edit;
+                    if !editing {
+                        *start_buf = format_time(seg.start_time);
+                        *end_buf = format_time(seg.end_time);
+                    }
+
+                    ui.horizontal(|ui| {
+                        ui.add_space(24.0);
+                        ui.small("Start:");
+                        let start_edit = ui.add(egui::TextEdit::singleline(start_buf)
+                            .id(start_id)
+                            .desired_width(72.0)
+                            .font(egui::TextStyle::Monospace));
+                        ui.small("End:");
+                        let end_edit = ui.add(egui::TextEdit::singleline(end_buf)
+                            .id(end_id)
+                            .desired_width(72.0)
+                            .font(egui::TextStyle::Monospace));
+                        if start_edit.lost_focus() || end_edit.lost_focus() {
+                            apply_times = Some(i);
+                        }
+                    });
+                }
             }
         });
 
@@ -780,12 +1566,40 @@ fn render_segment_list(app: &mut FFmpegApp, ui: &mut egui::Ui) {
         }
     }
     if let Some(idx) = to_select {
-        app.selected_segment = Some(idx);
+        let extend = ui.input(|i| i.modifiers.command);
+        app.click_segment(idx, extend);
+    }
+    if let Some(idx) = apply_times {
+        app.apply_segment_time_edit(idx);
+    }
+    if let Some(t) = to_seek {
+        app.seek(t);
     }
     if let Some(idx) = to_remove {
         app.remove_segment(idx);
     }
 
+    // Bulk actions on a Ctrl+click selection
+    let selected = app.segment_selection().len();
+    if selected > 1 {
+        ui.horizontal(|ui| {
+            ui.label(format!("{} selected:", selected));
+            if ui.small_button("Toggle").on_hover_text("Enable or disable all selected segments").clicked() {
+                app.toggle_selected_segments();
+            }
+            if ui.small_button("Delete").on_hover_text("Remove the selected segments (Del)").clicked() {
+                app.remove_selected_segments();
+            }
+            if ui.small_button("Export").on_hover_text("Export only the selected segments").clicked() {
+                app.export_selected_segments();
+            }
+            if ui.small_button("Clear").on_hover_text("Keep only the current segment selected").clicked() {
+                let current = app.selected_segment;
+                app.select_segment(current);
+            }
+        });
+    }
+
     ui.separator();
 
     ui.horizontal(|ui| {
@@ -802,8 +1616,8 @@ fn render_segment_list(app: &mut FFmpegApp, ui: &mut egui::Ui) {
 
         ui.separator();
 
-        let can_split = app.selected_segment.map_or(false, |idx| {
-            app.segments.get(idx).map_or(false, |seg| {
+        let can_split = app.selected_segment.is_some_and(|idx| {
+            app.segments.get(idx).is_some_and(|seg| {
                 app.current_time > seg.start_time && app.current_time < seg.end_time
             })
         });
@@ -824,24 +1638,682 @@ fn render_segment_list(app: &mut FFmpegApp, ui: &mut egui::Ui) {
             app.duplicate_segment();
         }
 
-        if ui.small_button("Clear All")
-            .on_hover_text("Remove all segments (Ctrl+Shift+Del)")
+        let has_fps = app.selected_file().is_some_and(|f| f.info.framerate.is_some());
+        if ui.add_enabled(has_fps, egui::Button::new("Snap").small())
+            .on_hover_text("Snap segment boundaries to the frame grid")
             .clicked()
         {
-            app.clear_all_segments();
+            app.snap_segments_to_frames();
         }
-    });
-}
 
-fn render_split_settings(app: &mut FFmpegApp, ui: &mut egui::Ui) {
-    ui.heading("Settings");
+        if app.project.files.len() >= 2 {
+            ui.menu_button("Copy to", |ui| {
+                let current = app.selected_file_index;
+                let others: Vec<(usize, String)> = app.project.files.iter().enumerate()
+                    .filter(|(i, _)| Some(*i) != current)
+                    .map(|(i, f)| (i, f.filename()))
+                    .collect();
+                if ui.button("All other files").clicked() {
+                    let targets: Vec<usize> = others.iter().map(|(i, _)| *i).collect();
+                    app.copy_segments_to(&targets);
+                    ui.close_menu();
+                }
+                ui.separator();
+                for (idx, name) in others {
+                    if ui.button(name).clicked() {
+                        app.copy_segments_to(&[idx]);
+                        ui.close_menu();
+                    }
+                }
+            })
+            .response
+            .on_hover_text("Replace other files' segments with these boundaries (clamped to each file's duration)");
+        }
+
+        if ui.small_button("EDL...")
+            .on_hover_text("Save enabled segments as an ffconcat playlist or a CMX3600 EDL (no encoding)")
+            .clicked()
+        {
+            let stem = app.selected_file()
+                .map(|f| f.path.file_stem().unwrap_or_default().to_string_lossy().to_string())
+                .unwrap_or_else(|| "segments".to_string());
+            if let Some(path) = rfd::FileDialog::new()
+                .set_file_name(format!("{}.ffconcat", stem))
+                .add_filter("ffconcat playlist", &["ffconcat", "txt"])
+                .add_filter("CMX3600 EDL", &["edl"])
+                .save_file()
+            {
+                app.export_edl(&path);
+            }
+        }
+
+        if ui.small_button("Thumbnails")
+            .on_hover_text("Save a JPEG of each enabled segment's first frame, named like its output file")
+            .clicked()
+        {
+            app.export_segment_thumbnails();
+        }
+
+        if ui.small_button("Invert")
+            .on_hover_text("Replace the segments with the gaps between them: mark what to cut, keep the rest")
+            .clicked()
+        {
+            app.invert_segments();
+        }
+
+        if ui.small_button("Clear All")
+            .on_hover_text("Remove all segments (Ctrl+Shift+Del)")
+            .clicked()
+        {
+            app.clear_all_segments();
+        }
+    });
+}
+
+/// Estimated vs. actual size of the last batch: overall error, one line per output on hover
+fn render_size_checks(app: &FFmpegApp, ui: &mut egui::Ui) {
+    let estimated: u64 = app.size_checks.iter().map(|c| c.estimated).sum();
+    let actual: u64 = app.size_checks.iter().map(|c| c.actual).sum();
+    let total = crate::export_queue::SizeCheck { output: std::path::PathBuf::new(), estimated, actual };
+    let error = total.error_percent();
+    let color = if error.abs() <= 10.0 {
+        egui::Color32::from_rgb(100, 200, 100)
+    } else {
+        egui::Color32::from_rgb(255, 180, 60)
+    };
+    let lines: Vec<String> = app.size_checks.iter()
+        .map(|c| format!(
+            "{}: estimated {} → {} ({:+.0}%)",
+            c.output.file_name().unwrap_or_default().to_string_lossy(),
+            format_size(c.estimated),
+            format_size(c.actual),
+            c.error_percent(),
+        ))
+        .collect();
+    ui.separator();
+    ui.colored_label(color, format!("Size {} vs est. {} ({:+.0}%)", format_size(actual), format_size(estimated), error))
+        .on_hover_text(format!("Estimates come from the source bitrate; re-encoding changes them.\n{}", lines.join("\n")));
+}
 
-    // Trim mode
+/// Free space on the output drive for the last export, red when it may not fit
+fn render_disk_space(disk: &crate::app::DiskSpaceCheck, ui: &mut egui::Ui) {
+    let hover = format!(
+        "{}\nEstimated output: ~{}\nFree: {}",
+        disk.folder.display(),
+        format_size(disk.needed),
+        format_size(disk.free)
+    );
+    match disk.shortfall() {
+        Some(missing) => {
+            ui.colored_label(egui::Color32::from_rgb(255, 100, 100), format!("⚠ Disk: {} free, ~{} short", format_size(disk.free), format_size(missing)))
+                .on_hover_text(format!("{}\nThe export will likely stop with a write error once the drive is full.", hover));
+        }
+        None => {
+            ui.weak(format!("{} free", format_size(disk.free))).on_hover_text(hover);
+        }
+    }
+}
+
+/// Quick-pick among the recently used output folders (folders that no
+/// longer exist are left out)
+fn render_recent_folders(app: &mut FFmpegApp, ui: &mut egui::Ui, id: &str) {
+    let current = app.split_settings.output_folder.as_ref();
+    if app.recent_output_folders.iter().all(|f| Some(f) == current) {
+        return;
+    }
+    let mut picked = None;
+    egui::ComboBox::from_id_salt(id)
+        .selected_text("Recent")
+        .show_ui(ui, |ui| {
+            let choices = app.recent_output_choices();
+            if choices.is_empty() {
+                ui.weak("No other folder");
+            }
+            for folder in choices {
+                let name = folder.file_name().map_or_else(|| folder.to_string_lossy(), |n| n.to_string_lossy());
+                if ui.selectable_label(false, name).on_hover_text(folder.display().to_string()).clicked() {
+                    picked = Some(folder);
+                }
+            }
+        })
+        .response
+        .on_hover_text("Recently used output folders");
+    if let Some(folder) = picked {
+        app.set_output_folder(Some(folder));
+    }
+}
+
+/// Existing-output policy combo (shared by segment exports and merges)
+fn render_conflict_policy(app: &mut FFmpegApp, ui: &mut egui::Ui) {
+    ui.label("If exists:");
+    egui::ComboBox::from_id_salt("conflict_policy")
+        .selected_text(app.split_settings.conflict_policy.name())
+        .show_ui(ui, |ui| {
+            for policy in ConflictPolicy::all() {
+                let item = ui.selectable_value(&mut app.split_settings.conflict_policy, *policy, policy.name());
+                if *policy == ConflictPolicy::Skip {
+                    item.on_hover_text("Keep outputs that already exist and validate (non-empty, probeable, right duration).\nBroken or truncated files are exported again.");
+                }
+            }
+        });
+}
+
+/// Output container; the file extension follows it so players pick the right demuxer
+fn render_container_setting(app: &mut FFmpegApp, ui: &mut egui::Ui) {
+    // Only re-encoded exports need the container's encoders
+    let reencode = app.split_settings.trim_mode.is_reencode();
+    let missing: Vec<Option<String>> = ExportSettings::CONTAINERS.iter()
+        .map(|ext| app.missing_container_encoder(ext).filter(|_| reencode))
+        .collect();
+    let settings = &mut app.export_settings;
+    ui.label("Format:");
+    let text = settings.container.clone().unwrap_or_else(|| "Same as source".to_string());
+    egui::ComboBox::from_id_salt("output_container")
+        .selected_text(text)
+        .show_ui(ui, |ui| {
+            ui.selectable_value(&mut settings.container, None, "Same as source");
+            for (&ext, missing) in ExportSettings::CONTAINERS.iter().zip(&missing) {
+                let selected = settings.container.as_deref() == Some(ext);
+                let item = ui.add_enabled(missing.is_none(), egui::SelectableLabel::new(selected, ext));
+                if item.clicked() {
+                    settings.container = Some(ext.to_string());
+                }
+                if let Some(why) = missing {
+                    item.on_disabled_hover_text(why);
+                }
+            }
+        })
+        .response
+        .on_hover_text("Lossless/Smart Cut can only switch to a container that accepts the source codecs");
+
+    let target = app.export_settings.container.clone();
+    let source_ext = app.selected_file()
+        .and_then(|f| f.path.extension())
+        .map(|e| e.to_string_lossy().to_lowercase());
+    if let Some(ext) = target.filter(|ext| Some(ext.to_lowercase()) != source_ext) {
+        if ui.small_button("Remux")
+            .on_hover_text(format!(
+                "Copy the whole file into .{} without re-encoding (instant, lossless).\n\
+                 Refused if the codecs don't fit .{}; outside MKV only audio and video streams are kept.",
+                ext, ext
+            ))
+            .clicked()
+        {
+            app.remux_selected_file();
+        }
+    }
+}
+
+/// Pixel format forced on re-encoded exports
+fn render_pix_fmt_setting(app: &mut FFmpegApp, ui: &mut egui::Ui) {
+    let settings = &mut app.export_settings;
+    ui.horizontal(|ui| {
+        ui.label("Pixel format:");
+        let text = settings.pix_fmt.clone().unwrap_or_else(|| "Auto".to_string());
+        egui::ComboBox::from_id_salt("pix_fmt")
+            .selected_text(text)
+            .show_ui(ui, |ui| {
+                ui.selectable_value(&mut settings.pix_fmt, None, "Auto")
+                    .on_hover_text("yuv420p for MP4/MOV, source format otherwise");
+                for &fmt in ExportSettings::PIX_FMTS {
+                    ui.selectable_value(&mut settings.pix_fmt, Some(fmt.to_string()), fmt);
+                }
+            });
+    });
+}
+
+/// Output frame rate (`fps` filter + `-r`), with hints for VFR and upconverted sources
+fn render_frame_rate_setting(app: &mut FFmpegApp, ui: &mut egui::Ui) {
+    let source = app.selected_file().map(|f| (f.info.framerate, f.info.avg_framerate, f.info.is_variable_framerate()));
+    let settings = &mut app.export_settings;
+    ui.horizontal(|ui| {
+        ui.label("Frame rate:");
+        let text = match settings.output_fps {
+            Some(fps) => format!("{} fps", fps),
+            None => "Keep".to_string(),
+        };
+        egui::ComboBox::from_id_salt("output_fps")
+            .selected_text(text)
+            .show_ui(ui, |ui| {
+                ui.selectable_value(&mut settings.output_fps, None, "Keep")
+                    .on_hover_text("Source frame rate");
+                for &fps in ExportSettings::FRAME_RATES {
+                    ui.selectable_value(&mut settings.output_fps, Some(fps), format!("{} fps", fps));
+                }
+            });
+    });
+
+    let Some((framerate, avg_framerate, vfr)) = source else { return };
+    let warn = egui::Color32::from_rgb(255, 165, 0);
+    match settings.output_fps {
+        None if vfr => {
+            ui.small(egui::RichText::new(format!(
+                "⚠ Variable frame rate source (avg {:.2} fps): pick a rate to export constant frame rate",
+                avg_framerate.unwrap_or_default()
+            )).color(warn));
+        }
+        Some(fps) => {
+            let source_fps = if vfr { avg_framerate } else { framerate };
+            if let Some(source_fps) = source_fps.filter(|s| fps > *s + 0.01) {
+                ui.small(egui::RichText::new(format!(
+                    "⚠ Source is {:.2} fps: frames will be duplicated, not interpolated",
+                    source_fps
+                )).color(warn));
+            } else if vfr {
+                ui.small("Variable frame rate source: converted to constant frame rate");
+            }
+        }
+        None => {}
+    }
+}
+
+/// Named encode profile (codec, CRF, preset...) from a shared JSON file
+fn render_encode_profile_setting(app: &mut FFmpegApp, ui: &mut egui::Ui) {
+    ui.horizontal(|ui| {
+        ui.label("Profile:");
+        let mut selected = app.selected_profile.clone();
+        egui::ComboBox::from_id_salt("encode_profile")
+            .selected_text(selected.as_deref().unwrap_or("Mode default"))
+            .show_ui(ui, |ui| {
+                ui.selectable_value(&mut selected, None, "Mode default");
+                for profile in &app.encode_profiles {
+                    ui.selectable_value(&mut selected, Some(profile.name.clone()), &profile.name);
+                }
+            });
+        if selected != app.selected_profile {
+            app.select_profile(selected);
+        }
+        let hover = match &app.profiles_path {
+            Some(path) => format!("Reload or choose a profile file\nCurrent: {}", path.display()),
+            None => "Load encode profiles from a JSON file".to_string(),
+        };
+        if ui.small_button("Load...").on_hover_text(hover).clicked() {
+            if let Some(path) = rfd::FileDialog::new().add_filter("Encode profiles", &["json"]).pick_file() {
+                app.load_encode_profiles(path);
+            }
+        }
+    });
+    let settings = &app.export_settings;
+    if app.selected_profile.is_some() {
+        let mut parts = Vec::new();
+        if let Some(codec) = &settings.video_codec { parts.push(codec.clone()); }
+        if let Some(crf) = settings.crf { parts.push(format!("crf {}", crf)); }
+        if let Some(preset) = &settings.preset { parts.push(preset.clone()); }
+        if !settings.extra_args.is_empty() { parts.push(settings.extra_args.join(" ")); }
+        if !parts.is_empty() {
+            ui.small(parts.join(" · "));
+        }
+    }
+}
+
+/// Audio bitrate / sample rate used by the re-encoding trim modes
+fn render_audio_encode_settings(app: &mut FFmpegApp, ui: &mut egui::Ui) {
+    let default_kbps = if app.split_settings.trim_mode == TrimMode::HighQuality { 256 } else { 192 };
+    let settings = &mut app.export_settings;
+
+    ui.horizontal(|ui| {
+        ui.label("Audio:");
+        let bitrate_text = match settings.audio_bitrate_kbps {
+            Some(kbps) => format!("{} kb/s", kbps),
+            None => format!("Default ({} kb/s)", default_kbps),
+        };
+        egui::ComboBox::from_id_salt("audio_bitrate")
+            .selected_text(bitrate_text)
+            .show_ui(ui, |ui| {
+                ui.selectable_value(&mut settings.audio_bitrate_kbps, None, format!("Default ({} kb/s)", default_kbps));
+                for &kbps in ExportSettings::AUDIO_BITRATES {
+                    ui.selectable_value(&mut settings.audio_bitrate_kbps, Some(kbps), format!("{} kb/s", kbps));
+                }
+            });
+
+        let rate_text = match settings.audio_sample_rate {
+            Some(rate) => format!("{} Hz", rate),
+            None => "Source rate".to_string(),
+        };
+        egui::ComboBox::from_id_salt("audio_sample_rate")
+            .selected_text(rate_text)
+            .show_ui(ui, |ui| {
+                ui.selectable_value(&mut settings.audio_sample_rate, None, "Source rate");
+                for &rate in ExportSettings::SAMPLE_RATES {
+                    ui.selectable_value(&mut settings.audio_sample_rate, Some(rate), format!("{} Hz", rate));
+                }
+            });
+    });
+
+    ui.horizontal(|ui| {
+        ui.label("Audio offset:");
+        let mut offset_ms = app.export_settings.audio_offset_ms.unwrap_or(0);
+        if ui.add(
+            egui::DragValue::new(&mut offset_ms)
+                .range(-5000..=5000)
+                .speed(5.0)
+                .suffix(" ms")
+        )
+        .on_hover_text("Shift audio relative to video (+ = audio later).\nApplied to the preview while playing.")
+        .changed()
+        {
+            app.set_audio_offset_ms(Some(offset_ms));
+        }
+        if app.export_settings.audio_offset_ms.is_some() && ui.small_button("Reset").clicked() {
+            app.set_audio_offset_ms(None);
+        }
+    });
+}
+
+fn rotation_combo(ui: &mut egui::Ui, rotation: &mut Rotation) {
+    egui::ComboBox::from_id_salt("filter_rotation")
+        .selected_text(rotation.name())
+        .show_ui(ui, |ui| {
+            for r in Rotation::all() {
+                ui.selectable_value(rotation, *r, r.name());
+            }
+        });
+}
+
+/// Lossless mode: fix sideways footage by rewriting the display matrix only
+fn render_rotation_flag_setting(app: &mut FFmpegApp, ui: &mut egui::Ui) {
+    let filters = &mut app.export_settings.filters;
+    ui.horizontal(|ui| {
+        let mut enabled = filters.rotation_method == RotationMethod::Metadata;
+        if ui.checkbox(&mut enabled, "Set rotation flag:")
+            .on_hover_text("Stream copy with a new display rotation (instant, no re-encode)")
+            .changed()
+        {
+            filters.rotation_method = if enabled { RotationMethod::Metadata } else { RotationMethod::Reencode };
+        }
+        if enabled {
+            rotation_combo(ui, &mut filters.rotation);
+        }
+    });
+}
+
+/// Cap on FFmpeg threads for re-encodes, to keep the machine usable during long batches
+fn render_thread_limit_setting(app: &mut FFmpegApp, ui: &mut egui::Ui) {
+    let cores = std::thread::available_parallelism().map_or(16, |n| n.get() as u32);
+    let mut threads = app.export_settings.threads.unwrap_or(0);
+    if ui.add(egui::Slider::new(&mut threads, 0..=cores).text("threads"))
+        .on_hover_text("Limit to N threads (0 = auto, all cores)")
+        .changed()
+    {
+        app.export_settings.threads = (threads > 0).then_some(threads);
+    }
+}
+
+/// Display aspect ratio tag: fixes squished anamorphic footage, also in Lossless mode
+fn render_display_aspect(ui: &mut egui::Ui, display_aspect: &mut Option<String>) {
+    ui.horizontal(|ui| {
+        let mut enabled = display_aspect.is_some();
+        if ui.checkbox(&mut enabled, "Display aspect ratio:")
+            .on_hover_text("Set the DAR tag (-aspect) without touching the pixels.\nLossless mode also rewrites the H.264/HEVC/MPEG-2 stream header.")
+            .changed()
+        {
+            *display_aspect = enabled.then(|| "16:9".to_string());
+        }
+        if let Some(ratio) = display_aspect.as_mut() {
+            ui.add(egui::TextEdit::singleline(ratio).desired_width(60.0).hint_text("16:9"));
+            egui::ComboBox::from_id_salt("filter_aspect")
+                .selected_text("Presets")
+                .show_ui(ui, |ui| {
+                    for preset in FilterSettings::ASPECT_PRESETS {
+                        if ui.selectable_label(ratio == preset, *preset).clicked() {
+                            *ratio = preset.to_string();
+                        }
+                    }
+                });
+            if let Err(e) = crate::ffmpeg::parse_aspect_ratio(ratio) {
+                ui.colored_label(egui::Color32::from_rgb(255, 100, 100), "⚠ invalid").on_hover_text(e);
+            }
+        }
+    });
+}
+
+/// Video and audio filters applied by the re-encoding trim modes
+fn render_filter_settings(app: &mut FFmpegApp, ui: &mut egui::Ui) {
+    // Crop / rotate / resize make no sense without a picture
+    let audio_only = app.selected_is_audio_only();
+    let no_yadif = app.missing_filter("yadif");
+    let filters = &mut app.export_settings.filters;
+    let header = if filters.is_identity() { "Filters" } else { "Filters (active)" };
+    egui::CollapsingHeader::new(header)
+        .id_salt("video_filters")
+        .show(ui, |ui| {
+            if !audio_only {
+                ui.horizontal(|ui| {
+                    ui.checkbox(&mut app.filter_preview, "Preview on paused frame")
+                        .on_hover_text("Show the paused frame with the crop, rotation, resize and color filters applied");
+                    ui.add_enabled(app.filter_preview && !app.filter_live, egui::Checkbox::new(&mut app.filter_compare, "A/B"))
+                        .on_hover_text("Compare with the unfiltered frame: drag the split line over the preview");
+                    ui.checkbox(&mut app.filter_live, "Live")
+                        .on_hover_text("Apply the filters in the player itself, also while playing.\n\
+                                        The preview restarts with the new filters a moment after a change.");
+                });
+                let deinterlace = ui.add_enabled(no_yadif.is_none(), egui::Checkbox::new(&mut filters.deinterlace, "Deinterlace"));
+                if let Some(why) = &no_yadif {
+                    deinterlace.on_disabled_hover_text(why);
+                }
+
+                ui.horizontal(|ui| {
+                    let mut crop_enabled = filters.crop.is_some();
+                    if ui.checkbox(&mut crop_enabled, "Crop").changed() {
+                        filters.crop = crop_enabled.then_some(CropRect { x: 0, y: 0, width: 1920, height: 1080 });
+                    }
+                    if let Some(crop) = filters.crop.as_mut() {
+                        ui.add(egui::DragValue::new(&mut crop.width).prefix("w "));
+                        ui.add(egui::DragValue::new(&mut crop.height).prefix("h "));
+                        ui.add(egui::DragValue::new(&mut crop.x).prefix("x "));
+                        ui.add(egui::DragValue::new(&mut crop.y).prefix("y "));
+                    }
+                });
+
+                ui.horizontal(|ui| {
+                    ui.label("Rotate:");
+                    rotation_combo(ui, &mut filters.rotation);
+                    ui.checkbox(&mut filters.hflip, "Flip H");
+                    ui.checkbox(&mut filters.vflip, "Flip V");
+                });
+                ui.horizontal(|ui| {
+                    ui.radio_value(&mut filters.rotation_method, RotationMethod::Reencode, "Rotate (re-encode)");
+                    ui.radio_value(&mut filters.rotation_method, RotationMethod::Metadata, "Set rotation flag")
+                        .on_hover_text("Overrides the source display rotation; use Lossless mode to only rewrite the flag");
+                });
+
+                ui.horizontal(|ui| {
+                    ui.label("Resize:");
+                    let text = match filters.scale_height {
+                        Some(h) => format!("{}p", h),
+                        None => "Source".to_string(),
+                    };
+                    egui::ComboBox::from_id_salt("filter_scale")
+                        .selected_text(text)
+                        .show_ui(ui, |ui| {
+                            ui.selectable_value(&mut filters.scale_height, None, "Source");
+                            for &h in FilterSettings::SCALE_HEIGHTS {
+                                ui.selectable_value(&mut filters.scale_height, Some(h), format!("{}p", h));
+                            }
+                        });
+                });
+
+                render_display_aspect(ui, &mut filters.display_aspect);
+
+                ui.add(egui::Slider::new(&mut filters.brightness, -1.0..=1.0).text("Brightness"));
+                ui.add(egui::Slider::new(&mut filters.contrast, 0.0..=2.0).text("Contrast"));
+                ui.add(egui::Slider::new(&mut filters.saturation, 0.0..=3.0).text("Saturation"));
+
+                ui.horizontal(|ui| {
+                    ui.label("Fade:");
+                    ui.add(egui::DragValue::new(&mut filters.fade_in_secs).range(0.0..=10.0).speed(0.05).prefix("in ").suffix(" s"));
+                    ui.add(egui::DragValue::new(&mut filters.fade_out_secs).range(0.0..=10.0).speed(0.05).prefix("out ").suffix(" s"));
+                });
+
+                ui.separator();
+            }
+            // Noir + silence, ou silence seul pour un fichier audio
+            let filler = if audio_only { "silence" } else { "black and silence" };
+            ui.horizontal(|ui| {
+                ui.label("Padding:");
+                ui.add(egui::DragValue::new(&mut filters.pad_before).range(0.0..=30.0).speed(0.1).prefix("before ").suffix(" s"))
+                    .on_hover_text(format!("Seconds of {} added before the clip", filler));
+                ui.add(egui::DragValue::new(&mut filters.pad_after).range(0.0..=30.0).speed(0.1).prefix("after ").suffix(" s"))
+                    .on_hover_text(format!("Seconds of {} added after the clip", filler));
+            });
+            ui.separator();
+            ui.label("Voice cleanup");
+            ui.horizontal(|ui| {
+                let mut enabled = filters.highpass.is_some();
+                if ui.checkbox(&mut enabled, "High-pass")
+                    .on_hover_text("Removes low-frequency hum and rumble")
+                    .changed()
+                {
+                    filters.highpass = enabled.then_some(80);
+                }
+                if let Some(hz) = filters.highpass.as_mut() {
+                    ui.add(egui::Slider::new(hz, FilterSettings::HIGHPASS_RANGE).suffix(" Hz"));
+                }
+            });
+            let mut presence = filters.presence_boost_db();
+            if ui.add(egui::Slider::new(&mut presence, 0.0..=6.0).step_by(0.5).suffix(" dB").text("Presence boost"))
+                .on_hover_text("Lifts the 3 kHz range to make speech clearer")
+                .changed()
+            {
+                filters.set_presence_boost_db(presence);
+            }
+
+            if !filters.is_identity() && ui.small_button("Reset filters").clicked() {
+                *filters = FilterSettings::default();
+            }
+        });
+}
+
+/// External subtitle file, burned in or embedded as a track (single-file exports)
+fn render_subtitle_settings(app: &mut FFmpegApp, ui: &mut egui::Ui) {
+    let container = app.selected_file()
+        .map(|f| app.export_settings.output_extension(&f.path).to_lowercase())
+        .unwrap_or_default();
+
+    ui.horizontal(|ui| {
+        ui.label("Subtitles:");
+        match &app.export_settings.subtitles {
+            Some(subs) => {
+                let name = subs.path.file_name().unwrap_or_default().to_string_lossy().to_string();
+                ui.label(&name).on_hover_text(subs.path.display().to_string());
+                if ui.small_button("✕").on_hover_text("Remove subtitles").clicked() {
+                    app.export_settings.subtitles = None;
+                }
+            }
+            None => {
+                if ui.button("Choose...").clicked() {
+                    if let Some(path) = rfd::FileDialog::new()
+                        .add_filter("Subtitles", &["srt", "ass", "ssa", "vtt"])
+                        .pick_file()
+                    {
+                        app.export_settings.subtitles = Some(SubtitleSettings { path, mode: SubtitleMode::default() });
+                    }
+                }
+            }
+        }
+    });
+
+    // Burn-in needs the libass-based `subtitles` filter
+    let no_libass = app.missing_filter("subtitles");
+    let Some(subs) = app.export_settings.subtitles.as_mut() else { return };
+    ui.horizontal(|ui| {
+        for mode in SubtitleMode::all() {
+            let missing = no_libass.as_ref().filter(|_| *mode == SubtitleMode::BurnIn);
+            let radio = ui.add_enabled(missing.is_none(), egui::RadioButton::new(subs.mode == *mode, mode.name()));
+            if radio.clicked() {
+                subs.mode = *mode;
+            }
+            if let Some(why) = missing {
+                radio.on_disabled_hover_text(why);
+            }
+        }
+    });
+    if subs.mode == SubtitleMode::BurnIn {
+        if let Some(why) = &no_libass {
+            ui.colored_label(egui::Color32::from_rgb(255, 180, 60), why);
+        }
+    }
+    if subs.mode == SubtitleMode::Embed {
+        let sub_ext = subs.path.extension().unwrap_or_default().to_string_lossy().to_lowercase();
+        let warn = egui::Color32::from_rgb(255, 180, 60);
+        match crate::ffmpeg::subtitle_codec_for(&container, &subs.path) {
+            Err(e) => { ui.colored_label(warn, e); }
+            Ok("mov_text") if matches!(sub_ext.as_str(), "ass" | "ssa") => {
+                ui.colored_label(warn, "MP4 subtitle tracks are plain text: ASS styling will be lost");
+            }
+            Ok(_) => {}
+        }
+    }
+    ui.small("Not applied to batch Export All");
+}
+
+/// Trim mode picked automatically when a file of a given codec / extension is selected
+fn render_trim_mode_rules(app: &mut FFmpegApp, ui: &mut egui::Ui) {
+    let header = match app.split_settings.trim_mode_rules.len() {
+        0 => "Default mode per file type".to_string(),
+        n => format!("Default mode per file type ({})", n),
+    };
+    egui::CollapsingHeader::new(header)
+        .id_salt("trim_mode_rules")
+        .show(ui, |ui| {
+            let mut remove = None;
+            for (i, rule) in app.split_settings.trim_mode_rules.iter_mut().enumerate() {
+                ui.horizontal(|ui| {
+                    ui.add(egui::TextEdit::singleline(&mut rule.source).desired_width(70.0).hint_text("h264 / mp4"))
+                        .on_hover_text("Video codec (h264, hevc, prores...) or file extension");
+                    egui::ComboBox::from_id_salt(("trim_mode_rule", i))
+                        .selected_text(rule.mode.name())
+                        .show_ui(ui, |ui| {
+                            for mode in TrimMode::all() {
+                                ui.selectable_value(&mut rule.mode, *mode, mode.name());
+                            }
+                        });
+                    if ui.small_button("x").on_hover_text("Remove rule").clicked() {
+                        remove = Some(i);
+                    }
+                });
+            }
+            if let Some(i) = remove {
+                app.split_settings.trim_mode_rules.remove(i);
+            }
+            ui.horizontal(|ui| {
+                if ui.small_button("+ Add").clicked() {
+                    app.split_settings.trim_mode_rules.push(crate::ui::TrimModeRule {
+                        source: String::new(),
+                        mode: app.split_settings.trim_mode,
+                    });
+                }
+                if ui.add_enabled(app.selected_file().is_some(), egui::Button::new("Use current mode for this type").small())
+                    .on_hover_text("Start files with the selected file's codec in the current mode")
+                    .clicked()
+                {
+                    app.set_default_trim_mode_for_selected();
+                }
+            });
+            ui.small("First match wins; changing the mode by hand still works");
+        });
+}
+
+fn render_split_settings(app: &mut FFmpegApp, ui: &mut egui::Ui) {
+    ui.heading("Settings");
+
+    // Trim mode, with where each one would cut the IN point
     ui.label("Mode:");
+    app.probe_in_point_keyframes();
+    let in_point = app.in_point.filter(|_| !app.selected_is_audio_only());
+    let fps = app.selected_file().and_then(|f| f.info.framerate);
+    let keyframes = app.selected_keyframes().to_vec();
     ui.indent("trim_mode_indent", |ui| {
         for mode in TrimMode::all() {
             let is_selected = app.split_settings.trim_mode == *mode;
-            if ui.radio(is_selected, mode.name()).clicked() {
+            let clicked = ui.horizontal(|ui| {
+                let clicked = ui.radio(is_selected, mode.name()).clicked();
+                if let Some(hint) = in_point.and_then(|t| trim_mode_boundary_hint(*mode, &keyframes, t, fps)) {
+                    ui.weak(hint).on_hover_text("Where this mode would start a cut at the IN point");
+                }
+                clicked
+            }).inner;
+            if clicked {
                 app.split_settings.trim_mode = *mode;
             }
             if is_selected {
@@ -851,6 +2323,70 @@ fn render_split_settings(app: &mut FFmpegApp, ui: &mut egui::Ui) {
             }
         }
     });
+    render_trim_mode_rules(app, ui);
+
+    if app.split_settings.trim_mode != TrimMode::SmartCut {
+        ui.checkbox(&mut app.export_settings.absolute_end, "Cut end by position (-to)")
+            .on_hover_text("Stop at the absolute OUT time instead of after a duration (-t).\nAvoids lossless cuts running long when -ss snaps back to a keyframe.");
+        ui.checkbox(&mut app.export_settings.copy_metadata, "Preserve creation time metadata")
+            .on_hover_text("Copy the source's global metadata (creation time, title...) with -map_metadata 0");
+    }
+    ui.checkbox(&mut app.export_settings.keep_chapters, "Keep chapters in segments")
+        .on_hover_text("Each output gets the source chapters inside its range, shifted to start at 0");
+    let mut normalize = app.export_settings.normalize_loudness;
+    if ui.checkbox(&mut normalize, "Normalize on export")
+        .on_hover_text(format!(
+            "Bring every exported clip to {} LUFS (loudnorm), so split parts sound equally loud.\nNeeds a re-encode: much slower than a lossless cut.",
+            crate::ffmpeg::TARGET_LUFS
+        ))
+        .changed()
+    {
+        app.set_normalize_on_export(normalize);
+    }
+    if app.export_settings.normalize_loudness && !app.split_settings.trim_mode.is_reencode() {
+        ui.colored_label(egui::Color32::from_rgb(255, 180, 60), "⚠ Normalization only applies in Precise / High quality");
+    }
+    if app.split_settings.trim_mode == TrimMode::Lossless {
+        let mut reset = !app.export_settings.keep_timestamps;
+        if ui.checkbox(&mut reset, "Reset timestamps")
+            .on_hover_text("Start each clip at 0 (-avoid_negative_ts make_zero).\nUntick to keep the source timestamps (-copyts) for tools that rely on them.")
+            .changed()
+        {
+            app.export_settings.keep_timestamps = !reset;
+        }
+    }
+
+    if app.split_settings.trim_mode.is_reencode() {
+        ui.checkbox(&mut app.split_settings.snap_to_frames, "Snap to frames on export")
+            .on_hover_text("Round segment boundaries to the nearest frame before re-encoding");
+        if !app.selected_is_audio_only() {
+            ui.checkbox(&mut app.export_settings.closed_gop, "Seamless joins (closed GOP)")
+                .on_hover_text(format!(
+                    "Keyframe at every segment start and every {} s, closed GOPs, no scene-cut keyframes.\n\
+                     Parts played back to back (or concatenated) join without a hitch; files get slightly larger.",
+                    crate::ffmpeg::CLOSED_GOP_SECS
+                ));
+        }
+        let no_x265 = app.missing_encoder("libx265");
+        ui.add_enabled(no_x265.is_none(), egui::Checkbox::new(&mut app.export_settings.match_source_codec, "Keep source codec"))
+            .on_hover_text("Re-encode HEVC sources with libx265 instead of converting to H.264")
+            .on_disabled_hover_text(no_x265.unwrap_or_default());
+        render_thread_limit_setting(app, ui);
+        let audio_only = app.selected_is_audio_only();
+        render_encode_profile_setting(app, ui);
+        if !audio_only {
+            render_pix_fmt_setting(app, ui);
+            render_frame_rate_setting(app, ui);
+        }
+        render_audio_encode_settings(app, ui);
+        render_filter_settings(app, ui);
+        if !audio_only {
+            render_subtitle_settings(app, ui);
+        }
+    } else if app.split_settings.trim_mode == TrimMode::Lossless && !app.selected_is_audio_only() {
+        render_rotation_flag_setting(app, ui);
+        render_display_aspect(ui, &mut app.export_settings.filters.display_aspect);
+    }
 
     ui.separator();
 
@@ -869,6 +2405,15 @@ fn render_split_settings(app: &mut FFmpegApp, ui: &mut egui::Ui) {
     });
     if app.split_settings.max_size_mb > 0.0 {
         ui.small("Segments exceeding this size will be auto-split.");
+        ui.add(
+            egui::Slider::new(&mut app.split_settings.cut_tolerance_secs, SplitSettings::CUT_TOLERANCE_RANGE)
+                .text("silence search")
+                .suffix(" s")
+                .integer(),
+        )
+        .on_hover_text("How far before the size limit Auto-Cut looks for a silence to cut in.\n\
+                        Narrow: cuts stay close to the limit. Wide: finds a pause in sparse speech.\n\
+                        Without a silence in that window the cut falls at the size limit.");
     } else {
         ui.small("0 = no size limit");
     }
@@ -898,6 +2443,32 @@ fn render_split_settings(app: &mut FFmpegApp, ui: &mut egui::Ui) {
         ui.small(&app.auto_cut_status);
     }
 
+    // Scene split
+    ui.horizontal(|ui| {
+        let can_split = app.selected_file().is_some() && !app.scene_split_running && !app.auto_cut_running;
+        if ui
+            .add_enabled(can_split, egui::Button::new("Split by Scenes"))
+            .on_hover_text("Detect scene changes and make one segment per scene")
+            .clicked()
+        {
+            app.start_scene_split();
+        }
+        if app.scene_split_running {
+            ui.spinner();
+        }
+    });
+    ui.horizontal(|ui| {
+        ui.small("Sensitivity:");
+        // Slider shows sensitivity; the ffmpeg scene threshold is its inverse
+        let mut sensitivity = 1.0 - app.scene_threshold;
+        if ui.add(egui::Slider::new(&mut sensitivity, 0.1..=0.9).show_value(false))
+            .on_hover_text(format!("Scene threshold: {:.2}", app.scene_threshold))
+            .changed()
+        {
+            app.scene_threshold = 1.0 - sensitivity;
+        }
+    });
+
     ui.separator();
 
     // Batch processing section
@@ -948,8 +2519,66 @@ fn render_split_settings(app: &mut FFmpegApp, ui: &mut egui::Ui) {
                     app.export_all_files();
                 }
             }
+
+            ui.menu_button("Extract audio", |ui| {
+                let missing: Vec<Option<String>> = AudioExtractFormat::all().iter()
+                    .map(|f| app.missing_encoder(f.encoder()))
+                    .collect();
+                ui.horizontal(|ui| {
+                    for (format, missing) in AudioExtractFormat::all().iter().zip(&missing) {
+                        let radio = ui.add_enabled(
+                            missing.is_none(),
+                            egui::RadioButton::new(app.audio_extract_format == *format, format.name()),
+                        );
+                        if radio.clicked() {
+                            app.audio_extract_format = *format;
+                        }
+                        if let Some(why) = missing {
+                            radio.on_disabled_hover_text(why);
+                        }
+                    }
+                });
+                ui.small(format!("First audio track, saved as <name>.{}", app.audio_extract_format.extension()));
+                if ui.button(format!("Queue {} extractions", file_count)).clicked() {
+                    app.extract_audio_all_files();
+                    ui.close_menu();
+                }
+            }).response.on_hover_text("Save the audio of every loaded file (e.g. for transcription)");
         });
 
+        if app.batch_running {
+            ui.add(
+                egui::ProgressBar::new(app.batch_overall_progress())
+                    .show_percentage()
+                    .desired_width(ui.available_width()),
+            );
+        }
+
+        if !app.batch_rows.is_empty() {
+            egui::CollapsingHeader::new(format!("Batch files ({})", app.batch_rows.len()))
+                .id_salt("batch_rows")
+                .default_open(app.batch_running)
+                .show(ui, |ui| {
+                    for row in &app.batch_rows {
+                        ui.horizontal(|ui| {
+                            let state = if row.done {
+                                "✔ done".to_string()
+                            } else if row.progress > 0.0 {
+                                format!("{:.0}%", row.progress * 100.0)
+                            } else if app.batch_running {
+                                "queued".to_string()
+                            } else {
+                                "stopped".to_string()
+                            };
+                            ui.small(&row.name).on_hover_text(row.path.display().to_string());
+                            ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                                ui.small(state);
+                            });
+                        });
+                    }
+                });
+        }
+
         if !app.batch_running && !app.batch_status.is_empty() {
             ui.small(&app.batch_status);
         }
@@ -964,6 +2593,8 @@ fn render_split_settings(app: &mut FFmpegApp, ui: &mut egui::Ui) {
         }
     }
 
+    render_proof_sheet(app, ui);
+
     ui.separator();
 
     // Output folder
@@ -979,15 +2610,78 @@ fn render_split_settings(app: &mut FFmpegApp, ui: &mut egui::Ui) {
     ui.horizontal(|ui| {
         if ui.button("Browse...").clicked() {
             if let Some(folder) = rfd::FileDialog::new().pick_folder() {
-                app.split_settings.output_folder = Some(folder);
+                app.set_output_folder(Some(folder));
             }
         }
-        if app.split_settings.output_folder.is_some() {
-            if ui.button("Reset").clicked() {
-                app.split_settings.output_folder = None;
-            }
+        if app.split_settings.output_folder.is_some()
+            && ui.button("Reset").clicked() {
+            app.split_settings.output_folder = None;
         }
+        render_recent_folders(app, ui, "recent_folders_split");
     });
+
+    ui.horizontal(|ui| {
+        render_conflict_policy(app, ui);
+        if !app.selected_is_audio_only() {
+            render_container_setting(app, ui);
+        }
+    });
+    ui.checkbox(&mut app.split_settings.write_playlist, "Write .m3u playlist")
+        .on_hover_text("After exporting several files, list them in order in an .m3u next to them (relative paths)");
+    render_label_formats(app, ui);
+}
+
+/// Templates of segment labels and of the parts the size limit splits them into
+fn render_label_formats(app: &mut FFmpegApp, ui: &mut egui::Ui) {
+    let placeholders = "{n} number, {nn} zero-padded number, {total} count";
+    ui.horizontal(|ui| {
+        ui.label("Labels:");
+        ui.add(egui::TextEdit::singleline(&mut app.split_settings.segment_label_format).desired_width(110.0))
+            .on_hover_text(format!("New segments: {}\ne.g. {}", placeholders, app.split_settings.segment_label(3, 12)));
+        ui.add(egui::TextEdit::singleline(&mut app.split_settings.part_label_format).desired_width(140.0))
+            .on_hover_text(format!(
+                "Parts of a segment over the size limit: {{label}} the segment's label, {}\ne.g. {}",
+                placeholders,
+                crate::utils::format_label(&app.split_settings.part_label_format, "Segment 1", 2, 5)
+            ));
+        if ui.add_enabled(!app.segments.is_empty(), egui::Button::new("Renumber").small())
+            .on_hover_text("Relabel the current segments with this template")
+            .clicked()
+        {
+            app.relabel_segments();
+        }
+    });
+}
+
+/// Short preview montage of the selected file, to judge a long recording quickly
+fn render_proof_sheet(app: &mut FFmpegApp, ui: &mut egui::Ui) {
+    if app.selected_file().is_none() || app.selected_is_audio_only() {
+        return;
+    }
+    egui::CollapsingHeader::new("Proof sheet")
+        .id_salt("proof_sheet")
+        .show(ui, |ui| {
+            let settings = &mut app.proof_sheet_settings;
+            ui.horizontal(|ui| {
+                ui.label("Clips:");
+                ui.add(egui::DragValue::new(&mut settings.clip_count).range(1..=100));
+                ui.label("Length:");
+                ui.add(egui::DragValue::new(&mut settings.clip_length).range(0.5..=60.0).speed(0.5).suffix(" s"));
+            });
+            let total = settings.clip_count as f64 * settings.clip_length;
+            ui.horizontal(|ui| {
+                let button = egui::Button::new(format!("Build preview (~{})", format_time(total)));
+                if ui.add_enabled(!app.proof_sheet_running, button)
+                    .on_hover_text("Cut evenly spaced clips (stream copy when possible) and join them into <name>_proof")
+                    .clicked()
+                {
+                    app.start_proof_sheet();
+                }
+                if app.proof_sheet_running {
+                    ui.spinner();
+                }
+            });
+        });
 }
 
 /// Dedicated merge editor — full-width panel with file cards, reordering, and merge controls
@@ -1000,9 +2694,7 @@ fn render_merge_editor(app: &mut FFmpegApp, ui: &mut egui::Ui, ctx: &egui::Conte
             ui.label("Add at least 2 videos to use the merge feature.");
             ui.add_space(10.0);
             if ui.button("+ Add Videos").clicked() {
-                if let Some(paths) = rfd::FileDialog::new()
-                    .add_filter("Video", &["mp4", "mkv", "avi", "mov", "webm", "ts", "flv"])
-                    .add_filter("All Files", &["*"])
+                if let Some(paths) = super::file_browser::media_file_dialog()
                     .pick_files()
                 {
                     app.add_files(paths);
@@ -1219,19 +2911,62 @@ fn render_merge_editor(app: &mut FFmpegApp, ui: &mut egui::Ui, ctx: &egui::Conte
         ui.label(&folder_text);
 
         ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
-            if app.split_settings.output_folder.is_some() {
-                if ui.button("Reset").on_hover_text("Use source folder").clicked() {
-                    app.split_settings.output_folder = None;
-                }
+            if app.split_settings.output_folder.is_some()
+                && ui.button("Reset").on_hover_text("Use source folder").clicked() {
+                app.split_settings.output_folder = None;
             }
             if ui.button("Browse...").on_hover_text("Choose output folder for merged file").clicked() {
                 if let Some(folder) = rfd::FileDialog::new().pick_folder() {
-                    app.split_settings.output_folder = Some(folder);
+                    app.set_output_folder(Some(folder));
                 }
             }
+            render_recent_folders(app, ui, "recent_folders_merge_tab");
         });
     });
 
+    ui.horizontal(|ui| {
+        ui.label("Output name:");
+        ui.add(egui::TextEdit::singleline(&mut app.merge_output_name)
+            .desired_width(180.0)
+            .hint_text("merged_output"))
+            .on_hover_text("Extension optional (defaults to the export format, else the first clip's)");
+        render_conflict_policy(app, ui);
+    });
+
+    ui.horizontal(|ui| {
+        ui.checkbox(&mut app.merge_reencode, "Re-encode")
+            .on_hover_text("Merge clips with different codecs or audio formats (slower).\nAudio is resampled to 48 kHz only when the inputs differ.");
+        if let Some(formats) = app.merge_audio_mismatch() {
+            let text = if app.merge_reencode {
+                "Audio will be resampled to a common format"
+            } else {
+                "⚠ Audio formats differ: stream copy may drift out of sync"
+            };
+            ui.colored_label(egui::Color32::from_rgb(255, 180, 60), text)
+                .on_hover_text(formats);
+        }
+    });
+    if app.merge_reencode {
+        ui.horizontal(|ui| {
+            ui.label("Size:");
+            let text = match app.merge_resolution {
+                Some(h) => format!("{}p", h),
+                None => "Largest input".to_string(),
+            };
+            egui::ComboBox::from_id_salt("merge_resolution")
+                .selected_text(text)
+                .show_ui(ui, |ui| {
+                    ui.selectable_value(&mut app.merge_resolution, None, "Largest input");
+                    for &h in crate::ffmpeg::ConcatVideoFormat::HEIGHTS {
+                        ui.selectable_value(&mut app.merge_resolution, Some(h), format!("{}p", h));
+                    }
+                });
+            if let Some(fmt) = app.merge_video_format() {
+                ui.small(format!("clips scaled to {}x{}, letterboxed", fmt.width, fmt.height));
+            }
+        });
+    }
+
     ui.add_space(4.0);
 
     // Merge button (prominent)
@@ -1280,6 +3015,16 @@ fn render_export_bar(app: &mut FFmpegApp, ui: &mut egui::Ui) {
             app.export_all();
         }
 
+        egui::ComboBox::from_id_salt("open_output")
+            .selected_text(app.open_output.name())
+            .show_ui(ui, |ui| {
+                for action in OpenOutputAction::all() {
+                    ui.selectable_value(&mut app.open_output, *action, action.name());
+                }
+            })
+            .response
+            .on_hover_text("When an export writes a single file: open it in the default player, or add it to the file list to review it here");
+
         // Progress bar if processing
         let (completed, total, is_processing) = app.export_queue.lock()
             .map(|q| { let (c, t) = q.total_progress(); (c, t, q.is_processing) })
@@ -1290,30 +3035,73 @@ fn render_export_bar(app: &mut FFmpegApp, ui: &mut egui::Ui) {
             if is_processing {
                 ui.spinner();
             }
-            let progress = if total > 0 { completed as f32 / total as f32 } else { 0.0 };
+            let progress = app.export_queue.lock().map_or(0.0, |q| q.overall_fraction());
             ui.add(egui::ProgressBar::new(progress)
                 .text(format!("{}/{}", completed, total))
                 .desired_width(150.0));
 
+            // Failed jobs (incl. outputs rejected by validation) with reasons on hover
+            let failures: Vec<String> = app.export_queue.lock()
+                .map(|q| q.jobs.iter()
+                    .filter_map(|j| match &j.status {
+                        crate::export_queue::JobStatus::Failed(msg) if msg != "Cancelled" => {
+                            Some(format!("{}: {}", j.output.file_name().unwrap_or_default().to_string_lossy(), msg))
+                        }
+                        _ => None,
+                    })
+                    .collect())
+                .unwrap_or_default();
+            if !failures.is_empty() {
+                ui.colored_label(egui::Color32::from_rgb(255, 100, 100), format!("{} failed", failures.len()))
+                    .on_hover_text(failures.join("\n"));
+            }
+            if !app.size_warnings.is_empty() {
+                ui.colored_label(egui::Color32::from_rgb(255, 180, 60), format!("⚠ {} over limit", app.size_warnings.len()))
+                    .on_hover_text(app.size_warnings.join("\n"));
+            }
+            if let Some(disk) = &app.disk_space {
+                render_disk_space(disk, ui);
+            }
+
             // Stop All button — cancel pending exports
             if completed < total && ui.button("Stop All").on_hover_text("Cancel all pending exports").clicked() {
                 app.cancel_exports();
             }
 
-            if completed == total && !is_processing {
-                if ui.button("Clear").clicked() {
-                    app.clear_finished_jobs();
-                    app.show_export_progress = false;
+            if ui.button("Jobs...").on_hover_text("List queued jobs and change pending outputs").clicked() {
+                app.show_queue_panel = true;
+            }
+
+            if ui.button("Save Script...")
+                .on_hover_text("Save unfinished jobs as a shell script (.sh / .bat) to run without the GUI")
+                .clicked()
+            {
+                let default_name = if cfg!(windows) { "export_queue.bat" } else { "export_queue.sh" };
+                if let Some(path) = rfd::FileDialog::new()
+                    .set_file_name(default_name)
+                    .add_filter("Shell script", &["sh"])
+                    .add_filter("Batch file", &["bat", "cmd"])
+                    .save_file()
+                {
+                    app.export_queue_script(&path);
                 }
             }
+
+            if completed == total && !is_processing
+                && ui.button("Clear").clicked() {
+                app.clear_finished_jobs();
+                app.show_export_progress = false;
+            }
+        }
+
+        if !app.size_checks.is_empty() {
+            render_size_checks(app, ui);
         }
 
         // Quick open new file (for chained workflow)
         ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
             if ui.button("Open Next Video...").clicked() {
-                if let Some(paths) = rfd::FileDialog::new()
-                    .add_filter("Video", &["mp4", "mkv", "avi", "mov", "webm", "ts", "flv"])
-                    .add_filter("All Files", &["*"])
+                if let Some(paths) = super::file_browser::media_file_dialog()
                     .pick_files()
                 {
                     app.add_files(paths);
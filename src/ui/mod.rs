@@ -1,11 +1,8 @@
 mod main_window;
 mod file_browser;
-mod preview;
 mod timeline_widget;
 mod tools;
 
 pub use main_window::*;
-pub use file_browser::*;
-pub use preview::*;
 pub use timeline_widget::*;
 pub use tools::*;
@@ -1,5 +1,5 @@
-use crate::ui::SplitSegment;
-use crate::utils::format_time;
+use crate::ui::{SplitSegment, TrimMode};
+use crate::utils::{format_size, format_time};
 use eframe::egui;
 
 /// Palette de couleurs pour les segments
@@ -14,6 +14,9 @@ const SEGMENT_COLORS: [(u8, u8, u8); 8] = [
     (149, 237, 100),  // lime
 ];
 
+/// Couleur des segments qui dépassent la taille max
+const OVER_LIMIT_COLOR: (u8, u8, u8) = (230, 60, 60);
+
 /// Un clip affiché bout à bout sur la timeline en mode Merge (un fichier à concaténer).
 pub struct TimelineClip {
     pub label: String,
@@ -22,6 +25,45 @@ pub struct TimelineClip {
     pub is_current: bool,
 }
 
+/// Tooltip for a segment boundary in Lossless mode: where the `-c copy` cut
+/// actually lands. The start seeks back to the previous keyframe; the end
+/// is not snapped, the copy stops at the requested time.
+pub fn lossless_cut_hint(keyframes: &[f64], time: f64, is_start: bool) -> String {
+    let snap = crate::ffmpeg::lossless_start_snap(keyframes, time);
+    match (is_start, snap) {
+        (true, Some((_, delta))) if delta < 0.001 => {
+            format!("Start {} is on a keyframe: exact lossless cut", format_time(time))
+        }
+        (true, Some((keyframe, delta))) => format!(
+            "Lossless start lands on keyframe {} ({:.3}s before {})",
+            format_time(keyframe), delta, format_time(time)
+        ),
+        (false, Some((keyframe, _))) => format!(
+            "Lossless end stops at {} (not snapped; its GOP starts at keyframe {})",
+            format_time(time), format_time(keyframe)
+        ),
+        (_, None) => format!("No keyframe at or before {}", format_time(time)),
+    }
+}
+
+/// Où `mode` couperait vraiment un début demandé à `time`, affiché à côté du
+/// bouton radio : keyframe précédente (et l'écart) en Lossless, frame exacte
+/// sinon. `None` en Lossless tant que les keyframes ne sont pas sondées.
+pub fn trim_mode_boundary_hint(mode: TrimMode, keyframes: &[f64], time: f64, fps: Option<f64>) -> Option<String> {
+    if mode != TrimMode::Lossless {
+        let exact = fps.map_or(time, |fps| crate::utils::snap_to_frame(time, fps));
+        return Some(format!("→ {} (exact frame)", format_time(exact)));
+    }
+    if keyframes.is_empty() {
+        return None;
+    }
+    Some(match crate::ffmpeg::lossless_start_snap(keyframes, time) {
+        Some((_, delta)) if delta < 0.001 => format!("→ {} (on a keyframe)", format_time(time)),
+        Some((keyframe, delta)) => format!("→ {} (keyframe, {:.3}s early)", format_time(keyframe), delta),
+        None => "→ no keyframe before IN".to_string(),
+    })
+}
+
 /// Timeline widget with waveform visualization and multi-segment support
 pub struct TimelineWidget<'a> {
     pub duration: f64,
@@ -32,9 +74,16 @@ pub struct TimelineWidget<'a> {
     pub scroll: f32,
     pub segments: &'a [SplitSegment],
     pub selected_segment: Option<usize>,
+    /// Segments sélectionnés en plus (Ctrl+clic), surlignés comme le segment courant
+    pub selected_segments: &'a [usize],
     pub waveform_data: &'a [f32],
     /// Clips à fusionner (mode Merge). Si non vide, ils remplacent l'affichage des segments.
     pub clips: &'a [TimelineClip],
+    /// Taille max d'un segment (0 = pas de limite) ; au-delà, le segment est dessiné en rouge
+    pub max_size_bytes: u64,
+    /// Keyframes de la source (mode Lossless) : le survol d'un bord de segment
+    /// montre la keyframe où tombe réellement la coupe `-c copy`
+    pub keyframes: &'a [f64],
 }
 
 impl<'a> TimelineWidget<'a> {
@@ -48,8 +97,11 @@ impl<'a> TimelineWidget<'a> {
             scroll: 0.0,
             segments: &[],
             selected_segment: None,
+            selected_segments: &[],
             waveform_data: &[],
             clips: &[],
+            max_size_bytes: 0,
+            keyframes: &[],
         }
     }
 
@@ -83,6 +135,11 @@ impl<'a> TimelineWidget<'a> {
         self
     }
 
+    pub fn selected_segments(mut self, selected: &'a [usize]) -> Self {
+        self.selected_segments = selected;
+        self
+    }
+
     pub fn waveform_data(mut self, data: &'a [f32]) -> Self {
         self.waveform_data = data;
         self
@@ -93,6 +150,16 @@ impl<'a> TimelineWidget<'a> {
         self
     }
 
+    pub fn max_size_bytes(mut self, max_bytes: u64) -> Self {
+        self.max_size_bytes = max_bytes;
+        self
+    }
+
+    pub fn keyframes(mut self, keyframes: &'a [f64]) -> Self {
+        self.keyframes = keyframes;
+        self
+    }
+
     /// Show the timeline widget and return seek position if clicked
     pub fn show(self, ui: &mut egui::Ui) -> TimelineResponse {
         let mut response = TimelineResponse {
@@ -100,8 +167,10 @@ impl<'a> TimelineWidget<'a> {
             zoom_changed: None,
             scroll_changed: None,
             segment_clicked: None,
+            segment_action: None,
             is_scrubbing: false,
             zoom_to_fit: false,
+            boundary_drag: None,
         };
 
         if self.duration <= 0.0 {
@@ -137,6 +206,10 @@ impl<'a> TimelineWidget<'a> {
         let visible_duration = self.duration / self.zoom as f64;
         let scroll_time = self.scroll as f64 * (self.duration - visible_duration).max(0.0);
 
+        if !self.waveform_data.is_empty() && self.clips.is_empty() {
+            self.show_minimap(ui, scroll_time, visible_duration, &mut response);
+        }
+
         let (rect, ui_response) = ui.allocate_exact_size(
             egui::vec2(available_width, timeline_height),
             egui::Sense::click_and_drag(),
@@ -177,28 +250,60 @@ impl<'a> TimelineWidget<'a> {
             self.draw_working_markers(&painter, rect, scroll_time, visible_duration);
             self.draw_playhead(&painter, rect, scroll_time, visible_duration);
 
+            // Lossless : bord de segment survolé → keyframe où la coupe tombe vraiment
+            if !self.keyframes.is_empty() && self.clips.is_empty() {
+                let edge = ui_response.hover_pos()
+                    .and_then(|pos| self.edge_at(pos, track_rect, scroll_time, visible_duration));
+                if let Some((_, time, is_start)) = edge {
+                    if let Some((keyframe, _)) = crate::ffmpeg::lossless_start_snap(self.keyframes, time) {
+                        let x = rect.left() + ((keyframe - scroll_time) / visible_duration) as f32 * rect.width();
+                        painter.line_segment(
+                            [egui::pos2(x, rect.top()), egui::pos2(x, rect.bottom())],
+                            egui::Stroke::new(1.0, egui::Color32::from_rgb(255, 200, 60)),
+                        );
+                    }
+                    ui_response.clone().on_hover_text_at_pointer(lossless_cut_hint(self.keyframes, time, is_start));
+                }
+            }
+
             // Handle click — seek + detect segment clicks
             if ui_response.clicked() {
                 if let Some(pos) = ui_response.interact_pointer_pos() {
                     let relative_x = (pos.x - rect.left()) / rect.width();
                     let click_time = scroll_time + relative_x as f64 * visible_duration;
 
-                    let pixels_per_second = track_rect.width() / visible_duration as f32;
-                    if pos.y >= track_rect.top() && pos.y <= track_rect.bottom() {
-                        for (i, seg) in self.segments.iter().enumerate() {
-                            let seg_start_x = track_rect.left() + ((seg.start_time - scroll_time) as f32 * pixels_per_second);
-                            let seg_end_x = track_rect.left() + ((seg.end_time - scroll_time) as f32 * pixels_per_second);
-                            if pos.x >= seg_start_x && pos.x <= seg_end_x {
-                                response.segment_clicked = Some(i);
-                                break;
-                            }
-                        }
-                    }
-
+                    response.segment_clicked = self.segment_at(pos, track_rect, scroll_time, visible_duration);
                     response.seek_to = Some(click_time.clamp(0.0, self.duration));
                 }
             }
 
+            // Right-click on a segment: context menu. The segment under the
+            // pointer is kept in memory while the menu stays open.
+            if self.clips.is_empty() {
+                let menu_id = ui_response.id.with("segment_menu");
+                if ui_response.secondary_clicked() {
+                    let hit = ui_response.interact_pointer_pos()
+                        .and_then(|pos| self.segment_at(pos, track_rect, scroll_time, visible_duration));
+                    ui.data_mut(|d| match hit {
+                        Some(idx) => d.insert_temp(menu_id, idx),
+                        None => d.remove::<usize>(menu_id),
+                    });
+                    if let Some(idx) = hit {
+                        response.segment_clicked = Some(idx);
+                    }
+                }
+                let menu_segment = ui.data(|d| d.get_temp::<usize>(menu_id))
+                    .filter(|&idx| idx < self.segments.len());
+                if let Some(idx) = menu_segment {
+                    ui_response.context_menu(|ui| {
+                        if let Some(action) = self.segment_menu(ui, idx) {
+                            response.segment_action = Some((idx, action));
+                            ui.close_menu();
+                        }
+                    });
+                }
+            }
+
             // Handle scroll wheel for zoom (centered on pointer)
             let scroll_delta = ui.input(|i| i.raw_scroll_delta.y);
             if scroll_delta != 0.0 && rect.contains(ui.input(|i| i.pointer.hover_pos().unwrap_or_default())) {
@@ -206,8 +311,39 @@ impl<'a> TimelineWidget<'a> {
                 response.zoom_changed = Some(new_zoom);
             }
 
-            // Handle drag: normal drag = scrub, Ctrl+drag = pan
-            if ui_response.dragged() {
+            // Drag started on a segment edge: resize that segment instead of scrubbing.
+            // The grabbed edge is kept in memory until the button is released.
+            let grab_id = ui_response.id.with("boundary_grab");
+            if ui_response.drag_started() && self.clips.is_empty() && !ui.input(|i| i.modifiers.ctrl) {
+                let grab = ui.input(|i| i.pointer.press_origin())
+                    .and_then(|pos| self.edge_at(pos, track_rect, scroll_time, visible_duration));
+                if let Some((idx, _, is_start)) = grab {
+                    ui.data_mut(|d| d.insert_temp(grab_id, (idx, is_start)));
+                }
+            }
+            let hovers_edge = self.clips.is_empty() && ui_response.hover_pos()
+                .is_some_and(|pos| self.edge_at(pos, track_rect, scroll_time, visible_duration).is_some());
+            if hovers_edge || (ui_response.dragged() && ui.data(|d| d.get_temp::<(usize, bool)>(grab_id)).is_some()) {
+                ui.ctx().set_cursor_icon(egui::CursorIcon::ResizeHorizontal);
+            }
+            let grabbed = ui.data(|d| d.get_temp::<(usize, bool)>(grab_id))
+                .filter(|&(idx, _)| idx < self.segments.len());
+            if ui_response.drag_stopped() || !ui_response.dragged() {
+                ui.data_mut(|d| d.remove::<(usize, bool)>(grab_id));
+            }
+
+            // Handle drag: edge drag = resize (Alt = rolling edit), normal drag = scrub, Ctrl+drag = pan
+            if let (Some((segment, is_start)), true) = (grabbed, ui_response.dragged()) {
+                if let Some(pos) = ui_response.interact_pointer_pos() {
+                    let relative_x = (pos.x - rect.left()) / rect.width();
+                    response.boundary_drag = Some(BoundaryDrag {
+                        segment,
+                        is_start,
+                        time: (scroll_time + relative_x as f64 * visible_duration).clamp(0.0, self.duration),
+                        rolling: ui.input(|i| i.modifiers.alt),
+                    });
+                }
+            } else if ui_response.dragged() {
                 if ui.input(|i| i.modifiers.ctrl) {
                     let delta = ui_response.drag_delta().x;
                     let scroll_delta = -delta / rect.width() * (self.duration / self.zoom as f64) as f32;
@@ -225,6 +361,108 @@ impl<'a> TimelineWidget<'a> {
         response
     }
 
+    /// Bande de waveform du fichier entier, indépendante du zoom, avec la
+    /// fenêtre visible encadrée. Un clic (ou drag) y saute et centre la vue.
+    fn show_minimap(&self, ui: &mut egui::Ui, scroll_time: f64, visible_duration: f64, response: &mut TimelineResponse) {
+        let (rect, minimap) = ui.allocate_exact_size(egui::vec2(ui.available_width(), 18.0), egui::Sense::click_and_drag());
+        if ui.is_rect_visible(rect) {
+            let painter = ui.painter_at(rect);
+            self.draw_waveform(&painter, rect, 0.0, self.duration);
+
+            let to_x = |t: f64| rect.left() + (t / self.duration) as f32 * rect.width();
+            let viewport = egui::Rect::from_x_y_ranges(
+                to_x(scroll_time)..=to_x(scroll_time + visible_duration).max(to_x(scroll_time) + 2.0),
+                rect.y_range(),
+            );
+            painter.rect_filled(viewport, 0.0, egui::Color32::from_rgba_unmultiplied(255, 255, 255, 25));
+            painter.rect_stroke(viewport, 0.0, egui::Stroke::new(1.0, egui::Color32::from_gray(200)));
+            let x = to_x(self.current_time);
+            painter.line_segment(
+                [egui::pos2(x, rect.top()), egui::pos2(x, rect.bottom())],
+                egui::Stroke::new(1.0, egui::Color32::from_rgb(255, 80, 80)),
+            );
+        }
+
+        if minimap.clicked() || minimap.dragged() {
+            if let Some(pos) = minimap.interact_pointer_pos() {
+                let time = ((pos.x - rect.left()) / rect.width()).clamp(0.0, 1.0) as f64 * self.duration;
+                response.seek_to = Some(time);
+                response.is_scrubbing |= minimap.dragged();
+                let scrollable = self.duration - visible_duration;
+                if scrollable > 0.0 {
+                    response.scroll_changed = Some((((time - visible_duration / 2.0) / scrollable).clamp(0.0, 1.0)) as f32);
+                }
+            }
+        }
+        minimap.on_hover_text("Whole file: click to jump there");
+    }
+
+    /// Index of the segment under `pos` on the segment track
+    fn segment_at(&self, pos: egui::Pos2, track_rect: egui::Rect, scroll_time: f64, visible_duration: f64) -> Option<usize> {
+        if pos.y < track_rect.top() || pos.y > track_rect.bottom() {
+            return None;
+        }
+        let pixels_per_second = track_rect.width() / visible_duration as f32;
+        self.segments.iter().position(|seg| {
+            let seg_start_x = track_rect.left() + ((seg.start_time - scroll_time) as f32 * pixels_per_second);
+            let seg_end_x = track_rect.left() + ((seg.end_time - scroll_time) as f32 * pixels_per_second);
+            pos.x >= seg_start_x && pos.x <= seg_end_x
+        })
+    }
+
+    /// Segment boundary within a few pixels of `pos`: (segment, time, is it a start)
+    fn edge_at(&self, pos: egui::Pos2, track_rect: egui::Rect, scroll_time: f64, visible_duration: f64) -> Option<(usize, f64, bool)> {
+        const GRAB_PX: f32 = 4.0;
+        if pos.y < track_rect.top() || pos.y > track_rect.bottom() {
+            return None;
+        }
+        let pixels_per_second = track_rect.width() / visible_duration as f32;
+        self.segments.iter()
+            .enumerate()
+            .flat_map(|(i, seg)| [(i, seg.start_time, true), (i, seg.end_time, false)])
+            .map(|(i, time, is_start)| {
+                let x = track_rect.left() + (time - scroll_time) as f32 * pixels_per_second;
+                ((x - pos.x).abs(), i, time, is_start)
+            })
+            .filter(|(dist, ..)| *dist <= GRAB_PX)
+            .min_by(|a, b| a.0.total_cmp(&b.0))
+            .map(|(_, i, time, is_start)| (i, time, is_start))
+    }
+
+    /// Context menu entries for segment `idx`
+    fn segment_menu(&self, ui: &mut egui::Ui, idx: usize) -> Option<SegmentAction> {
+        let seg = &self.segments[idx];
+        ui.label(egui::RichText::new(&seg.label).strong());
+        ui.separator();
+
+        let mut action = None;
+        let playhead_inside = self.current_time > seg.start_time && self.current_time < seg.end_time;
+        if ui.button("Go to start").clicked() {
+            action = Some(SegmentAction::GoToStart);
+        }
+        if ui.add_enabled(playhead_inside, egui::Button::new("Split at playhead")).clicked() {
+            action = Some(SegmentAction::SplitAtPlayhead);
+        }
+        if ui.add_enabled(idx + 1 < self.segments.len(), egui::Button::new("Merge with next")).clicked() {
+            action = Some(SegmentAction::MergeWithNext);
+        }
+        if ui.button("Rename...").clicked() {
+            action = Some(SegmentAction::Rename);
+        }
+        let toggle = if seg.enabled { "Disable" } else { "Enable" };
+        if ui.button(toggle).clicked() {
+            action = Some(SegmentAction::ToggleEnabled);
+        }
+        ui.separator();
+        if ui.button("Export this segment").clicked() {
+            action = Some(SegmentAction::Export);
+        }
+        if ui.button("Delete").clicked() {
+            action = Some(SegmentAction::Delete);
+        }
+        action
+    }
+
     fn draw_ruler(&self, painter: &egui::Painter, rect: egui::Rect, scroll_time: f64, visible_duration: f64) {
         let pixels_per_second = rect.width() / visible_duration as f32;
         let step = self.calculate_ruler_step(pixels_per_second);
@@ -258,6 +496,27 @@ impl<'a> TimelineWidget<'a> {
         }
     }
 
+    /// Waveform seule sur toute la surface disponible, fichier entier sans
+    /// zoom (aperçu des fichiers audio). Renvoie la position cliquée/scrubbée.
+    pub fn show_waveform_preview(self, ui: &mut egui::Ui) -> Option<f64> {
+        let (rect, response) = ui.allocate_exact_size(ui.available_size(), egui::Sense::click_and_drag());
+        if self.duration <= 0.0 {
+            return None;
+        }
+        if ui.is_rect_visible(rect) {
+            let painter = ui.painter_at(rect);
+            self.draw_waveform(&painter, rect, 0.0, self.duration);
+            self.draw_working_markers(&painter, rect, 0.0, self.duration);
+            self.draw_playhead(&painter, rect, 0.0, self.duration);
+        }
+        if response.clicked() || response.dragged() {
+            let pos = response.interact_pointer_pos()?;
+            let rel = ((pos.x - rect.left()) / rect.width()).clamp(0.0, 1.0);
+            return Some(rel as f64 * self.duration);
+        }
+        None
+    }
+
     fn draw_waveform(&self, painter: &egui::Painter, rect: egui::Rect, scroll_time: f64, visible_duration: f64) {
         painter.rect_filled(rect, 0.0, egui::Color32::from_gray(25));
 
@@ -312,6 +571,12 @@ impl<'a> TimelineWidget<'a> {
         );
     }
 
+    /// Octets au-dessus de la limite, `None` si le segment la respecte
+    fn overage_bytes(&self, seg: &SplitSegment) -> Option<u64> {
+        (self.max_size_bytes > 0 && seg.estimated_size_bytes > self.max_size_bytes)
+            .then(|| seg.estimated_size_bytes - self.max_size_bytes)
+    }
+
     fn draw_segments(&self, painter: &egui::Painter, rect: egui::Rect, scroll_time: f64, visible_duration: f64) {
         let pixels_per_second = rect.width() / visible_duration as f32;
 
@@ -323,8 +588,13 @@ impl<'a> TimelineWidget<'a> {
 
             if start_x > rect.right() || end_x < rect.left() { continue; }
 
-            let (r, g, b) = SEGMENT_COLORS[i % SEGMENT_COLORS.len()];
-            let is_selected = self.selected_segment == Some(i);
+            let overage = self.overage_bytes(seg);
+            let (r, g, b) = if overage.is_some() {
+                OVER_LIMIT_COLOR
+            } else {
+                SEGMENT_COLORS[i % SEGMENT_COLORS.len()]
+            };
+            let is_selected = self.selected_segment == Some(i) || self.selected_segments.contains(&i);
             let alpha = if is_selected { 140 } else { 70 };
 
             let seg_rect = egui::Rect::from_min_max(
@@ -340,10 +610,14 @@ impl<'a> TimelineWidget<'a> {
 
             let label_width = seg_rect.width();
             if label_width > 25.0 {
+                let label = match overage {
+                    Some(over) => format!("{} (+{})", seg.label, format_size(over)),
+                    None => seg.label.clone(),
+                };
                 painter.text(
                     egui::pos2(seg_rect.left() + 3.0, seg_rect.top() + 1.0),
                     egui::Align2::LEFT_TOP,
-                    &seg.label,
+                    label,
                     egui::FontId::proportional(9.0),
                     egui::Color32::WHITE,
                 );
@@ -481,7 +755,7 @@ impl<'a> TimelineWidget<'a> {
         let steps = [0.1, 0.5, 1.0, 2.0, 5.0, 10.0, 30.0, 60.0, 300.0, 600.0];
 
         for step in steps {
-            if step * pixels_per_second as f64 >= min_pixel_gap as f64 {
+            if step * pixels_per_second as f64 >= min_pixel_gap {
                 return step;
             }
         }
@@ -489,13 +763,40 @@ impl<'a> TimelineWidget<'a> {
     }
 }
 
+/// Operations offered by the right-click menu of a timeline segment
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SegmentAction {
+    Delete,
+    SplitAtPlayhead,
+    MergeWithNext,
+    Rename,
+    ToggleEnabled,
+    GoToStart,
+    Export,
+}
+
+/// Segment edge being dragged on the timeline
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BoundaryDrag {
+    pub segment: usize,
+    /// Start edge (otherwise the end)
+    pub is_start: bool,
+    /// Time under the pointer
+    pub time: f64,
+    /// Alt held: the neighbour sharing the edge follows (rolling edit)
+    pub rolling: bool,
+}
+
 pub struct TimelineResponse {
     pub seek_to: Option<f64>,
     pub zoom_changed: Option<f32>,
     pub scroll_changed: Option<f32>,
     pub segment_clicked: Option<usize>,
+    /// Entry picked in a segment's right-click menu
+    pub segment_action: Option<(usize, SegmentAction)>,
     pub is_scrubbing: bool,
     pub zoom_to_fit: bool,
+    pub boundary_drag: Option<BoundaryDrag>,
 }
 
 /// Barre de lecture compacte affichant les clips à fusionner bout à bout
@@ -564,3 +865,106 @@ pub fn clip_seekbar(
     }
     (None, false)
 }
+
+/// Poignée saisie sur le `range_slider`
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum RangeHandle {
+    Start,
+    End,
+    /// Milieu de la plage : les deux bornes glissent ensemble
+    Both,
+}
+
+/// Résultat d'un `range_slider` : nouvelles bornes pendant un drag
+pub struct RangeSliderResponse {
+    /// `Some` quand l'utilisateur a bougé la plage cette frame
+    pub range: Option<(f64, f64)>,
+    /// Temps de la borne tenue (ou du début si toute la plage glisse), pour
+    /// y amener la tête de lecture
+    pub preview_time: Option<f64>,
+}
+
+/// Curseur double de coupe rapide : une poignée IN, une poignée OUT sur toute
+/// la durée du fichier. Saisir près d'une poignée la déplace, saisir entre les
+/// deux fait glisser la plage sans changer sa durée. Bornes absentes = début
+/// et fin du fichier.
+pub fn range_slider(
+    ui: &mut egui::Ui,
+    start: Option<f64>,
+    end: Option<f64>,
+    duration: f64,
+    current_time: f64,
+) -> RangeSliderResponse {
+    const HANDLE_GRAB: f32 = 8.0;
+    let width = ui.available_width();
+    let (rect, response) = ui.allocate_exact_size(egui::vec2(width, 18.0), egui::Sense::click_and_drag());
+    let mut out = RangeSliderResponse { range: None, preview_time: None };
+    if duration <= 0.0 {
+        return out;
+    }
+
+    let start = start.unwrap_or(0.0).clamp(0.0, duration);
+    let end = end.unwrap_or(duration).clamp(start, duration);
+    let to_x = |t: f64| rect.left() + (t / duration) as f32 * rect.width();
+    let to_time = |x: f32| (((x - rect.left()) / rect.width()).clamp(0.0, 1.0) as f64) * duration;
+    let (start_x, end_x) = (to_x(start), to_x(end));
+
+    // Poignée choisie au début du drag, gardée jusqu'au relâchement
+    let handle_id = response.id.with("handle");
+    if response.drag_started() {
+        if let Some(pos) = response.interact_pointer_pos() {
+            let handle = if (pos.x - start_x).abs() <= HANDLE_GRAB && (pos.x - start_x).abs() <= (pos.x - end_x).abs() {
+                RangeHandle::Start
+            } else if (pos.x - end_x).abs() <= HANDLE_GRAB {
+                RangeHandle::End
+            } else if pos.x > start_x && pos.x < end_x {
+                RangeHandle::Both
+            } else if pos.x < start_x {
+                RangeHandle::Start
+            } else {
+                RangeHandle::End
+            };
+            ui.memory_mut(|m| m.data.insert_temp(handle_id, (handle, to_time(pos.x) - start)));
+        }
+    }
+    if response.dragged() {
+        let grabbed = ui.memory(|m| m.data.get_temp::<(RangeHandle, f64)>(handle_id));
+        if let (Some((handle, grab_offset)), Some(pos)) = (grabbed, response.interact_pointer_pos()) {
+            let t = to_time(pos.x);
+            let (s, e, preview) = match handle {
+                RangeHandle::Start => (t.min(end), end, t.min(end)),
+                RangeHandle::End => (start, t.max(start), t.max(start)),
+                RangeHandle::Both => {
+                    let s = (t - grab_offset).clamp(0.0, duration - (end - start));
+                    (s, s + (end - start), s)
+                }
+            };
+            out.range = Some((s, e));
+            out.preview_time = Some(preview);
+        }
+    }
+    if response.drag_stopped() {
+        ui.memory_mut(|m| m.data.remove::<(RangeHandle, f64)>(handle_id));
+    }
+
+    if ui.is_rect_visible(rect) {
+        let painter = ui.painter_at(rect);
+        let track = rect.shrink2(egui::vec2(0.0, 6.0));
+        painter.rect_filled(track, 2.0, egui::Color32::from_gray(45));
+        let kept = egui::Rect::from_min_max(egui::pos2(start_x, track.top()), egui::pos2(end_x, track.bottom()));
+        painter.rect_filled(kept, 2.0, egui::Color32::from_rgb(80, 160, 90));
+        for (x, color) in [(start_x, egui::Color32::GREEN), (end_x, egui::Color32::RED)] {
+            painter.rect_filled(
+                egui::Rect::from_center_size(egui::pos2(x, rect.center().y), egui::vec2(5.0, rect.height())),
+                1.0,
+                color,
+            );
+        }
+        let px = to_x(current_time.clamp(0.0, duration));
+        painter.line_segment(
+            [egui::pos2(px, rect.top()), egui::pos2(px, rect.bottom())],
+            egui::Stroke::new(1.0, egui::Color32::WHITE),
+        );
+    }
+    out
+}
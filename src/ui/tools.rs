@@ -1,21 +1,16 @@
 use serde::{Deserialize, Serialize};
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 /// Mode d'édition principal
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
 pub enum EditingMode {
     /// Découpe de segments
+    #[default]
     Split,
     /// Assemblage / merge de fichiers
     Merge,
 }
 
-impl Default for EditingMode {
-    fn default() -> Self {
-        EditingMode::Split
-    }
-}
-
 /// Mode de trim - détermine la vitesse vs précision du cut
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
 pub enum TrimMode {
@@ -33,6 +28,11 @@ pub enum TrimMode {
 }
 
 impl TrimMode {
+    /// Vrai si le mode ré-encode tout le segment (cut précis à la frame)
+    pub fn is_reencode(&self) -> bool {
+        matches!(self, TrimMode::Precise | TrimMode::HighQuality)
+    }
+
     pub fn all() -> &'static [TrimMode] {
         &[
             TrimMode::SmartCut,
@@ -82,13 +82,6 @@ impl Default for TrimSettings {
     }
 }
 
-impl TrimSettings {
-    pub fn update_from_file_duration(&mut self, duration: f64) {
-        self.end_time = duration;
-        self.end_time_str = crate::utils::format_time(duration);
-    }
-}
-
 /// Un segment de découpe vidéo
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SplitSegment {
@@ -115,12 +108,261 @@ impl SplitSegment {
     }
 }
 
+/// Que faire quand un fichier de sortie existe déjà
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+pub enum ConflictPolicy {
+    /// Écrase le fichier existant
+    Overwrite,
+    /// Ajoute un suffixe `_1`, `_2`... jusqu'à trouver un nom libre
+    #[default]
+    Rename,
+    /// Garde le fichier existant s'il est valide (non vide, lisible, bonne
+    /// durée) au lieu de le refaire ; un fichier invalide est ré-exporté.
+    /// Rend la reprise d'un batch interrompu idempotente.
+    Skip,
+}
+
+impl ConflictPolicy {
+    pub fn all() -> &'static [ConflictPolicy] {
+        &[ConflictPolicy::Rename, ConflictPolicy::Overwrite, ConflictPolicy::Skip]
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            ConflictPolicy::Overwrite => "Overwrite",
+            ConflictPolicy::Rename => "Rename",
+            ConflictPolicy::Skip => "Skip existing",
+        }
+    }
+}
+
+/// Report des points IN/OUT du fichier courant sur les autres fichiers
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+pub enum BatchTrimAnchor {
+    /// Mêmes instants dans chaque fichier
+    #[default]
+    Absolute,
+    /// IN depuis le début, OUT à la même distance de la fin (générique de fin)
+    FromEnd,
+}
+
+impl BatchTrimAnchor {
+    pub fn all() -> &'static [BatchTrimAnchor] {
+        &[BatchTrimAnchor::Absolute, BatchTrimAnchor::FromEnd]
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            BatchTrimAnchor::Absolute => "Same times",
+            BatchTrimAnchor::FromEnd => "OUT from end",
+        }
+    }
+}
+
+/// Action à la fin d'un export d'un seul fichier (pas d'un lot)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+pub enum OpenOutputAction {
+    #[default]
+    Nothing,
+    /// Lecteur par défaut du système
+    SystemPlayer,
+    /// Ajout du résultat à la liste des fichiers, pour le revoir ici
+    AddToProject,
+}
+
+impl OpenOutputAction {
+    pub fn all() -> &'static [OpenOutputAction] {
+        &[OpenOutputAction::Nothing, OpenOutputAction::SystemPlayer, OpenOutputAction::AddToProject]
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            OpenOutputAction::Nothing => "Do nothing",
+            OpenOutputAction::SystemPlayer => "Open in player",
+            OpenOutputAction::AddToProject => "Review in app",
+        }
+    }
+}
+
+/// Pas de navigation clavier, en secondes
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SeekSettings {
+    /// ←/→ pendant la lecture
+    pub small_secs: f64,
+    /// J/L
+    pub large_secs: f64,
+    /// Shift+J/L
+    pub fine_secs: f64,
+}
+
+impl Default for SeekSettings {
+    fn default() -> Self {
+        Self {
+            small_secs: 5.0,
+            large_secs: 10.0,
+            fine_secs: 1.0,
+        }
+    }
+}
+
+/// Format d'une animation courte exportée depuis la zone IN/OUT
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+pub enum AnimationFormat {
+    Gif,
+    /// WebP animé : bien plus léger qu'un GIF à qualité égale
+    #[default]
+    WebP,
+    Apng,
+}
+
+impl AnimationFormat {
+    pub fn all() -> &'static [AnimationFormat] {
+        &[AnimationFormat::WebP, AnimationFormat::Apng, AnimationFormat::Gif]
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            AnimationFormat::Gif => "GIF",
+            AnimationFormat::WebP => "WebP",
+            AnimationFormat::Apng => "APNG",
+        }
+    }
+
+    /// Encodeur FFmpeg utilisé (`-c:v`, implicite pour le GIF)
+    pub fn encoder(&self) -> &'static str {
+        match self {
+            AnimationFormat::Gif => "gif",
+            AnimationFormat::WebP => "libwebp",
+            AnimationFormat::Apng => "apng",
+        }
+    }
+
+    pub fn extension(&self) -> &'static str {
+        match self {
+            AnimationFormat::Gif => "gif",
+            AnimationFormat::WebP => "webp",
+            AnimationFormat::Apng => "apng",
+        }
+    }
+}
+
+/// Paramètres d'export en animation (boucle infinie, sans audio)
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AnimationSettings {
+    pub format: AnimationFormat,
+    pub fps: u32,
+    /// Largeur de sortie en pixels, hauteur proportionnelle. 0 = celle de la source
+    pub width: u32,
+}
+
+impl Default for AnimationSettings {
+    fn default() -> Self {
+        Self {
+            format: AnimationFormat::default(),
+            fps: 15,
+            width: 480,
+        }
+    }
+}
+
+/// Format de la piste audio extraite d'un fichier entier
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+pub enum AudioExtractFormat {
+    #[default]
+    Mp3,
+    /// AAC dans un conteneur `.m4a`
+    Aac,
+    /// PCM 16 bits, sans perte (transcription, montage)
+    Wav,
+}
+
+impl AudioExtractFormat {
+    pub fn all() -> &'static [AudioExtractFormat] {
+        &[AudioExtractFormat::Mp3, AudioExtractFormat::Aac, AudioExtractFormat::Wav]
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            AudioExtractFormat::Mp3 => "MP3",
+            AudioExtractFormat::Aac => "AAC",
+            AudioExtractFormat::Wav => "WAV",
+        }
+    }
+
+    /// Encodeur FFmpeg (`-c:a`)
+    pub fn encoder(&self) -> &'static str {
+        match self {
+            AudioExtractFormat::Mp3 => "libmp3lame",
+            AudioExtractFormat::Aac => "aac",
+            AudioExtractFormat::Wav => "pcm_s16le",
+        }
+    }
+
+    pub fn extension(&self) -> &'static str {
+        match self {
+            AudioExtractFormat::Mp3 => "mp3",
+            AudioExtractFormat::Aac => "m4a",
+            AudioExtractFormat::Wav => "wav",
+        }
+    }
+
+    /// Taille approximative de la sortie : ~190 kb/s en MP3 VBR q2, 192 kb/s
+    /// en AAC, débit PCM brut en WAV
+    pub fn estimated_bytes(&self, duration: f64, sample_rate: Option<u32>, channels: Option<u32>) -> u64 {
+        let bps = match self {
+            AudioExtractFormat::Mp3 => 190_000.0,
+            AudioExtractFormat::Aac => 192_000.0,
+            AudioExtractFormat::Wav => {
+                sample_rate.unwrap_or(48_000) as f64 * channels.unwrap_or(2) as f64 * 16.0
+            }
+        };
+        (duration.max(0.0) * bps / 8.0) as u64
+    }
+}
+
+/// Montage d'aperçu (« proof sheet ») : N extraits courts répartis sur le fichier
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ProofSheetSettings {
+    pub clip_count: usize,
+    /// Durée de chaque extrait, en secondes
+    pub clip_length: f64,
+}
+
+impl Default for ProofSheetSettings {
+    fn default() -> Self {
+        // 12 × 5 s ≈ une minute d'aperçu
+        Self {
+            clip_count: 12,
+            clip_length: 5.0,
+        }
+    }
+}
+
 /// Paramètres globaux de découpe
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
 pub struct SplitSettings {
     pub trim_mode: TrimMode,
     pub max_size_mb: f64,
     pub output_folder: Option<PathBuf>,
+    /// Aligne les bornes des segments sur la grille des frames avant un export ré-encodé
+    pub snap_to_frames: bool,
+    /// Politique si le fichier de sortie existe déjà
+    pub conflict_policy: ConflictPolicy,
+    /// Écrit une playlist `.m3u` des fichiers produits à la fin d'un export multiple
+    pub write_playlist: bool,
+    /// Mode choisi d'office à la sélection d'un fichier, par type de source
+    pub trim_mode_rules: Vec<TrimModeRule>,
+    /// Modèle des libellés des nouveaux segments (`{n}`, `{nn}`, `{total}`)
+    pub segment_label_format: String,
+    /// Modèle des libellés des parties d'un segment trop gros (`{label}` en plus)
+    pub part_label_format: String,
+    /// Auto-Cut : fenêtre de recherche d'un silence avant la coupe idéale
+    /// (secondes, un quart de cette fenêtre après)
+    pub cut_tolerance_secs: f64,
 }
 
 impl Default for SplitSettings {
@@ -129,6 +371,407 @@ impl Default for SplitSettings {
             trim_mode: TrimMode::SmartCut,
             max_size_mb: 1000.0, // défaut 1000 MB pour Auto-Cut
             output_folder: None,
+            snap_to_frames: false,
+            conflict_policy: ConflictPolicy::default(),
+            write_playlist: false,
+            trim_mode_rules: Vec::new(),
+            segment_label_format: crate::utils::DEFAULT_SEGMENT_LABEL.to_string(),
+            part_label_format: crate::utils::DEFAULT_PART_LABEL.to_string(),
+            cut_tolerance_secs: 30.0,
+        }
+    }
+}
+
+impl SplitSettings {
+    /// Limite de taille en octets (0 = pas de limite)
+    pub fn max_size_bytes(&self) -> u64 {
+        if self.max_size_mb > 0.0 {
+            (self.max_size_mb * 1024.0 * 1024.0) as u64
+        } else {
+            0
+        }
+    }
+
+    /// Plage de `cut_tolerance_secs` proposée dans l'UI
+    pub const CUT_TOLERANCE_RANGE: std::ops::RangeInclusive<f64> = 5.0..=60.0;
+
+    /// Libellé du segment `n` sur `total`
+    pub fn segment_label(&self, n: usize, total: usize) -> String {
+        crate::utils::format_label(&self.segment_label_format, "", n, total)
+    }
+
+    /// Mode de la première règle qui vise le codec vidéo ou l'extension de la source
+    pub fn default_trim_mode(&self, extension: &str, video_codec: Option<&str>) -> Option<TrimMode> {
+        self.trim_mode_rules.iter()
+            .find(|rule| rule.matches(extension, video_codec))
+            .map(|rule| rule.mode)
+    }
+}
+
+/// Mode de coupe par défaut d'un type de source
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TrimModeRule {
+    /// Codec vidéo (`h264`, `prores`) ou extension (`mp4`), casse ignorée
+    pub source: String,
+    pub mode: TrimMode,
+}
+
+impl TrimModeRule {
+    pub fn matches(&self, extension: &str, video_codec: Option<&str>) -> bool {
+        let source = self.source.trim().trim_start_matches('.');
+        !source.is_empty()
+            && (source.eq_ignore_ascii_case(extension)
+                || video_codec.is_some_and(|codec| source.eq_ignore_ascii_case(codec)))
+    }
+}
+
+/// Rotation appliquée à l'export (filtre `transpose`)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+pub enum Rotation {
+    #[default]
+    None,
+    /// 90° sens horaire
+    Cw90,
+    Rot180,
+    /// 90° sens anti-horaire
+    Ccw90,
+}
+
+impl Rotation {
+    pub fn all() -> &'static [Rotation] {
+        &[Rotation::None, Rotation::Cw90, Rotation::Rot180, Rotation::Ccw90]
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Rotation::None => "None",
+            Rotation::Cw90 => "90° CW",
+            Rotation::Rot180 => "180°",
+            Rotation::Ccw90 => "90° CCW",
+        }
+    }
+}
+
+/// Comment appliquer la rotation
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+pub enum RotationMethod {
+    /// Filtre `transpose` (modes ré-encodés)
+    #[default]
+    Reencode,
+    /// Réécrit seulement la matrice d'affichage, en copie (mode Lossless) :
+    /// instantané, l'image n'est pas touchée
+    Metadata,
+}
+
+impl Rotation {
+    /// Valeur de `-display_rotation` : degrés dans le sens anti-horaire
+    pub fn display_rotation_degrees(&self) -> i32 {
+        match self {
+            Rotation::None => 0,
+            Rotation::Cw90 => -90,
+            Rotation::Rot180 => 180,
+            Rotation::Ccw90 => 90,
+        }
+    }
+}
+
+/// Rectangle de crop en pixels source
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CropRect {
+    pub x: u32,
+    pub y: u32,
+    pub width: u32,
+    pub height: u32,
+}
+
+/// Bande d'égaliseur paramétrique (filtre `equalizer`)
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct EqBand {
+    /// Fréquence centrale en Hz
+    pub freq_hz: u32,
+    /// Largeur de bande en octaves
+    pub width_octaves: f32,
+    /// Gain en dB (négatif = atténuation)
+    pub gain_db: f32,
+}
+
+impl EqBand {
+    /// Zone de présence de la voix (intelligibilité)
+    pub const PRESENCE_HZ: u32 = 3000;
+}
+
+/// Filtres des exports ré-encodés. L'ordre d'application est fixé par
+/// `ffmpeg::video_filter_chain` et `ffmpeg::audio_filter_chain`, pas par
+/// l'ordre des champs.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct FilterSettings {
+    /// Désentrelacement (yadif)
+    pub deinterlace: bool,
+    pub crop: Option<CropRect>,
+    pub rotation: Rotation,
+    /// Rotation incrustée (ré-encodage) ou simple drapeau (copie)
+    pub rotation_method: RotationMethod,
+    pub hflip: bool,
+    pub vflip: bool,
+    /// Hauteur de sortie, largeur calculée pour garder le ratio
+    pub scale_height: Option<u32>,
+    /// Ratio d'affichage forcé (`16:9`, `2.35`...), tel que saisi ; validé
+    /// par `ffmpeg::parse_aspect_ratio` avant l'export
+    pub display_aspect: Option<String>,
+    /// Luminosité (-1.0..1.0, 0 = neutre)
+    pub brightness: f32,
+    /// Contraste (0.0..2.0, 1 = neutre)
+    pub contrast: f32,
+    /// Saturation (0.0..3.0, 1 = neutre)
+    pub saturation: f32,
+    /// Durée du fondu d'entrée en secondes (0 = aucun)
+    pub fade_in_secs: f64,
+    /// Durée du fondu de sortie en secondes (0 = aucun)
+    pub fade_out_secs: f64,
+    /// Coupe-bas audio (ronflement, rumble), fréquence de coupure en Hz
+    pub highpass: Option<u32>,
+    /// Bandes d'égaliseur audio, appliquées après le coupe-bas
+    pub equalizer: Vec<EqBand>,
+    /// Secondes de noir (et de silence) ajoutées avant le segment
+    pub pad_before: f64,
+    /// Secondes de noir (et de silence) ajoutées après le segment
+    pub pad_after: f64,
+}
+
+impl Default for FilterSettings {
+    fn default() -> Self {
+        Self {
+            deinterlace: false,
+            crop: None,
+            rotation: Rotation::None,
+            rotation_method: RotationMethod::Reencode,
+            hflip: false,
+            vflip: false,
+            scale_height: None,
+            display_aspect: None,
+            brightness: 0.0,
+            contrast: 1.0,
+            saturation: 1.0,
+            fade_in_secs: 0.0,
+            fade_out_secs: 0.0,
+            highpass: None,
+            equalizer: Vec::new(),
+            pad_before: 0.0,
+            pad_after: 0.0,
         }
     }
 }
+
+impl FilterSettings {
+    /// Hauteurs proposées dans l'UI
+    pub const SCALE_HEIGHTS: &'static [u32] = &[2160, 1440, 1080, 720, 480, 360];
+
+    /// Plage du coupe-bas proposée dans l'UI (Hz)
+    pub const HIGHPASS_RANGE: std::ops::RangeInclusive<u32> = 50..=300;
+
+    /// Ratios d'affichage proposés dans l'UI
+    pub const ASPECT_PRESETS: &'static [&'static str] = &["16:9", "4:3", "21:9", "1:1", "9:16", "2.35:1"];
+
+    /// Vrai si aucun filtre (vidéo ou audio) n'est actif
+    pub fn is_identity(&self) -> bool {
+        *self == FilterSettings::default()
+    }
+
+    /// Vrai si du noir / silence est ajouté autour du segment
+    pub fn has_padding(&self) -> bool {
+        self.pad_before > 0.0 || self.pad_after > 0.0
+    }
+
+    /// Durée ajoutée au segment par le padding (secondes)
+    pub fn padding_secs(&self) -> f64 {
+        self.pad_before.max(0.0) + self.pad_after.max(0.0)
+    }
+
+    /// Gain de la bande de présence (0 si absente)
+    pub fn presence_boost_db(&self) -> f32 {
+        self.equalizer
+            .iter()
+            .find(|b| b.freq_hz == EqBand::PRESENCE_HZ)
+            .map_or(0.0, |b| b.gain_db)
+    }
+
+    /// Règle la bande de présence (une octave autour de 3 kHz) ; 0 dB la retire
+    pub fn set_presence_boost_db(&mut self, gain_db: f32) {
+        self.equalizer.retain(|b| b.freq_hz != EqBand::PRESENCE_HZ);
+        if gain_db != 0.0 {
+            self.equalizer.push(EqBand { freq_hz: EqBand::PRESENCE_HZ, width_octaves: 1.0, gain_db });
+        }
+    }
+}
+
+/// Façon d'appliquer un fichier de sous-titres externe
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+pub enum SubtitleMode {
+    /// Incrustés dans l'image (filtre `subtitles`), toujours visibles
+    #[default]
+    BurnIn,
+    /// Ajoutés comme piste activable dans le lecteur (mov_text en mp4, copie en mkv)
+    Embed,
+}
+
+impl SubtitleMode {
+    pub fn all() -> &'static [SubtitleMode] {
+        &[SubtitleMode::BurnIn, SubtitleMode::Embed]
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            SubtitleMode::BurnIn => "Burn in",
+            SubtitleMode::Embed => "Embed as track",
+        }
+    }
+}
+
+/// Fichier de sous-titres (.srt / .ass / .vtt) à appliquer à l'export
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SubtitleSettings {
+    pub path: PathBuf,
+    pub mode: SubtitleMode,
+}
+
+/// Paramètres d'encodage appliqués aux exports ré-encodés (Précis / Haute qualité).
+/// Une copie est figée dans chaque job au moment de la mise en file.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ExportSettings {
+    /// Débit audio AAC en kb/s. `None` = défaut du mode (192k / 256k)
+    pub audio_bitrate_kbps: Option<u32>,
+    /// Fréquence d'échantillonnage de sortie. `None` = celle de la source
+    pub audio_sample_rate: Option<u32>,
+    /// Ré-encode dans le codec de la source (HEVC → libx265) au lieu de forcer x264
+    pub match_source_codec: bool,
+    /// Format de pixel forcé (`-pix_fmt`). `None` = auto (yuv420p pour mp4/mov)
+    pub pix_fmt: Option<String>,
+    /// Décalage audio en ms (positif = audio retardé, négatif = avancé)
+    pub audio_offset_ms: Option<i64>,
+    /// Filtres vidéo (crop, rotation, resize, couleur, fondus)
+    pub filters: FilterSettings,
+    /// Sous-titres externes, incrustés ou intégrés en piste
+    pub subtitles: Option<SubtitleSettings>,
+    /// Encodeur vidéo imposé (`-c:v`). `None` = choix du mode
+    pub video_codec: Option<String>,
+    /// CRF imposé. `None` = valeur du mode
+    pub crf: Option<u32>,
+    /// Preset d'encodeur imposé. `None` = valeur du mode
+    pub preset: Option<String>,
+    /// Arguments FFmpeg ajoutés tels quels avant le fichier de sortie
+    pub extra_args: Vec<String>,
+    /// Conteneur de sortie (extension sans le point). `None` = celui de la source
+    pub container: Option<String>,
+    /// Fin de coupe en position absolue (`-to`) plutôt qu'en durée (`-t`)
+    pub absolute_end: bool,
+    /// Nombre max de threads FFmpeg en ré-encodage. `None` = auto (tous les coeurs)
+    pub threads: Option<u32>,
+    /// Cadence de sortie en images/s (ré-encodage). `None` = celle de la source
+    pub output_fps: Option<f64>,
+    /// Copie les métadonnées globales de la source (`-map_metadata 0`, date de création...)
+    pub copy_metadata: bool,
+    /// Coupe sans perte : garde les timestamps d'origine (`-copyts`) au lieu
+    /// de les remettre à zéro
+    pub keep_timestamps: bool,
+    /// Garde les chapitres de la source compris dans le segment, recalés sur son début
+    pub keep_chapters: bool,
+    /// Normalise le volume de chaque clip (`loudnorm`, modes ré-encodés)
+    pub normalize_loudness: bool,
+    /// GOP fermés à intervalle fixe (ré-encodage) : les parties se
+    /// rejouent bout à bout sans accroc aux jointures
+    pub closed_gop: bool,
+}
+
+impl Default for ExportSettings {
+    fn default() -> Self {
+        Self {
+            audio_bitrate_kbps: None,
+            audio_sample_rate: None,
+            match_source_codec: true,
+            pix_fmt: None,
+            audio_offset_ms: None,
+            filters: FilterSettings::default(),
+            subtitles: None,
+            video_codec: None,
+            crf: None,
+            preset: None,
+            extra_args: Vec::new(),
+            container: None,
+            absolute_end: false,
+            threads: None,
+            output_fps: None,
+            copy_metadata: false,
+            keep_timestamps: false,
+            keep_chapters: false,
+            normalize_loudness: false,
+            closed_gop: false,
+        }
+    }
+}
+
+impl ExportSettings {
+    /// Débits proposés dans l'UI
+    pub const AUDIO_BITRATES: &'static [u32] = &[96, 128, 160, 192, 256, 320];
+    /// Fréquences proposées dans l'UI
+    pub const SAMPLE_RATES: &'static [u32] = &[44100, 48000];
+    /// Formats de pixel proposés dans l'UI
+    pub const PIX_FMTS: &'static [&'static str] = &["yuv420p", "yuv420p10le", "yuv444p"];
+    /// Cadences de sortie proposées dans l'UI
+    pub const FRAME_RATES: &'static [f64] = &[24.0, 25.0, 30.0, 50.0, 60.0];
+    /// Conteneurs de sortie proposés dans l'UI
+    pub const CONTAINERS: &'static [&'static str] = &["mp4", "mkv", "mov", "webm"];
+
+    /// Extension des fichiers produits à partir de `source` : le conteneur
+    /// choisi, sinon celle de la source (le contenu suit l'extension, FFmpeg
+    /// choisissant le muxer d'après elle)
+    pub fn output_extension(&self, source: &Path) -> String {
+        match &self.container {
+            Some(ext) => ext.clone(),
+            None => source.extension().unwrap_or_default().to_string_lossy().to_string(),
+        }
+    }
+
+    /// Retire tout ce qu'un profil d'encodage a pu imposer
+    pub fn clear_profile(&mut self) {
+        self.video_codec = None;
+        self.crf = None;
+        self.preset = None;
+        self.pix_fmt = None;
+        self.audio_bitrate_kbps = None;
+        self.extra_args.clear();
+    }
+}
+
+/// Profil d'encodage nommé, chargé depuis un fichier JSON partagé.
+/// Les champs absents gardent le comportement du mode.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct EncodeProfile {
+    pub name: String,
+    #[serde(default)]
+    pub video_codec: Option<String>,
+    #[serde(default)]
+    pub crf: Option<u32>,
+    #[serde(default)]
+    pub preset: Option<String>,
+    #[serde(default)]
+    pub pix_fmt: Option<String>,
+    #[serde(default)]
+    pub audio_bitrate_kbps: Option<u32>,
+    #[serde(default)]
+    pub extra_args: Vec<String>,
+}
+
+impl EncodeProfile {
+    /// Reporte le profil sur les paramètres d'export (remplace le précédent)
+    pub fn apply_to(&self, settings: &mut ExportSettings) {
+        settings.clear_profile();
+        settings.video_codec = self.video_codec.clone();
+        settings.crf = self.crf;
+        settings.preset = self.preset.clone();
+        settings.pix_fmt = self.pix_fmt.clone();
+        settings.audio_bitrate_kbps = self.audio_bitrate_kbps;
+        settings.extra_args = self.extra_args.clone();
+    }
+}
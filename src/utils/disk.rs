@@ -0,0 +1,75 @@
+use std::path::Path;
+
+/// Slack kept on top of the estimates: they come from the source bitrate,
+/// and chapter remuxes write a temporary copy of each segment
+const HEADROOM_PERCENT: u64 = 5;
+
+/// Free bytes available to the current user on the volume holding `path`.
+/// `path` may not exist yet (output folder created at export): its nearest
+/// existing parent is queried. `None` when the platform call fails.
+pub fn free_space(path: &Path) -> Option<u64> {
+    let existing = path.ancestors().find(|p| p.exists())?;
+    available_bytes(existing)
+}
+
+#[cfg(unix)]
+fn available_bytes(path: &Path) -> Option<u64> {
+    use std::os::unix::ffi::OsStrExt;
+    let c_path = std::ffi::CString::new(path.as_os_str().as_bytes()).ok()?;
+    let mut stats: libc::statvfs = unsafe { std::mem::zeroed() };
+    // SAFETY: c_path is NUL-terminated and stats is a valid out pointer
+    if unsafe { libc::statvfs(c_path.as_ptr(), &mut stats) } != 0 {
+        return None;
+    }
+    #[allow(clippy::unnecessary_cast)]
+    Some(stats.f_bavail as u64 * stats.f_frsize as u64)
+}
+
+#[cfg(windows)]
+fn available_bytes(path: &Path) -> Option<u64> {
+    use std::os::windows::ffi::OsStrExt;
+    #[link(name = "kernel32")]
+    extern "system" {
+        fn GetDiskFreeSpaceExW(
+            directory: *const u16,
+            free_to_caller: *mut u64,
+            total: *mut u64,
+            total_free: *mut u64,
+        ) -> i32;
+    }
+    let wide: Vec<u16> = path.as_os_str().encode_wide().chain(std::iter::once(0)).collect();
+    let mut free = 0u64;
+    // SAFETY: wide is NUL-terminated; unused outputs may be null
+    let ok = unsafe { GetDiskFreeSpaceExW(wide.as_ptr(), &mut free, std::ptr::null_mut(), std::ptr::null_mut()) };
+    (ok != 0).then_some(free)
+}
+
+#[cfg(not(any(unix, windows)))]
+fn available_bytes(_path: &Path) -> Option<u64> {
+    None
+}
+
+/// Bytes missing to write `needed` (plus headroom) into `free`, `None` if it fits
+pub fn space_shortfall(needed: u64, free: u64) -> Option<u64> {
+    let required = needed.saturating_add(needed / 100 * HEADROOM_PERCENT);
+    (required > free).then(|| required - free)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_space_shortfall_keeps_headroom() {
+        assert_eq!(space_shortfall(1_000, 2_000), None);
+        assert_eq!(space_shortfall(1_000, 1_050), None);
+        assert_eq!(space_shortfall(1_000, 1_000), Some(50));
+        assert_eq!(space_shortfall(0, 0), None);
+    }
+
+    #[test]
+    fn test_free_space_of_missing_folder_uses_parent() {
+        let missing = std::env::temp_dir().join("ffmpeg_ui_no_such_dir").join("out");
+        assert_eq!(free_space(&missing).is_some(), free_space(&std::env::temp_dir()).is_some());
+    }
+}
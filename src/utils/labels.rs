@@ -0,0 +1,35 @@
+/// Default template of the labels given to new segments
+pub const DEFAULT_SEGMENT_LABEL: &str = "Segment {n}";
+/// Default template of the parts produced by the size-limit auto-split
+pub const DEFAULT_PART_LABEL: &str = "{label} ({n}/{total})";
+
+/// Fill a label template: `{label}` (parent segment's label, parts only),
+/// `{n}` (1-based number), `{nn}` (zero-padded to the width of `{total}`)
+/// and `{total}`. A template that yields nothing falls back to `{n}`.
+pub fn format_label(template: &str, label: &str, n: usize, total: usize) -> String {
+    let width = total.max(n).to_string().len().max(2);
+    let out = template
+        .replace("{label}", label)
+        .replace("{nn}", &format!("{:0width$}", n, width = width))
+        .replace("{n}", &n.to_string())
+        .replace("{total}", &total.to_string());
+    if out.trim().is_empty() {
+        n.to_string()
+    } else {
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_label() {
+        assert_eq!(format_label(DEFAULT_SEGMENT_LABEL, "", 3, 12), "Segment 3");
+        assert_eq!(format_label(DEFAULT_PART_LABEL, "Segment 1", 2, 5), "Segment 1 (2/5)");
+        assert_eq!(format_label("{label}_part{n}of{total}", "intro", 2, 5), "intro_part2of5");
+        assert_eq!(format_label("clip_{nn}", "", 7, 120), "clip_007");
+        assert_eq!(format_label("  ", "", 4, 9), "4");
+    }
+}
@@ -1,3 +1,9 @@
+mod disk;
+mod labels;
+mod output;
 mod time;
 
+pub use disk::*;
+pub use labels::*;
+pub use output::*;
 pub use time::*;
@@ -0,0 +1,88 @@
+use crate::ui::ConflictPolicy;
+use std::path::{Path, PathBuf};
+
+/// Apply the conflict policy to a planned output path.
+/// Returns `None` when the export should be skipped.
+pub fn resolve_output_path(path: &Path, policy: ConflictPolicy) -> Option<PathBuf> {
+    resolve_output_path_with(path, policy, |p| p.exists())
+}
+
+/// Same as [`resolve_output_path`], with the "already taken" check injected
+/// so several outputs planned in one batch don't pick the same free name.
+pub fn resolve_output_path_with(
+    path: &Path,
+    policy: ConflictPolicy,
+    is_taken: impl Fn(&Path) -> bool,
+) -> Option<PathBuf> {
+    if !is_taken(path) {
+        return Some(path.to_path_buf());
+    }
+
+    match policy {
+        ConflictPolicy::Overwrite => Some(path.to_path_buf()),
+        ConflictPolicy::Skip => None,
+        ConflictPolicy::Rename => {
+            let stem = path.file_stem().unwrap_or_default().to_string_lossy().to_string();
+            let ext = path.extension().map(|e| e.to_string_lossy().to_string());
+            let parent = path.parent().unwrap_or(Path::new(""));
+            (1..).map(|n| {
+                let name = match &ext {
+                    Some(ext) => format!("{}_{}.{}", stem, n, ext),
+                    None => format!("{}_{}", stem, n),
+                };
+                parent.join(name)
+            })
+            .find(|candidate| !is_taken(candidate))
+        }
+    }
+}
+
+/// Length of the recent output folders list
+pub const MAX_RECENT_FOLDERS: usize = 8;
+
+/// Move `folder` to the front of `recent` (most recent first), without
+/// duplicates and capped at `MAX_RECENT_FOLDERS`
+pub fn remember_folder(recent: &mut Vec<PathBuf>, folder: &Path) {
+    recent.retain(|f| f != folder);
+    recent.insert(0, folder.to_path_buf());
+    recent.truncate(MAX_RECENT_FOLDERS);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_free_path_is_kept() {
+        let out = resolve_output_path_with(Path::new("/out/a.mp4"), ConflictPolicy::Skip, |_| false);
+        assert_eq!(out, Some(PathBuf::from("/out/a.mp4")));
+    }
+
+    #[test]
+    fn recent_folders_are_deduplicated_and_capped() {
+        let mut recent = vec![PathBuf::from("/uploads"), PathBuf::from("/archive")];
+        remember_folder(&mut recent, Path::new("/archive"));
+        assert_eq!(recent, [PathBuf::from("/archive"), PathBuf::from("/uploads")]);
+
+        for i in 0..MAX_RECENT_FOLDERS {
+            remember_folder(&mut recent, &PathBuf::from(format!("/out{}", i)));
+        }
+        assert_eq!(recent.len(), MAX_RECENT_FOLDERS);
+        assert_eq!(recent[0], PathBuf::from(format!("/out{}", MAX_RECENT_FOLDERS - 1)));
+        assert!(!recent.contains(&PathBuf::from("/uploads")));
+    }
+
+    #[test]
+    fn test_conflict_policies() {
+        let taken = |p: &Path| p == Path::new("/out/a.mp4") || p == Path::new("/out/a_1.mp4");
+        assert_eq!(
+            resolve_output_path_with(Path::new("/out/a.mp4"), ConflictPolicy::Overwrite, taken),
+            Some(PathBuf::from("/out/a.mp4"))
+        );
+        assert_eq!(resolve_output_path_with(Path::new("/out/a.mp4"), ConflictPolicy::Skip, taken), None);
+        assert_eq!(
+            resolve_output_path_with(Path::new("/out/a.mp4"), ConflictPolicy::Rename, taken),
+            Some(PathBuf::from("/out/a_2.mp4"))
+        );
+    }
+}
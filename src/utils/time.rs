@@ -79,6 +79,154 @@ pub fn estimate_segment_size(info: &crate::ffmpeg::MediaInfo, start: f64, end: f
     (total_bitrate_bps as f64 * segment_duration / 8.0) as u64
 }
 
+/// Snap a time to the nearest frame boundary (`round(t * fps) / fps`)
+pub fn snap_to_frame(seconds: f64, fps: f64) -> f64 {
+    if fps <= 0.0 || !fps.is_finite() {
+        return seconds;
+    }
+    (seconds * fps).round() / fps
+}
+
+/// Number of frames in `seconds` at `fps`. A trailing partial frame counts
+/// as a whole one, since FFmpeg keeps every frame whose pts falls inside the
+/// cut (5.00s at 30 fps = 150 frames, 5.01s = 151). `None` if fps is unknown.
+pub fn frame_count(seconds: f64, fps: f64) -> Option<u64> {
+    if fps <= 0.0 || !fps.is_finite() || seconds < 0.0 {
+        return None;
+    }
+    // Tolérance pour les durées déjà alignées sur une frame (erreurs d'arrondi)
+    Some((seconds * fps - 1e-6).ceil().max(0.0) as u64)
+}
+
+/// Duration readout with frame count when the framerate is known:
+/// `5.000s (150 fr)`
+pub fn format_duration_frames(seconds: f64, fps: Option<f64>) -> String {
+    match fps.and_then(|fps| frame_count(seconds, fps)) {
+        Some(frames) => format!("{:.3}s ({} fr)", seconds, frames),
+        None => format!("{:.3}s", seconds),
+    }
+}
+
+/// Map an IN/OUT range picked on a `reference` duration onto a file of
+/// `target` duration. With `out_from_end`, OUT keeps its distance to the end
+/// of the file instead of its absolute time. The result is clamped to the
+/// target; `None` when nothing is left.
+pub fn remap_trim_range(start: f64, end: f64, reference: f64, target: f64, out_from_end: bool) -> Option<(f64, f64)> {
+    let (start, end) = (start.min(end), start.max(end));
+    let end = if out_from_end { target - (reference - end).max(0.0) } else { end };
+    let start = start.clamp(0.0, target);
+    let end = end.clamp(0.0, target);
+    (end - start > 0.001).then_some((start, end))
+}
+
+/// `count` clips of `clip_len` seconds spread evenly over `duration`, each
+/// centred in its slice of the file. A file shorter than the montage
+/// yields one clip covering it entirely.
+pub fn proof_sheet_clips(duration: f64, count: usize, clip_len: f64) -> Vec<(f64, f64)> {
+    if duration <= 0.0 || count == 0 || clip_len <= 0.0 {
+        return Vec::new();
+    }
+    if count as f64 * clip_len >= duration {
+        return vec![(0.0, duration)];
+    }
+    let slice = duration / count as f64;
+    (0..count)
+        .map(|i| {
+            let start = (slice * (i as f64 + 0.5) - clip_len / 2.0).clamp(0.0, duration - clip_len);
+            (start, start + clip_len)
+        })
+        .collect()
+}
+
+/// Shortest segment a boundary drag can leave
+const MIN_SEGMENT_SECS: f64 = 0.1;
+
+/// Move the start (`is_start`) or end of `segments[index]` to `time`,
+/// clamped so no segment overlaps its neighbours or shrinks below 0.1 s.
+/// With `rolling`, a neighbour sharing that boundary moves with it, so
+/// contiguous segments stay contiguous. Returns whether anything moved.
+pub fn move_segment_boundary(
+    segments: &mut [crate::ui::SplitSegment],
+    index: usize,
+    is_start: bool,
+    time: f64,
+    rolling: bool,
+    duration: f64,
+) -> bool {
+    let Some(seg) = segments.get(index) else { return false };
+    let old = if is_start { seg.start_time } else { seg.end_time };
+    let neighbour = if is_start { index.checked_sub(1) } else { Some(index + 1) }
+        .filter(|&i| i < segments.len());
+    let shared = neighbour.filter(|&i| {
+        let edge = if is_start { segments[i].end_time } else { segments[i].start_time };
+        (edge - old).abs() < 0.001
+    });
+
+    let (lo, hi) = match (is_start, neighbour) {
+        (true, n) => (
+            match (n, rolling && shared.is_some()) {
+                (Some(i), true) => segments[i].start_time + MIN_SEGMENT_SECS,
+                (Some(i), false) => segments[i].end_time,
+                (None, _) => 0.0,
+            },
+            seg.end_time - MIN_SEGMENT_SECS,
+        ),
+        (false, n) => (
+            seg.start_time + MIN_SEGMENT_SECS,
+            match (n, rolling && shared.is_some()) {
+                (Some(i), true) => segments[i].end_time - MIN_SEGMENT_SECS,
+                (Some(i), false) => segments[i].start_time,
+                (None, _) => duration,
+            },
+        ),
+    };
+    if lo > hi {
+        return false;
+    }
+    let time = time.clamp(lo.max(0.0), hi.min(duration));
+    if (time - old).abs() < 1e-9 {
+        return false;
+    }
+
+    if is_start {
+        segments[index].start_time = time;
+    } else {
+        segments[index].end_time = time;
+    }
+    if let Some(i) = shared.filter(|_| rolling) {
+        if is_start {
+            segments[i].end_time = time;
+        } else {
+            segments[i].start_time = time;
+        }
+    }
+    true
+}
+
+/// Parts of `[0, duration]` not covered by any of `ranges` (which may be
+/// unsorted or overlap). Gaps under a millisecond, e.g. a range ending a
+/// hair before the next begins or before the end of the file, are dropped.
+pub fn complement_ranges(ranges: &[(f64, f64)], duration: f64) -> Vec<(f64, f64)> {
+    const MIN_GAP: f64 = 0.001;
+    let mut sorted: Vec<(f64, f64)> = ranges.iter()
+        .map(|&(a, b)| (a.min(b).clamp(0.0, duration), a.max(b).clamp(0.0, duration)))
+        .collect();
+    sorted.sort_by(|a, b| a.0.total_cmp(&b.0));
+
+    let mut gaps = Vec::new();
+    let mut cursor = 0.0f64;
+    for (start, end) in sorted {
+        if start - cursor > MIN_GAP {
+            gaps.push((cursor, start));
+        }
+        cursor = cursor.max(end);
+    }
+    if duration - cursor > MIN_GAP {
+        gaps.push((cursor, duration));
+    }
+    gaps
+}
+
 /// Format bitrate in human-readable format
 pub fn format_bitrate(bps: u64) -> String {
     const KBPS: u64 = 1000;
@@ -110,4 +258,86 @@ mod tests {
         assert_eq!(parse_time("1:30"), Some(90.0));
         assert_eq!(parse_time("1:01:30"), Some(3690.0));
     }
+
+    #[test]
+    fn test_snap_to_frame() {
+        assert!((snap_to_frame(1.01, 25.0) - 1.0).abs() < 1e-9);
+        assert!((snap_to_frame(1.03, 25.0) - 1.04).abs() < 1e-9);
+        // 29.97 fps: frame 30 = 1.001001s
+        let snapped = snap_to_frame(1.0, 30000.0 / 1001.0);
+        assert!((snapped - 30.0 * 1001.0 / 30000.0).abs() < 1e-9);
+        // Unknown framerate leaves the time untouched
+        assert_eq!(snap_to_frame(1.2345, 0.0), 1.2345);
+    }
+
+    #[test]
+    fn test_frame_count() {
+        assert_eq!(frame_count(5.0, 30.0), Some(150));
+        assert_eq!(frame_count(5.01, 30.0), Some(151));
+        assert_eq!(frame_count(0.0, 25.0), Some(0));
+        // 100 frames à 29.97 fps, malgré l'arrondi flottant
+        assert_eq!(frame_count(100.0 * 1001.0 / 30000.0, 30000.0 / 1001.0), Some(100));
+        assert_eq!(frame_count(5.0, 0.0), None);
+
+        assert_eq!(format_duration_frames(5.0, Some(30.0)), "5.000s (150 fr)");
+        assert_eq!(format_duration_frames(5.0, None), "5.000s");
+    }
+
+    #[test]
+    fn test_remap_trim_range() {
+        // Intro de 30 s et générique de 20 s repérés sur un épisode de 600 s
+        assert_eq!(remap_trim_range(30.0, 580.0, 600.0, 900.0, false), Some((30.0, 580.0)));
+        assert_eq!(remap_trim_range(30.0, 580.0, 600.0, 900.0, true), Some((30.0, 880.0)));
+        // Fichier plus court : bornage à sa durée
+        assert_eq!(remap_trim_range(30.0, 580.0, 600.0, 400.0, false), Some((30.0, 400.0)));
+        assert_eq!(remap_trim_range(30.0, 580.0, 600.0, 400.0, true), Some((30.0, 380.0)));
+        // Rien ne reste
+        assert_eq!(remap_trim_range(30.0, 580.0, 600.0, 20.0, false), None);
+        assert_eq!(remap_trim_range(30.0, 580.0, 600.0, 40.0, true), None);
+    }
+
+    #[test]
+    fn test_proof_sheet_clips() {
+        let clips = proof_sheet_clips(100.0, 4, 5.0);
+        assert_eq!(clips, vec![(10.0, 15.0), (35.0, 40.0), (60.0, 65.0), (85.0, 90.0)]);
+        assert_eq!(proof_sheet_clips(10.0, 4, 5.0), vec![(0.0, 10.0)]);
+        assert!(proof_sheet_clips(100.0, 0, 5.0).is_empty());
+    }
+
+    #[test]
+    fn test_move_segment_boundary() {
+        use crate::ui::SplitSegment;
+        let mut segs = vec![
+            SplitSegment::new(0.0, 10.0, "A".to_string()),
+            SplitSegment::new(10.0, 20.0, "B".to_string()),
+        ];
+
+        // Sans rolling : bloqué au début du voisin, puis crée un trou
+        assert!(!move_segment_boundary(&mut segs, 0, false, 12.0, false, 20.0));
+        assert!(move_segment_boundary(&mut segs, 0, false, 8.0, false, 20.0));
+        assert_eq!((segs[0].end_time, segs[1].start_time), (8.0, 10.0));
+
+        // Bords séparés : le rolling ne touche pas le voisin
+        assert!(move_segment_boundary(&mut segs, 0, false, 9.0, true, 20.0));
+        assert_eq!(segs[1].start_time, 10.0);
+
+        // Bord partagé : les deux bougent, dans la limite de 0.1 s par segment
+        assert!(move_segment_boundary(&mut segs, 1, true, 9.0, true, 20.0));
+        assert_eq!((segs[0].end_time, segs[1].start_time), (9.0, 9.0));
+        assert!(move_segment_boundary(&mut segs, 0, false, 25.0, true, 20.0));
+        assert!((segs[0].end_time - 19.9).abs() < 1e-9);
+        assert_eq!(segs[0].end_time, segs[1].start_time);
+        assert!(!move_segment_boundary(&mut segs, 5, true, 1.0, true, 20.0));
+    }
+
+    #[test]
+    fn test_complement_ranges() {
+        assert_eq!(complement_ranges(&[], 10.0), vec![(0.0, 10.0)]);
+        // Non triés, chevauchants, collés au début et à la fin
+        assert_eq!(
+            complement_ranges(&[(6.0, 8.0), (0.0, 2.0), (1.0, 3.0), (9.9995, 10.0)], 10.0),
+            vec![(3.0, 6.0), (8.0, 9.9995)]
+        );
+        assert!(complement_ranges(&[(0.0, 5.0), (5.0, 10.0)], 10.0).is_empty());
+    }
 }